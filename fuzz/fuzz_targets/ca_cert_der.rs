@@ -0,0 +1,21 @@
+#![no_main]
+
+use std::sync::OnceLock;
+
+use libfuzzer_sys::fuzz_target;
+use rcgen::{CertificateParams, Issuer, KeyPair};
+
+fuzz_target!(|data: &[u8]| {
+	static KEY: OnceLock<KeyPair> = OnceLock::new();
+	let key = KEY.get_or_init(|| KeyPair::generate().unwrap());
+
+	// Parsing must never panic, no matter the input.
+	let Ok(issuer) = Issuer::from_ca_cert_der(&data.into(), key) else {
+		return;
+	};
+
+	// Anything we managed to parse must also be usable for issuance.
+	CertificateParams::default()
+		.signed_by(key, &issuer)
+		.unwrap();
+});
@@ -0,0 +1,36 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rcgen::{test_keys, CertificateParams, CertificateSigningRequestParams};
+
+fuzz_target!(|params: CertificateParams| {
+	// Generated parameters are valid, so serialization must succeed.
+	let key = test_keys::ecdsa_p256();
+	let cert = params.clone().self_signed(&key).unwrap();
+
+	let extensions = CertificateParams::extensions_from_der(cert.der()).unwrap();
+	for custom in &params.custom_extensions {
+		let ext = extensions
+			.iter()
+			.find(|ext| ext.oid_components().eq(custom.oid_components()))
+			.unwrap();
+		assert_eq!(ext.value(), custom.content());
+		assert_eq!(ext.criticality(), custom.criticality());
+	}
+
+	// CSRs with extensions unknown to rcgen can't be parsed, so only round trip the others.
+	if !params.custom_extensions.is_empty() {
+		return;
+	}
+	let mut params = params;
+	params.serial_number = None;
+	params.crl_distribution_points.clear();
+	params.use_authority_key_identifier_extension = false;
+	let csr = params.serialize_request(&key).unwrap();
+	let parsed = CertificateSigningRequestParams::from_der(csr.der()).unwrap().params;
+	assert_eq!(parsed.distinguished_name, params.distinguished_name);
+	assert_eq!(parsed.subject_alt_names, params.subject_alt_names);
+	assert_eq!(parsed.key_usages, params.key_usages);
+	assert_eq!(parsed.extended_key_usages, params.extended_key_usages);
+	assert_eq!(parsed.is_ca, params.is_ca);
+});
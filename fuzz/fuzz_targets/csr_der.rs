@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rcgen::CertificateSigningRequestParams;
+
+fuzz_target!(|data: &[u8]| {
+	// Parsing must never panic, no matter the input.
+	let _ = CertificateSigningRequestParams::from_der(&data.into());
+});
@@ -0,0 +1,70 @@
+//! Issues a certificate with a signer that is awaited, like a remote KMS or HSM
+//!
+//! The "remote" signer here is a local key behind an async interface. A real one would send
+//! the to-be-signed bytes to the signing service and await its response.
+
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+use rcgen::{
+	AsyncSigningKey, BasicConstraints, CertificateParams, DnType, Error, IsCa, Issuer, KeyPair,
+	PublicKeyData, SignatureAlgorithm, SigningKey,
+};
+
+struct RemoteSigner {
+	key: KeyPair,
+}
+
+impl PublicKeyData for RemoteSigner {
+	fn der_bytes(&self) -> &[u8] {
+		self.key.der_bytes()
+	}
+
+	fn algorithm(&self) -> &'static SignatureAlgorithm {
+		self.key.algorithm()
+	}
+}
+
+impl AsyncSigningKey for RemoteSigner {
+	async fn sign(&self, msg: &[u8]) -> Result<Vec<u8>, Error> {
+		// Send `msg` to the signing service and await the signature here
+		self.key.sign(msg)
+	}
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+	let mut ca_params = CertificateParams::new(Vec::new())?;
+	ca_params
+		.distinguished_name
+		.push(DnType::CommonName, "Remote CA");
+	ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+	let ca_signer = RemoteSigner {
+		key: KeyPair::generate()?,
+	};
+
+	let leaf_params = CertificateParams::new(vec!["crabs.crabs".to_string()])?;
+	let leaf_key = KeyPair::generate()?;
+
+	let (ca_cert, leaf_cert) = block_on(async {
+		let ca_cert = ca_params.self_signed_async(&ca_signer).await?;
+		let issuer = Issuer::from_params(&ca_params, &ca_signer);
+		let leaf_cert = leaf_params.signed_by_async(&leaf_key, &issuer).await?;
+		Ok::<_, Error>((ca_cert, leaf_cert))
+	})?;
+
+	println!("{}", ca_cert.pem());
+	println!("{}", leaf_cert.pem());
+	Ok(())
+}
+
+/// Runs a future to completion, in place of the executor of an async runtime
+fn block_on<T>(future: impl Future<Output = T>) -> T {
+	let mut future = pin!(future);
+	let mut cx = Context::from_waker(Waker::noop());
+	loop {
+		if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+			return output;
+		}
+	}
+}
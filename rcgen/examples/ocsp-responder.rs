@@ -0,0 +1,50 @@
+use rcgen::{
+	BasicConstraints, Certificate, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa,
+	Issuer, KeyPair, KeyUsagePurpose,
+};
+use time::{Duration, OffsetDateTime};
+
+/// Example demonstrating a CA delegating OCSP signing to a responder certificate
+fn main() {
+	let (ca, issuer) = new_ca();
+	let responder = new_ocsp_responder(&issuer);
+
+	println!("ocsp responder certificate: {}", responder.pem());
+	println!("ca certificate: {}", ca.pem());
+}
+
+fn new_ca() -> (Certificate, Issuer<'static, KeyPair>) {
+	let mut params = CertificateParams::default();
+	params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+	params
+		.distinguished_name
+		.push(DnType::CommonName, "Crab CA");
+	params.key_usages.push(KeyUsagePurpose::KeyCertSign);
+	params.key_usages.push(KeyUsagePurpose::CrlSign);
+
+	let key_pair = KeyPair::generate().unwrap();
+	let cert = params.self_signed(&key_pair).unwrap();
+	(cert, Issuer::new(params, key_pair))
+}
+
+/// The profile of a delegated OCSP responder, see RFC 6960 Section 4.2.2.2
+fn new_ocsp_responder(issuer: &Issuer<'static, KeyPair>) -> Certificate {
+	let mut params = CertificateParams::default();
+	params
+		.distinguished_name
+		.push(DnType::CommonName, "Crab CA OCSP responder");
+	params.use_authority_key_identifier_extension = true;
+	params.key_usages.push(KeyUsagePurpose::DigitalSignature);
+	// Clients only accept responses signed on behalf of the CA with this purpose
+	params
+		.extended_key_usages
+		.push(ExtendedKeyUsagePurpose::OcspSigning);
+	// The responder can't vouch for its own revocation status, so its lifetime is kept short
+	// and clients are told not to check it
+	params.ocsp_no_check = true;
+	params.not_before = OffsetDateTime::now_utc();
+	params.not_after = params.not_before + Duration::days(7);
+
+	let key_pair = KeyPair::generate().unwrap();
+	params.signed_by(&key_pair, issuer).unwrap()
+}
@@ -0,0 +1,333 @@
+//! [`Arbitrary`] implementations for fuzzing
+//!
+//! The generated values are structurally valid: strings only contain characters allowed by
+//! their ASN.1 string type, OIDs are well-formed, times can be represented in certificates and
+//! names look like names. Serializing them should thus always succeed, and parsing the result
+//! should give back the same values.
+
+use std::net::IpAddr;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+use time::OffsetDateTime;
+
+use crate::string::{BmpString, Ia5String, PrintableString, TeletexString, UniversalString};
+use crate::{
+	BasicConstraints, CertificateParams, CrlDistributionPoint, CustomExtension, DistinguishedName,
+	DnType, DnValue, ExtendedKeyUsagePurpose, IsCa, KeyIdMethod, KeyUsagePurpose, OtherNameValue,
+	SanType, SerialNumber,
+};
+
+/// The private enterprise number reserved for documentation by RFC 5612, which is used as the
+/// prefix of generated OIDs so they never collide with OIDs rcgen handles itself
+const EXAMPLE_ARC: &[u64] = &[1, 3, 6, 1, 4, 1, 32473];
+
+/// 1950-01-01T00:00:00Z, the earliest time a UTCTime can represent
+const MIN_TIME: i64 = -631_152_000;
+/// 9999-12-31T23:59:59Z, the latest time a GeneralizedTime can represent
+const MAX_TIME: i64 = 253_402_300_799;
+
+const PRINTABLE: &[u8] =
+	b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789 '()+,-./:=?";
+const LABEL: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+
+impl<'a> Arbitrary<'a> for CertificateParams {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		let not_before = u.int_in_range(MIN_TIME..=MAX_TIME)?;
+		let not_after = u.int_in_range(not_before..=MAX_TIME)?;
+
+		let mut key_usages = Vec::new();
+		for purpose in KEY_USAGES {
+			if u.arbitrary()? {
+				key_usages.push(*purpose);
+			}
+		}
+
+		let mut extended_key_usages = Vec::<ExtendedKeyUsagePurpose>::new();
+		for _ in 0..u.int_in_range(0..=4)? {
+			let purpose = u.arbitrary()?;
+			if !extended_key_usages.contains(&purpose) {
+				extended_key_usages.push(purpose);
+			}
+		}
+
+		// Certificates can't contain an extension twice
+		let mut custom_extensions = Vec::<CustomExtension>::new();
+		for _ in 0..u.int_in_range(0..=3)? {
+			let extension = CustomExtension::arbitrary(u)?;
+			if custom_extensions
+				.iter()
+				.all(|e| e.oid_components().ne(extension.oid_components()))
+			{
+				custom_extensions.push(extension);
+			}
+		}
+
+		let mut crl_distribution_points = Vec::new();
+		for _ in 0..u.int_in_range(0..=2)? {
+			let mut uris = vec![uri(u)?];
+			for _ in 0..u.int_in_range(0..=2)? {
+				uris.push(uri(u)?);
+			}
+			crl_distribution_points.push(CrlDistributionPoint { uris });
+		}
+
+		let key_identifier_method = match u.int_in_range(0..=4)? {
+			#[cfg(feature = "crypto")]
+			0 => KeyIdMethod::Sha256,
+			#[cfg(feature = "crypto")]
+			1 => KeyIdMethod::Sha384,
+			#[cfg(feature = "crypto")]
+			2 => KeyIdMethod::Sha512,
+			#[cfg(feature = "crypto")]
+			3 => KeyIdMethod::Sha1,
+			_ => {
+				let len = u.int_in_range(1..=20)?;
+				KeyIdMethod::PreSpecified(u.bytes(len)?.to_vec())
+			},
+		};
+
+		Ok(CertificateParams {
+			not_before: time(not_before)?,
+			not_after: time(not_after)?,
+			serial_number: u.arbitrary()?,
+			subject_alt_names: arbitrary_vec(u, 0..=8)?,
+			distinguished_name: u.arbitrary()?,
+			is_ca: u.arbitrary()?,
+			key_usages,
+			extended_key_usages,
+			crl_distribution_points,
+			custom_extensions,
+			use_authority_key_identifier_extension: u.arbitrary()?,
+			key_identifier_method,
+			..CertificateParams::default()
+		})
+	}
+}
+
+impl<'a> Arbitrary<'a> for DistinguishedName {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		let mut dn = DistinguishedName::new();
+		for _ in 0..u.int_in_range(0..=8)? {
+			dn.push(u.arbitrary()?, DnValue::arbitrary(u)?);
+		}
+		Ok(dn)
+	}
+}
+
+impl<'a> Arbitrary<'a> for DnType {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		Ok(match u.int_in_range(0..=11)? {
+			0 => Self::CountryName,
+			1 => Self::LocalityName,
+			2 => Self::StateOrProvinceName,
+			3 => Self::OrganizationName,
+			4 => Self::OrganizationalUnitName,
+			5 => Self::CommonName,
+			6 => Self::BusinessCategory,
+			7 => Self::OrganizationIdentifier,
+			8 => Self::JurisdictionLocalityName,
+			9 => Self::JurisdictionStateOrProvinceName,
+			10 => Self::JurisdictionCountryName,
+			_ => Self::CustomDnType(oid(u, 2)?),
+		})
+	}
+}
+
+impl<'a> Arbitrary<'a> for DnValue {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		Ok(match u.int_in_range(0..=5)? {
+			0 => Self::BmpString(valid(BmpString::try_from(string(u, |u| {
+				// Any character of the basic multilingual plane, except for surrogates
+				Ok(char::from_u32(u.int_in_range(0x20..=0xfffd)?).unwrap_or('?'))
+			})?))?),
+			1 => Self::Ia5String(valid(Ia5String::try_from(string(u, |u| {
+				Ok(char::from(u.int_in_range(0x20..=0x7e)?))
+			})?))?),
+			2 => Self::PrintableString(valid(PrintableString::try_from(string(u, |u| {
+				Ok(char::from(*u.choose(PRINTABLE)?))
+			})?))?),
+			3 => Self::TeletexString(valid(TeletexString::try_from(string(u, |u| {
+				Ok(char::from(u.int_in_range(0x20..=0x7e)?))
+			})?))?),
+			4 => Self::UniversalString(valid(UniversalString::try_from(string(u, |u| {
+				u.arbitrary::<char>()
+			})?))?),
+			_ => Self::Utf8String(string(u, |u| u.arbitrary::<char>())?),
+		})
+	}
+}
+
+impl<'a> Arbitrary<'a> for SanType {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		Ok(match u.int_in_range(0..=4)? {
+			0 => {
+				let local = label(u)?;
+				Self::Rfc822Name(valid(format!("{local}@{}", domain(u)?).try_into())?)
+			},
+			1 => {
+				let name = match u.arbitrary()? {
+					true => format!("*.{}", domain(u)?),
+					false => domain(u)?,
+				};
+				Self::DnsName(valid(name.try_into())?)
+			},
+			2 => Self::URI(valid(uri(u)?.try_into())?),
+			3 => Self::IpAddress(IpAddr::arbitrary(u)?),
+			_ => Self::OtherName((
+				oid(u, 3)?,
+				OtherNameValue::Utf8String(string(u, |u| u.arbitrary::<char>())?),
+			)),
+		})
+	}
+}
+
+impl<'a> Arbitrary<'a> for SerialNumber {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		// Positive and without leading zeros, so that the encoding is the same as the value
+		let mut serial = vec![u.int_in_range(1..=0x7f)?];
+		let len = u.int_in_range(0..=19)?;
+		serial.extend_from_slice(u.bytes(len)?);
+		Ok(Self::from(serial))
+	}
+}
+
+impl<'a> Arbitrary<'a> for IsCa {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		Ok(match u.int_in_range(0..=3)? {
+			0 => Self::NoCa,
+			1 => Self::ExplicitNoCa,
+			2 => Self::Ca(BasicConstraints::Unconstrained),
+			_ => Self::Ca(BasicConstraints::Constrained(u.arbitrary()?)),
+		})
+	}
+}
+
+const KEY_USAGES: &[KeyUsagePurpose] = &[
+	KeyUsagePurpose::DigitalSignature,
+	KeyUsagePurpose::ContentCommitment,
+	KeyUsagePurpose::KeyEncipherment,
+	KeyUsagePurpose::DataEncipherment,
+	KeyUsagePurpose::KeyAgreement,
+	KeyUsagePurpose::KeyCertSign,
+	KeyUsagePurpose::CrlSign,
+	KeyUsagePurpose::EncipherOnly,
+	KeyUsagePurpose::DecipherOnly,
+];
+
+impl<'a> Arbitrary<'a> for KeyUsagePurpose {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		u.choose(KEY_USAGES).copied()
+	}
+}
+
+impl<'a> Arbitrary<'a> for ExtendedKeyUsagePurpose {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		Ok(match u.int_in_range(0..=7)? {
+			0 => Self::Any,
+			1 => Self::ServerAuth,
+			2 => Self::ClientAuth,
+			3 => Self::CodeSigning,
+			4 => Self::EmailProtection,
+			5 => Self::TimeStamping,
+			6 => Self::OcspSigning,
+			_ => Self::Other(oid(u, 4)?),
+		})
+	}
+}
+
+impl<'a> Arbitrary<'a> for CustomExtension {
+	fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+		// Wrapped in an OCTET STRING, as the content must be DER
+		let bytes = arbitrary_bytes(u)?;
+		let content = yasna::construct_der(|writer| writer.write_bytes(bytes));
+		let mut extension = Self::from_oid_content(&oid(u, 1)?, content);
+		extension.set_criticality(u.arbitrary()?);
+		Ok(extension)
+	}
+}
+
+/// An OID below [`EXAMPLE_ARC`], with `kind` as the first component to keep OIDs used for
+/// different purposes apart
+fn oid(u: &mut Unstructured<'_>, kind: u64) -> Result<Vec<u64>> {
+	let mut oid = [EXAMPLE_ARC, &[kind]].concat();
+	for _ in 0..u.int_in_range(1..=3)? {
+		// yasna fails to parse OIDs with a zero base 128 digit that isn't the last, so avoid
+		// those to keep the results parseable
+		let mut component = 0;
+		for _ in 0..u.int_in_range(1..=4)? {
+			component = component << 7 | u.int_in_range(1..=0x7f)?;
+		}
+		oid.push(component);
+	}
+	Ok(oid)
+}
+
+fn time(unix_timestamp: i64) -> Result<OffsetDateTime> {
+	OffsetDateTime::from_unix_timestamp(unix_timestamp)
+		.map_err(|_| arbitrary::Error::IncorrectFormat)
+}
+
+/// A non-empty string of up to 32 characters produced by `char`
+fn string(
+	u: &mut Unstructured<'_>,
+	mut char: impl FnMut(&mut Unstructured<'_>) -> Result<char>,
+) -> Result<String> {
+	(0..u.int_in_range(1..=32)?).map(|_| char(u)).collect()
+}
+
+fn label(u: &mut Unstructured<'_>) -> Result<String> {
+	string(u, |u| Ok(char::from(*u.choose(LABEL)?)))
+}
+
+fn domain(u: &mut Unstructured<'_>) -> Result<String> {
+	let mut labels = vec![label(u)?];
+	for _ in 0..u.int_in_range(1..=3)? {
+		labels.push(label(u)?);
+	}
+	Ok(labels.join("."))
+}
+
+fn uri(u: &mut Unstructured<'_>) -> Result<String> {
+	Ok(format!("https://{}/{}", domain(u)?, label(u)?))
+}
+
+fn arbitrary_bytes<'a>(u: &mut Unstructured<'a>) -> Result<&'a [u8]> {
+	let len = u.int_in_range(0..=64)?;
+	u.bytes(len)
+}
+
+fn arbitrary_vec<'a, T: Arbitrary<'a>>(
+	u: &mut Unstructured<'a>,
+	len: std::ops::RangeInclusive<usize>,
+) -> Result<Vec<T>> {
+	(0..u.int_in_range(len)?).map(|_| T::arbitrary(u)).collect()
+}
+
+/// Unwraps a string that was generated to be valid, failing the generation if it isn't
+fn valid<T, E>(result: std::result::Result<T, E>) -> Result<T> {
+	result.map_err(|_| arbitrary::Error::IncorrectFormat)
+}
+
+#[cfg(all(test, feature = "crypto"))]
+mod tests {
+	use super::*;
+	use crate::test_keys;
+
+	#[test]
+	fn generated_params_serialize() {
+		let key = test_keys::ecdsa_p256();
+		let mut generated = 0;
+		for seed in 0..64u32 {
+			let data = (0..2048u32)
+				.map(|i| (i.wrapping_add(seed << 11).wrapping_mul(2_654_435_761) >> 13) as u8)
+				.collect::<Vec<_>>();
+			let Ok(params) = CertificateParams::arbitrary(&mut Unstructured::new(&data)) else {
+				continue;
+			};
+			assert!(params.not_before <= params.not_after);
+			params.self_signed(&key).unwrap();
+			generated += 1;
+		}
+		assert!(generated > 0);
+	}
+}
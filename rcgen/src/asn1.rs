@@ -0,0 +1,179 @@
+//! Parsing untrusted input with yasna
+//!
+//! yasna skips elements without checking their lengths for overflow, so a length close to
+//! `usize::MAX` makes it panic, or move backwards in BER mode. The functions here check the
+//! lengths of all elements first, and are to be used instead of [`yasna::parse_der()`] and
+//! [`yasna::parse_ber()`].
+
+use yasna::{ASN1Error, ASN1ErrorKind, ASN1Result, BERReader};
+
+/// Like [`yasna::parse_der()`], but fails instead of panicking for oversized lengths
+pub(crate) fn parse_der<'a, T, F>(buf: &'a [u8], callback: F) -> ASN1Result<T>
+where
+	F: for<'b> FnOnce(BERReader<'a, 'b>) -> ASN1Result<T>,
+{
+	check_lengths(buf)?;
+	yasna::parse_der(buf, callback)
+}
+
+/// Like [`yasna::parse_ber()`], but fails instead of panicking for oversized lengths
+#[cfg(any(test, feature = "crypto", feature = "x509-parser"))]
+pub(crate) fn parse_ber<'a, T, F>(buf: &'a [u8], callback: F) -> ASN1Result<T>
+where
+	F: for<'b> FnOnce(BERReader<'a, 'b>) -> ASN1Result<T>,
+{
+	check_lengths(buf)?;
+	yasna::parse_ber(buf, callback)
+}
+
+/// Checks that the length of no element overflows when added to its position
+///
+/// Only the contents of constructed elements are walked, as yasna doesn't read the contents
+/// of primitive ones as elements. Those are parsed separately, and checked then. Other
+/// malformed elements are left for yasna to reject.
+fn check_lengths(buf: &[u8]) -> ASN1Result<()> {
+	// The end of each enclosing element, and whether its length is definite. Elements of
+	// indefinite length end where the element enclosing them ends at the latest.
+	let mut enclosing: Vec<(usize, bool)> = Vec::new();
+	let mut pos = 0;
+	loop {
+		let (end, definite) = enclosing.last().copied().unwrap_or((buf.len(), true));
+		let header = match pos == end {
+			true if definite => match enclosing.pop() {
+				Some(_) => continue,
+				None => return Ok(()),
+			},
+			_ => read_header(&buf[..end], &mut pos),
+		};
+
+		match header {
+			// End-of-contents of an element of indefinite length
+			Some((0, Some(0))) if !definite => {
+				enclosing.pop();
+				continue;
+			},
+			Some((identifier, length)) => {
+				let constructed = identifier & 0x20 != 0;
+				match length.map(|length| pos.checked_add(length)) {
+					None if constructed => {
+						enclosing.push((end, false));
+						continue;
+					},
+					Some(None) => return Err(ASN1Error::new(ASN1ErrorKind::IntegerOverflow)),
+					Some(Some(element_end)) if element_end <= end => {
+						match constructed {
+							true => enclosing.push((element_end, true)),
+							false => pos = element_end,
+						}
+						continue;
+					},
+					_ => {},
+				}
+			},
+			None => {},
+		}
+
+		// yasna fails when it reads the malformed element, which it may skip as part of an
+		// enclosing element of definite length. The elements after that one are still read.
+		loop {
+			match enclosing.pop() {
+				Some((end, true)) => {
+					pos = end;
+					break;
+				},
+				Some((_, false)) => continue,
+				None => return Ok(()),
+			}
+		}
+	}
+}
+
+/// Reads the identifier octet and the length of the element at `pos`, with `None` as length
+/// for the indefinite form
+fn read_header(buf: &[u8], pos: &mut usize) -> Option<(u8, Option<usize>)> {
+	let mut next = || {
+		let byte = buf.get(*pos).copied();
+		*pos += 1;
+		byte
+	};
+	let identifier = next()?;
+	if identifier & 0x1f == 0x1f {
+		// The tag number continues in the following octets
+		while next()? & 0x80 != 0 {}
+	}
+	let length = match next()? {
+		0x80 => None,
+		short @ ..=0x7f => Some(usize::from(short)),
+		long => {
+			let mut length = 0usize;
+			for _ in 0..long & 0x7f {
+				length = length.checked_mul(256)? | usize::from(next()?);
+			}
+			Some(length)
+		},
+	};
+	Some((identifier, length))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn overflowing_lengths() {
+		let max = [0x88, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+		for der in [
+			// Found by the csr_der fuzzer, which made yasna move backwards in BER mode
+			[&[0x30, 0x80, 0x30, 0x80, 0x01][..], &max].concat(),
+			[&[0x04][..], &max].concat(),
+			[&[0x30, 0x0c, 0x04, 0x00, 0x04][..], &max].concat(),
+		] {
+			let overflow = ASN1Error::new(ASN1ErrorKind::IntegerOverflow);
+			assert_eq!(check_lengths(&der), Err(overflow));
+			assert_eq!(parse_der(&der, |reader| reader.read_der()), Err(overflow));
+			assert_eq!(parse_ber(&der, |reader| reader.read_der()), Err(overflow));
+		}
+	}
+
+	#[test]
+	fn malformed() {
+		// The overflowing length comes after a malformed element that is skipped as part of
+		// the enclosing one
+		let der = [
+			&[0x30, 0x0f, 0x30, 0x03, 0x04, 0x05, 0x00][..],
+			&[0x04, 0x88, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+		]
+		.concat();
+		assert!(check_lengths(&der).is_err());
+
+		// Anything else is left to yasna
+		for der in [
+			&[0x30, 0x03, 0x04, 0x02, 0x00][..],
+			&[0x30, 0x80, 0x04, 0x00],
+			&[0x04, 0x00, 0x00],
+			&[0x04, 0x81],
+		] {
+			assert_eq!(check_lengths(der), Ok(()));
+			assert_eq!(
+				parse_ber(der, |reader| reader.read_der()),
+				yasna::parse_ber(der, |reader| reader.read_der())
+			);
+		}
+	}
+
+	#[test]
+	fn nested() {
+		// The contents of primitive elements aren't walked
+		let der = [0x30, 0x06, 0x30, 0x04, 0x04, 0x02, 0x30, 0x88];
+		assert_eq!(
+			parse_der(&der, |reader| reader.read_der()),
+			Ok(der.to_vec())
+		);
+
+		let ber = [0x30, 0x80, 0x30, 0x80, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00];
+		assert_eq!(
+			parse_ber(&ber, |reader| reader.read_der()),
+			Ok(ber.to_vec())
+		);
+	}
+}
@@ -0,0 +1,437 @@
+use std::collections::HashSet;
+
+#[cfg(feature = "crypto")]
+use crate::error::ExternalError;
+#[cfg(feature = "crypto")]
+use crate::ring_like::rand::{SecureRandom, SystemRandom};
+use crate::{
+	Certificate, CertificateParams, CertificateRevocationList, CertificateRevocationListParams,
+	Error, Issuer, PublicKeyData, SerialNumber, SigningKey,
+};
+
+/// Identifies the serialization format of [`CaState::to_bytes()`]
+const MAGIC: &[u8] = b"rcgen-ca-state";
+const VERSION: u8 = 1;
+
+/// Bookkeeping for a long-lived certificate authority
+///
+/// Tracks the serial numbers issued so far, so that [`Issuer::issue_with_state()`] never
+/// hands out the same serial number twice, as well as the number of the next CRL and the
+/// serial numbers revoked by the last one issued through [`Issuer::next_crl()`].
+///
+/// The state can be persisted between runs with [`CaState::to_bytes()`] and
+/// [`CaState::from_bytes()`]. Sharing it between concurrent issuers is up to the caller.
+///
+/// Serial numbers are compared by their value, so `SerialNumber::from(42)` and
+/// `SerialNumber::from_slice(&[42])` are the same serial number. They are kept without
+/// leading zero bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaState {
+	next_serial: u64,
+	next_crl_number: u64,
+	issued: HashSet<SerialNumber>,
+	revoked: HashSet<SerialNumber>,
+}
+
+impl CaState {
+	/// Creates the state of a CA that hasn't issued anything yet
+	pub fn new() -> Self {
+		Self {
+			next_serial: 1,
+			next_crl_number: 1,
+			issued: HashSet::new(),
+			revoked: HashSet::new(),
+		}
+	}
+
+	/// Records a serial number that was issued without this state, e.g. before it was introduced
+	///
+	/// Returns [`Error::DuplicateSerialNumber`] if it was already recorded.
+	pub fn record_issued(&mut self, serial: SerialNumber) -> Result<(), Error> {
+		if !self.issued.insert(normalize(&serial)) {
+			return Err(Error::DuplicateSerialNumber(serial));
+		}
+		Ok(())
+	}
+
+	/// Whether the given serial number has been issued
+	pub fn is_issued(&self, serial: &SerialNumber) -> bool {
+		self.issued.contains(&normalize(serial))
+	}
+
+	/// The serial numbers issued so far, in no particular order
+	pub fn issued_serials(&self) -> impl Iterator<Item = &SerialNumber> {
+		self.issued.iter()
+	}
+
+	/// The serial numbers listed in the last CRL issued through [`Issuer::next_crl()`],
+	/// in no particular order
+	pub fn revoked_serials(&self) -> impl Iterator<Item = &SerialNumber> {
+		self.revoked.iter()
+	}
+
+	/// The CRL number that will be used by the next call to [`Issuer::next_crl()`]
+	pub fn next_crl_number(&self) -> u64 {
+		self.next_crl_number
+	}
+
+	/// Serializes the state into a compact binary format
+	///
+	/// Returns [`Error::CaStateOverflow`] if there are more than `u32::MAX` issued or revoked
+	/// serial numbers, or one is longer than `u16::MAX` bytes.
+	pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+		let mut out = Vec::from(MAGIC);
+		out.push(VERSION);
+		out.extend_from_slice(&self.next_serial.to_be_bytes());
+		out.extend_from_slice(&self.next_crl_number.to_be_bytes());
+		write_serials(&mut out, &self.issued)?;
+		write_serials(&mut out, &self.revoked)?;
+		Ok(out)
+	}
+
+	/// Parses a state previously serialized with [`CaState::to_bytes()`]
+	pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+		let mut reader = Reader(bytes);
+		if reader.take(MAGIC.len())? != MAGIC || reader.take(1)? != [VERSION] {
+			return Err(Error::CouldNotParseCaState);
+		}
+		let next_serial = reader.u64()?;
+		let next_crl_number = reader.u64()?;
+		let issued = reader.serials()?;
+		let revoked = reader.serials()?;
+		if !reader.0.is_empty() {
+			return Err(Error::CouldNotParseCaState);
+		}
+
+		Ok(Self {
+			next_serial,
+			next_crl_number,
+			issued,
+			revoked,
+		})
+	}
+
+	/// Picks a serial number that hasn't been issued before, along with the value of the
+	/// counter once it is used
+	///
+	/// Serial numbers consist of a counter followed, when a crypto provider is available,
+	/// by 8 random bytes so that they aren't predictable. The state isn't changed, so that
+	/// nothing is consumed if issuance fails.
+	fn allocate_serial(&self) -> Result<(SerialNumber, u64), Error> {
+		let mut next_serial = self.next_serial;
+		loop {
+			let counter = next_serial.to_be_bytes();
+			next_serial = next_serial.checked_add(1).ok_or(Error::CaStateOverflow)?;
+			#[cfg(feature = "crypto")]
+			let serial = {
+				let mut suffix = [0u8; 8];
+				SystemRandom::new().fill(&mut suffix)._err()?;
+				SerialNumber::from([counter, suffix].concat())
+			};
+			#[cfg(not(feature = "crypto"))]
+			let serial = SerialNumber::from_slice(&counter);
+
+			if !self.is_issued(&serial) {
+				return Ok((serial, next_serial));
+			}
+		}
+	}
+}
+
+impl Default for CaState {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<S: SigningKey> Issuer<'_, S> {
+	/// Issues a certificate for `public_key`, recording its serial number in `state`
+	///
+	/// If `params` has no serial number, a fresh one is allocated from `state`. Otherwise
+	/// the given serial number is used, and [`Error::DuplicateSerialNumber`] is returned if
+	/// it has been issued before. The state is only updated if issuance succeeds.
+	pub fn issue_with_state(
+		&self,
+		state: &mut CaState,
+		params: &CertificateParams,
+		public_key: &(impl PublicKeyData + ?Sized),
+	) -> Result<Certificate, Error> {
+		let (serial, next_serial) = match &params.serial_number {
+			Some(serial) if state.is_issued(serial) => {
+				return Err(Error::DuplicateSerialNumber(serial.clone()))
+			},
+			Some(serial) => (serial.clone(), state.next_serial),
+			None => state.allocate_serial()?,
+		};
+
+		let mut params = params.clone();
+		let key = normalize(&serial);
+		params.serial_number = Some(serial);
+		let cert = params.signed_by(public_key, self)?;
+		state.next_serial = next_serial;
+		state.issued.insert(key);
+		Ok(cert)
+	}
+
+	/// Issues the next CRL, numbered according to `state`
+	///
+	/// The `crl_number` of `params` is ignored. Once the CRL is signed, the CRL number is
+	/// incremented and the revoked serial numbers in `state` are replaced with the ones
+	/// listed in this CRL.
+	pub fn next_crl(
+		&self,
+		state: &mut CaState,
+		params: &CertificateRevocationListParams,
+	) -> Result<CertificateRevocationList, Error> {
+		let next_crl_number = state
+			.next_crl_number
+			.checked_add(1)
+			.ok_or(Error::CaStateOverflow)?;
+		let mut params = params.clone();
+		params.crl_number = SerialNumber::from(state.next_crl_number);
+		let crl = params.signed_by(self)?;
+
+		state.next_crl_number = next_crl_number;
+		state.revoked = params
+			.revoked_certs
+			.iter()
+			.map(|revoked| normalize(&revoked.serial_number))
+			.collect();
+		Ok(crl)
+	}
+}
+
+/// The serial number without leading zero bytes, keeping one byte for zero
+fn normalize(serial: &SerialNumber) -> SerialNumber {
+	let bytes = serial.as_ref();
+	let start = bytes
+		.iter()
+		.position(|&b| b != 0)
+		.unwrap_or(bytes.len().saturating_sub(1));
+	SerialNumber::from_slice(&bytes[start..])
+}
+
+fn write_serials(out: &mut Vec<u8>, serials: &HashSet<SerialNumber>) -> Result<(), Error> {
+	// Sort to keep the output independent of the hash set's iteration order.
+	let mut serials = serials.iter().map(|s| s.as_ref()).collect::<Vec<_>>();
+	serials.sort_unstable();
+
+	let count = u32::try_from(serials.len()).map_err(|_| Error::CaStateOverflow)?;
+	out.extend_from_slice(&count.to_be_bytes());
+	for serial in serials {
+		let len = u16::try_from(serial.len()).map_err(|_| Error::CaStateOverflow)?;
+		out.extend_from_slice(&len.to_be_bytes());
+		out.extend_from_slice(serial);
+	}
+	Ok(())
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+	fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+		if self.0.len() < len {
+			return Err(Error::CouldNotParseCaState);
+		}
+		let (head, tail) = self.0.split_at(len);
+		self.0 = tail;
+		Ok(head)
+	}
+
+	fn u16(&mut self) -> Result<u16, Error> {
+		let bytes = self.take(2)?;
+		Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+	}
+
+	fn u32(&mut self) -> Result<u32, Error> {
+		let mut bytes = [0; 4];
+		bytes.copy_from_slice(self.take(4)?);
+		Ok(u32::from_be_bytes(bytes))
+	}
+
+	fn u64(&mut self) -> Result<u64, Error> {
+		let mut bytes = [0; 8];
+		bytes.copy_from_slice(self.take(8)?);
+		Ok(u64::from_be_bytes(bytes))
+	}
+
+	fn serials(&mut self) -> Result<HashSet<SerialNumber>, Error> {
+		let count = self.u32()?;
+		let mut serials = HashSet::new();
+		for _ in 0..count {
+			let len = self.u16()?;
+			let serial = normalize(&SerialNumber::from_slice(self.take(len.into())?));
+			if !serials.insert(serial) {
+				return Err(Error::CouldNotParseCaState);
+			}
+		}
+		Ok(serials)
+	}
+}
+
+#[cfg(all(test, feature = "crypto"))]
+mod tests {
+	use time::{Duration, OffsetDateTime};
+
+	use super::*;
+	use crate::{
+		BasicConstraints, IsCa, KeyIdMethod, KeyPair, KeyUsagePurpose, RevocationReason,
+		RevokedCertParams,
+	};
+
+	fn issuer() -> Issuer<'static, KeyPair> {
+		let params = CertificateParams {
+			is_ca: IsCa::Ca(BasicConstraints::Unconstrained),
+			key_usages: vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign],
+			..CertificateParams::default()
+		};
+		Issuer::new(params, KeyPair::generate().unwrap())
+	}
+
+	#[test]
+	fn unique_serials() {
+		let issuer = issuer();
+		let mut state = CaState::new();
+		let key = KeyPair::generate().unwrap();
+
+		// Without a state, both certificates would get a serial derived from the key.
+		let params = CertificateParams::default();
+		issuer.issue_with_state(&mut state, &params, &key).unwrap();
+		issuer.issue_with_state(&mut state, &params, &key).unwrap();
+		assert_eq!(state.issued_serials().count(), 2);
+
+		let params = CertificateParams {
+			serial_number: Some(SerialNumber::from(42)),
+			..CertificateParams::default()
+		};
+		issuer.issue_with_state(&mut state, &params, &key).unwrap();
+		assert!(matches!(
+			issuer.issue_with_state(&mut state, &params, &key),
+			Err(Error::DuplicateSerialNumber(serial)) if serial == SerialNumber::from(42)
+		));
+		assert_eq!(state.issued_serials().count(), 3);
+
+		// Serial numbers are compared by value, regardless of leading zeros
+		assert!(state.is_issued(&SerialNumber::from_slice(&[42])));
+		assert!(state.is_issued(&SerialNumber::from_slice(&[0, 42])));
+		assert_eq!(
+			state.record_issued(SerialNumber::from_slice(&[42])),
+			Err(Error::DuplicateSerialNumber(SerialNumber::from_slice(&[
+				42
+			])))
+		);
+		let params = CertificateParams {
+			serial_number: Some(SerialNumber::from_slice(&[42])),
+			..CertificateParams::default()
+		};
+		assert!(matches!(
+			issuer.issue_with_state(&mut state, &params, &key),
+			Err(Error::DuplicateSerialNumber(_))
+		));
+	}
+
+	#[test]
+	fn failed_issuance() {
+		let issuer = issuer();
+		let mut state = CaState::new();
+		let key = KeyPair::generate().unwrap();
+
+		// An attribute without values can't be written
+		let params = CertificateParams {
+			subject_directory_attributes: vec![(vec![1, 2, 3], Vec::new())],
+			..CertificateParams::default()
+		};
+		assert!(issuer.issue_with_state(&mut state, &params, &key).is_err());
+		assert_eq!(state, CaState::new());
+	}
+
+	#[test]
+	fn overflow() {
+		let issuer = issuer();
+		let key = KeyPair::generate().unwrap();
+		let mut state = CaState {
+			next_serial: u64::MAX,
+			next_crl_number: u64::MAX,
+			..CaState::new()
+		};
+
+		assert_eq!(
+			issuer
+				.issue_with_state(&mut state, &CertificateParams::default(), &key)
+				.map(|_| ()),
+			Err(Error::CaStateOverflow)
+		);
+		let now = OffsetDateTime::now_utc();
+		let params = CertificateRevocationListParams {
+			this_update: now,
+			next_update: now + Duration::weeks(1),
+			crl_number: SerialNumber::from(1),
+			issuing_distribution_point: None,
+			revoked_certs: Vec::new(),
+			key_identifier_method: KeyIdMethod::Sha256,
+		};
+		assert_eq!(
+			issuer.next_crl(&mut state, &params).map(|_| ()),
+			Err(Error::CaStateOverflow)
+		);
+		assert_eq!(state.issued_serials().count(), 0);
+	}
+
+	#[test]
+	fn crl_numbers() {
+		let issuer = issuer();
+		let mut state = CaState::new();
+
+		let now = OffsetDateTime::now_utc();
+		let params = CertificateRevocationListParams {
+			this_update: now,
+			next_update: now + Duration::weeks(1),
+			crl_number: SerialNumber::from(1234),
+			issuing_distribution_point: None,
+			revoked_certs: vec![RevokedCertParams {
+				serial_number: SerialNumber::from(42),
+				revocation_time: now,
+				reason_code: Some(RevocationReason::KeyCompromise),
+				invalidity_date: None,
+			}],
+			key_identifier_method: KeyIdMethod::Sha256,
+		};
+
+		issuer.next_crl(&mut state, &params).unwrap();
+		issuer.next_crl(&mut state, &params).unwrap();
+		assert_eq!(state.next_crl_number(), 3);
+		assert_eq!(
+			state.revoked_serials().collect::<Vec<_>>(),
+			vec![&SerialNumber::from_slice(&[42])]
+		);
+
+		// A failed CRL doesn't consume a number.
+		let mut invalid = params.clone();
+		invalid.next_update = invalid.this_update;
+		assert!(issuer.next_crl(&mut state, &invalid).is_err());
+		assert_eq!(state.next_crl_number(), 3);
+	}
+
+	#[test]
+	fn round_trip() {
+		let mut state = CaState::new();
+		state.record_issued(SerialNumber::from(7)).unwrap();
+		state
+			.record_issued(SerialNumber::from(vec![1, 2, 3]))
+			.unwrap();
+		state.next_serial = 5;
+		state.revoked.insert(normalize(&SerialNumber::from(7)));
+		state.next_crl_number = 12;
+
+		let bytes = state.to_bytes().unwrap();
+		assert_eq!(CaState::from_bytes(&bytes).unwrap(), state);
+		assert_eq!(
+			CaState::from_bytes(&bytes[..bytes.len() - 1]),
+			Err(Error::CouldNotParseCaState)
+		);
+		assert_eq!(
+			CaState::from_bytes(b"not a ca state"),
+			Err(Error::CouldNotParseCaState)
+		);
+	}
+}
@@ -1,4 +1,9 @@
+#[cfg(feature = "x509-parser")]
+use std::array;
+use std::collections::BTreeMap;
+use std::fmt;
 use std::net::IpAddr;
+use std::ops;
 use std::str::FromStr;
 
 #[cfg(feature = "pem")]
@@ -6,25 +11,39 @@ use pem::Pem;
 use pki_types::{CertificateDer, CertificateSigningRequestDer};
 use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time};
 use yasna::models::ObjectIdentifier;
-use yasna::{DERWriter, DERWriterSeq, Tag};
+use yasna::tags::TAG_BMPSTRING;
+use yasna::{ASN1Result, BERReader, DERWriter, DERWriterSeq, Tag};
 
-use crate::crl::CrlDistributionPoint;
+use crate::crl::{write_distribution_points, CrlDistributionPoint};
 use crate::csr::CertificateSigningRequest;
-use crate::key_pair::{serialize_public_key_der, sign_der, PublicKeyData};
+#[cfg(feature = "crypto")]
+use crate::key_pair::SubjectPublicKeyInfo;
+use crate::key_pair::{
+	assemble_signed_der, check_tbs_der, serialize_public_key_der, sign_der, AsyncSigningKey,
+	PublicKeyData,
+};
+#[cfg(feature = "x509-parser")]
+use crate::limits::{ParseLimit, ParseLimits, ParseOptions};
+use crate::lint::{lint_certificate_policies, lint_key_usages, Lint};
 #[cfg(feature = "crypto")]
 use crate::ring_like::digest;
+use crate::string::{BmpString, PrintableString};
+#[cfg(feature = "tracing")]
+use crate::trace;
 #[cfg(feature = "pem")]
 use crate::ENCODE_CONFIG;
 use crate::{
-	oid, write_distinguished_name, write_dt_utc_or_generalized,
-	write_x509_authority_key_identifier, write_x509_extension, DistinguishedName, Error, Issuer,
-	KeyIdMethod, KeyUsagePurpose, SanType, SerialNumber, SigningKey,
+	dt_to_generalized, oid, write_distinguished_name, write_dt_utc_or_generalized,
+	write_x509_authority_key_identifier, write_x509_extension, AuthorityKeyIdMode,
+	DistinguishedName, Error, Ia5String, Issuer, KeyIdMethod, KeyUsagePurpose, SanType,
+	SerialNumber, SignatureAlgorithm, SigningKey,
 };
 
 /// An issued certificate
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Certificate {
 	pub(crate) der: CertificateDer<'static>,
+	pub(crate) signature_algorithm: &'static SignatureAlgorithm,
 }
 
 impl Certificate {
@@ -41,6 +60,132 @@ impl Certificate {
 	pub fn pem(&self) -> String {
 		pem::encode_config(&Pem::new("CERTIFICATE", self.der().to_vec()), ENCODE_CONFIG)
 	}
+
+	/// The algorithm the certificate was signed with by its issuer
+	pub fn signature_algorithm(&self) -> &'static SignatureAlgorithm {
+		self.signature_algorithm
+	}
+
+	/// The extensions of the certificate, in the order they were emitted
+	///
+	/// Returns [`Error::CouldNotParseCertificate`] if the certificate isn't valid DER, which
+	/// can only happen for one [assembled](Self::assemble) from an external TBSCertificate.
+	pub fn extensions(&self) -> Result<Vec<ExtensionRef<'_>>, Error> {
+		CertificateParams::extensions_from_der(&self.der)
+	}
+
+	/// Whether a TLS client would accept the certificate for the host `name`
+	///
+	/// This implements the matching rules of RFC 6125 as applied by current TLS clients:
+	/// - Only the subject alternative names are considered, the common name is ignored.
+	/// - DNS names are compared ASCII case-insensitively, ignoring a trailing dot.
+	///   Internationalized names have to be given in their A-label (punycode) form.
+	/// - A wildcard is only recognized as the entire left-most label of a DNS name, like
+	///   `*.example.com`, and matches exactly one non-empty label. It requires at least two
+	///   more labels, so `*.com` matches nothing, and partial-label wildcards like
+	///   `w*.example.com` never match.
+	/// - If `name` is an IP address literal, it is matched against the IP address subject
+	///   alternative names only, see [`matches_ip_address`](Self::matches_ip_address).
+	pub fn matches_hostname(&self, name: &str) -> bool {
+		if let Ok(ip) = IpAddr::from_str(name) {
+			return self.matches_ip_address(ip);
+		}
+
+		let Some(name) = normalize_dns_name(name) else {
+			return false;
+		};
+		if name
+			.split('.')
+			.any(|label| label.is_empty() || label.contains('*'))
+		{
+			return false;
+		}
+
+		self.subject_alt_names(2).iter().any(|san| {
+			let Some(san) = std::str::from_utf8(san).ok().and_then(normalize_dns_name) else {
+				return false;
+			};
+			match san.strip_prefix("*.") {
+				Some(suffix) if suffix.contains('.') => name
+					.split_once('.')
+					.is_some_and(|(_, rest)| rest.eq_ignore_ascii_case(suffix)),
+				Some(_) => false,
+				None => san.eq_ignore_ascii_case(name),
+			}
+		})
+	}
+
+	/// Whether the certificate has a subject alternative name with exactly the IP address `ip`
+	///
+	/// IPv4 and IPv6 addresses are distinct, so an IPv4-mapped IPv6 address doesn't match
+	/// the IPv4 address.
+	pub fn matches_ip_address(&self, ip: IpAddr) -> bool {
+		let octets = match ip {
+			IpAddr::V4(ip) => ip.octets().to_vec(),
+			IpAddr::V6(ip) => ip.octets().to_vec(),
+		};
+		self.subject_alt_names(7).contains(&octets)
+	}
+
+	/// The contents of the subject alternative names with the given context-specific tag
+	///
+	/// A certificate or extension that doesn't parse has no names.
+	fn subject_alt_names(&self, tag: u64) -> Vec<Vec<u8>> {
+		let extensions = self.extensions().unwrap_or_default();
+		let Some(extension) = extensions
+			.iter()
+			.find(|ext| ext.oid == oid::SUBJECT_ALT_NAME)
+		else {
+			return Vec::new();
+		};
+		let names = crate::asn1::parse_der(extension.value(), |reader| {
+			reader.collect_sequence_of(|reader| reader.read_tagged_der())
+		});
+
+		names
+			.unwrap_or_default()
+			.into_iter()
+			.filter(|name| name.tag() == Tag::context(tag))
+			.map(|name| name.value().to_vec())
+			.collect()
+	}
+
+	/// Assembles a certificate from a TBSCertificate and a signature produced outside of rcgen
+	///
+	/// `tbs_der` is the output of [`CertificateParams::serialize_tbs_der()`] and `signature`
+	/// the signature over it, made with `signature_algorithm`. Returns
+	/// [`Error::SignatureAlgorithmMismatch`] if the TBSCertificate names a different algorithm.
+	/// The signature itself isn't verified, see [`assemble_verified()`](Self::assemble_verified).
+	pub fn assemble(
+		tbs_der: &[u8],
+		signature_algorithm: &'static SignatureAlgorithm,
+		signature: &[u8],
+	) -> Result<Self, Error> {
+		check_tbs_der(tbs_der, signature_algorithm)?;
+		Ok(Self {
+			der: assemble_signed_der(tbs_der, signature_algorithm, signature).into(),
+			signature_algorithm,
+		})
+	}
+
+	/// Like [`assemble()`](Self::assemble), but also verifies the signature with the public key
+	/// of the issuer
+	///
+	/// This catches a signature made with the wrong key, or over other data, before the
+	/// certificate is handed out. The signature is checked with
+	/// [`SubjectPublicKeyInfo::verify_with_algorithm()`], so this returns
+	/// [`Error::InvalidSignature`] if it doesn't verify.
+	#[cfg(feature = "crypto")]
+	pub fn assemble_verified(
+		tbs_der: &[u8],
+		signature_algorithm: &'static SignatureAlgorithm,
+		signature: &[u8],
+		issuer_spki: &SubjectPublicKeyInfo,
+	) -> Result<Self, Error> {
+		let cert = Self::assemble(tbs_der, signature_algorithm, signature)?;
+		issuer_spki.verify_with_algorithm(signature_algorithm, tbs_der, signature)?;
+		Ok(cert)
+	}
 }
 
 impl From<Certificate> for CertificateDer<'static> {
@@ -58,10 +203,26 @@ pub struct CertificateParams {
 	pub not_after: OffsetDateTime,
 	pub serial_number: Option<SerialNumber>,
 	pub subject_alt_names: Vec<SanType>,
+	/// Alternative names of the issuer, written as the 'Issuer Alternative Name' extension
+	///
+	/// These are taken as they are, not copied from the issuer certificate. Defaults to empty,
+	/// which omits the extension.
+	pub issuer_alt_names: Vec<SanType>,
+	/// Whether the 'Issuer Alternative Name' extension is marked as critical
+	///
+	/// Defaults to `false`. RFC 5280 requires it to be critical if the subject of the issuer
+	/// is empty, as its alternative names are the only way to identify it.
+	pub issuer_alt_names_critical: bool,
 	pub distinguished_name: DistinguishedName,
 	pub is_ca: IsCa,
 	pub key_usages: Vec<KeyUsagePurpose>,
 	pub extended_key_usages: Vec<ExtendedKeyUsagePurpose>,
+	/// The TLS features that the server has to support, written as the TLS feature extension
+	/// of [RFC 7633](https://www.rfc-editor.org/rfc/rfc7633). Defaults to empty, which omits
+	/// the extension.
+	///
+	/// [`TlsFeature::StatusRequest`] makes the certificate "OCSP must-staple".
+	pub tls_features: Vec<TlsFeature>,
 	pub name_constraints: Option<NameConstraints>,
 	/// An optional list of certificate revocation list (CRL) distribution points as described
 	/// in RFC 5280 Section 4.2.1.13[^1]. Each distribution point contains one or more URIs where
@@ -69,13 +230,133 @@ pub struct CertificateParams {
 	///
 	/// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.13>
 	pub crl_distribution_points: Vec<CrlDistributionPoint>,
+	/// Where delta CRLs for this certificate can be retrieved, written as the freshest CRL
+	/// extension described in RFC 5280 Section 4.2.1.15[^1]. The extension is always
+	/// non-critical and omitted if the list is empty.
+	///
+	/// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.15>
+	pub freshest_crl: Vec<CrlDistributionPoint>,
+	/// An optional list of access descriptions for the authority information access extension
+	/// described in RFC 5280 Section 4.2.2.1[^1], like the URI of an OCSP responder. The URIs
+	/// must be valid IA5 strings, or serialization fails.
+	///
+	/// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.2.1>
+	pub authority_info_access: Vec<(AccessMethod, String)>,
+	/// The policies for the certificate policies extension described in RFC 5280
+	/// Section 4.2.1.4[^1]. Defaults to empty, which omits the extension.
+	///
+	/// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.4>
+	pub certificate_policies: Vec<PolicyInformation>,
+	/// Whether the certificate policies extension is marked as critical
+	///
+	/// Defaults to `false`, which RFC 5280 recommends unless the use of the certificate must
+	/// be limited to the given policies.
+	pub certificate_policies_critical: bool,
+	/// The policy constraints extension described in RFC 5280 Section 4.2.1.11[^1], for CA
+	/// certificates. It is always marked as critical, as required by RFC 5280.
+	///
+	/// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.11>
+	pub policy_constraints: Option<PolicyConstraints>,
+	/// The number of further certificates in a path that may rely on anyPolicy, written as
+	/// the inhibit anyPolicy extension described in RFC 5280 Section 4.2.1.14[^1], for CA
+	/// certificates. It is always marked as critical, as required by RFC 5280.
+	///
+	/// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.14>
+	pub inhibit_any_policy: Option<u32>,
+	/// If `true`, the certificate has the OCSP no check extension described in RFC 6960
+	/// Section 4.2.2.2.1[^1]
+	///
+	/// It's meant for delegated OCSP responder certificates, which also need
+	/// [`ExtendedKeyUsagePurpose::OcspSigning`], and tells clients to trust them without
+	/// checking their revocation status.
+	///
+	/// [^1]: <https://www.rfc-editor.org/rfc/rfc6960#section-4.2.2.2.1>
+	pub ocsp_no_check: bool,
+	/// Attributes of the subject, written as the subject directory attributes extension
+	/// described in RFC 5280 Section 4.2.1.8[^1], which is always non-critical
+	///
+	/// Each attribute type, like dateOfBirth (1.3.6.1.5.5.7.9.1), placeOfBirth
+	/// (1.3.6.1.5.5.7.9.2) or countryOfCitizenship (1.3.6.1.5.5.7.9.4) of RFC 3739, needs at
+	/// least one value, and the values are written in the sorted order of a DER `SET OF`.
+	/// CSRs can't have this extension.
+	///
+	/// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.8>
+	pub subject_directory_attributes: Vec<(Vec<u64>, Vec<AttributeValue>)>,
+	/// The legacy Netscape certificate type extension, non-critical
+	///
+	/// Only needed for old software that predates extended key usages.
+	pub netscape_cert_type: Option<NetscapeCertType>,
+	/// The legacy Netscape comment extension, non-critical
+	///
+	/// The comment is encoded as an IA5String, so serialization fails with
+	/// [`Error::InvalidAsn1String`] if it isn't ASCII.
+	pub netscape_comment: Option<String>,
 	pub custom_extensions: Vec<CustomExtension>,
+	/// If `true`, the certificate is a precertificate for Certificate Transparency, with the
+	/// critical poison extension of RFC 6962 Section 3.1[^1]
+	///
+	/// The final certificate is issued from the same parameters with `ct_poison` unset and
+	/// [`signed_certificate_timestamps`](Self::signed_certificate_timestamps) set. Both
+	/// extensions are written last, so that the certificates are identical otherwise. The
+	/// serial number and validity must not be changed in between.
+	///
+	/// [^1]: <https://www.rfc-editor.org/rfc/rfc6962#section-3.1>
+	pub ct_poison: bool,
+	/// The `SignedCertificateTimestamp` structures returned by Certificate Transparency logs
+	/// for the precertificate, each in its TLS encoding
+	///
+	/// They are written as the SCT list extension of RFC 6962 Section 3.3[^1], which frames
+	/// them as a `SignedCertificateTimestampList`. Defaults to empty, which omits the extension.
+	///
+	/// [^1]: <https://www.rfc-editor.org/rfc/rfc6962#section-3.3>
+	pub signed_certificate_timestamps: Vec<Vec<u8>>,
+	/// Overrides of the criticality of standard extensions
+	///
+	/// Extensions without an entry keep rcgen's default, which follows the recommendations of
+	/// RFC 5280. An entry for [`ExtensionId::CertificatePolicies`] takes precedence over
+	/// [`certificate_policies_critical`](Self::certificate_policies_critical).
+	///
+	/// Overrides that violate a requirement of RFC 5280, like a non-critical name constraints
+	/// extension, fail serialization with [`Error::NonconformingCriticality`] unless
+	/// [`allow_nonconforming_criticality`](Self::allow_nonconforming_criticality) is set.
+	pub extension_criticality: BTreeMap<ExtensionId, bool>,
+	/// If `true`, [`extension_criticality`](Self::extension_criticality) may violate the
+	/// criticality requirements of RFC 5280
+	///
+	/// Only meant for producing test certificates for validators.
+	pub allow_nonconforming_criticality: bool,
+	/// The order of the extensions in the certificate
+	///
+	/// The extensions listed here come first, in the given order, followed by the others in
+	/// rcgen's default order. Listed extensions that the certificate doesn't have are
+	/// skipped. The Certificate Transparency extensions are always written last, as described
+	/// for [`ct_poison`](Self::ct_poison), wherever they're listed. Defaults to `None`, which
+	/// keeps the default order.
+	pub extension_order: Option<Vec<ExtensionId>>,
 	/// If `true`, the 'Authority Key Identifier' extension will be added to the generated cert
 	pub use_authority_key_identifier_extension: bool,
+	/// The form of the 'Authority Key Identifier' extension, if added
+	///
+	/// Defaults to [`AuthorityKeyIdMode::KeyIdentifier`].
+	pub authority_key_identifier_mode: AuthorityKeyIdMode,
+	/// If `true`, the 'Subject Key Identifier' extension will be added to the generated cert
+	///
+	/// Certificates with [`IsCa::NoCa`] never have it. Defaults to `true`.
+	pub use_subject_key_identifier_extension: bool,
 	/// Method to generate key identifiers from public keys
 	///
 	/// Defaults to a truncated SHA-256 digest. See [`KeyIdMethod`] for more information.
 	pub key_identifier_method: KeyIdMethod,
+	/// The X.509 version of the certificate
+	///
+	/// Defaults to [`CertificateVersion::V3`]. Version 1 certificates can't have extensions,
+	/// so serialization fails with [`Error::UnsupportedInV1Certificate`] if any parameter
+	/// that is encoded as an extension is set.
+	pub version: CertificateVersion,
+	/// Whether the [`IssuanceDefaults`](crate::IssuanceDefaults) of the issuer are merged into these parameters
+	///
+	/// Defaults to `true`. The maximum validity of the issuer applies either way.
+	pub use_issuer_defaults: bool,
 }
 
 impl Default for CertificateParams {
@@ -90,22 +371,57 @@ impl Default for CertificateParams {
 			not_after,
 			serial_number: None,
 			subject_alt_names: Vec::new(),
+			issuer_alt_names: Vec::new(),
+			issuer_alt_names_critical: false,
 			distinguished_name,
 			is_ca: IsCa::NoCa,
 			key_usages: Vec::new(),
 			extended_key_usages: Vec::new(),
+			tls_features: Vec::new(),
 			name_constraints: None,
 			crl_distribution_points: Vec::new(),
+			freshest_crl: Vec::new(),
+			authority_info_access: Vec::new(),
+			certificate_policies: Vec::new(),
+			certificate_policies_critical: false,
+			policy_constraints: None,
+			inhibit_any_policy: None,
+			ocsp_no_check: false,
+			subject_directory_attributes: Vec::new(),
+			netscape_cert_type: None,
+			netscape_comment: None,
 			custom_extensions: Vec::new(),
+			ct_poison: false,
+			signed_certificate_timestamps: Vec::new(),
+			extension_criticality: BTreeMap::new(),
+			allow_nonconforming_criticality: false,
+			extension_order: None,
 			use_authority_key_identifier_extension: false,
+			authority_key_identifier_mode: AuthorityKeyIdMode::KeyIdentifier,
+			use_subject_key_identifier_extension: true,
 			#[cfg(feature = "crypto")]
 			key_identifier_method: KeyIdMethod::Sha256,
 			#[cfg(not(feature = "crypto"))]
 			key_identifier_method: KeyIdMethod::PreSpecified(Vec::new()),
+			version: CertificateVersion::V3,
+			use_issuer_defaults: true,
 		}
 	}
 }
 
+/// The version of an X.509 certificate
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub enum CertificateVersion {
+	/// Version 1, which predates extensions
+	///
+	/// Only meant for producing fixtures for legacy systems. The version field is omitted from
+	/// the encoding, as it defaults to version 1.
+	V1,
+	/// Version 3, which supports extensions
+	#[default]
+	V3,
+}
+
 impl CertificateParams {
 	/// Generate certificate parameters with reasonable defaults
 	pub fn new(subject_alt_names: impl Into<Vec<String>>) -> Result<Self, Error> {
@@ -139,11 +455,13 @@ impl CertificateParams {
 	/// [`Certificate::pem`].
 	pub fn signed_by(
 		&self,
-		public_key: &impl PublicKeyData,
+		public_key: &(impl PublicKeyData + ?Sized),
 		issuer: &Issuer<'_, impl SigningKey>,
 	) -> Result<Certificate, Error> {
+		let params = issuer.apply_defaults(self)?;
 		Ok(Certificate {
-			der: self.serialize_der_with_signer(public_key, issuer)?,
+			der: params.serialize_der_with_signer(public_key, issuer)?,
+			signature_algorithm: issuer.signing_key.algorithm(),
 		})
 	}
 
@@ -151,38 +469,213 @@ impl CertificateParams {
 	///
 	/// The returned [`Certificate`] may be serialized using [`Certificate::der`] and
 	/// [`Certificate::pem`].
-	pub fn self_signed(&self, signing_key: &impl SigningKey) -> Result<Certificate, Error> {
+	pub fn self_signed(
+		&self,
+		signing_key: &(impl SigningKey + ?Sized),
+	) -> Result<Certificate, Error> {
 		let issuer = Issuer::from_params(self, signing_key);
 		Ok(Certificate {
 			der: self.serialize_der_with_signer(signing_key, &issuer)?,
+			signature_algorithm: signing_key.algorithm(),
+		})
+	}
+
+	/// Like [`signed_by()`](Self::signed_by), but signs with an [`AsyncSigningKey`]
+	///
+	/// The certificate is byte-for-byte the one [`signed_by()`](Self::signed_by) would produce
+	/// for a key that returns the same signature.
+	pub async fn signed_by_async(
+		&self,
+		public_key: &(impl PublicKeyData + ?Sized),
+		issuer: &Issuer<'_, impl AsyncSigningKey>,
+	) -> Result<Certificate, Error> {
+		let tbs_der = self.serialize_tbs_der(public_key, issuer)?;
+		let signature = issuer.signing_key.sign(&tbs_der).await?;
+		let signature_algorithm = issuer.signing_key.algorithm();
+		Ok(Certificate {
+			der: assemble_signed_der(&tbs_der, signature_algorithm, &signature).into(),
+			signature_algorithm,
+		})
+	}
+
+	/// Like [`self_signed()`](Self::self_signed), but signs with an [`AsyncSigningKey`]
+	pub async fn self_signed_async(
+		&self,
+		signing_key: &impl AsyncSigningKey,
+	) -> Result<Certificate, Error> {
+		let issuer = Issuer::from_params(self, signing_key);
+		let tbs_der = yasna::try_construct_der(|writer| {
+			writer.write_sequence(|writer| self.write_tbs(writer, signing_key, &issuer))
+		})?;
+		let signature = signing_key.sign(&tbs_der).await?;
+		let signature_algorithm = signing_key.algorithm();
+		Ok(Certificate {
+			der: assemble_signed_der(&tbs_der, signature_algorithm, &signature).into(),
+			signature_algorithm,
 		})
 	}
 
 	/// Calculates a subject key identifier for the certificate subject's public key.
 	/// This key identifier is used in the SubjectKeyIdentifier X.509v3 extension.
-	pub fn key_identifier(&self, key: &impl PublicKeyData) -> Vec<u8> {
-		self.key_identifier_method
-			.derive(key.subject_public_key_info())
+	pub fn key_identifier(&self, key: &(impl PublicKeyData + ?Sized)) -> Vec<u8> {
+		self.key_identifier_method.derive(key)
 	}
 
-	#[cfg(all(test, feature = "x509-parser"))]
+	/// Checks the parameters for combinations that are allowed to be serialized,
+	/// but that some validators reject
+	///
+	/// `public_key` is the key that the certificate will be issued for. An empty
+	/// result means that no problems were found.
+	pub fn lint(&self, public_key: &(impl PublicKeyData + ?Sized)) -> Vec<Lint> {
+		let mut lints = Vec::new();
+		lint_key_usages(&self.key_usages, public_key.algorithm(), &mut lints);
+		lint_certificate_policies(&self.certificate_policies, &mut lints);
+		if self.policy_constraints == Some(PolicyConstraints::default()) {
+			lints.push(Lint::EmptyPolicyConstraints);
+		}
+		lints
+	}
+
+	/// Lists the extensions of a DER-encoded certificate, in the order they appear in it
+	///
+	/// This complements [`Issuer::from_ca_cert_der()`], which only takes the parts of the
+	/// certificate that are needed for issuance, and [`Certificate::extensions()`] for
+	/// certificates issued by rcgen. The extension values aren't interpreted, so this works
+	/// for any well-formed certificate.
+	pub fn extensions_from_der<'a>(
+		cert: &'a CertificateDer<'_>,
+	) -> Result<Vec<ExtensionRef<'a>>, Error> {
+		crate::asn1::parse_der(cert, ExtensionRef::read_all)
+			.map_err(|_| Error::CouldNotParseCertificate)
+	}
+
+	#[cfg(feature = "x509-parser")]
 	pub(crate) fn from_ca_cert_der(ca_cert: &CertificateDer<'_>) -> Result<Self, Error> {
-		let (_remainder, x509) = x509_parser::parse_x509_certificate(ca_cert)
-			.map_err(|_| Error::CouldNotParseCertificate)?;
+		Self::from_ca_cert_der_with_options(
+			ca_cert,
+			&ParseLimits::UNLIMITED,
+			&ParseOptions::default(),
+		)
+	}
 
-		Ok(CertificateParams {
+	/// Parses the parameters of an existing certificate from the DER format, rejecting input
+	/// that exceeds `limits` or is refused by `options`
+	///
+	/// The limits on the input length, the number of extensions and subject alternative names,
+	/// and the length of the distinguished name attributes apply, and
+	/// [`Error::ParseLimitExceeded`] is returned for the first one exceeded. With
+	/// [`ParseOptions::reject_raw_names`], alternative names that would be kept as
+	/// [`SanType::Raw`] result in [`Error::InvalidNameType`].
+	///
+	/// Unlike [`Issuer::from_ca_cert_der()`], which only takes the parts of the certificate
+	/// needed for issuance, this takes everything rcgen can represent, e.g. to issue a
+	/// similar certificate. The private key and signature aren't part of the parameters.
+	#[cfg(feature = "x509-parser")]
+	pub fn from_ca_cert_der_with_options(
+		ca_cert: &CertificateDer<'_>,
+		limits: &ParseLimits,
+		options: &ParseOptions,
+	) -> Result<Self, Error> {
+		use x509_parser::extensions::ParsedExtension;
+		use x509_parser::oid_registry::{
+			OID_X509_EXT_AUTHORITY_KEY_IDENTIFIER, OID_X509_EXT_SUBJECT_KEY_IDENTIFIER,
+		};
+
+		limits.check(ParseLimit::InputLength, ca_cert.len())?;
+		let x509 = crate::parse_x509_certificate(ca_cert)?;
+		limits.check(ParseLimit::Extensions, x509.extensions().len())?;
+		limits.check_name(&x509.tbs_certificate.subject)?;
+		let aki = match x509
+			.get_extension_unique(&OID_X509_EXT_AUTHORITY_KEY_IDENTIFIER)
+			.map_err(|_| Error::CouldNotParseCertificate)?
+			.map(|ext| ext.parsed_extension())
+		{
+			Some(ParsedExtension::AuthorityKeyIdentifier(aki)) => Some(aki),
+			Some(_) => return Err(Error::CouldNotParseCertificate),
+			None => None,
+		};
+
+		let params = CertificateParams {
 			is_ca: IsCa::from_x509(&x509)?,
 			subject_alt_names: SanType::from_x509(&x509)?,
+			issuer_alt_names: SanType::issuer_alt_names_from_x509(&x509)?,
+			issuer_alt_names_critical: x509
+				.get_extension_unique(&x509_parser::oid_registry::OID_X509_EXT_ISSUER_ALT_NAME)
+				.map_err(|_| Error::CouldNotParseCertificate)?
+				.is_some_and(|ext| ext.critical),
 			key_usages: KeyUsagePurpose::from_x509(&x509)?,
 			extended_key_usages: ExtendedKeyUsagePurpose::from_x509(&x509)?,
+			tls_features: TlsFeature::from_x509(&x509)?,
+			policy_constraints: x509
+				.policy_constraints()
+				.map_err(|_| Error::CouldNotParseCertificate)?
+				.map(|ext| PolicyConstraints {
+					require_explicit_policy: ext.value.require_explicit_policy,
+					inhibit_policy_mapping: ext.value.inhibit_policy_mapping,
+				}),
+			inhibit_any_policy: x509
+				.inhibit_anypolicy()
+				.map_err(|_| Error::CouldNotParseCertificate)?
+				.map(|ext| ext.value.skip_certs),
+			ocsp_no_check: x509_extension(&x509, oid::OCSP_NO_CHECK)?.is_some(),
+			subject_directory_attributes: match x509_extension(
+				&x509,
+				oid::SUBJECT_DIRECTORY_ATTRIBUTES,
+			)? {
+				Some(ext) => AttributeValue::from_extension_value(ext.value)?,
+				None => Vec::new(),
+			},
+			netscape_cert_type: x509_extension(&x509, oid::NETSCAPE_CERT_TYPE)?
+				.map(|ext| NetscapeCertType::from_extension_value(ext.value))
+				.transpose()?,
+			netscape_comment: x509_extension(&x509, oid::NETSCAPE_COMMENT)?
+				.map(|ext| {
+					crate::asn1::parse_der(ext.value, |reader| reader.read_ia5_string())
+						.map_err(|_| Error::CouldNotParseCertificate)
+				})
+				.transpose()?,
+			ct_poison: x509_extension(&x509, oid::CT_POISON)?.is_some(),
+			signed_certificate_timestamps: match x509_extension(&x509, oid::CT_SCT_LIST)? {
+				Some(ext) => parse_sct_list(ext.value).ok_or(Error::CouldNotParseCertificate)?,
+				None => Vec::new(),
+			},
 			name_constraints: NameConstraints::from_x509(&x509)?,
-			serial_number: Some(x509.serial.to_bytes_be().into()),
+			freshest_crl: match x509_extension(&x509, oid::FRESHEST_CRL)? {
+				Some(ext) => CrlDistributionPoint::from_extension_value(ext.value)?,
+				None => Vec::new(),
+			},
+			authority_info_access: AccessMethod::from_x509(&x509)?,
+			certificate_policies: PolicyInformation::from_x509(&x509)?,
+			certificate_policies_critical: x509
+				.get_extension_unique(&x509_parser::oid_registry::OID_X509_EXT_CERTIFICATE_POLICIES)
+				.map_err(|_| Error::CouldNotParseCertificate)?
+				.is_some_and(|ext| ext.critical),
+			serial_number: Some(SerialNumber::from_x509(&x509)?),
+			use_authority_key_identifier_extension: aki.is_some(),
+			authority_key_identifier_mode: aki.map_or(AuthorityKeyIdMode::KeyIdentifier, |aki| {
+				match (
+					aki.key_identifier.is_some(),
+					aki.authority_cert_serial.is_some(),
+				) {
+					(true, true) => AuthorityKeyIdMode::Both,
+					(false, true) => AuthorityKeyIdMode::IssuerAndSerialNumber,
+					_ => AuthorityKeyIdMode::KeyIdentifier,
+				}
+			}),
+			use_subject_key_identifier_extension: x509
+				.get_extension_unique(&OID_X509_EXT_SUBJECT_KEY_IDENTIFIER)
+				.map_err(|_| Error::CouldNotParseCertificate)?
+				.is_some(),
 			key_identifier_method: KeyIdMethod::from_x509(&x509)?,
 			distinguished_name: DistinguishedName::from_name(&x509.tbs_certificate.subject)?,
 			not_before: x509.validity().not_before.to_datetime(),
 			not_after: x509.validity().not_after.to_datetime(),
 			..Default::default()
-		})
+		};
+		limits.check(ParseLimit::SubjectAltNames, params.subject_alt_names.len())?;
+		options.check_raw_names(&params.subject_alt_names)?;
+		options.check_raw_names(&params.issuer_alt_names)?;
+		Ok(params)
 	}
 
 	/// Write a CSR extension request attribute as defined in [RFC 2985].
@@ -200,6 +693,7 @@ impl CertificateParams {
 					// Write subject_alt_names
 					self.write_subject_alt_names(writer.next());
 					self.write_extended_key_usage(writer.next());
+					self.write_tls_features(writer.next());
 					// Write is_ca
 					self.write_is_ca(writer.next());
 
@@ -221,7 +715,8 @@ impl CertificateParams {
 		}
 
 		// "When present, conforming CAs SHOULD mark this extension as critical."
-		write_x509_extension(writer, oid::KEY_USAGE, true, |writer| {
+		let critical = self.criticality(&ExtensionId::KeyUsage, true);
+		write_x509_extension(writer, oid::KEY_USAGE, critical, |writer| {
 			// u16 is large enough to encode the largest possible key usage (two-bytes)
 			let bit_string = self.key_usages.iter().fold(0u16, |bit_string, key_usage| {
 				bit_string | key_usage.to_u16()
@@ -241,7 +736,8 @@ impl CertificateParams {
 
 	fn write_extended_key_usage(&self, writer: DERWriter) {
 		if !self.extended_key_usages.is_empty() {
-			write_x509_extension(writer, oid::EXT_KEY_USAGE, false, |writer| {
+			let critical = self.criticality(&ExtensionId::ExtendedKeyUsage, false);
+			write_x509_extension(writer, oid::EXT_KEY_USAGE, critical, |writer| {
 				writer.write_sequence(|writer| {
 					for usage in &self.extended_key_usages {
 						writer
@@ -253,6 +749,22 @@ impl CertificateParams {
 		}
 	}
 
+	/// Write a certificate's TLS feature extension as defined in RFC 7633.
+	fn write_tls_features(&self, writer: DERWriter) {
+		if self.tls_features.is_empty() {
+			return;
+		}
+
+		let critical = self.criticality(&ExtensionId::TlsFeature, false);
+		write_x509_extension(writer, oid::TLS_FEATURE, critical, |writer| {
+			writer.write_sequence(|writer| {
+				for feature in &self.tls_features {
+					writer.next().write_u16(feature.value());
+				}
+			});
+		});
+	}
+
 	/// Write a certificate's BasicConstraints as defined in RFC 5280.
 	fn write_is_ca(&self, writer: DERWriter) {
 		let is_ca = match &self.is_ca {
@@ -262,7 +774,8 @@ impl CertificateParams {
 		};
 
 		// Write basic_constraints
-		write_x509_extension(writer, oid::BASIC_CONSTRAINTS, true, |writer| {
+		let critical = self.criticality(&ExtensionId::BasicConstraints, true);
+		write_x509_extension(writer, oid::BASIC_CONSTRAINTS, critical, |writer| {
 			writer.write_sequence(|writer| {
 				writer.next().write_bool(is_ca.is_some()); // cA flag
 				if let Some(BasicConstraints::Constrained(path_len_constraint)) = is_ca {
@@ -279,34 +792,90 @@ impl CertificateParams {
 
 		// Per https://tools.ietf.org/html/rfc5280#section-4.1.2.6, SAN must be marked
 		// as critical if subject is empty.
-		let critical = self.distinguished_name.entries.is_empty();
+		let critical = self.criticality(
+			&ExtensionId::SubjectAltName,
+			self.distinguished_name.entries.is_empty(),
+		);
 		write_x509_extension(writer, oid::SUBJECT_ALT_NAME, critical, |writer| {
-			writer.write_sequence(|writer| {
-				for san in self.subject_alt_names.iter() {
-					writer.next().write_tagged_implicit(
-						Tag::context(san.tag()),
-						|writer| match san {
-							SanType::Rfc822Name(name)
-							| SanType::DnsName(name)
-							| SanType::URI(name) => writer.write_ia5_string(name.as_str()),
-							SanType::IpAddress(IpAddr::V4(addr)) => {
-								writer.write_bytes(&addr.octets())
-							},
-							SanType::IpAddress(IpAddr::V6(addr)) => {
-								writer.write_bytes(&addr.octets())
-							},
-							SanType::OtherName((oid, value)) => {
-								// otherName SEQUENCE { OID, [0] explicit any defined by oid }
-								// https://datatracker.ietf.org/doc/html/rfc5280#page-38
-								writer.write_sequence(|writer| {
-									writer.next().write_oid(&ObjectIdentifier::from_slice(oid));
-									value.write_der(writer.next());
-								});
-							},
-						},
-					);
-				}
-			});
+			write_general_names(writer, &self.subject_alt_names)
+		});
+	}
+
+	/// The criticality of the given extension, after applying the overrides
+	fn criticality(&self, id: &ExtensionId, default: bool) -> bool {
+		self.extension_criticality
+			.get(id)
+			.copied()
+			.unwrap_or(default)
+	}
+
+	/// Rejects criticality overrides of present extensions that violate RFC 5280
+	fn check_criticality(&self) -> Result<(), Error> {
+		if self.allow_nonconforming_criticality {
+			return Ok(());
+		}
+
+		let present = |id: &ExtensionId| match id {
+			ExtensionId::AuthorityKeyIdentifier => self.use_authority_key_identifier_extension,
+			ExtensionId::SubjectAltName => !self.subject_alt_names.is_empty(),
+			ExtensionId::SubjectKeyIdentifier => {
+				self.use_subject_key_identifier_extension && !matches!(self.is_ca, IsCa::NoCa)
+			},
+			ExtensionId::BasicConstraints => matches!(self.is_ca, IsCa::Ca(_)),
+			ExtensionId::NameConstraints => self.name_constraints.iter().any(|c| !c.is_empty()),
+			ExtensionId::FreshestCrl => !self.freshest_crl.is_empty(),
+			ExtensionId::AuthorityInfoAccess => !self.authority_info_access.is_empty(),
+			ExtensionId::PolicyConstraints => self.policy_constraints.is_some(),
+			ExtensionId::InhibitAnyPolicy => self.inhibit_any_policy.is_some(),
+			ExtensionId::CtPoison => self.ct_poison,
+			ExtensionId::SubjectDirectoryAttributes => {
+				!self.subject_directory_attributes.is_empty()
+			},
+			_ => false,
+		};
+		for (id, critical) in &self.extension_criticality {
+			let conforming = match id {
+				// Section 4.1.2.6: the subjectAltName extension MUST be critical if the
+				// subject is empty
+				ExtensionId::SubjectAltName => {
+					*critical || !self.distinguished_name.entries.is_empty()
+				},
+				// Section 4.2.1.9: CAs MUST mark basicConstraints as critical in CA certificates
+				// Section 4.2.1.10: nameConstraints MUST be marked as critical
+				// Sections 4.2.1.11 and 4.2.1.14: so must policyConstraints and inhibitAnyPolicy
+				// RFC 6962 Section 3.1: the precertificate poison is critical
+				ExtensionId::BasicConstraints
+				| ExtensionId::NameConstraints
+				| ExtensionId::PolicyConstraints
+				| ExtensionId::InhibitAnyPolicy
+				| ExtensionId::CtPoison => *critical,
+				// Sections 4.2.1.1, 4.2.1.2, 4.2.1.8, 4.2.1.15 and 4.2.2.1:
+				// authorityKeyIdentifier, subjectKeyIdentifier, subjectDirectoryAttributes,
+				// freshestCRL and authorityInfoAccess MUST be marked as non-critical
+				ExtensionId::AuthorityKeyIdentifier
+				| ExtensionId::SubjectKeyIdentifier
+				| ExtensionId::SubjectDirectoryAttributes
+				| ExtensionId::FreshestCrl
+				| ExtensionId::AuthorityInfoAccess => !*critical,
+				_ => true,
+			};
+			if !conforming && present(id) {
+				return Err(Error::NonconformingCriticality(id.clone()));
+			}
+		}
+		Ok(())
+	}
+
+	/// Write a certificate's IssuerAltName as defined in RFC 5280.
+	fn write_issuer_alt_names(&self, writer: DERWriter) {
+		if self.issuer_alt_names.is_empty() {
+			return;
+		}
+
+		let critical =
+			self.criticality(&ExtensionId::IssuerAltName, self.issuer_alt_names_critical);
+		write_x509_extension(writer, oid::ISSUER_ALT_NAME, critical, |writer| {
+			write_general_names(writer, &self.issuer_alt_names)
 		});
 	}
 
@@ -320,7 +889,7 @@ impl CertificateParams {
 	/// same output.
 	pub fn serialize_request(
 		&self,
-		subject_key: &impl SigningKey,
+		subject_key: &(impl SigningKey + ?Sized),
 	) -> Result<CertificateSigningRequest, Error> {
 		self.serialize_request_with_attributes(subject_key, Vec::new())
 	}
@@ -338,9 +907,72 @@ impl CertificateParams {
 	/// [RFC 2986]: <https://datatracker.ietf.org/doc/html/rfc2986#section-4>
 	pub fn serialize_request_with_attributes(
 		&self,
-		subject_key: &impl SigningKey,
+		subject_key: &(impl SigningKey + ?Sized),
+		attrs: Vec<Attribute>,
+	) -> Result<CertificateSigningRequest, Error> {
+		let sign = || {
+			sign_der(subject_key, |writer| {
+				self.write_request_info(writer, subject_key, attrs)
+			})
+		};
+		#[cfg(feature = "tracing")]
+		let der = trace::certificate_request(self, subject_key, sign)?;
+		#[cfg(not(feature = "tracing"))]
+		let der = sign()?;
+
+		Ok(CertificateSigningRequest {
+			der: CertificateSigningRequestDer::from(der),
+		})
+	}
+
+	/// Like [`serialize_request()`](Self::serialize_request), but signs with an
+	/// [`AsyncSigningKey`]
+	pub async fn serialize_request_async(
+		&self,
+		subject_key: &impl AsyncSigningKey,
+	) -> Result<CertificateSigningRequest, Error> {
+		self.serialize_request_with_attributes_async(subject_key, Vec::new())
+			.await
+	}
+
+	/// Like [`serialize_request_with_attributes()`](Self::serialize_request_with_attributes),
+	/// but signs with an [`AsyncSigningKey`]
+	pub async fn serialize_request_with_attributes_async(
+		&self,
+		subject_key: &impl AsyncSigningKey,
 		attrs: Vec<Attribute>,
 	) -> Result<CertificateSigningRequest, Error> {
+		let tbs_der = self.serialize_request_tbs_der(subject_key, attrs)?;
+		let signature = subject_key.sign(&tbs_der).await?;
+		let der = assemble_signed_der(&tbs_der, subject_key.algorithm(), &signature);
+		Ok(CertificateSigningRequest {
+			der: CertificateSigningRequestDer::from(der),
+		})
+	}
+
+	/// Serializes the CertificationRequestInfo, i.e. the part of a CSR covered by the signature
+	///
+	/// Like [`serialize_tbs_der()`](Self::serialize_tbs_der), this allows signing with a key that
+	/// isn't available to rcgen: sign the returned bytes with the subject's key, then pass the
+	/// signature to [`CertificateSigningRequest::assemble()`]. Pass an empty `attrs` for the
+	/// request that [`serialize_request()`](Self::serialize_request) would produce.
+	pub fn serialize_request_tbs_der(
+		&self,
+		subject_key: &(impl PublicKeyData + ?Sized),
+		attrs: Vec<Attribute>,
+	) -> Result<Vec<u8>, Error> {
+		yasna::try_construct_der(|writer| {
+			writer.write_sequence(|writer| self.write_request_info(writer, subject_key, attrs))
+		})
+	}
+
+	/// Writes the members of the CertificationRequestInfo of a CSR
+	fn write_request_info(
+		&self,
+		writer: &mut DERWriterSeq,
+		subject_key: &(impl PublicKeyData + ?Sized),
+		attrs: Vec<Attribute>,
+	) -> Result<(), Error> {
 		// No .. pattern, we use this to ensure every field is used
 		#[deny(unused)]
 		let Self {
@@ -348,19 +980,51 @@ impl CertificateParams {
 			not_after,
 			serial_number,
 			subject_alt_names,
+			issuer_alt_names,
+			issuer_alt_names_critical,
 			distinguished_name,
 			is_ca,
 			key_usages,
 			extended_key_usages,
+			tls_features,
 			name_constraints,
 			crl_distribution_points,
+			freshest_crl,
+			authority_info_access,
+			certificate_policies,
+			certificate_policies_critical,
+			policy_constraints,
+			inhibit_any_policy,
+			ocsp_no_check,
+			subject_directory_attributes,
+			netscape_cert_type,
+			netscape_comment,
 			custom_extensions,
+			ct_poison,
+			signed_certificate_timestamps,
+			extension_criticality,
+			allow_nonconforming_criticality,
+			extension_order,
 			use_authority_key_identifier_extension,
+			authority_key_identifier_mode,
+			use_subject_key_identifier_extension,
 			key_identifier_method,
+			version,
+			use_issuer_defaults,
 		} = self;
 		// - subject_key will be used by the caller
 		// - not_before and not_after cannot be put in a CSR
+		// - version only applies to the certificate, CSRs have their own
+		// - use_issuer_defaults is up to the issuer of the requested certificate
+		// - issuer_alt_names_critical and certificate_policies_critical are unused, as
+		// their extensions are rejected
 		// - key_identifier_method is here because self.write_extended_key_usage uses it
+		// - extension_criticality and allow_nonconforming_criticality are applied by the
+		// methods writing the extensions
+		// - extension_order only applies to the certificate
+		// - authority_key_identifier_mode only matters with
+		// use_authority_key_identifier_extension, which is rejected
+		// - use_subject_key_identifier_extension is unused, as CSRs never have the extension
 		// - There might be a use case for specifying the key identifier
 		// in the CSR, but in the current API it can't be distinguished
 		// from the defaults so this is left for a later version if
@@ -370,10 +1034,31 @@ impl CertificateParams {
 			not_after,
 			key_identifier_method,
 			extended_key_usages,
+			version,
+			use_issuer_defaults,
+			issuer_alt_names_critical,
+			certificate_policies_critical,
+			extension_criticality,
+			allow_nonconforming_criticality,
+			extension_order,
+			authority_key_identifier_mode,
+			use_subject_key_identifier_extension,
 		);
 		if serial_number.is_some()
+			|| !issuer_alt_names.is_empty()
 			|| name_constraints.is_some()
 			|| !crl_distribution_points.is_empty()
+			|| !freshest_crl.is_empty()
+			|| !authority_info_access.is_empty()
+			|| !certificate_policies.is_empty()
+			|| policy_constraints.is_some()
+			|| inhibit_any_policy.is_some()
+			|| *ocsp_no_check
+			|| !subject_directory_attributes.is_empty()
+			|| netscape_cert_type.is_some()
+			|| netscape_comment.is_some()
+			|| *ct_poison
+			|| !signed_certificate_timestamps.is_empty()
 			|| *use_authority_key_identifier_extension
 		{
 			return Err(Error::UnsupportedInCsr);
@@ -383,152 +1068,258 @@ impl CertificateParams {
 		let write_extension_request = !key_usages.is_empty()
 			|| !subject_alt_names.is_empty()
 			|| !extended_key_usages.is_empty()
+			|| !tls_features.is_empty()
 			|| !custom_extensions.is_empty()
 			|| matches!(is_ca, IsCa::ExplicitNoCa | IsCa::Ca(_));
+		self.check_criticality()?;
+
+		// Write version
+		writer.next().write_u8(0);
+		write_distinguished_name(writer.next(), distinguished_name);
+		serialize_public_key_der(subject_key, writer.next());
+
+		// According to the spec in RFC 2986, even if attributes are empty we need the empty attribute tag
+		writer
+			.next()
+			.write_tagged_implicit(Tag::context(0), |writer| {
+				// RFC 2986 specifies that attributes are a SET OF Attribute
+				writer.write_set_of(|writer| {
+					if write_extension_request {
+						self.write_extension_request_attribute(writer.next());
+					}
 
-		let der = sign_der(subject_key, |writer| {
-			// Write version
-			writer.next().write_u8(0);
-			write_distinguished_name(writer.next(), distinguished_name);
-			serialize_public_key_der(subject_key, writer.next());
-
-			// According to the spec in RFC 2986, even if attributes are empty we need the empty attribute tag
-			writer
-				.next()
-				.write_tagged_implicit(Tag::context(0), |writer| {
-					// RFC 2986 specifies that attributes are a SET OF Attribute
-					writer.write_set_of(|writer| {
-						if write_extension_request {
-							self.write_extension_request_attribute(writer.next());
-						}
-
-						for Attribute { oid, values } in attrs {
-							writer.next().write_sequence(|writer| {
-								writer.next().write_oid(&ObjectIdentifier::from_slice(oid));
-								writer.next().write_der(&values);
-							});
-						}
-					});
+					for Attribute { oid, values } in attrs {
+						writer.next().write_sequence(|writer| {
+							writer.next().write_oid(&ObjectIdentifier::from_slice(oid));
+							writer.next().write_der(&values);
+						});
+					}
 				});
+			});
 
-			Ok(())
-		})?;
+		Ok(())
+	}
 
-		Ok(CertificateSigningRequest {
-			der: CertificateSigningRequestDer::from(der),
+	/// Serializes the TBSCertificate, i.e. the part of the certificate covered by the signature
+	///
+	/// This is the first phase of issuing a certificate with a key that isn't available to rcgen,
+	/// e.g. one on an offline HSM: sign the returned bytes with the issuer's key, then pass the
+	/// signature to [`Certificate::assemble()`]. Only the public key of `issuer` is used.
+	///
+	/// All fields, including the serial number, validity and authority key identifier, are fixed
+	/// by the returned bytes, and calling this again with the same arguments yields the same
+	/// bytes. For a self-signed certificate, pass an issuer created with
+	/// [`Issuer::from_params()`] from these parameters and the subject's public key.
+	pub fn serialize_tbs_der(
+		&self,
+		public_key: &(impl PublicKeyData + ?Sized),
+		issuer: &Issuer<'_, impl PublicKeyData>,
+	) -> Result<Vec<u8>, Error> {
+		let params = issuer.apply_defaults(self)?;
+		yasna::try_construct_der(|writer| {
+			writer.write_sequence(|writer| params.write_tbs(writer, public_key, issuer))
 		})
 	}
 
-	pub(crate) fn serialize_der_with_signer<K: PublicKeyData>(
+	pub(crate) fn serialize_der_with_signer<K: PublicKeyData + ?Sized>(
 		&self,
 		pub_key: &K,
 		issuer: &Issuer<'_, impl SigningKey>,
 	) -> Result<CertificateDer<'static>, Error> {
-		let der = sign_der(&issuer.signing_key, |writer| {
-			let pub_key_spki = pub_key.subject_public_key_info();
-			// Write version
-			writer.next().write_tagged(Tag::context(0), |writer| {
+		let sign = || {
+			sign_der(&issuer.signing_key, |writer| {
+				self.write_tbs(writer, pub_key, issuer)
+			})
+		};
+		#[cfg(feature = "tracing")]
+		let der = trace::certificate(self, pub_key, issuer, sign)?;
+		#[cfg(not(feature = "tracing"))]
+		let der = sign()?;
+
+		Ok(der.into())
+	}
+
+	fn write_tbs(
+		&self,
+		writer: &mut DERWriterSeq,
+		pub_key: &(impl PublicKeyData + ?Sized),
+		issuer: &Issuer<'_, impl PublicKeyData>,
+	) -> Result<(), Error> {
+		// Write version
+		// RFC 5280 §4.1.2.1:
+		//   When extensions are used, as expected in this profile, version MUST be 3
+		//   (value is 2).
+		// Version 1 is the DEFAULT, so it's omitted from the DER encoding.
+		match self.version {
+			CertificateVersion::V1 => self.check_v1()?,
+			CertificateVersion::V3 => writer.next().write_tagged(Tag::context(0), |writer| {
 				writer.write_u8(2);
-			});
-			// Write serialNumber
-			if let Some(ref serial) = self.serial_number {
-				writer.next().write_bigint_bytes(serial.as_ref(), true);
-			} else {
-				#[cfg(feature = "crypto")]
-				{
-					let hash = digest::digest(&digest::SHA256, pub_key.der_bytes());
-					// RFC 5280 specifies at most 20 bytes for a serial number
-					let mut sl = hash.as_ref()[0..20].to_vec();
-					sl[0] &= 0x7f; // MSB must be 0 to ensure encoding bignum in 20 bytes
-					writer.next().write_bigint_bytes(&sl, true);
-				}
-				#[cfg(not(feature = "crypto"))]
-				if self.serial_number.is_none() {
-					return Err(Error::MissingSerialNumber);
-				}
-			};
-			// Write signature algorithm
-			issuer
-				.signing_key
-				.algorithm()
-				.write_alg_ident(writer.next());
-			// Write issuer name
-			write_distinguished_name(writer.next(), issuer.distinguished_name.as_ref());
-			// Write validity
-			writer.next().write_sequence(|writer| {
-				// Not before
-				write_dt_utc_or_generalized(writer.next(), self.not_before);
-				// Not after
-				write_dt_utc_or_generalized(writer.next(), self.not_after);
-				Ok::<(), Error>(())
-			})?;
-			// Write subject
-			write_distinguished_name(writer.next(), &self.distinguished_name);
-			// Write subjectPublicKeyInfo
-			serialize_public_key_der(pub_key, writer.next());
-			// write extensions
-			let should_write_exts = self.use_authority_key_identifier_extension
-				|| !self.subject_alt_names.is_empty()
-				|| !self.extended_key_usages.is_empty()
-				|| self.name_constraints.iter().any(|c| !c.is_empty())
-				|| matches!(self.is_ca, IsCa::ExplicitNoCa)
-				|| matches!(self.is_ca, IsCa::Ca(_))
-				|| !self.custom_extensions.is_empty();
-			if !should_write_exts {
-				return Ok(());
+			}),
+		}
+		// Write serialNumber
+		if let Some(ref serial) = self.serial_number {
+			writer.next().write_bigint_bytes(serial.as_ref(), true);
+		} else {
+			#[cfg(feature = "crypto")]
+			writer
+				.next()
+				.write_bigint_bytes(&derive_serial_number(pub_key), true);
+			#[cfg(not(feature = "crypto"))]
+			if self.serial_number.is_none() {
+				return Err(Error::MissingSerialNumber);
 			}
+		};
+		// Write signature algorithm
+		issuer
+			.signing_key
+			.algorithm()
+			.write_alg_ident(writer.next());
+		// Write issuer name
+		write_distinguished_name(writer.next(), issuer.distinguished_name.as_ref());
+		// Write validity
+		writer.next().write_sequence(|writer| {
+			// Not before
+			write_dt_utc_or_generalized(writer.next(), self.not_before);
+			// Not after
+			write_dt_utc_or_generalized(writer.next(), self.not_after);
+			Ok::<(), Error>(())
+		})?;
+		// Write subject
+		write_distinguished_name(writer.next(), &self.distinguished_name);
+		// Write subjectPublicKeyInfo
+		serialize_public_key_der(pub_key, writer.next());
+		// write extensions
+		let should_write_exts = self.use_authority_key_identifier_extension
+			|| !self.subject_alt_names.is_empty()
+			|| !self.issuer_alt_names.is_empty()
+			|| !self.key_usages.is_empty()
+			|| !self.extended_key_usages.is_empty()
+			|| !self.tls_features.is_empty()
+			|| !self.crl_distribution_points.is_empty()
+			|| !self.freshest_crl.is_empty()
+			|| !self.authority_info_access.is_empty()
+			|| !self.certificate_policies.is_empty()
+			|| self.policy_constraints.is_some()
+			|| self.inhibit_any_policy.is_some()
+			|| self.ocsp_no_check
+			|| !self.subject_directory_attributes.is_empty()
+			|| self.netscape_cert_type.is_some()
+			|| self.netscape_comment.is_some()
+			|| self.name_constraints.iter().any(|c| !c.is_empty())
+			|| matches!(self.is_ca, IsCa::ExplicitNoCa)
+			|| matches!(self.is_ca, IsCa::Ca(_))
+			|| !self.custom_extensions.is_empty()
+			|| self.ct_poison
+			|| !self.signed_certificate_timestamps.is_empty();
+		if !should_write_exts {
+			return Ok(());
+		}
 
-			writer.next().write_tagged(Tag::context(3), |writer| {
-				writer.write_sequence(|writer| self.write_extensions(writer, &pub_key_spki, issuer))
-			})?;
-
-			Ok(())
+		writer.next().write_tagged(Tag::context(3), |writer| {
+			writer.write_sequence(|writer| self.write_extensions(writer, pub_key, issuer))
 		})?;
 
-		Ok(der.into())
+		Ok(())
+	}
+
+	/// Checks that no parameter is set that would have to be encoded as an extension
+	fn check_v1(&self) -> Result<(), Error> {
+		if self.use_authority_key_identifier_extension
+			|| !self.subject_alt_names.is_empty()
+			|| !self.issuer_alt_names.is_empty()
+			|| !self.key_usages.is_empty()
+			|| !self.extended_key_usages.is_empty()
+			|| !self.tls_features.is_empty()
+			|| self.name_constraints.is_some()
+			|| !self.crl_distribution_points.is_empty()
+			|| !self.freshest_crl.is_empty()
+			|| !self.authority_info_access.is_empty()
+			|| !self.certificate_policies.is_empty()
+			|| self.policy_constraints.is_some()
+			|| self.inhibit_any_policy.is_some()
+			|| self.ocsp_no_check
+			|| !self.subject_directory_attributes.is_empty()
+			|| self.netscape_cert_type.is_some()
+			|| self.netscape_comment.is_some()
+			|| !matches!(self.is_ca, IsCa::NoCa)
+			|| !self.custom_extensions.is_empty()
+			|| self.ct_poison
+			|| !self.signed_certificate_timestamps.is_empty()
+		{
+			return Err(Error::UnsupportedInV1Certificate);
+		}
+		Ok(())
 	}
 
 	fn write_extensions(
 		&self,
 		writer: &mut DERWriterSeq,
-		pub_key_spki: &[u8],
-		issuer: &Issuer<'_, impl SigningKey>,
+		pub_key: &(impl PublicKeyData + ?Sized),
+		issuer: &Issuer<'_, impl PublicKeyData>,
 	) -> Result<(), Error> {
+		self.check_criticality()?;
+		let mut extensions = Extensions::default();
 		if self.use_authority_key_identifier_extension {
-			write_x509_authority_key_identifier(
-				writer.next(),
-				match issuer.key_identifier_method.as_ref() {
-					KeyIdMethod::PreSpecified(aki) => aki.clone(),
-					#[cfg(feature = "crypto")]
-					_ => issuer
-						.key_identifier_method
-						.derive(issuer.signing_key.subject_public_key_info()),
-				},
+			let mode = self.authority_key_identifier_mode;
+			let key_id = match mode.key_identifier() {
+				true => Some(issuer.authority_key_identifier(&issuer.key_identifier_method)),
+				false => None,
+			};
+			let cert_issuer = match mode.issuer_and_serial_number() {
+				true => Some(issuer.authority_cert_issuer_and_serial()?),
+				false => None,
+			};
+			extensions.push_der(
+				oid::AUTHORITY_KEY_IDENTIFIER,
+				yasna::construct_der(|writer| {
+					write_x509_authority_key_identifier(
+						writer,
+						key_id.as_deref(),
+						cert_issuer
+							.as_ref()
+							.map(|(name, serial)| (*name, &serial[..])),
+						self.criticality(&ExtensionId::AuthorityKeyIdentifier, false),
+					)
+				}),
 			);
 		}
 
 		// Write subject_alt_names
-		self.write_subject_alt_names(writer.next());
+		extensions.push_der(
+			oid::SUBJECT_ALT_NAME,
+			yasna::construct_der(|writer| self.write_subject_alt_names(writer)),
+		);
+
+		// Write issuer_alt_names
+		extensions.push_der(
+			oid::ISSUER_ALT_NAME,
+			yasna::construct_der(|writer| self.write_issuer_alt_names(writer)),
+		);
 
 		// Write standard key usage
-		self.write_key_usage(writer.next());
+		extensions.push_der(
+			oid::KEY_USAGE,
+			yasna::construct_der(|writer| self.write_key_usage(writer)),
+		);
 
 		// Write extended key usage
-		if !self.extended_key_usages.is_empty() {
-			write_x509_extension(writer.next(), oid::EXT_KEY_USAGE, false, |writer| {
-				writer.write_sequence(|writer| {
-					for usage in self.extended_key_usages.iter() {
-						let oid = ObjectIdentifier::from_slice(usage.oid());
-						writer.next().write_oid(&oid);
-					}
-				});
-			});
-		}
+		extensions.push_der(
+			oid::EXT_KEY_USAGE,
+			yasna::construct_der(|writer| self.write_extended_key_usage(writer)),
+		);
+
+		// Write TLS features
+		extensions.push_der(
+			oid::TLS_FEATURE,
+			yasna::construct_der(|writer| self.write_tls_features(writer)),
+		);
 
 		if let Some(name_constraints) = &self.name_constraints {
+			name_constraints.check()?;
 			// If both trees are empty, the extension must be omitted.
 			if !name_constraints.is_empty() {
-				write_x509_extension(writer.next(), oid::NAME_CONSTRAINTS, true, |writer| {
+				let critical = self.criticality(&ExtensionId::NameConstraints, true);
+				extensions.push(oid::NAME_CONSTRAINTS, critical, |writer| {
 					writer.write_sequence(|writer| {
 						if !name_constraints.permitted_subtrees.is_empty() {
 							write_general_subtrees(
@@ -550,68 +1341,178 @@ impl CertificateParams {
 		}
 
 		if !self.crl_distribution_points.is_empty() {
-			write_x509_extension(
-				writer.next(),
-				oid::CRL_DISTRIBUTION_POINTS,
-				false,
-				|writer| {
-					writer.write_sequence(|writer| {
-						for distribution_point in &self.crl_distribution_points {
-							distribution_point.write_der(writer.next());
-						}
-					})
-				},
+			let critical = self.criticality(&ExtensionId::CrlDistributionPoints, false);
+			extensions.push(oid::CRL_DISTRIBUTION_POINTS, critical, |writer| {
+				write_distribution_points(writer, &self.crl_distribution_points)
+			});
+		}
+
+		if !self.freshest_crl.is_empty() {
+			let critical = self.criticality(&ExtensionId::FreshestCrl, false);
+			extensions.push(oid::FRESHEST_CRL, critical, |writer| {
+				write_distribution_points(writer, &self.freshest_crl)
+			});
+		}
+
+		if !self.authority_info_access.is_empty() {
+			let descriptions = self
+				.authority_info_access
+				.iter()
+				.map(|(method, uri)| Ok((method.oid(), Ia5String::try_from(uri.as_str())?)))
+				.collect::<Result<Vec<_>, Error>>()?;
+			let critical = self.criticality(&ExtensionId::AuthorityInfoAccess, false);
+			extensions.push(oid::AUTHORITY_INFO_ACCESS, critical, |writer| {
+				writer.write_sequence(|writer| {
+					for (method, uri) in &descriptions {
+						// AccessDescription SEQUENCE { accessMethod, accessLocation }
+						writer.next().write_sequence(|writer| {
+							writer
+								.next()
+								.write_oid(&ObjectIdentifier::from_slice(method));
+							// uniformResourceIdentifier [6] IA5String
+							writer
+								.next()
+								.write_tagged_implicit(Tag::context(6), |writer| {
+									writer.write_ia5_string(uri.as_str())
+								});
+						});
+					}
+				})
+			});
+		}
+
+		if !self.certificate_policies.is_empty() {
+			for policy in &self.certificate_policies {
+				policy.check()?;
+			}
+			let critical = self.criticality(
+				&ExtensionId::CertificatePolicies,
+				self.certificate_policies_critical,
 			);
+			extensions.push(oid::CERTIFICATE_POLICIES, critical, |writer| {
+				writer.write_sequence(|writer| {
+					for policy in &self.certificate_policies {
+						policy.write_der(writer.next());
+					}
+				})
+			});
 		}
 
-		match self.is_ca {
-			IsCa::Ca(ref constraint) => {
-				// Write subject_key_identifier
-				write_x509_extension(
-					writer.next(),
-					oid::SUBJECT_KEY_IDENTIFIER,
-					false,
-					|writer| {
-						writer.write_bytes(&self.key_identifier_method.derive(pub_key_spki));
-					},
-				);
-				// Write basic_constraints
-				write_x509_extension(writer.next(), oid::BASIC_CONSTRAINTS, true, |writer| {
-					writer.write_sequence(|writer| {
-						writer.next().write_bool(true); // cA flag
-						if let BasicConstraints::Constrained(path_len_constraint) = constraint {
-							writer.next().write_u8(*path_len_constraint);
+		if let Some(constraints) = &self.policy_constraints {
+			let critical = self.criticality(&ExtensionId::PolicyConstraints, true);
+			extensions.push(oid::POLICY_CONSTRAINTS, critical, |writer| {
+				writer.write_sequence(|writer| {
+					for (tag, skip_certs) in [
+						(0, constraints.require_explicit_policy),
+						(1, constraints.inhibit_policy_mapping),
+					] {
+						if let Some(skip_certs) = skip_certs {
+							writer
+								.next()
+								.write_tagged_implicit(Tag::context(tag), |writer| {
+									writer.write_u32(skip_certs)
+								});
 						}
-					});
+					}
+				})
+			});
+		}
+
+		if let Some(skip_certs) = self.inhibit_any_policy {
+			let critical = self.criticality(&ExtensionId::InhibitAnyPolicy, true);
+			extensions.push(oid::INHIBIT_ANY_POLICY, critical, |writer| {
+				writer.write_u32(skip_certs)
+			});
+		}
+
+		if self.ocsp_no_check {
+			let critical = self.criticality(&ExtensionId::OcspNoCheck, false);
+			extensions.push(oid::OCSP_NO_CHECK, critical, |writer| writer.write_null());
+		}
+
+		if !self.subject_directory_attributes.is_empty() {
+			for (oid, values) in &self.subject_directory_attributes {
+				if values.is_empty() || !values.iter().all(AttributeValue::is_valid) {
+					return Err(Error::InvalidSubjectDirectoryAttribute(oid.clone()));
+				}
+			}
+			let critical = self.criticality(&ExtensionId::SubjectDirectoryAttributes, false);
+			extensions.push(oid::SUBJECT_DIRECTORY_ATTRIBUTES, critical, |writer| {
+				writer.write_sequence(|writer| {
+					for (oid, values) in &self.subject_directory_attributes {
+						writer.next().write_sequence(|writer| {
+							writer.next().write_oid(&ObjectIdentifier::from_slice(oid));
+							writer.next().write_set_of(|writer| {
+								for value in values {
+									value.write(writer.next());
+								}
+							});
+						});
+					}
 				});
-			},
-			IsCa::ExplicitNoCa => {
-				// Write subject_key_identifier
-				write_x509_extension(
-					writer.next(),
-					oid::SUBJECT_KEY_IDENTIFIER,
-					false,
-					|writer| {
-						writer.write_bytes(&self.key_identifier_method.derive(pub_key_spki));
-					},
-				);
-				// Write basic_constraints
-				write_x509_extension(writer.next(), oid::BASIC_CONSTRAINTS, true, |writer| {
-					writer.write_sequence(|writer| {
-						writer.next().write_bool(false); // cA flag
-					});
+			});
+		}
+
+		if let Some(cert_type) = self.netscape_cert_type {
+			let critical = self.criticality(&ExtensionId::NetscapeCertType, false);
+			extensions.push(oid::NETSCAPE_CERT_TYPE, critical, |writer| {
+				// Trailing zero bits are omitted from the named bit list
+				let bits = (u8::BITS - cert_type.0.trailing_zeros()) as usize;
+				writer.write_bitvec_bytes(&[cert_type.0][..bits.div_ceil(8)], bits)
+			});
+		}
+
+		if let Some(comment) = &self.netscape_comment {
+			let comment = Ia5String::try_from(comment.as_str())?;
+			let critical = self.criticality(&ExtensionId::NetscapeComment, false);
+			extensions.push(oid::NETSCAPE_COMMENT, critical, |writer| {
+				writer.write_ia5_string(comment.as_str())
+			});
+		}
+
+		if !matches!(self.is_ca, IsCa::NoCa) {
+			// Write subject_key_identifier
+			if self.use_subject_key_identifier_extension {
+				let critical = self.criticality(&ExtensionId::SubjectKeyIdentifier, false);
+				extensions.push(oid::SUBJECT_KEY_IDENTIFIER, critical, |writer| {
+					writer.write_bytes(&self.key_identifier_method.derive(pub_key));
 				});
-			},
-			IsCa::NoCa => {},
+			}
+			// Write basic_constraints
+			extensions.push_der(
+				oid::BASIC_CONSTRAINTS,
+				yasna::construct_der(|writer| self.write_is_ca(writer)),
+			);
 		}
 
 		// Write the custom extensions
 		for ext in &self.custom_extensions {
-			write_x509_extension(writer.next(), &ext.oid, ext.critical, |writer| {
-				writer.write_der(ext.content())
+			let critical = self.criticality(&ExtensionId::Custom(ext.oid.clone()), ext.critical);
+			extensions.push(&ext.oid, critical, |writer| writer.write_der(ext.content()));
+		}
+
+		// The Certificate Transparency extensions come last, so that a precertificate and its
+		// final certificate only differ in the last extension
+		if self.ct_poison {
+			if !self.signed_certificate_timestamps.is_empty() {
+				return Err(Error::InvalidSignedCertificateTimestamps(
+					"a precertificate can't contain timestamps".to_owned(),
+				));
+			}
+			let critical = self.criticality(&ExtensionId::CtPoison, true);
+			extensions.push(oid::CT_POISON, critical, |writer| writer.write_null());
+		}
+		if !self.signed_certificate_timestamps.is_empty() {
+			let list = sct_list(&self.signed_certificate_timestamps)?;
+			let critical = self.criticality(&ExtensionId::SignedCertificateTimestamps, false);
+			extensions.push(oid::CT_SCT_LIST, critical, |writer| {
+				writer.write_bytes(&list)
 			});
 		}
 
+		for der in extensions.ordered(self.extension_order.as_deref()) {
+			writer.next().write_der(&der);
+		}
 		Ok(())
 	}
 
@@ -629,6 +1530,128 @@ impl AsRef<CertificateParams> for CertificateParams {
 	}
 }
 
+/// Derives the serial number of a certificate without one from its public key
+#[cfg(feature = "crypto")]
+pub(crate) fn derive_serial_number(pub_key: &(impl PublicKeyData + ?Sized)) -> Vec<u8> {
+	let hash = digest::digest(&digest::SHA256, pub_key.der_bytes());
+	// RFC 5280 specifies at most 20 bytes for a serial number
+	let mut sl = hash.as_ref()[0..20].to_vec();
+	sl[0] &= 0x7f; // MSB must be 0 to ensure encoding bignum in 20 bytes
+	sl
+}
+
+/// Looks up an extension of a certificate by an OID that the registry of x509-parser lacks
+#[cfg(feature = "x509-parser")]
+fn x509_extension<'a>(
+	x509: &'a x509_parser::certificate::X509Certificate<'_>,
+	oid: &[u64],
+) -> Result<Option<&'a x509_parser::extensions::X509Extension<'a>>, Error> {
+	let oid = x509_parser::der_parser::asn1_rs::Oid::from(oid)
+		.map_err(|_| Error::CouldNotParseCertificate)?;
+	x509.get_extension_unique(&oid)
+		.map_err(|_| Error::CouldNotParseCertificate)
+}
+
+/// Frames TLS-encoded signed certificate timestamps as a `SignedCertificateTimestampList`
+///
+/// Each timestamp and the list are prefixed with their length as a 16-bit integer, and
+/// neither may be empty.
+fn sct_list(scts: &[Vec<u8>]) -> Result<Vec<u8>, Error> {
+	let mut list = vec![0, 0];
+	for sct in scts {
+		let len = match u16::try_from(sct.len()) {
+			Ok(0) => {
+				return Err(Error::InvalidSignedCertificateTimestamps(
+					"empty timestamp".to_owned(),
+				))
+			},
+			Ok(len) => len,
+			Err(_) => {
+				return Err(Error::InvalidSignedCertificateTimestamps(format!(
+					"timestamp of {} bytes exceeds 65535 bytes",
+					sct.len()
+				)))
+			},
+		};
+		list.extend_from_slice(&len.to_be_bytes());
+		list.extend_from_slice(sct);
+	}
+
+	let Ok(len) = u16::try_from(list.len() - 2) else {
+		return Err(Error::InvalidSignedCertificateTimestamps(format!(
+			"list of {} bytes exceeds 65535 bytes",
+			list.len() - 2
+		)));
+	};
+	list[..2].copy_from_slice(&len.to_be_bytes());
+	Ok(list)
+}
+
+/// Parses the value of an SCT list extension, the reverse of [`sct_list()`]
+#[cfg(feature = "x509-parser")]
+fn parse_sct_list(value: &[u8]) -> Option<Vec<Vec<u8>>> {
+	let list = crate::asn1::parse_der(value, |reader| reader.read_bytes()).ok()?;
+	let (len, mut rest) = list.split_first_chunk::<2>()?;
+	if usize::from(u16::from_be_bytes(*len)) != rest.len() {
+		return None;
+	}
+
+	let mut scts = Vec::new();
+	while let Some((len, tail)) = rest.split_first_chunk::<2>() {
+		let len = usize::from(u16::from_be_bytes(*len));
+		if len == 0 || tail.len() < len {
+			return None;
+		}
+		let (sct, tail) = tail.split_at(len);
+		scts.push(sct.to_vec());
+		rest = tail;
+	}
+	match rest.is_empty() && !scts.is_empty() {
+		true => Some(scts),
+		false => None,
+	}
+}
+
+/// Writes a `GeneralNames` sequence, as used by the subject and issuer alternative names
+fn write_general_names(writer: DERWriter, names: &[SanType]) {
+	writer.write_sequence(|writer| {
+		for san in names {
+			writer
+				.next()
+				.write_tagged_implicit(Tag::context(san.tag()), |writer| match san {
+					SanType::Rfc822Name(name) | SanType::URI(name) => {
+						writer.write_ia5_string(name.as_str())
+					},
+					SanType::DnsName(name) => writer.write_ia5_string(name.as_str()),
+					SanType::IpAddress(IpAddr::V4(addr)) => writer.write_bytes(&addr.octets()),
+					SanType::IpAddress(IpAddr::V6(addr)) => writer.write_bytes(&addr.octets()),
+					SanType::OtherName((oid, value)) => {
+						// otherName SEQUENCE { OID, [0] explicit any defined by oid }
+						// https://datatracker.ietf.org/doc/html/rfc5280#page-38
+						writer.write_sequence(|writer| {
+							writer.next().write_oid(&ObjectIdentifier::from_slice(oid));
+							value.write_der(writer.next());
+						});
+					},
+					// The implicit tag keeps the constructed bit of the SEQUENCE, and
+					// replaces the explicit tag that the Name CHOICE requires
+					SanType::DirectoryName(name) => writer
+						.write_sequence(|writer| write_distinguished_name(writer.next(), name)),
+					SanType::RegisteredId(oid) => {
+						writer.write_oid(&ObjectIdentifier::from_slice(oid))
+					},
+					SanType::Raw { tag, der_value } => match tag {
+						// The implicit tag keeps the constructed bit of the SEQUENCE
+						0 | 3 | 4 | 5 => {
+							writer.write_sequence(|writer| writer.next().write_der(der_value))
+						},
+						_ => writer.write_bytes(der_value),
+					},
+				});
+		}
+	});
+}
+
 fn write_general_subtrees(writer: DERWriter, tag: u64, general_subtrees: &[GeneralSubtree]) {
 	writer.write_tagged_implicit(Tag::context(tag), |writer| {
 		writer.write_sequence(|writer| {
@@ -637,7 +1660,9 @@ fn write_general_subtrees(writer: DERWriter, tag: u64, general_subtrees: &[Gener
 					let writer = writer.next();
 					let tag = Tag::context(subtree.tag());
 					match subtree {
-						GeneralSubtree::Rfc822Name(name) | GeneralSubtree::DnsName(name) => writer
+						GeneralSubtree::Rfc822Name(name)
+						| GeneralSubtree::DnsName(name)
+						| GeneralSubtree::URI(name) => writer
 							.write_tagged_implicit(tag, |writer| writer.write_ia5_string(name)),
 						// `Name` is a CHOICE, so X.680 §31.2.7 requires explicit tagging.
 						GeneralSubtree::DirectoryName(name) => writer
@@ -673,6 +1698,88 @@ pub struct Attribute {
 	pub values: Vec<u8>,
 }
 
+/// An attribute type and its values, as in [`CertificateParams::subject_directory_attributes`]
+#[cfg(feature = "x509-parser")]
+type DirectoryAttribute = (Vec<u64>, Vec<AttributeValue>);
+
+/// A value of an attribute in
+/// [`CertificateParams::subject_directory_attributes`]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+#[non_exhaustive]
+pub enum AttributeValue {
+	/// A PrintableString, e.g. the country code of countryOfCitizenship
+	PrintableString(PrintableString),
+	/// A UTF8String, e.g. the place of placeOfBirth
+	Utf8String(String),
+	/// A GeneralizedTime, e.g. the date of dateOfBirth, see
+	/// [`date_of_birth`](Self::date_of_birth)
+	GeneralizedTime(OffsetDateTime),
+	/// A DER-encoded value of any other type, which must be a single element
+	Der(Vec<u8>),
+}
+
+impl AttributeValue {
+	/// A dateOfBirth value, encoded as noon UTC (`YYYYMMDD120000Z`)
+	///
+	/// Noon is the convention of qualified certificate profiles, so that the date stays the
+	/// same in every time zone.
+	pub fn date_of_birth(date: Date) -> Self {
+		Self::GeneralizedTime(date.midnight().assume_utc() + time::Duration::hours(12))
+	}
+
+	fn is_valid(&self) -> bool {
+		match self {
+			Self::Der(der) => crate::asn1::parse_der(der, |reader| reader.read_der()).is_ok(),
+			_ => true,
+		}
+	}
+
+	fn write(&self, writer: DERWriter) {
+		match self {
+			Self::PrintableString(s) => writer.write_printable_string(s.as_str()),
+			Self::Utf8String(s) => writer.write_utf8_string(s),
+			Self::GeneralizedTime(dt) => writer.write_generalized_time(&dt_to_generalized(*dt)),
+			Self::Der(der) => writer.write_der(der),
+		}
+	}
+
+	/// Parses the value of a subject directory attributes extension
+	#[cfg(feature = "x509-parser")]
+	fn from_extension_value(value: &[u8]) -> Result<Vec<DirectoryAttribute>, Error> {
+		crate::asn1::parse_der(value, |reader| {
+			reader.collect_sequence_of(|reader| {
+				reader.read_sequence(|reader| {
+					let oid = reader.next().read_oid()?.components().clone();
+					let values = reader
+						.next()
+						.collect_set_of(|reader| Ok(Self::from_der(reader.read_der()?)))?;
+					Ok((oid, values))
+				})
+			})
+		})
+		.map_err(|_| Error::CouldNotParseCertificate)
+	}
+
+	/// Decodes the types of this enum, keeping other values as DER
+	#[cfg(feature = "x509-parser")]
+	fn from_der(der: Vec<u8>) -> Self {
+		let value = match der.first() {
+			Some(0x13) => crate::asn1::parse_der(&der, |reader| reader.read_printable_string())
+				.ok()
+				.and_then(|s| PrintableString::try_from(s).ok())
+				.map(Self::PrintableString),
+			Some(0x0c) => crate::asn1::parse_der(&der, |reader| reader.read_utf8string())
+				.ok()
+				.map(Self::Utf8String),
+			Some(0x18) => crate::asn1::parse_der(&der, |reader| reader.read_generalized_time())
+				.ok()
+				.map(|time| Self::GeneralizedTime(*time.datetime())),
+			_ => None,
+		};
+		value.unwrap_or(Self::Der(der))
+	}
+}
+
 /// A custom extension of a certificate, as specified in
 /// [RFC 5280](https://tools.ietf.org/html/rfc5280#section-4.2)
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
@@ -700,6 +1807,38 @@ impl CustomExtension {
 			content,
 		}
 	}
+	/// Creates a new certificate template name extension of Active Directory Certificate
+	/// Services, also known as szOID_ENROLL_CERTTYPE_EXTENSION
+	///
+	/// The `name` is encoded as a BMPString, so characters outside the Basic Multilingual
+	/// Plane are rejected with [`Error::InvalidAsn1String`].
+	pub fn new_ms_template_name(name: &str) -> Result<Self, Error> {
+		let name = BmpString::try_from(name)?;
+		let content = yasna::construct_der(|writer| {
+			writer
+				.write_tagged_implicit(TAG_BMPSTRING, |writer| writer.write_bytes(name.as_bytes()))
+		});
+		Ok(Self::from_oid_content(
+			oid::MS_CERTIFICATE_TEMPLATE_NAME,
+			content,
+		))
+	}
+	/// Creates a new certificate template information extension of Active Directory
+	/// Certificate Services, which version 2 templates and later require
+	///
+	/// `template` is the OID of the template, followed by its major and minor version.
+	pub fn new_ms_template_info(template: &[u64], major_version: u32, minor_version: u32) -> Self {
+		let content = yasna::construct_der(|writer| {
+			writer.write_sequence(|writer| {
+				writer
+					.next()
+					.write_oid(&ObjectIdentifier::from_slice(template));
+				writer.next().write_u32(major_version);
+				writer.next().write_u32(minor_version);
+			})
+		});
+		Self::from_oid_content(oid::MS_CERTIFICATE_TEMPLATE, content)
+	}
 	/// Create a new custom extension with the specified content
 	pub fn from_oid_content(oid: &[u64], content: Vec<u8>) -> Self {
 		Self {
@@ -726,6 +1865,155 @@ impl CustomExtension {
 	}
 }
 
+/// An extension of a certificate, borrowed from its DER encoding
+///
+/// Returned by [`Certificate::extensions()`] and [`CertificateParams::extensions_from_der()`].
+/// Both the [`Debug`](fmt::Debug) and [`Display`](fmt::Display) output name the extensions
+/// defined in RFC 5280 and a few other well-known ones.
+#[derive(PartialEq, Eq, Hash, Clone)]
+pub struct ExtensionRef<'a> {
+	oid: Vec<u64>,
+	critical: bool,
+	value: &'a [u8],
+}
+
+impl<'a> ExtensionRef<'a> {
+	/// Obtains the OID components of the extension, as u64 pieces
+	pub fn oid_components(&self) -> impl Iterator<Item = u64> + '_ {
+		self.oid.iter().copied()
+	}
+
+	/// Obtains the criticality flag of the extension
+	pub fn criticality(&self) -> bool {
+		self.critical
+	}
+
+	/// Obtains the DER-encoded value of the extension, without the enclosing OCTET STRING
+	pub fn value(&self) -> &'a [u8] {
+		self.value
+	}
+
+	/// The name of the extension, like `subjectAltName`, if it is a well-known one
+	pub fn name(&self) -> Option<&'static str> {
+		extension_name(&self.oid)
+	}
+
+	/// Reads the extensions of a DER-encoded certificate
+	fn read_all(reader: BERReader<'a, '_>) -> ASN1Result<Vec<Self>> {
+		reader.read_sequence(|reader| {
+			// The extensions are the last field of the TBSCertificate, tagged with [3]
+			let mut extensions = None;
+			reader.next().read_sequence(|reader| {
+				while let Some((value, der)) = reader.read_optional(|reader| {
+					reader.read_with_buffer(|reader| reader.read_tagged_der())
+				})? {
+					if value.tag() == Tag::context(3) {
+						extensions = Some(der);
+					}
+				}
+				Ok(())
+			})?;
+			reader.next().read_der()?;
+			reader.next().read_der()?;
+
+			let Some(extensions) = extensions else {
+				return Ok(Vec::new());
+			};
+			crate::asn1::parse_der(extensions, |reader| {
+				reader.read_tagged(Tag::context(3), |reader| {
+					reader.collect_sequence_of(Self::read)
+				})
+			})
+		})
+	}
+
+	fn read(reader: BERReader<'a, '_>) -> ASN1Result<Self> {
+		reader.read_sequence(|reader| {
+			let oid = reader.next().read_oid()?;
+			let critical = reader.read_optional(|reader| reader.read_bool())?;
+			let (value, der) = reader
+				.next()
+				.read_with_buffer(|reader| reader.read_bytes())?;
+			Ok(Self {
+				oid: oid.components().clone(),
+				critical: critical.unwrap_or(false),
+				// The contents of the OCTET STRING, which follow its tag and length
+				value: &der[der.len() - value.len()..],
+			})
+		})
+	}
+}
+
+impl fmt::Display for ExtensionRef<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let oid = self.oid.iter().map(u64::to_string).collect::<Vec<_>>();
+		match self.name() {
+			Some(name) => write!(f, "{name} ({})", oid.join("."))?,
+			None => write!(f, "{}", oid.join("."))?,
+		}
+		if self.critical {
+			write!(f, ", critical")?;
+		}
+		write!(f, ": ")?;
+		for byte in self.value {
+			write!(f, "{byte:02x}")?;
+		}
+		Ok(())
+	}
+}
+
+impl fmt::Debug for ExtensionRef<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let oid = self.oid.iter().map(u64::to_string).collect::<Vec<_>>();
+		let value = self.value.iter().map(|b| format!("{b:02x}"));
+		f.debug_struct("ExtensionRef")
+			.field("oid", &format_args!("{}", oid.join(".")))
+			.field("name", &self.name())
+			.field("critical", &self.critical)
+			.field("value", &format_args!("{}", value.collect::<String>()))
+			.finish()
+	}
+}
+
+/// Strips a single trailing dot from a DNS name, rejecting empty and non-ASCII names
+fn normalize_dns_name(name: &str) -> Option<&str> {
+	let name = name.strip_suffix('.').unwrap_or(name);
+	(!name.is_empty() && name.is_ascii()).then_some(name)
+}
+
+/// The name of an extension, like `subjectAltName`, if it is a well-known one
+fn extension_name(oid: &[u64]) -> Option<&'static str> {
+	Some(match oid {
+		oid::SUBJECT_KEY_IDENTIFIER => "subjectKeyIdentifier",
+		oid::KEY_USAGE => "keyUsage",
+		oid::SUBJECT_ALT_NAME => "subjectAltName",
+		oid::ISSUER_ALT_NAME => "issuerAltName",
+		oid::BASIC_CONSTRAINTS => "basicConstraints",
+		oid::NAME_CONSTRAINTS => "nameConstraints",
+		oid::SUBJECT_DIRECTORY_ATTRIBUTES => "subjectDirectoryAttributes",
+		oid::CRL_DISTRIBUTION_POINTS => "cRLDistributionPoints",
+		oid::CERTIFICATE_POLICIES => "certificatePolicies",
+		[2, 5, 29, 33] => "policyMappings",
+		oid::AUTHORITY_KEY_IDENTIFIER => "authorityKeyIdentifier",
+		oid::POLICY_CONSTRAINTS => "policyConstraints",
+		oid::EXT_KEY_USAGE => "extKeyUsage",
+		oid::FRESHEST_CRL => "freshestCRL",
+		oid::INHIBIT_ANY_POLICY => "inhibitAnyPolicy",
+		oid::AUTHORITY_INFO_ACCESS => "authorityInfoAccess",
+		[1, 3, 6, 1, 5, 5, 7, 1, 11] => "subjectInfoAccess",
+		oid::TLS_FEATURE => "tlsFeature",
+		oid::OCSP_NO_CHECK => "ocspNoCheck",
+		oid::NETSCAPE_CERT_TYPE => "nsCertType",
+		oid::NETSCAPE_COMMENT => "nsComment",
+		oid::PE_ACME => "acmeIdentifier",
+		oid::CT_SCT_LIST => "signedCertificateTimestampList",
+		oid::CT_POISON => "ctPoison",
+		oid::MS_CERTIFICATE_TEMPLATE_NAME => "certificateTemplateName",
+		oid::MS_CERTIFICATE_TEMPLATE => "certificateTemplate",
+		_ => return None,
+	})
+}
+
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 #[non_exhaustive]
 /// The attribute type of a distinguished name entry
@@ -742,6 +2030,19 @@ pub enum DnType {
 	OrganizationalUnitName,
 	/// X520CommonName
 	CommonName,
+	/// businessCategory, e.g. "Private Organization" in EV certificates
+	BusinessCategory,
+	/// organizationIdentifier, e.g. a VAT or registration number in ETSI certificates
+	OrganizationIdentifier,
+	/// jurisdictionOfIncorporationLocalityName from the CA/Browser Forum EV Guidelines
+	JurisdictionLocalityName,
+	/// jurisdictionOfIncorporationStateOrProvinceName from the CA/Browser Forum EV Guidelines
+	JurisdictionStateOrProvinceName,
+	/// jurisdictionOfIncorporationCountryName from the CA/Browser Forum EV Guidelines
+	///
+	/// Values given as plain strings are encoded as a PrintableString, as the
+	/// attribute requires.
+	JurisdictionCountryName,
 	/// Custom distinguished name type
 	CustomDnType(Vec<u64>),
 }
@@ -755,6 +2056,11 @@ impl DnType {
 			DnType::OrganizationName => oid::ORG_NAME,
 			DnType::OrganizationalUnitName => oid::ORG_UNIT_NAME,
 			DnType::CommonName => oid::COMMON_NAME,
+			DnType::BusinessCategory => oid::BUSINESS_CATEGORY,
+			DnType::OrganizationIdentifier => oid::ORG_IDENTIFIER,
+			DnType::JurisdictionLocalityName => oid::JURISDICTION_LOCALITY_NAME,
+			DnType::JurisdictionStateOrProvinceName => oid::JURISDICTION_STATE_OR_PROVINCE_NAME,
+			DnType::JurisdictionCountryName => oid::JURISDICTION_COUNTRY_NAME,
 			DnType::CustomDnType(ref oid) => oid.as_slice(),
 		};
 		ObjectIdentifier::from_slice(sl)
@@ -769,6 +2075,11 @@ impl DnType {
 			oid::ORG_NAME => DnType::OrganizationName,
 			oid::ORG_UNIT_NAME => DnType::OrganizationalUnitName,
 			oid::COMMON_NAME => DnType::CommonName,
+			oid::BUSINESS_CATEGORY => DnType::BusinessCategory,
+			oid::ORG_IDENTIFIER => DnType::OrganizationIdentifier,
+			oid::JURISDICTION_LOCALITY_NAME => DnType::JurisdictionLocalityName,
+			oid::JURISDICTION_STATE_OR_PROVINCE_NAME => DnType::JurisdictionStateOrProvinceName,
+			oid::JURISDICTION_COUNTRY_NAME => DnType::JurisdictionCountryName,
 			oid => DnType::CustomDnType(oid.into()),
 		}
 	}
@@ -796,39 +2107,47 @@ pub enum ExtendedKeyUsagePurpose {
 }
 
 impl ExtendedKeyUsagePurpose {
-	#[cfg(all(test, feature = "x509-parser"))]
+	#[cfg(feature = "x509-parser")]
 	fn from_x509(x509: &x509_parser::certificate::X509Certificate<'_>) -> Result<Vec<Self>, Error> {
 		let extended_key_usage = x509
-			.extended_key_usage()
-			.map_err(|_| Error::CouldNotParseCertificate)?
-			.map(|ext| ext.value);
+			.get_extension_unique(&x509_parser::oid_registry::OID_X509_EXT_EXTENDED_KEY_USAGE)
+			.map_err(|_| Error::CouldNotParseCertificate)?;
 
-		let mut extended_key_usages = Vec::new();
-		if let Some(extended_key_usage) = extended_key_usage {
-			if extended_key_usage.any {
-				extended_key_usages.push(Self::Any);
-			}
-			if extended_key_usage.server_auth {
-				extended_key_usages.push(Self::ServerAuth);
-			}
-			if extended_key_usage.client_auth {
-				extended_key_usages.push(Self::ClientAuth);
-			}
-			if extended_key_usage.code_signing {
-				extended_key_usages.push(Self::CodeSigning);
-			}
-			if extended_key_usage.email_protection {
-				extended_key_usages.push(Self::EmailProtection);
-			}
-			if extended_key_usage.time_stamping {
-				extended_key_usages.push(Self::TimeStamping);
-			}
-			if extended_key_usage.ocsp_signing {
-				extended_key_usages.push(Self::OcspSigning);
-			}
+		match extended_key_usage {
+			Some(ext) => Self::from_extension_value(ext.value),
+			None => Ok(Vec::new()),
 		}
+	}
+
+	/// Parses the value of an extended key usage extension, keeping the order of the purposes
+	///
+	/// Purposes without a dedicated variant are returned as [`ExtendedKeyUsagePurpose::Other`].
+	#[cfg(any(feature = "crypto", feature = "x509-parser"))]
+	pub(crate) fn from_extension_value(value: &[u8]) -> Result<Vec<Self>, Error> {
+		let oids =
+			crate::asn1::parse_der(value, |reader| reader.collect_sequence_of(|r| r.read_oid()))
+				.map_err(|_| Error::CouldNotParseCertificate)?;
+		Ok(oids
+			.iter()
+			.map(|oid| Self::from_oid(oid.components()))
+			.collect())
+	}
 
-		Ok(extended_key_usages)
+	#[cfg(any(feature = "crypto", feature = "x509-parser"))]
+	fn from_oid(oid: &[u64]) -> Self {
+		use ExtendedKeyUsagePurpose::*;
+		[
+			Any,
+			ServerAuth,
+			ClientAuth,
+			CodeSigning,
+			EmailProtection,
+			TimeStamping,
+			OcspSigning,
+		]
+		.into_iter()
+		.find(|purpose| purpose.oid() == oid)
+		.unwrap_or_else(|| Other(oid.to_vec()))
 	}
 
 	fn oid(&self) -> &[u64] {
@@ -848,75 +2167,625 @@ impl ExtendedKeyUsagePurpose {
 	}
 }
 
-/// The [NameConstraints extension](https://tools.ietf.org/html/rfc5280#section-4.2.1.10)
-/// (only relevant for CA certificates)
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub struct NameConstraints {
-	/// A list of subtrees that the domain has to match.
-	pub permitted_subtrees: Vec<GeneralSubtree>,
-	/// A list of subtrees that the domain must not match.
-	///
-	/// Any name matching an excluded subtree is invalid even if it also matches a permitted subtree.
-	pub excluded_subtrees: Vec<GeneralSubtree>,
+/// An extension of a certificate, for [`CertificateParams::extension_criticality`] and
+/// [`CertificateParams::extension_order`]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+#[non_exhaustive]
+pub enum ExtensionId {
+	/// authorityKeyIdentifier, non-critical by default
+	AuthorityKeyIdentifier,
+	/// subjectAltName, critical if the subject is empty by default
+	SubjectAltName,
+	/// issuerAltName, which follows [`CertificateParams::issuer_alt_names_critical`] by
+	/// default
+	IssuerAltName,
+	/// keyUsage, critical by default
+	KeyUsage,
+	/// extKeyUsage, non-critical by default
+	ExtendedKeyUsage,
+	/// tlsFeature, non-critical by default
+	TlsFeature,
+	/// nameConstraints, critical by default
+	NameConstraints,
+	/// cRLDistributionPoints, non-critical by default
+	CrlDistributionPoints,
+	/// freshestCRL, non-critical by default
+	FreshestCrl,
+	/// authorityInfoAccess, non-critical by default
+	AuthorityInfoAccess,
+	/// certificatePolicies, which follows
+	/// [`CertificateParams::certificate_policies_critical`] by default
+	CertificatePolicies,
+	/// policyConstraints, critical by default
+	PolicyConstraints,
+	/// inhibitAnyPolicy, critical by default
+	InhibitAnyPolicy,
+	/// ocspNoCheck, non-critical by default
+	OcspNoCheck,
+	/// subjectDirectoryAttributes, non-critical by default
+	SubjectDirectoryAttributes,
+	/// nsCertType, non-critical by default
+	NetscapeCertType,
+	/// nsComment, non-critical by default
+	NetscapeComment,
+	/// subjectKeyIdentifier, non-critical by default
+	SubjectKeyIdentifier,
+	/// basicConstraints, critical by default
+	BasicConstraints,
+	/// The Certificate Transparency poison, critical by default
+	CtPoison,
+	/// The Certificate Transparency SCT list, non-critical by default
+	SignedCertificateTimestamps,
+	/// A [`CustomExtension`] with the given OID, whose criticality defaults to its own
+	Custom(Vec<u64>),
 }
 
-impl NameConstraints {
-	#[cfg(all(test, feature = "x509-parser"))]
-	fn from_x509(
-		x509: &x509_parser::certificate::X509Certificate<'_>,
-	) -> Result<Option<Self>, Error> {
-		let constraints = x509
-			.name_constraints()
-			.map_err(|_| Error::CouldNotParseCertificate)?
-			.map(|ext| ext.value);
+impl ExtensionId {
+	/// The name of the extension, like `subjectAltName`, if it is a well-known one
+	pub fn name(&self) -> Option<&'static str> {
+		extension_name(self.oid())
+	}
 
-		let Some(constraints) = constraints else {
-			return Ok(None);
-		};
+	fn oid(&self) -> &[u64] {
+		match self {
+			Self::AuthorityKeyIdentifier => oid::AUTHORITY_KEY_IDENTIFIER,
+			Self::SubjectAltName => oid::SUBJECT_ALT_NAME,
+			Self::IssuerAltName => oid::ISSUER_ALT_NAME,
+			Self::KeyUsage => oid::KEY_USAGE,
+			Self::ExtendedKeyUsage => oid::EXT_KEY_USAGE,
+			Self::TlsFeature => oid::TLS_FEATURE,
+			Self::NameConstraints => oid::NAME_CONSTRAINTS,
+			Self::CrlDistributionPoints => oid::CRL_DISTRIBUTION_POINTS,
+			Self::FreshestCrl => oid::FRESHEST_CRL,
+			Self::AuthorityInfoAccess => oid::AUTHORITY_INFO_ACCESS,
+			Self::CertificatePolicies => oid::CERTIFICATE_POLICIES,
+			Self::PolicyConstraints => oid::POLICY_CONSTRAINTS,
+			Self::InhibitAnyPolicy => oid::INHIBIT_ANY_POLICY,
+			Self::OcspNoCheck => oid::OCSP_NO_CHECK,
+			Self::SubjectDirectoryAttributes => oid::SUBJECT_DIRECTORY_ATTRIBUTES,
+			Self::NetscapeCertType => oid::NETSCAPE_CERT_TYPE,
+			Self::NetscapeComment => oid::NETSCAPE_COMMENT,
+			Self::SubjectKeyIdentifier => oid::SUBJECT_KEY_IDENTIFIER,
+			Self::BasicConstraints => oid::BASIC_CONSTRAINTS,
+			Self::CtPoison => oid::CT_POISON,
+			Self::SignedCertificateTimestamps => oid::CT_SCT_LIST,
+			Self::Custom(oid) => oid,
+		}
+	}
+}
 
-		let permitted_subtrees = if let Some(permitted) = &constraints.permitted_subtrees {
-			GeneralSubtree::from_x509(permitted)?
-		} else {
-			Vec::new()
-		};
+/// The extensions of a certificate, each as a DER-encoded `Extension` next to its OID
+#[derive(Default)]
+struct Extensions<'a>(Vec<(&'a [u64], Vec<u8>)>);
 
-		let excluded_subtrees = if let Some(excluded) = &constraints.excluded_subtrees {
-			GeneralSubtree::from_x509(excluded)?
-		} else {
-			Vec::new()
-		};
+impl<'a> Extensions<'a> {
+	fn push(&mut self, oid: &'a [u64], critical: bool, value: impl FnOnce(DERWriter)) {
+		let der = yasna::construct_der(|writer| write_x509_extension(writer, oid, critical, value));
+		self.0.push((oid, der));
+	}
 
-		Ok(Some(Self {
-			permitted_subtrees,
-			excluded_subtrees,
-		}))
+	/// Adds an already encoded extension, unless it's empty because the extension is omitted
+	fn push_der(&mut self, oid: &'a [u64], der: Vec<u8>) {
+		if !der.is_empty() {
+			self.0.push((oid, der));
+		}
 	}
 
-	fn is_empty(&self) -> bool {
-		self.permitted_subtrees.is_empty() && self.excluded_subtrees.is_empty()
+	/// The extensions listed in `order` first, then the others in the order they were added
+	///
+	/// The Certificate Transparency extensions stay where they were added, which is last.
+	fn ordered(self, order: Option<&[ExtensionId]>) -> impl Iterator<Item = Vec<u8>> + 'a {
+		let mut rest = self.0;
+		let mut ordered = Vec::with_capacity(rest.len());
+		let movable = |id: &&ExtensionId| {
+			!matches!(
+				id,
+				ExtensionId::CtPoison | ExtensionId::SignedCertificateTimestamps
+			)
+		};
+		for id in order.unwrap_or_default().iter().filter(movable) {
+			let (matching, others) = rest.into_iter().partition(|(oid, _)| *oid == id.oid());
+			ordered.extend::<Vec<_>>(matching);
+			rest = others;
+		}
+		ordered.into_iter().chain(rest).map(|(_, der)| der)
 	}
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-#[allow(missing_docs)]
-#[non_exhaustive]
-/// General Subtree type.
-///
-/// This type has similarities to the [`SanType`] enum but is not equal.
-/// For example, `GeneralSubtree` has CIDR subnets for ip addresses
-/// while [`SanType`] has IP addresses.
-pub enum GeneralSubtree {
+/// The method of an access description in the
+/// [authority information access extension](https://www.rfc-editor.org/rfc/rfc5280#section-4.2.2.1)
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum AccessMethod {
+	/// id-ad-ocsp, the location of an OCSP responder
+	Ocsp,
+	/// id-ad-caIssuers, the location of certificates issued to the issuer
+	CaIssuers,
+	/// A custom method not from the pre-specified list of methods
+	Other(Vec<u64>),
+}
+
+impl AccessMethod {
+	/// Parses the access descriptions of the authority information access extension
+	///
+	/// Access locations other than URIs are skipped.
+	#[cfg(feature = "x509-parser")]
+	fn from_x509(
+		x509: &x509_parser::certificate::X509Certificate<'_>,
+	) -> Result<Vec<(Self, String)>, Error> {
+		use x509_parser::extensions::{GeneralName, ParsedExtension};
+
+		let ext = x509
+			.get_extension_unique(&x509_parser::oid_registry::OID_PKIX_AUTHORITY_INFO_ACCESS)
+			.map_err(|_| Error::CouldNotParseCertificate)?;
+		let aia = match ext.map(|ext| ext.parsed_extension()) {
+			Some(ParsedExtension::AuthorityInfoAccess(aia)) => aia,
+			Some(_) => return Err(Error::CouldNotParseCertificate),
+			None => return Ok(Vec::new()),
+		};
+
+		let mut descriptions = Vec::with_capacity(aia.accessdescs.len());
+		for desc in &aia.accessdescs {
+			let GeneralName::URI(uri) = desc.access_location else {
+				continue;
+			};
+			let Some(method) = crate::oid_arcs(&desc.access_method) else {
+				return Err(Error::CouldNotParseCertificate);
+			};
+			descriptions.push((Self::from_oid(&method), uri.to_owned()));
+		}
+		Ok(descriptions)
+	}
+
+	#[cfg(feature = "x509-parser")]
+	fn from_oid(oid: &[u64]) -> Self {
+		match oid {
+			oid::AD_OCSP => Self::Ocsp,
+			oid::AD_CA_ISSUERS => Self::CaIssuers,
+			_ => Self::Other(oid.to_vec()),
+		}
+	}
+
+	fn oid(&self) -> &[u64] {
+		match self {
+			Self::Ocsp => oid::AD_OCSP,
+			Self::CaIssuers => oid::AD_CA_ISSUERS,
+			Self::Other(oid) => oid,
+		}
+	}
+}
+
+/// The certificate types of the legacy Netscape certificate type extension
+///
+/// Types are combined with `|`, e.g. `NetscapeCertType::SSL_CLIENT | NetscapeCertType::SSL_SERVER`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct NetscapeCertType(u8);
+
+impl NetscapeCertType {
+	/// SSL client
+	pub const SSL_CLIENT: Self = Self(0x80);
+	/// SSL server
+	pub const SSL_SERVER: Self = Self(0x40);
+	/// S/MIME
+	pub const SMIME: Self = Self(0x20);
+	/// Object signing
+	pub const OBJECT_SIGNING: Self = Self(0x10);
+	/// SSL CA
+	pub const SSL_CA: Self = Self(0x04);
+	/// S/MIME CA
+	pub const SMIME_CA: Self = Self(0x02);
+	/// Object signing CA
+	pub const OBJECT_SIGNING_CA: Self = Self(0x01);
+
+	/// Whether all types of `other` are included
+	pub fn contains(self, other: Self) -> bool {
+		self.0 & other.0 == other.0
+	}
+
+	/// Parses the value of a Netscape certificate type extension, a BIT STRING
+	#[cfg(feature = "x509-parser")]
+	fn from_extension_value(value: &[u8]) -> Result<Self, Error> {
+		let (bytes, _) = crate::asn1::parse_der(value, |reader| reader.read_bitvec_bytes())
+			.map_err(|_| Error::CouldNotParseCertificate)?;
+		Ok(Self(bytes.first().copied().unwrap_or(0)))
+	}
+}
+
+impl ops::BitOr for NetscapeCertType {
+	type Output = Self;
+
+	fn bitor(self, rhs: Self) -> Self {
+		Self(self.0 | rhs.0)
+	}
+}
+
+impl ops::BitOrAssign for NetscapeCertType {
+	fn bitor_assign(&mut self, rhs: Self) {
+		self.0 |= rhs.0;
+	}
+}
+
+/// A TLS extension that a server has to support, from the
+/// [TLS feature extension](https://www.rfc-editor.org/rfc/rfc7633)
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum TlsFeature {
+	/// status_request, the OCSP stapling of TLS 1.2 and 1.3
+	StatusRequest,
+	/// status_request_v2, the multiple certificate status stapling of RFC 6961
+	StatusRequestV2,
+	/// Another TLS extension, by its number
+	Other(u16),
+}
+
+impl TlsFeature {
+	#[cfg(feature = "x509-parser")]
+	fn from_x509(x509: &x509_parser::certificate::X509Certificate<'_>) -> Result<Vec<Self>, Error> {
+		let tls_feature = x509_extension(x509, oid::TLS_FEATURE)?;
+
+		match tls_feature {
+			Some(ext) => Self::from_extension_value(ext.value),
+			None => Ok(Vec::new()),
+		}
+	}
+
+	/// Parses the value of a TLS feature extension, a sequence of extension numbers
+	#[cfg(any(feature = "crypto", feature = "x509-parser"))]
+	pub(crate) fn from_extension_value(value: &[u8]) -> Result<Vec<Self>, Error> {
+		let values =
+			crate::asn1::parse_der(value, |reader| reader.collect_sequence_of(|r| r.read_u16()))
+				.map_err(|_| Error::CouldNotParseCertificate)?;
+		Ok(values.into_iter().map(Self::from_value).collect())
+	}
+
+	#[cfg(any(feature = "crypto", feature = "x509-parser"))]
+	fn from_value(value: u16) -> Self {
+		match value {
+			5 => Self::StatusRequest,
+			17 => Self::StatusRequestV2,
+			_ => Self::Other(value),
+		}
+	}
+
+	/// The number of the TLS extension
+	fn value(self) -> u16 {
+		match self {
+			Self::StatusRequest => 5,
+			Self::StatusRequestV2 => 17,
+			Self::Other(value) => value,
+		}
+	}
+}
+
+/// A policy of the [certificate policies extension](https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.4)
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct PolicyInformation {
+	/// The OID of the policy
+	pub oid: Vec<u64>,
+	/// Optional qualifiers with more information about the policy
+	pub qualifiers: Vec<PolicyQualifier>,
+}
+
+impl PolicyInformation {
+	/// The special anyPolicy, which stands for all policies
+	///
+	/// In a CA certificate, it permits the certificates it issues to have any policy. RFC 5280
+	/// limits its qualifiers to the ones of [`PolicyQualifier`].
+	pub fn any_policy(qualifiers: Vec<PolicyQualifier>) -> Self {
+		Self {
+			oid: oid::ANY_POLICY.to_vec(),
+			qualifiers,
+		}
+	}
+
+	/// Whether the policy is [`PolicyInformation::any_policy()`]
+	pub fn is_any_policy(&self) -> bool {
+		self.oid == oid::ANY_POLICY
+	}
+
+	/// Checks that the CPS URIs are valid IA5 strings
+	fn check(&self) -> Result<(), Error> {
+		for qualifier in &self.qualifiers {
+			if let PolicyQualifier::CpsUri(uri) = qualifier {
+				Ia5String::try_from(uri.as_str())?;
+			}
+		}
+		Ok(())
+	}
+
+	fn write_der(&self, writer: DERWriter) {
+		// PolicyInformation SEQUENCE { policyIdentifier, policyQualifiers OPTIONAL }
+		writer.write_sequence(|writer| {
+			writer
+				.next()
+				.write_oid(&ObjectIdentifier::from_slice(&self.oid));
+			if self.qualifiers.is_empty() {
+				return;
+			}
+			writer.next().write_sequence(|writer| {
+				for qualifier in &self.qualifiers {
+					qualifier.write_der(writer.next());
+				}
+			});
+		});
+	}
+
+	#[cfg(feature = "x509-parser")]
+	fn from_x509(x509: &x509_parser::certificate::X509Certificate<'_>) -> Result<Vec<Self>, Error> {
+		let ext = x509
+			.get_extension_unique(&x509_parser::oid_registry::OID_X509_EXT_CERTIFICATE_POLICIES)
+			.map_err(|_| Error::CouldNotParseCertificate)?;
+		match ext {
+			Some(ext) => Self::from_extension_value(ext.value),
+			None => Ok(Vec::new()),
+		}
+	}
+
+	/// Parses the value of a certificate policies extension
+	///
+	/// Qualifiers other than [`PolicyQualifier`] are skipped.
+	#[cfg(feature = "x509-parser")]
+	fn from_extension_value(value: &[u8]) -> Result<Vec<Self>, Error> {
+		crate::asn1::parse_der(value, |reader| {
+			reader.collect_sequence_of(|reader| {
+				reader.read_sequence(|reader| {
+					let oid = reader.next().read_oid()?.components().to_vec();
+					let qualifiers = reader.read_optional(|reader| {
+						reader.collect_sequence_of(|reader| {
+							reader.read_sequence(PolicyQualifier::read_der)
+						})
+					})?;
+					Ok(Self {
+						oid,
+						qualifiers: qualifiers.into_iter().flatten().flatten().collect(),
+					})
+				})
+			})
+		})
+		.map_err(|_| Error::CouldNotParseCertificate)
+	}
+}
+
+/// The [policy constraints extension](https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.11)
+///
+/// Each constraint is a number of further certificates in a path, after which it applies.
+/// RFC 5280 requires at least one of them to be set.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct PolicyConstraints {
+	/// The number of certificates after which every certificate in the path needs an
+	/// acceptable policy
+	pub require_explicit_policy: Option<u32>,
+	/// The number of certificates after which policy mappings are no longer permitted
+	pub inhibit_policy_mapping: Option<u32>,
+}
+
+/// A qualifier of a [`PolicyInformation`]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum PolicyQualifier {
+	/// The URI of the certification practice statement (CPS), which must be an IA5 string
+	CpsUri(String),
+	/// A notice to be displayed to relying parties
+	///
+	/// The notice reference is only written if `organization` is set. The texts are written
+	/// as UTF8String, and any string type is accepted when parsing.
+	UserNotice {
+		/// The organization of the notice reference
+		organization: Option<String>,
+		/// The numbers of the notices of the organization that apply
+		notice_numbers: Vec<u64>,
+		/// The text of the notice, RFC 5280 allows at most 200 characters
+		explicit_text: Option<String>,
+	},
+}
+
+impl PolicyQualifier {
+	fn write_der(&self, writer: DERWriter) {
+		// PolicyQualifierInfo SEQUENCE { policyQualifierId, qualifier }
+		writer.write_sequence(|writer| match self {
+			Self::CpsUri(uri) => {
+				writer
+					.next()
+					.write_oid(&ObjectIdentifier::from_slice(oid::QT_CPS));
+				writer.next().write_ia5_string(uri);
+			},
+			Self::UserNotice {
+				organization,
+				notice_numbers,
+				explicit_text,
+			} => {
+				writer
+					.next()
+					.write_oid(&ObjectIdentifier::from_slice(oid::QT_UNOTICE));
+				writer.next().write_sequence(|writer| {
+					if let Some(organization) = organization {
+						// NoticeReference SEQUENCE { organization, noticeNumbers }
+						writer.next().write_sequence(|writer| {
+							writer.next().write_utf8_string(organization);
+							writer.next().write_sequence(|writer| {
+								for number in notice_numbers {
+									writer.next().write_u64(*number);
+								}
+							});
+						});
+					}
+					if let Some(text) = explicit_text {
+						writer.next().write_utf8_string(text);
+					}
+				});
+			},
+		});
+	}
+
+	/// Reads the members of a PolicyQualifierInfo, or `None` for an unknown qualifier
+	#[cfg(feature = "x509-parser")]
+	fn read_der(reader: &mut yasna::BERReaderSeq<'_, '_>) -> yasna::ASN1Result<Option<Self>> {
+		let id = reader.next().read_oid()?;
+		Ok(Some(match id.components().as_slice() {
+			oid::QT_CPS => Self::CpsUri(reader.next().read_ia5_string()?),
+			oid::QT_UNOTICE => reader.next().read_sequence(|reader| {
+				let notice_ref = reader.read_optional(|reader| {
+					reader.read_sequence(|reader| {
+						let organization = read_display_text(reader.next())?;
+						let numbers = reader
+							.next()
+							.collect_sequence_of(|reader| reader.read_u64())?;
+						Ok((organization, numbers))
+					})
+				})?;
+				let explicit_text = reader.read_optional(read_display_text)?;
+				let (organization, notice_numbers) = match notice_ref {
+					Some((organization, numbers)) => (Some(organization), numbers),
+					None => (None, Vec::new()),
+				};
+				Ok(Self::UserNotice {
+					organization,
+					notice_numbers,
+					explicit_text,
+				})
+			})?,
+			_ => {
+				reader.next().read_der()?;
+				return Ok(None);
+			},
+		}))
+	}
+}
+
+/// Reads a DisplayText, which is one of several string types
+#[cfg(feature = "x509-parser")]
+fn read_display_text(reader: yasna::BERReader<'_, '_>) -> yasna::ASN1Result<String> {
+	use yasna::tags::{TAG_BMPSTRING, TAG_IA5STRING, TAG_UTF8STRING, TAG_VISIBLESTRING};
+
+	let value = reader.read_tagged_der()?;
+	let invalid = || yasna::ASN1Error::new(yasna::ASN1ErrorKind::Invalid);
+	match value.tag() {
+		TAG_UTF8STRING => String::from_utf8(value.value().to_vec()).map_err(|_| invalid()),
+		TAG_IA5STRING | TAG_VISIBLESTRING if value.value().is_ascii() => {
+			String::from_utf8(value.value().to_vec()).map_err(|_| invalid())
+		},
+		TAG_BMPSTRING if value.value().len() % 2 == 0 => {
+			let units = value
+				.value()
+				.chunks_exact(2)
+				.map(|pair| u16::from_be_bytes([pair[0], pair[1]]));
+			char::decode_utf16(units)
+				.collect::<Result<String, _>>()
+				.map_err(|_| invalid())
+		},
+		_ => Err(invalid()),
+	}
+}
+
+/// The [NameConstraints extension](https://tools.ietf.org/html/rfc5280#section-4.2.1.10)
+/// (only relevant for CA certificates)
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct NameConstraints {
+	/// A list of subtrees that the domain has to match.
+	pub permitted_subtrees: Vec<GeneralSubtree>,
+	/// A list of subtrees that the domain must not match.
+	///
+	/// Any name matching an excluded subtree is invalid even if it also matches a permitted subtree.
+	pub excluded_subtrees: Vec<GeneralSubtree>,
+}
+
+impl NameConstraints {
+	/// Silently dropping a subtree would widen (permitted) or narrow (excluded) the
+	/// constraints, so subtrees on forms that can't be represented are rejected
+	#[cfg(feature = "x509-parser")]
+	pub(crate) fn from_x509(
+		x509: &x509_parser::certificate::X509Certificate<'_>,
+	) -> Result<Option<Self>, Error> {
+		let (constraints, unsupported) = Self::from_x509_lenient(x509)?;
+		match unsupported.into_iter().next() {
+			Some(subtree) => Err(Error::X509(format!(
+				"unsupported name constraint subtree: {subtree}"
+			))),
+			None => Ok(constraints),
+		}
+	}
+
+	/// Like [`NameConstraints::from_x509()`], but skips the subtrees on forms that can't be
+	/// represented, and returns their descriptions alongside
+	#[cfg(feature = "x509-parser")]
+	pub(crate) fn from_x509_lenient(
+		x509: &x509_parser::certificate::X509Certificate<'_>,
+	) -> Result<(Option<Self>, Vec<String>), Error> {
+		let constraints = x509
+			.name_constraints()
+			.map_err(|_| Error::CouldNotParseCertificate)?
+			.map(|ext| ext.value);
+
+		let Some(constraints) = constraints else {
+			return Ok((None, Vec::new()));
+		};
+
+		let mut unsupported = Vec::new();
+		let permitted_subtrees = if let Some(permitted) = &constraints.permitted_subtrees {
+			GeneralSubtree::from_x509(permitted, &mut unsupported)?
+		} else {
+			Vec::new()
+		};
+
+		let excluded_subtrees = if let Some(excluded) = &constraints.excluded_subtrees {
+			GeneralSubtree::from_x509(excluded, &mut unsupported)?
+		} else {
+			Vec::new()
+		};
+
+		let constraints = Self {
+			permitted_subtrees,
+			excluded_subtrees,
+		};
+		Ok((Some(constraints), unsupported))
+	}
+
+	fn is_empty(&self) -> bool {
+		self.permitted_subtrees.is_empty() && self.excluded_subtrees.is_empty()
+	}
+
+	/// Rejects subnets whose mask can't be written as a prefix length
+	fn check(&self) -> Result<(), Error> {
+		let subnets = self
+			.permitted_subtrees
+			.iter()
+			.chain(&self.excluded_subtrees)
+			.filter_map(|subtree| match subtree {
+				GeneralSubtree::IpAddress(subnet) => Some(subnet),
+				_ => None,
+			});
+		for subnet in subnets {
+			if !subnet.is_contiguous() {
+				return Err(Error::InvalidSubnetMask(*subnet));
+			}
+		}
+		Ok(())
+	}
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+#[allow(missing_docs)]
+#[non_exhaustive]
+/// General Subtree type.
+///
+/// This type has similarities to the [`SanType`] enum but is not equal.
+/// For example, `GeneralSubtree` has CIDR subnets for ip addresses
+/// while [`SanType`] has IP addresses.
+pub enum GeneralSubtree {
 	/// Also known as E-Mail address
 	Rfc822Name(String),
 	DnsName(String),
 	DirectoryName(DistinguishedName),
+	/// Constrains the host of URIs, see [RFC 5280 section 4.2.1.10](https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.10)
+	URI(String),
+	/// An IP subnet, whose mask has to be contiguous, or serialization fails with
+	/// [`Error::InvalidSubnetMask`]
 	IpAddress(CidrSubnet),
 }
 
 impl GeneralSubtree {
-	#[cfg(all(test, feature = "x509-parser"))]
+	/// Subtrees on forms that can't be represented are skipped, and described in `unsupported`
+	#[cfg(feature = "x509-parser")]
 	fn from_x509(
 		subtrees: &[x509_parser::extensions::GeneralSubtree<'_>],
+		unsupported: &mut Vec<String>,
 	) -> Result<Vec<Self>, Error> {
 		use x509_parser::extensions::GeneralName;
 
@@ -925,20 +2794,15 @@ impl GeneralSubtree {
 			let subtree = match &subtree.base {
 				GeneralName::RFC822Name(s) => Self::Rfc822Name(s.to_string()),
 				GeneralName::DNSName(s) => Self::DnsName(s.to_string()),
+				GeneralName::URI(s) => Self::URI(s.to_string()),
 				GeneralName::DirectoryName(n) => {
 					Self::DirectoryName(DistinguishedName::from_name(n)?)
 				},
-				GeneralName::IPAddress(bytes) if bytes.len() == 8 => {
-					let addr: [u8; 4] = bytes[..4].try_into().unwrap();
-					let mask: [u8; 4] = bytes[4..].try_into().unwrap();
-					Self::IpAddress(CidrSubnet::V4(addr, mask))
-				},
-				GeneralName::IPAddress(bytes) if bytes.len() == 32 => {
-					let addr: [u8; 16] = bytes[..16].try_into().unwrap();
-					let mask: [u8; 16] = bytes[16..].try_into().unwrap();
-					Self::IpAddress(CidrSubnet::V6(addr, mask))
+				GeneralName::IPAddress(bytes) => Self::IpAddress(CidrSubnet::from_octets(bytes)?),
+				other => {
+					unsupported.push(other.to_string());
+					continue;
 				},
-				_ => continue,
 			};
 			result.push(subtree);
 		}
@@ -952,12 +2816,14 @@ impl GeneralSubtree {
 		const TAG_RFC822_NAME: u64 = 1;
 		const TAG_DNS_NAME: u64 = 2;
 		const TAG_DIRECTORY_NAME: u64 = 4;
+		const TAG_URI: u64 = 6;
 		const TAG_IP_ADDRESS: u64 = 7;
 
 		match self {
 			GeneralSubtree::Rfc822Name(_name) => TAG_RFC822_NAME,
 			GeneralSubtree::DnsName(_name) => TAG_DNS_NAME,
 			GeneralSubtree::DirectoryName(_name) => TAG_DIRECTORY_NAME,
+			GeneralSubtree::URI(_name) => TAG_URI,
 			GeneralSubtree::IpAddress(_addr) => TAG_IP_ADDRESS,
 		}
 	}
@@ -1016,6 +2882,34 @@ impl CidrSubnet {
 	pub fn from_v6_prefix(addr: [u8; 16], prefix: u8) -> Self {
 		CidrSubnet::V6(addr, mask!(u128, prefix))
 	}
+	/// Parses the address and mask from the `iPAddress` form of a name constraint subtree,
+	/// which is the address immediately followed by the mask (8 or 32 octets in total).
+	#[cfg(feature = "x509-parser")]
+	fn from_octets(octets: &[u8]) -> Result<Self, Error> {
+		if let Ok(octets) = <[u8; 8]>::try_from(octets) {
+			let addr = array::from_fn(|i| octets[i]);
+			let mask = array::from_fn(|i| octets[4 + i]);
+			Ok(CidrSubnet::V4(addr, mask))
+		} else if let Ok(octets) = <[u8; 32]>::try_from(octets) {
+			let addr = array::from_fn(|i| octets[i]);
+			let mask = array::from_fn(|i| octets[16 + i]);
+			Ok(CidrSubnet::V6(addr, mask))
+		} else {
+			Err(Error::InvalidIpAddressOctetLength(octets.len()))
+		}
+	}
+
+	/// Whether all one bits of the mask come before its zero bits, as in every mask that
+	/// corresponds to a prefix length
+	fn is_contiguous(&self) -> bool {
+		let inverted = match self {
+			CidrSubnet::V4(_, mask) => u128::from(!u32::from_be_bytes(*mask)),
+			CidrSubnet::V6(_, mask) => !u128::from_be_bytes(*mask),
+		};
+		// The zero bits of the mask are the trailing ones of `inverted`
+		inverted & inverted.wrapping_add(1) == 0
+	}
+
 	fn to_bytes(self) -> Vec<u8> {
 		let mut res = Vec::new();
 		match self {
@@ -1034,6 +2928,8 @@ impl CidrSubnet {
 
 /// Obtains the CidrSubnet from the well-known
 /// addr/prefix notation.
+///
+/// Prefixes longer than the address are rejected.
 /// ```
 /// # use std::str::FromStr;
 /// # use rcgen::CidrSubnet;
@@ -1050,6 +2946,13 @@ impl FromStr for CidrSubnet {
 		if let (Some(addr_s), Some(prefix_s)) = (iter.next(), iter.next()) {
 			let addr = IpAddr::from_str(addr_s).map_err(|_| ())?;
 			let prefix = u8::from_str(prefix_s).map_err(|_| ())?;
+			let max_prefix = match addr {
+				IpAddr::V4(_) => 32,
+				IpAddr::V6(_) => 128,
+			};
+			if prefix > max_prefix {
+				return Err(());
+			}
 			Ok(Self::from_addr_prefix(addr, prefix))
 		} else {
 			Err(())
@@ -1065,6 +2968,11 @@ impl FromStr for CidrSubnet {
 /// have to import the time crate yourself in order to specify date
 /// information, second so that users don't have to type unproportionately
 /// long code just to generate an instance of [`OffsetDateTime`].
+///
+/// # Panics
+///
+/// Panics if the values don't form a valid date.
+#[allow(clippy::expect_used)]
 pub fn date_time_ymd(year: i32, month: u8, day: u8) -> OffsetDateTime {
 	let month = Month::try_from(month).expect("out-of-range month");
 	let primitive_dt = PrimitiveDateTime::new(
@@ -1086,7 +2994,7 @@ pub enum IsCa {
 }
 
 impl IsCa {
-	#[cfg(all(test, feature = "x509-parser"))]
+	#[cfg(feature = "x509-parser")]
 	fn from_x509(x509: &x509_parser::certificate::X509Certificate<'_>) -> Result<Self, Error> {
 		let basic_constraints = x509
 			.basic_constraints()
@@ -1145,10 +3053,128 @@ mod tests {
 
 	#[cfg(feature = "pem")]
 	use super::*;
-	#[cfg(feature = "x509-parser")]
-	use crate::DnValue;
+	#[cfg(feature = "crypto")]
+	use crate::test_keys;
 	#[cfg(feature = "crypto")]
 	use crate::KeyPair;
+	#[cfg(feature = "x509-parser")]
+	use crate::{DnValue, OtherNameValue};
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn v1_certificate() {
+		use x509_parser::prelude::{FromDer, X509Certificate};
+		use x509_parser::x509::X509Version;
+
+		let key = test_keys::ecdsa_p256();
+		let params = CertificateParams {
+			version: CertificateVersion::V1,
+			serial_number: Some(SerialNumber::from(1)),
+			..CertificateParams::default()
+		};
+		let cert = params.self_signed(&key).unwrap();
+
+		let (_, x509) = X509Certificate::from_der(cert.der()).unwrap();
+		assert_eq!(x509.version(), X509Version::V1);
+		assert!(x509.extensions().is_empty());
+
+		// The first member of the TBSCertificate is the serial number, not [0] EXPLICIT version
+		let tbs = crate::asn1::parse_der(cert.der(), |reader| {
+			reader.read_sequence(|reader| {
+				let tbs = reader.next().read_der()?;
+				reader.next().read_der()?;
+				reader.next().read_der()?;
+				Ok(tbs)
+			})
+		})
+		.unwrap();
+		let first = crate::asn1::parse_der(&tbs, |reader| {
+			reader.read_sequence(|reader| {
+				let first = reader.next().read_der()?;
+				while reader.read_optional(|reader| reader.read_der())?.is_some() {}
+				Ok(first)
+			})
+		})
+		.unwrap();
+		assert_eq!(first, [0x02, 0x01, 0x01]);
+
+		let extension_params: [fn(&mut CertificateParams); 4] = [
+			|params| {
+				params.subject_alt_names = vec![SanType::DnsName("a.example".try_into().unwrap())]
+			},
+			|params| params.key_usages = vec![KeyUsagePurpose::DigitalSignature],
+			|params| params.is_ca = IsCa::ExplicitNoCa,
+			|params| {
+				params.custom_extensions = vec![CustomExtension::from_oid_content(
+					&[1, 2, 3],
+					vec![0x05, 0x00],
+				)]
+			},
+		];
+		for set in extension_params {
+			let mut params = params.clone();
+			set(&mut params);
+			assert_eq!(
+				params.self_signed(&key).unwrap_err(),
+				Error::UnsupportedInV1Certificate
+			);
+		}
+	}
+
+	#[cfg(feature = "crypto")]
+	#[test]
+	fn test_matches_hostname() {
+		let mut params = CertificateParams::new(vec![
+			"example.com".to_owned(),
+			"*.wild.example.com".to_owned(),
+			"Mixed.Case.ORG.".to_owned(),
+			"w*.partial.example".to_owned(),
+			"*.com".to_owned(),
+			"192.0.2.1".to_owned(),
+			"2001:db8::1".to_owned(),
+		])
+		.unwrap();
+		params
+			.distinguished_name
+			.push(DnType::CommonName, "common.example.com");
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+
+		let cases = [
+			("example.com", true),
+			("EXAMPLE.com", true),
+			("example.com.", true),
+			("www.example.com", false),
+			("example.co", false),
+			("mixed.case.org", true),
+			("a.wild.example.com", true),
+			("A.WILD.Example.Com.", true),
+			("wild.example.com", false),
+			("a.b.wild.example.com", false),
+			(".wild.example.com", false),
+			("*.wild.example.com", false),
+			("www.partial.example", false),
+			("w*.partial.example", false),
+			("example.com..", false),
+			("foo.com", false),
+			("common.example.com", false),
+			("", false),
+			("192.0.2.1", true),
+			("192.0.2.2", false),
+			("2001:db8::1", true),
+			("2001:DB8:0::1", true),
+			("::ffff:192.0.2.1", false),
+		];
+		for (name, expected) in cases {
+			assert_eq!(cert.matches_hostname(name), expected, "{name}");
+		}
+		assert!(cert.matches_ip_address(IpAddr::from([192, 0, 2, 1])));
+		assert!(!cert.matches_ip_address(IpAddr::from([0u16; 8])));
+
+		let cert = CertificateParams::default()
+			.self_signed(&test_keys::ecdsa_p256())
+			.unwrap();
+		assert!(!cert.matches_hostname("example.com"));
+	}
 
 	#[cfg(feature = "crypto")]
 	#[test]
@@ -1166,7 +3192,7 @@ mod tests {
 		};
 
 		// Make the cert
-		let key_pair = KeyPair::generate().unwrap();
+		let key_pair = test_keys::ecdsa_p256();
 		let cert = params.self_signed(&key_pair).unwrap();
 
 		// Parse it
@@ -1207,7 +3233,7 @@ mod tests {
 		};
 
 		// Make the cert
-		let key_pair = KeyPair::generate().unwrap();
+		let key_pair = test_keys::ecdsa_p256();
 		let cert = params.self_signed(&key_pair).unwrap();
 
 		// Parse it
@@ -1242,7 +3268,7 @@ mod tests {
 		};
 
 		// Make the cert
-		let key_pair = KeyPair::generate().unwrap();
+		let key_pair = test_keys::ecdsa_p256();
 		let cert = params.self_signed(&key_pair).unwrap();
 
 		// Parse it
@@ -1254,6 +3280,35 @@ mod tests {
 		assert!(extension.value.any);
 	}
 
+	#[cfg(feature = "crypto")]
+	#[test]
+	fn test_lint_key_usages() {
+		let params = CertificateParams {
+			key_usages: vec![
+				KeyUsagePurpose::DigitalSignature,
+				KeyUsagePurpose::KeyEncipherment,
+			],
+			..CertificateParams::default()
+		};
+
+		let key_pair = KeyPair::generate_for(&crate::PKCS_ED25519).unwrap();
+		let lints = params.lint(&key_pair);
+		assert_eq!(
+			lints,
+			vec![Lint::KeyUsageIncompatibleWithKey {
+				usage: KeyUsagePurpose::KeyEncipherment,
+				algorithm: &crate::PKCS_ED25519,
+			}]
+		);
+		assert_eq!(
+			lints[0].to_string(),
+			"Key usage keyEncipherment is not applicable to Ed25519 keys"
+		);
+
+		// Linting doesn't prevent serialization.
+		params.self_signed(&key_pair).unwrap();
+	}
+
 	#[cfg(feature = "crypto")]
 	#[test]
 	fn test_with_extended_key_usages_other() {
@@ -1270,7 +3325,7 @@ mod tests {
 		};
 
 		// Make the cert
-		let key_pair = KeyPair::generate().unwrap();
+		let key_pair = test_keys::ecdsa_p256();
 		let cert = params.self_signed(&key_pair).unwrap();
 
 		// Parse it
@@ -1284,14 +3339,93 @@ mod tests {
 		assert_eq!(extension.value.other, expected_oids);
 	}
 
-	#[cfg(feature = "pem")]
+	#[cfg(all(feature = "crypto", feature = "x509-parser"))]
+	#[test]
+	fn test_extensions() {
+		let params = CertificateParams {
+			subject_alt_names: vec![SanType::DnsName("crabs.crabs".try_into().unwrap())],
+			key_usages: vec![KeyUsagePurpose::DigitalSignature],
+			extended_key_usages: vec![ExtendedKeyUsagePurpose::ServerAuth],
+			is_ca: IsCa::ExplicitNoCa,
+			custom_extensions: vec![CustomExtension::from_oid_content(
+				&[1, 2, 3, 4, 100_000],
+				yasna::construct_der(|writer| writer.write_utf8_string("crab")),
+			)],
+			..CertificateParams::default()
+		};
+		let key_pair = test_keys::ecdsa_p256();
+		let cert = params.self_signed(&key_pair).unwrap();
+
+		let (_, x509) = x509_parser::parse_x509_certificate(cert.der()).unwrap();
+		let expected = x509
+			.extensions()
+			.iter()
+			.map(|ext| (ext.oid.to_id_string(), ext.critical, ext.value))
+			.collect::<Vec<_>>();
+		let extensions = cert.extensions().unwrap();
+		let actual = extensions
+			.iter()
+			.map(|ext| {
+				let oid = ext.oid_components().map(|c| c.to_string());
+				(
+					oid.collect::<Vec<_>>().join("."),
+					ext.criticality(),
+					ext.value(),
+				)
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(actual, expected);
+		assert_eq!(
+			CertificateParams::extensions_from_der(cert.der()).unwrap(),
+			extensions
+		);
+
+		assert_eq!(
+			extensions[1].to_string(),
+			"keyUsage (2.5.29.15), critical: 03020780"
+		);
+		assert_eq!(
+			format!("{:?}", extensions[2]),
+			"ExtensionRef { oid: 2.5.29.37, name: Some(\"extKeyUsage\"), critical: false, \
+			 value: 300a06082b06010505070301 }"
+		);
+		assert_eq!(
+			extensions.last().unwrap().to_string(),
+			"1.2.3.4.100000: 0c0463726162"
+		);
+
+		let no_extensions = CertificateParams::default().self_signed(&key_pair).unwrap();
+		assert_eq!(no_extensions.extensions().unwrap().len(), 0);
+		assert_eq!(
+			CertificateParams::extensions_from_der(&CertificateDer::from(&cert.der()[1..])),
+			Err(Error::CouldNotParseCertificate)
+		);
+
+		// An external TBSCertificate with an extension that isn't DER, as its criticality is
+		// encoded as 0x01 instead of 0xff
+		let tbs = yasna::construct_der(|writer| {
+			writer.write_sequence(|writer| {
+				writer.next().write_u8(1);
+				key_pair.algorithm().write_alg_ident(writer.next());
+				writer.next().write_tagged(Tag::context(3), |writer| {
+					writer.write_der(&[
+						0x30, 0x0a, 0x30, 0x08, 0x06, 0x01, 0x2a, 0x01, 0x01, 0x01, 0x04, 0x00,
+					]);
+				});
+			})
+		});
+		let assembled = Certificate::assemble(&tbs, key_pair.algorithm(), &[0; 64]).unwrap();
+		assert_eq!(assembled.extensions(), Err(Error::CouldNotParseCertificate));
+	}
+
+	#[cfg(feature = "pem")]
 	mod test_pem_serialization {
 		use super::*;
 
 		#[test]
 		#[cfg(windows)]
 		fn test_windows_line_endings() {
-			let key_pair = KeyPair::generate().unwrap();
+			let key_pair = test_keys::ecdsa_p256();
 			let cert = CertificateParams::default().self_signed(&key_pair).unwrap();
 			assert!(cert.pem().contains("\r\n"));
 		}
@@ -1299,12 +3433,46 @@ mod tests {
 		#[test]
 		#[cfg(not(windows))]
 		fn test_not_windows_line_endings() {
-			let key_pair = KeyPair::generate().unwrap();
+			let key_pair = test_keys::ecdsa_p256();
 			let cert = CertificateParams::default().self_signed(&key_pair).unwrap();
 			assert!(!cert.pem().contains('\r'));
 		}
 	}
 
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn parse_unknown_extended_key_usages() {
+		let eku_value = |der: &[u8]| {
+			let (_, x509) = x509_parser::parse_x509_certificate(der).unwrap();
+			x509.get_extension_unique(&x509_parser::oid_registry::OID_X509_EXT_EXTENDED_KEY_USAGE)
+				.unwrap()
+				.unwrap()
+				.value
+				.to_vec()
+		};
+
+		let params = CertificateParams {
+			extended_key_usages: vec![
+				ExtendedKeyUsagePurpose::Other(vec![1, 3, 6, 1, 4, 1, 55555, 1]),
+				ExtendedKeyUsagePurpose::ServerAuth,
+				ExtendedKeyUsagePurpose::Other(vec![1, 3, 6, 1, 4, 1, 55555, 2]),
+				ExtendedKeyUsagePurpose::ClientAuth,
+			],
+			..CertificateParams::default()
+		};
+		let key_pair = test_keys::ecdsa_p256();
+		let cert = params.self_signed(&key_pair).unwrap();
+
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert_eq!(
+			params_from_cert.extended_key_usages,
+			params.extended_key_usages
+		);
+
+		let reissued = params_from_cert.self_signed(&key_pair).unwrap();
+		assert_eq!(eku_value(reissued.der()), eku_value(cert.der()));
+	}
+
 	#[cfg(feature = "x509-parser")]
 	#[test]
 	fn parse_other_name_alt_name() {
@@ -1312,7 +3480,7 @@ mod tests {
 		let mut params = CertificateParams::default();
 		let other_name = SanType::OtherName((vec![1, 2, 3, 4], "Foo".into()));
 		params.subject_alt_names.push(other_name.clone());
-		let key_pair = KeyPair::generate().unwrap();
+		let key_pair = test_keys::ecdsa_p256();
 		let cert = params.self_signed(&key_pair).unwrap();
 
 		// We should be able to parse the certificate with x509-parser.
@@ -1330,6 +3498,1231 @@ mod tests {
 		assert_eq!(subject_alt_names, expected_alt_names);
 	}
 
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn parse_raw_alt_names() {
+		use x509_parser::extensions::GeneralName;
+
+		let directory_name = {
+			let mut dn = DistinguishedName::new();
+			dn.push(DnType::CommonName, "Crab");
+			yasna::construct_der(|writer| crate::write_distinguished_name(writer, &dn))
+		};
+		let raw_names = vec![
+			// otherName with an INTEGER value: OID 1.2.3.4, [0] { INTEGER 5 }
+			SanType::Raw {
+				tag: 0,
+				der_value: vec![0x06, 0x03, 0x2a, 0x03, 0x04, 0xa0, 0x03, 0x02, 0x01, 0x05],
+			},
+			// x400Address with an empty built-in-standard-attributes SEQUENCE
+			SanType::Raw {
+				tag: 3,
+				der_value: vec![0x30, 0x00],
+			},
+			SanType::Raw {
+				tag: 4,
+				der_value: directory_name,
+			},
+			// registeredID 1.2.3.4
+			SanType::Raw {
+				tag: 8,
+				der_value: vec![0x2a, 0x03, 0x04],
+			},
+		];
+		let params = CertificateParams {
+			subject_alt_names: raw_names.clone(),
+			..CertificateParams::default()
+		};
+		let key_pair = test_keys::ecdsa_p256();
+		let cert = params.self_signed(&key_pair).unwrap();
+
+		let (_, x509) = x509_parser::parse_x509_certificate(cert.der()).unwrap();
+		let san = x509.subject_alternative_name().unwrap().unwrap();
+		let names = &san.value.general_names;
+		assert!(
+			matches!(&names[0], GeneralName::OtherName(oid, _) if oid.to_id_string() == "1.2.3.4")
+		);
+		assert!(matches!(&names[1], GeneralName::X400Address(_)));
+		assert!(
+			matches!(&names[2], GeneralName::DirectoryName(name) if name.to_string() == "CN=Crab")
+		);
+		assert!(
+			matches!(&names[3], GeneralName::RegisteredID(oid) if oid.to_id_string() == "1.2.3.4")
+		);
+
+		// Only the x400Address has no dedicated variant
+		let mut expected = raw_names;
+		let mut crab = DistinguishedName::new();
+		crab.push(DnType::CommonName, "Crab");
+		expected[2] = SanType::DirectoryName(crab);
+		expected[3] = SanType::RegisteredId(vec![1, 2, 3, 4]);
+		expected[0] = SanType::OtherName((
+			vec![1, 2, 3, 4],
+			OtherNameValue::Der(vec![0x02, 0x01, 0x05]),
+		));
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert_eq!(params_from_cert.subject_alt_names, expected);
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn directory_name() {
+		use x509_parser::extensions::GeneralName;
+
+		let mut dn = DistinguishedName::new();
+		dn.push(
+			DnType::CountryName,
+			DnValue::PrintableString("DE".try_into().unwrap()),
+		);
+		dn.push(DnType::OrganizationName, "Crab widgits SE");
+		dn.push(DnType::CommonName, "Crab");
+
+		// Created by `openssl req -x509` with `subjectAltName = dirName:dir_sect`, where the
+		// section contains the name above
+		let reference =
+			CertificateDer::from(&include_bytes!("test_data/openssl_dirname_san.der")[..]);
+		let params = CertificateParams::from_ca_cert_der(&reference).unwrap();
+		assert_eq!(
+			params.subject_alt_names,
+			[SanType::DirectoryName(dn.clone())]
+		);
+
+		let params = CertificateParams {
+			subject_alt_names: vec![SanType::DirectoryName(dn)],
+			..CertificateParams::default()
+		};
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let san_der = |der: &[u8]| {
+			let (_, x509) = x509_parser::parse_x509_certificate(der).unwrap();
+			let san = x509.subject_alternative_name().unwrap().unwrap();
+			assert!(matches!(
+				&san.value.general_names[..],
+				[GeneralName::DirectoryName(name)] if name.to_string() == "C=DE, O=Crab widgits SE, CN=Crab"
+			));
+			x509.extensions_map().unwrap()
+				[&x509_parser::oid_registry::OID_X509_EXT_SUBJECT_ALT_NAME]
+				.value
+				.to_vec()
+		};
+		assert_eq!(san_der(cert.der()), san_der(&reference));
+
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert_eq!(params_from_cert.subject_alt_names, params.subject_alt_names);
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn registered_id() {
+		let expected = [SanType::RegisteredId(vec![1, 3, 6, 1, 4, 1, 54392, 5, 1])];
+
+		// Created by `openssl req -x509 -addext 'subjectAltName=RID:1.3.6.1.4.1.54392.5.1'`
+		let reference = CertificateDer::from(&include_bytes!("test_data/openssl_rid_san.der")[..]);
+		let params = CertificateParams::from_ca_cert_der(&reference).unwrap();
+		assert_eq!(params.subject_alt_names, expected);
+
+		let params = CertificateParams {
+			subject_alt_names: expected.to_vec(),
+			..CertificateParams::default()
+		};
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let san_der = |der: &[u8]| {
+			let (_, x509) = x509_parser::parse_x509_certificate(der).unwrap();
+			x509.extensions_map().unwrap()
+				[&x509_parser::oid_registry::OID_X509_EXT_SUBJECT_ALT_NAME]
+				.value
+				.to_vec()
+		};
+		assert_eq!(san_der(cert.der()), san_der(&reference));
+
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert_eq!(params_from_cert.subject_alt_names, expected);
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn authority_info_access() {
+		let expected = vec![
+			(AccessMethod::Ocsp, "http://ocsp.crabs.example".to_owned()),
+			(
+				AccessMethod::CaIssuers,
+				"http://crabs.example/ca.der".to_owned(),
+			),
+		];
+
+		// Created by `openssl req -x509` with `authorityInfoAccess =
+		// OCSP;URI:http://ocsp.crabs.example,caIssuers;URI:http://crabs.example/ca.der`
+		let reference = CertificateDer::from(&include_bytes!("test_data/openssl_aia.der")[..]);
+		let params = CertificateParams::from_ca_cert_der(&reference).unwrap();
+		assert_eq!(params.authority_info_access, expected);
+
+		let params = CertificateParams {
+			authority_info_access: expected.clone(),
+			..CertificateParams::default()
+		};
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let aia = |der: &[u8]| {
+			let (_, x509) = x509_parser::parse_x509_certificate(der).unwrap();
+			let ext = &x509.extensions_map().unwrap()
+				[&x509_parser::oid_registry::OID_PKIX_AUTHORITY_INFO_ACCESS];
+			(ext.critical, ext.value.to_vec())
+		};
+		assert_eq!(aia(cert.der()), aia(&reference));
+
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert_eq!(params_from_cert.authority_info_access, expected);
+
+		let params = CertificateParams {
+			authority_info_access: vec![(AccessMethod::Other(vec![1, 2, 3, 4]), "crab".to_owned())],
+			..CertificateParams::default()
+		};
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert_eq!(
+			params_from_cert.authority_info_access,
+			params.authority_info_access
+		);
+
+		let params = CertificateParams {
+			authority_info_access: vec![(AccessMethod::Ocsp, "http://ocsp.🦀.example".to_owned())],
+			..CertificateParams::default()
+		};
+		assert!(matches!(
+			params.self_signed(&test_keys::ecdsa_p256()),
+			Err(Error::InvalidAsn1String(_))
+		));
+		assert!(matches!(
+			params.serialize_request(&test_keys::ecdsa_p256()),
+			Err(Error::UnsupportedInCsr)
+		));
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn certificate_transparency() {
+		let issuer_key = test_keys::ecdsa_p256();
+		let issuer_params = CertificateParams {
+			is_ca: IsCa::Ca(BasicConstraints::Unconstrained),
+			..CertificateParams::default()
+		};
+		let issuer = Issuer::new(issuer_params, &issuer_key);
+		let key = KeyPair::generate_for(&crate::PKCS_ECDSA_P256_SHA256).unwrap();
+
+		let mut params = CertificateParams::new(vec!["crabs.crabs".to_owned()]).unwrap();
+		params.serial_number = Some(SerialNumber::from(42));
+		params.use_authority_key_identifier_extension = true;
+		params.ct_poison = true;
+		let precert = params.signed_by(&key, &issuer).unwrap();
+
+		params.ct_poison = false;
+		let scts = vec![vec![0x00, 0x01, 0x02], vec![0xff; 300]];
+		params.signed_certificate_timestamps = scts.clone();
+		let cert = params.signed_by(&key, &issuer).unwrap();
+
+		let (_, x509_precert) = x509_parser::parse_x509_certificate(precert.der()).unwrap();
+		let (_, x509_cert) = x509_parser::parse_x509_certificate(cert.der()).unwrap();
+		let poison = x509_precert.extensions().last().unwrap();
+		assert_eq!(poison.oid.to_id_string(), "1.3.6.1.4.1.11129.2.4.3");
+		assert!(poison.critical);
+		// NULL
+		assert_eq!(poison.value, [0x05, 0x00]);
+		let sct_list = x509_cert.extensions().last().unwrap();
+		assert_eq!(sct_list.oid.to_id_string(), "1.3.6.1.4.1.11129.2.4.2");
+		assert!(!sct_list.critical);
+		// OCTET STRING of the list, which is framed by 16-bit lengths
+		let mut expected = vec![
+			0x04, 0x82, 0x01, 0x35, 0x01, 0x33, 0x00, 0x03, 0x00, 0x01, 0x02,
+		];
+		expected.extend_from_slice(&[0x01, 0x2c]);
+		expected.extend_from_slice(&[0xff; 300]);
+		assert_eq!(sct_list.value, expected);
+
+		// Without the CT extensions, the certificates are the same
+		let tbs_without_last_ext = |x509: &x509_parser::certificate::X509Certificate<'_>| {
+			let tbs = &x509.tbs_certificate;
+			let extensions = tbs.extensions();
+			(
+				tbs.serial.clone(),
+				tbs.issuer.as_raw().to_vec(),
+				tbs.subject.as_raw().to_vec(),
+				tbs.validity.clone(),
+				tbs.subject_pki.raw.to_vec(),
+				extensions[..extensions.len() - 1]
+					.iter()
+					.map(|ext| (ext.oid.to_id_string(), ext.critical, ext.value.to_vec()))
+					.collect::<Vec<_>>(),
+			)
+		};
+		assert_eq!(
+			tbs_without_last_ext(&x509_precert),
+			tbs_without_last_ext(&x509_cert)
+		);
+
+		let precert_params = CertificateParams::from_ca_cert_der(precert.der()).unwrap();
+		assert!(precert_params.ct_poison);
+		assert!(precert_params.signed_certificate_timestamps.is_empty());
+		let cert_params = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert!(!cert_params.ct_poison);
+		assert_eq!(cert_params.signed_certificate_timestamps, scts);
+
+		params.ct_poison = true;
+		assert!(matches!(
+			params.signed_by(&key, &issuer),
+			Err(Error::InvalidSignedCertificateTimestamps(_))
+		));
+		params.ct_poison = false;
+		for scts in [
+			vec![Vec::new()],
+			vec![vec![0; 65536]],
+			vec![vec![0; 40000]; 2],
+		] {
+			params.signed_certificate_timestamps = scts;
+			assert!(matches!(
+				params.signed_by(&key, &issuer),
+				Err(Error::InvalidSignedCertificateTimestamps(_))
+			));
+		}
+		assert!(matches!(
+			params.serialize_request(&key),
+			Err(Error::UnsupportedInCsr)
+		));
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn policy_constraints() {
+		let constraints = PolicyConstraints {
+			require_explicit_policy: Some(0),
+			inhibit_policy_mapping: Some(1),
+		};
+
+		// Created by `openssl req -x509` with `policyConstraints = critical,
+		// requireExplicitPolicy:0, inhibitPolicyMapping:1` and `inhibitAnyPolicy = critical, 2`
+		let reference =
+			CertificateDer::from(&include_bytes!("test_data/openssl_policy_constraints.der")[..]);
+		let params = CertificateParams::from_ca_cert_der(&reference).unwrap();
+		assert_eq!(params.policy_constraints, Some(constraints));
+		assert_eq!(params.inhibit_any_policy, Some(2));
+
+		let params = CertificateParams {
+			is_ca: IsCa::Ca(BasicConstraints::Unconstrained),
+			policy_constraints: Some(constraints),
+			inhibit_any_policy: Some(2),
+			..CertificateParams::default()
+		};
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let extensions = |der: &[u8]| {
+			let (_, x509) = x509_parser::parse_x509_certificate(der).unwrap();
+			let extensions = x509.extensions_map().unwrap();
+			[
+				x509_parser::oid_registry::OID_X509_EXT_POLICY_CONSTRAINTS,
+				x509_parser::oid_registry::OID_X509_EXT_INHIBIT_ANY_POLICY,
+			]
+			.map(|oid| (extensions[&oid].critical, extensions[&oid].value.to_vec()))
+		};
+		assert_eq!(extensions(cert.der()), extensions(&reference));
+
+		// A single constraint, with its own context tag
+		let params = CertificateParams {
+			policy_constraints: Some(PolicyConstraints {
+				require_explicit_policy: None,
+				inhibit_policy_mapping: Some(3),
+			}),
+			..params
+		};
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let ext = cert
+			.extensions()
+			.unwrap()
+			.into_iter()
+			.find(|ext| ext.name() == Some("policyConstraints"))
+			.unwrap();
+		assert_eq!(ext.value(), [0x30, 0x03, 0x81, 0x01, 0x03]);
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert_eq!(
+			params_from_cert.policy_constraints,
+			params.policy_constraints
+		);
+
+		assert!(matches!(
+			params.serialize_request(&test_keys::ecdsa_p256()),
+			Err(Error::UnsupportedInCsr)
+		));
+		let params = CertificateParams {
+			policy_constraints: None,
+			..params
+		};
+		assert!(matches!(
+			params.serialize_request(&test_keys::ecdsa_p256()),
+			Err(Error::UnsupportedInCsr)
+		));
+
+		let params = CertificateParams {
+			policy_constraints: Some(PolicyConstraints::default()),
+			..CertificateParams::default()
+		};
+		assert_eq!(
+			params.lint(&test_keys::ecdsa_p256()),
+			vec![Lint::EmptyPolicyConstraints]
+		);
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn ocsp_no_check() {
+		let params = CertificateParams {
+			extended_key_usages: vec![ExtendedKeyUsagePurpose::OcspSigning],
+			ocsp_no_check: true,
+			..CertificateParams::default()
+		};
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let ext = cert
+			.extensions()
+			.unwrap()
+			.into_iter()
+			.find(|ext| ext.name() == Some("ocspNoCheck"))
+			.unwrap();
+		assert!(!ext.criticality());
+		// NULL, not an empty value
+		assert_eq!(ext.value(), [0x05, 0x00]);
+
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert!(params_from_cert.ocsp_no_check);
+		let params_from_cert = CertificateParams::from_ca_cert_der(
+			CertificateParams::default()
+				.self_signed(&test_keys::ecdsa_p256())
+				.unwrap()
+				.der(),
+		)
+		.unwrap();
+		assert!(!params_from_cert.ocsp_no_check);
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn extension_criticality() {
+		let params = CertificateParams {
+			subject_alt_names: vec![SanType::DnsName("crabs.example".try_into().unwrap())],
+			key_usages: vec![KeyUsagePurpose::KeyCertSign],
+			extended_key_usages: vec![ExtendedKeyUsagePurpose::ServerAuth],
+			is_ca: IsCa::Ca(BasicConstraints::Unconstrained),
+			name_constraints: Some(NameConstraints {
+				permitted_subtrees: vec![GeneralSubtree::DnsName("example".to_string())],
+				excluded_subtrees: Vec::new(),
+			}),
+			crl_distribution_points: vec![CrlDistributionPoint {
+				uris: vec!["http://crl.example/ca.crl".to_string()],
+			}],
+			authority_info_access: vec![(AccessMethod::Ocsp, "http://ocsp.example/".to_string())],
+			certificate_policies: vec![PolicyInformation::any_policy(Vec::new())],
+			extension_criticality: [
+				(ExtensionId::SubjectAltName, true),
+				(ExtensionId::ExtendedKeyUsage, false),
+				(ExtensionId::KeyUsage, false),
+				(ExtensionId::CrlDistributionPoints, true),
+				(ExtensionId::CertificatePolicies, true),
+			]
+			.into(),
+			..CertificateParams::default()
+		};
+		let criticality = |params: &CertificateParams| {
+			let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+			cert.extensions()
+				.unwrap()
+				.into_iter()
+				.map(|ext| (ext.name().unwrap(), ext.criticality()))
+				.collect::<BTreeMap<_, _>>()
+		};
+		assert_eq!(
+			criticality(&params),
+			BTreeMap::from([
+				("subjectAltName", true),
+				("keyUsage", false),
+				("extKeyUsage", false),
+				("nameConstraints", true),
+				("cRLDistributionPoints", true),
+				("authorityInfoAccess", false),
+				("certificatePolicies", true),
+				("subjectKeyIdentifier", false),
+				("basicConstraints", true),
+			])
+		);
+
+		for (id, critical) in [
+			(ExtensionId::NameConstraints, false),
+			(ExtensionId::BasicConstraints, false),
+			(ExtensionId::AuthorityInfoAccess, true),
+		] {
+			let mut params = params.clone();
+			params.extension_criticality.insert(id.clone(), critical);
+			assert_eq!(
+				params.self_signed(&test_keys::ecdsa_p256()).unwrap_err(),
+				Error::NonconformingCriticality(id.clone())
+			);
+
+			params.allow_nonconforming_criticality = true;
+			assert_eq!(criticality(&params)[id.name().unwrap()], critical);
+		}
+
+		// The subject alternative names must be critical if the subject is empty
+		let mut params = CertificateParams {
+			extension_criticality: [(ExtensionId::SubjectAltName, false)].into(),
+			..params
+		};
+		criticality(&params);
+		params.distinguished_name = DistinguishedName::new();
+		assert_eq!(
+			params.self_signed(&test_keys::ecdsa_p256()).unwrap_err(),
+			Error::NonconformingCriticality(ExtensionId::SubjectAltName)
+		);
+		params.extension_criticality.clear();
+		assert!(criticality(&params)["subjectAltName"]);
+
+		// Custom extensions are looked up by their OID
+		let oid = vec![1, 2, 3, 4];
+		params.custom_extensions = vec![CustomExtension::from_oid_content(&oid, vec![0x05, 0x00])];
+		params.extension_criticality = [(ExtensionId::Custom(oid.clone()), true)].into();
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let exts = cert.extensions().unwrap();
+		let custom = exts
+			.iter()
+			.find(|ext| ext.oid_components().eq(oid.iter().copied()));
+		assert!(custom.unwrap().criticality());
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn extension_order() {
+		const CUSTOM_OID: &[u64] = &[1, 2, 3, 4];
+		let params = CertificateParams {
+			subject_alt_names: vec![SanType::DnsName("crabs.example".try_into().unwrap())],
+			key_usages: vec![KeyUsagePurpose::KeyCertSign],
+			is_ca: IsCa::Ca(BasicConstraints::Unconstrained),
+			custom_extensions: vec![CustomExtension::from_oid_content(
+				CUSTOM_OID,
+				vec![0x05, 0x00],
+			)],
+			..CertificateParams::default()
+		};
+		let order = |params: &CertificateParams| {
+			let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+			let names = cert
+				.extensions()
+				.unwrap()
+				.into_iter()
+				.map(|ext| ext.name().unwrap_or("custom"))
+				.collect::<Vec<_>>();
+			(
+				names,
+				CertificateParams::from_ca_cert_der(cert.der()).unwrap(),
+			)
+		};
+
+		let (default_names, default_params) = order(&params);
+		assert_eq!(
+			default_names,
+			[
+				"subjectAltName",
+				"keyUsage",
+				"subjectKeyIdentifier",
+				"basicConstraints",
+				"custom"
+			]
+		);
+
+		let (names, parsed) = order(&CertificateParams {
+			extension_order: Some(vec![
+				ExtensionId::BasicConstraints,
+				ExtensionId::Custom(CUSTOM_OID.to_vec()),
+				ExtensionId::CrlDistributionPoints,
+				ExtensionId::KeyUsage,
+			]),
+			..params.clone()
+		});
+		assert_eq!(
+			names,
+			[
+				"basicConstraints",
+				"custom",
+				"keyUsage",
+				"subjectAltName",
+				"subjectKeyIdentifier"
+			]
+		);
+		assert_eq!(parsed, default_params);
+
+		// An empty order keeps the default order
+		let (names, _) = order(&CertificateParams {
+			extension_order: Some(Vec::new()),
+			..params.clone()
+		});
+		assert_eq!(names, default_names);
+
+		// The CT poison stays last, so that the final certificate only differs in it
+		let (names, _) = order(&CertificateParams {
+			ct_poison: true,
+			extension_order: Some(vec![ExtensionId::CtPoison, ExtensionId::KeyUsage]),
+			..params.clone()
+		});
+		assert_eq!(names.first(), Some(&"keyUsage"));
+		assert_eq!(names.last(), Some(&"ctPoison"));
+		let (names, _) = order(&CertificateParams {
+			signed_certificate_timestamps: vec![vec![0; 4]],
+			extension_order: Some(vec![ExtensionId::SignedCertificateTimestamps]),
+			..params
+		});
+		assert_eq!(names[..names.len() - 1], default_names);
+		assert_eq!(names.last(), Some(&"signedCertificateTimestampList"));
+	}
+
+	#[test]
+	fn ms_template_extensions() {
+		use crate::{CustomExtension, Error};
+
+		// Reference encodings generated with `openssl asn1parse -genconf`
+		let name = CustomExtension::new_ms_template_name("WebServer").unwrap();
+		assert_eq!(
+			name.content(),
+			[
+				0x1e, 0x12, 0x00, 0x57, 0x00, 0x65, 0x00, 0x62, 0x00, 0x53, 0x00, 0x65, 0x00, 0x72,
+				0x00, 0x76, 0x00, 0x65, 0x00, 0x72,
+			]
+		);
+		assert!(!name.criticality());
+		assert!(matches!(
+			CustomExtension::new_ms_template_name("Web🦀Server"),
+			Err(Error::InvalidAsn1String(_))
+		));
+
+		let template = [
+			1, 3, 6, 1, 4, 1, 311, 21, 8, 16187918, 14945684, 15749023, 11519519, 4925321, 197,
+			13392998, 8282280,
+		];
+		let info = CustomExtension::new_ms_template_info(&template, 100, 4);
+		assert_eq!(
+			info.content(),
+			[
+				0x30, 0x2f, 0x06, 0x27, 0x2b, 0x06, 0x01, 0x04, 0x01, 0x82, 0x37, 0x15, 0x08, 0x87,
+				0xdc, 0x84, 0x0e, 0x87, 0x90, 0x9b, 0x14, 0x87, 0xc1, 0x9f, 0x1f, 0x85, 0xbf, 0x8c,
+				0x1f, 0x82, 0xac, 0xcf, 0x09, 0x81, 0x45, 0x86, 0xb1, 0xb8, 0x66, 0x83, 0xf9, 0xc1,
+				0x28, 0x02, 0x01, 0x64, 0x02, 0x01, 0x04,
+			]
+		);
+
+		#[cfg(feature = "crypto")]
+		{
+			let params = crate::CertificateParams {
+				custom_extensions: vec![name, info],
+				..crate::CertificateParams::default()
+			};
+			let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+			assert_eq!(
+				cert.extensions()
+					.unwrap()
+					.into_iter()
+					.map(|ext| ext.name())
+					.collect::<Vec<_>>(),
+				[Some("certificateTemplateName"), Some("certificateTemplate")]
+			);
+		}
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn authority_key_identifier_mode() {
+		use x509_parser::extensions::{GeneralName, ParsedExtension};
+		use x509_parser::oid_registry::OID_X509_EXT_AUTHORITY_KEY_IDENTIFIER;
+
+		use crate::CertifiedIssuer;
+
+		let ca_params = |cn: &str, serial_number| {
+			let mut distinguished_name = DistinguishedName::new();
+			distinguished_name.push(DnType::CommonName, cn);
+			CertificateParams {
+				distinguished_name,
+				serial_number,
+				is_ca: IsCa::Ca(BasicConstraints::Unconstrained),
+				..CertificateParams::default()
+			}
+		};
+		let root =
+			CertifiedIssuer::self_signed(ca_params("Root CA", None), test_keys::ecdsa_p256())
+				.unwrap();
+		let intermediate = CertifiedIssuer::signed_by(
+			ca_params("Intermediate CA", Some(SerialNumber::from(42))),
+			test_keys::ecdsa_p384(),
+			&root,
+		)
+		.unwrap();
+
+		// Both certificates are issued by the root
+		for issuer in [&root, &intermediate] {
+			let (_, issuer_x509) = x509_parser::parse_x509_certificate(issuer.der()).unwrap();
+			let issuer_key_id = issuer_x509
+				.iter_extensions()
+				.find_map(|ext| match ext.parsed_extension() {
+					ParsedExtension::SubjectKeyIdentifier(key_id) => Some(key_id.0),
+					_ => None,
+				})
+				.unwrap();
+
+			for (mode, key_id, issuer_and_serial) in [
+				(AuthorityKeyIdMode::KeyIdentifier, true, false),
+				(AuthorityKeyIdMode::IssuerAndSerialNumber, false, true),
+				(AuthorityKeyIdMode::Both, true, true),
+			] {
+				let params = CertificateParams {
+					use_authority_key_identifier_extension: true,
+					authority_key_identifier_mode: mode,
+					..CertificateParams::default()
+				};
+				let cert = params.signed_by(&test_keys::ed25519(), issuer).unwrap();
+				let (_, x509) = x509_parser::parse_x509_certificate(cert.der()).unwrap();
+				let ext = x509
+					.get_extension_unique(&OID_X509_EXT_AUTHORITY_KEY_IDENTIFIER)
+					.unwrap()
+					.unwrap();
+				let ParsedExtension::AuthorityKeyIdentifier(aki) = ext.parsed_extension() else {
+					unreachable!()
+				};
+
+				assert_eq!(
+					aki.key_identifier.as_ref().map(|key_id| key_id.0),
+					key_id.then_some(issuer_key_id)
+				);
+				assert_eq!(
+					aki.authority_cert_serial,
+					issuer_and_serial.then_some(issuer_x509.raw_serial())
+				);
+				match aki.authority_cert_issuer.as_deref() {
+					Some([GeneralName::DirectoryName(name)]) => {
+						assert!(issuer_and_serial);
+						assert_eq!(name.to_string(), "CN=Root CA");
+					},
+					None => assert!(!issuer_and_serial),
+					Some(names) => panic!("unexpected authority cert issuer {names:?}"),
+				}
+
+				let parsed = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+				assert!(parsed.use_authority_key_identifier_extension);
+				assert_eq!(parsed.authority_key_identifier_mode, mode);
+			}
+		}
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn no_subject_key_identifier() {
+		let params = CertificateParams {
+			is_ca: IsCa::ExplicitNoCa,
+			..CertificateParams::default()
+		};
+		let names = |params: &CertificateParams| {
+			let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+			let parsed = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+			assert_eq!(
+				parsed.use_subject_key_identifier_extension,
+				params.use_subject_key_identifier_extension
+			);
+			cert.extensions()
+				.unwrap()
+				.into_iter()
+				.map(|ext| ext.name().unwrap())
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(names(&params), ["subjectKeyIdentifier", "basicConstraints"]);
+		let params = CertificateParams {
+			use_subject_key_identifier_extension: false,
+			..params
+		};
+		assert_eq!(names(&params), ["basicConstraints"]);
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn freshest_crl() {
+		let dp = |uris: &[&str]| CrlDistributionPoint {
+			uris: uris.iter().map(|uri| uri.to_string()).collect(),
+		};
+		let freshest_crl = vec![
+			dp(&["http://crl.example.com/delta.crl"]),
+			dp(&[
+				"http://crl.example.com/delta2.crl",
+				"ldap://ldap.example.com/cn=delta",
+			]),
+		];
+
+		let reference =
+			CertificateDer::from(&include_bytes!("test_data/openssl_freshest_crl.der")[..]);
+		let params = CertificateParams::from_ca_cert_der(&reference).unwrap();
+		assert_eq!(params.freshest_crl, freshest_crl);
+
+		let params = CertificateParams {
+			freshest_crl,
+			..CertificateParams::default()
+		};
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let value = |cert: &CertificateDer<'_>| {
+			let ext = CertificateParams::extensions_from_der(cert)
+				.unwrap()
+				.into_iter()
+				.find(|ext| ext.name() == Some("freshestCRL"))
+				.unwrap();
+			assert!(!ext.criticality());
+			ext.value().to_vec()
+		};
+		assert_eq!(value(cert.der()), value(&reference));
+
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert_eq!(params_from_cert.freshest_crl, params.freshest_crl);
+
+		assert!(matches!(
+			params.serialize_request(&test_keys::ecdsa_p256()),
+			Err(Error::UnsupportedInCsr)
+		));
+
+		let params = CertificateParams {
+			extension_criticality: [(ExtensionId::FreshestCrl, true)].into(),
+			..params
+		};
+		assert_eq!(
+			params.self_signed(&test_keys::ecdsa_p256()).unwrap_err(),
+			Error::NonconformingCriticality(ExtensionId::FreshestCrl)
+		);
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn subject_directory_attributes() {
+		const DATE_OF_BIRTH: &[u64] = &[1, 3, 6, 1, 5, 5, 7, 9, 1];
+		const PLACE_OF_BIRTH: &[u64] = &[1, 3, 6, 1, 5, 5, 7, 9, 2];
+		const COUNTRY_OF_CITIZENSHIP: &[u64] = &[1, 3, 6, 1, 5, 5, 7, 9, 4];
+
+		let country = |code: &str| AttributeValue::PrintableString(code.try_into().unwrap());
+		let date = Date::from_calendar_date(1970, Month::January, 31).unwrap();
+		let params = CertificateParams {
+			subject_directory_attributes: vec![
+				(
+					DATE_OF_BIRTH.to_vec(),
+					vec![AttributeValue::date_of_birth(date)],
+				),
+				(
+					PLACE_OF_BIRTH.to_vec(),
+					vec![AttributeValue::Utf8String("Zürich".to_string())],
+				),
+				(
+					COUNTRY_OF_CITIZENSHIP.to_vec(),
+					vec![country("DE"), country("FR")],
+				),
+				// An INTEGER
+				(
+					vec![1, 2, 3, 4],
+					vec![AttributeValue::Der(vec![0x02, 0x01, 0x05])],
+				),
+			],
+			..CertificateParams::default()
+		};
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let ext = cert.extensions().unwrap().into_iter().next().unwrap();
+		assert_eq!(ext.name(), Some("subjectDirectoryAttributes"));
+		assert!(!ext.criticality());
+		assert!(ext
+			.value()
+			.windows(17)
+			.any(|window| window == b"\x18\x0f19700131120000Z"));
+
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert_eq!(
+			params_from_cert.subject_directory_attributes,
+			params.subject_directory_attributes
+		);
+
+		for values in [
+			Vec::new(),
+			vec![AttributeValue::Der(vec![0x05, 0x00, 0x05, 0x00])],
+		] {
+			let params = CertificateParams {
+				subject_directory_attributes: vec![(DATE_OF_BIRTH.to_vec(), values)],
+				..CertificateParams::default()
+			};
+			assert_eq!(
+				params.self_signed(&test_keys::ecdsa_p256()).unwrap_err(),
+				Error::InvalidSubjectDirectoryAttribute(DATE_OF_BIRTH.to_vec())
+			);
+		}
+
+		let params = CertificateParams {
+			extension_criticality: [(ExtensionId::SubjectDirectoryAttributes, true)].into(),
+			..params
+		};
+		assert_eq!(
+			params.self_signed(&test_keys::ecdsa_p256()).unwrap_err(),
+			Error::NonconformingCriticality(ExtensionId::SubjectDirectoryAttributes)
+		);
+		assert_eq!(
+			params
+				.serialize_request(&test_keys::ecdsa_p256())
+				.unwrap_err(),
+			Error::UnsupportedInCsr
+		);
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn netscape_extensions() {
+		let params = CertificateParams {
+			netscape_cert_type: Some(NetscapeCertType::SSL_SERVER),
+			netscape_comment: Some("Ticket 1234".to_string()),
+			..CertificateParams::default()
+		};
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let exts = cert.extensions().unwrap();
+		assert_eq!(exts[0].name(), Some("nsCertType"));
+		assert!(!exts[0].criticality());
+		// The first bit of a BIT STRING is its most significant one, and the six unused
+		// trailing bits are omitted
+		assert_eq!(exts[0].value(), [0x03, 0x02, 0x06, 0x40]);
+		assert_eq!(exts[1].name(), Some("nsComment"));
+		assert!(!exts[1].criticality());
+		assert_eq!(exts[1].value(), b"\x16\x0bTicket 1234");
+
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert_eq!(
+			params_from_cert.netscape_cert_type,
+			params.netscape_cert_type
+		);
+		assert_eq!(params_from_cert.netscape_comment, params.netscape_comment);
+
+		let cert_type = NetscapeCertType::SSL_CLIENT | NetscapeCertType::OBJECT_SIGNING_CA;
+		assert!(cert_type.contains(NetscapeCertType::SSL_CLIENT));
+		assert!(!cert_type.contains(NetscapeCertType::SSL_SERVER));
+		let params = CertificateParams {
+			netscape_cert_type: Some(cert_type),
+			netscape_comment: None,
+			..params
+		};
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		assert_eq!(
+			cert.extensions()
+				.unwrap()
+				.into_iter()
+				.next()
+				.unwrap()
+				.value(),
+			[0x03, 0x02, 0x00, 0x81]
+		);
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert_eq!(params_from_cert.netscape_cert_type, Some(cert_type));
+
+		let params = CertificateParams {
+			netscape_comment: Some("Ticket №1234".to_string()),
+			..params
+		};
+		assert!(matches!(
+			params.self_signed(&test_keys::ecdsa_p256()),
+			Err(Error::InvalidAsn1String(_))
+		));
+		assert_eq!(
+			params
+				.serialize_request(&test_keys::ecdsa_p256())
+				.unwrap_err(),
+			Error::UnsupportedInCsr
+		);
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn tls_features() {
+		let params = CertificateParams {
+			tls_features: vec![TlsFeature::StatusRequest],
+			..CertificateParams::default()
+		};
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let ext = cert
+			.extensions()
+			.unwrap()
+			.into_iter()
+			.find(|ext| ext.name() == Some("tlsFeature"))
+			.unwrap();
+		assert!(!ext.criticality());
+		// SEQUENCE { INTEGER 5 }
+		assert_eq!(ext.value(), [0x30, 0x03, 0x02, 0x01, 0x05]);
+
+		let params = CertificateParams {
+			tls_features: vec![
+				TlsFeature::StatusRequest,
+				TlsFeature::StatusRequestV2,
+				TlsFeature::Other(1000),
+			],
+			..CertificateParams::default()
+		};
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert_eq!(params_from_cert.tls_features, params.tls_features);
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn certificate_policies() {
+		let expected = vec![
+			PolicyInformation {
+				oid: vec![1, 3, 6, 1, 4, 1, 54392, 2, 1],
+				qualifiers: vec![
+					PolicyQualifier::CpsUri("https://crabs.example/cps".to_owned()),
+					PolicyQualifier::UserNotice {
+						organization: None,
+						notice_numbers: Vec::new(),
+						explicit_text: Some("Crabs only".to_owned()),
+					},
+				],
+			},
+			PolicyInformation::any_policy(Vec::new()),
+		];
+
+		// Created by `openssl req -x509` with a policy section holding the policy OID, a CPS
+		// URI and a userNotice with `explicitText = "UTF8:Crabs only"`, followed by anyPolicy
+		let reference = CertificateDer::from(&include_bytes!("test_data/openssl_policies.der")[..]);
+		let params = CertificateParams::from_ca_cert_der(&reference).unwrap();
+		assert_eq!(params.certificate_policies, expected);
+		assert!(params.certificate_policies[1].is_any_policy());
+
+		let params = CertificateParams {
+			certificate_policies: expected.clone(),
+			..CertificateParams::default()
+		};
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let policies = |der: &[u8]| {
+			let (_, x509) = x509_parser::parse_x509_certificate(der).unwrap();
+			let ext = &x509.extensions_map().unwrap()
+				[&x509_parser::oid_registry::OID_X509_EXT_CERTIFICATE_POLICIES];
+			(ext.critical, ext.value.to_vec())
+		};
+		assert_eq!(policies(cert.der()), policies(&reference));
+
+		// Notice references and the criticality survive a round trip
+		let params = CertificateParams {
+			certificate_policies: vec![PolicyInformation {
+				oid: vec![1, 2, 3, 4],
+				qualifiers: vec![PolicyQualifier::UserNotice {
+					organization: Some("Crab widgits SE".to_owned()),
+					notice_numbers: vec![1, 2],
+					explicit_text: None,
+				}],
+			}],
+			certificate_policies_critical: true,
+			..CertificateParams::default()
+		};
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert_eq!(
+			params_from_cert.certificate_policies,
+			params.certificate_policies
+		);
+		assert!(params_from_cert.certificate_policies_critical);
+		assert!(matches!(
+			params.serialize_request(&test_keys::ecdsa_p256()),
+			Err(Error::UnsupportedInCsr)
+		));
+
+		let params = CertificateParams {
+			certificate_policies: vec![PolicyInformation::any_policy(vec![
+				PolicyQualifier::CpsUri("https://🦀.example/cps".to_owned()),
+			])],
+			..CertificateParams::default()
+		};
+		assert!(matches!(
+			params.self_signed(&test_keys::ecdsa_p256()),
+			Err(Error::InvalidAsn1String(_))
+		));
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn issuer_alt_names() {
+		let issuer_alt_names = vec![
+			SanType::Rfc822Name("ca@crabs.example".try_into().unwrap()),
+			SanType::URI("https://crabs.example/ca".try_into().unwrap()),
+		];
+		let params = CertificateParams {
+			issuer_alt_names: issuer_alt_names.clone(),
+			..CertificateParams::default()
+		};
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+
+		let (_, x509) = x509_parser::parse_x509_certificate(cert.der()).unwrap();
+		let ext = x509
+			.get_extension_unique(&x509_parser::oid_registry::OID_X509_EXT_ISSUER_ALT_NAME)
+			.unwrap()
+			.unwrap();
+		assert!(!ext.critical);
+		// The same GeneralNames encoding as the subject alternative names
+		let san_params = CertificateParams {
+			subject_alt_names: issuer_alt_names.clone(),
+			..CertificateParams::default()
+		};
+		let san_cert = san_params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let (_, san_x509) = x509_parser::parse_x509_certificate(san_cert.der()).unwrap();
+		assert_eq!(
+			ext.value,
+			san_x509.extensions_map().unwrap()
+				[&x509_parser::oid_registry::OID_X509_EXT_SUBJECT_ALT_NAME]
+				.value
+		);
+
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert_eq!(params_from_cert.issuer_alt_names, issuer_alt_names);
+		assert!(!params_from_cert.issuer_alt_names_critical);
+		assert!(params_from_cert.subject_alt_names.is_empty());
+
+		// An issuer without a subject needs the extension to be critical
+		let params = CertificateParams {
+			distinguished_name: DistinguishedName::new(),
+			issuer_alt_names: issuer_alt_names.clone(),
+			issuer_alt_names_critical: true,
+			..CertificateParams::default()
+		};
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert_eq!(params_from_cert.issuer_alt_names, issuer_alt_names);
+		assert!(params_from_cert.issuer_alt_names_critical);
+
+		assert!(matches!(
+			params.serialize_request(&test_keys::ecdsa_p256()),
+			Err(Error::UnsupportedInCsr)
+		));
+		let params = CertificateParams {
+			version: CertificateVersion::V1,
+			..params
+		};
+		assert!(matches!(
+			params.self_signed(&test_keys::ecdsa_p256()),
+			Err(Error::UnsupportedInV1Certificate)
+		));
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn user_principal_name() {
+		use x509_parser::extensions::GeneralName;
+
+		let upn = SanType::user_principal_name("user@corp.example");
+		let params = CertificateParams {
+			subject_alt_names: vec![
+				upn.clone(),
+				SanType::OtherName((
+					vec![1, 2, 3, 4],
+					OtherNameValue::Ia5String("crab".try_into().unwrap()),
+				)),
+			],
+			..CertificateParams::default()
+		};
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+
+		// The GeneralName as issued by Active Directory Certificate Services
+		let expected = [
+			&[0xa0, 0x21, 0x06, 0x0a][..],
+			&[0x2b, 0x06, 0x01, 0x04, 0x01, 0x82, 0x37, 0x14, 0x02, 0x03],
+			&[0xa0, 0x13, 0x0c, 0x11],
+			b"user@corp.example",
+		]
+		.concat();
+		assert!(cert
+			.der()
+			.windows(expected.len())
+			.any(|w| w == &expected[..]));
+
+		let (_, x509) = x509_parser::parse_x509_certificate(cert.der()).unwrap();
+		let san = x509.subject_alternative_name().unwrap().unwrap();
+		assert!(matches!(
+			&san.value.general_names[0],
+			GeneralName::OtherName(oid, _) if oid.to_id_string() == "1.3.6.1.4.1.311.20.2.3"
+		));
+
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert_eq!(params_from_cert.subject_alt_names, params.subject_alt_names);
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn unsupported_san_types() {
+		use crate::{ParseLimits, ParseOptions};
+
+		let mut dn = DistinguishedName::new();
+		dn.push(
+			DnType::CommonName,
+			DnValue::PrintableString("Example".try_into().unwrap()),
+		);
+		// An ORAddress with only the country name US
+		let x400_address = SanType::Raw {
+			tag: 3,
+			der_value: vec![0x30, 0x06, 0x61, 0x04, 0x13, 0x02, b'U', b'S'],
+		};
+
+		// Created by `openssl req -x509` for a CA with a raw `subjectAltName = DER:...` value
+		// holding the directoryName and the x400Address above
+		let reference =
+			CertificateDer::from(&include_bytes!("test_data/openssl_dirname_x400_san_ca.der")[..]);
+		let params = CertificateParams::from_ca_cert_der(&reference).unwrap();
+		assert_eq!(
+			params.subject_alt_names,
+			[SanType::DirectoryName(dn), x400_address]
+		);
+		assert!(matches!(params.is_ca, IsCa::Ca(_)));
+
+		let limits = ParseLimits::default();
+		let mut options = ParseOptions::default();
+		let params =
+			CertificateParams::from_ca_cert_der_with_options(&reference, &limits, &options)
+				.unwrap();
+		assert_eq!(params.subject_alt_names.len(), 2);
+		options.reject_raw_names = true;
+		assert_eq!(
+			CertificateParams::from_ca_cert_der_with_options(&reference, &limits, &options),
+			Err(Error::InvalidNameType)
+		);
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn dns_name_round_trip() {
+		use crate::string::DnsName;
+
+		let params = CertificateParams::new(vec!["Crabs.Example.".to_owned()]).unwrap();
+		assert_eq!(
+			params.subject_alt_names,
+			[SanType::DnsName("crabs.example".try_into().unwrap())]
+		);
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert_eq!(params_from_cert.subject_alt_names, params.subject_alt_names);
+
+		// Raw names are kept as they are, on both ends
+		let raw = vec![SanType::DnsName(DnsName::raw(
+			"Crabs.Example.".try_into().unwrap(),
+		))];
+		let params = CertificateParams {
+			subject_alt_names: raw.clone(),
+			..CertificateParams::default()
+		};
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert_eq!(params_from_cert.subject_alt_names, raw);
+
+		assert!(CertificateParams::new(vec![".crabs.example".to_owned()]).is_err());
+
+		#[cfg(feature = "idna")]
+		{
+			let params = CertificateParams::new(vec!["krabben.Bücher.example".to_owned()]).unwrap();
+			let expected = [SanType::DnsName(DnsName::raw(
+				"krabben.xn--bcher-kva.example".try_into().unwrap(),
+			))];
+			assert_eq!(params.subject_alt_names, expected);
+			let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+			let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+			assert_eq!(params_from_cert.subject_alt_names, expected);
+		}
+	}
+
 	#[cfg(feature = "x509-parser")]
 	#[test]
 	fn parse_ia5string_subject() {
@@ -1342,7 +4735,7 @@ mod tests {
 			email_address_dn_type.clone(),
 			email_address_dn_value.clone(),
 		);
-		let key_pair = KeyPair::generate().unwrap();
+		let key_pair = test_keys::ecdsa_p256();
 		let cert = params.self_signed(&key_pair).unwrap();
 
 		// We should be able to parse the certificate with x509-parser.
@@ -1360,6 +4753,69 @@ mod tests {
 		assert_eq!(names, expected_names);
 	}
 
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn parse_ev_subject() {
+		let mut params = CertificateParams::new(vec!["crabs".to_owned()]).unwrap();
+		params.distinguished_name = DistinguishedName::new();
+		let dn = &mut params.distinguished_name;
+		dn.push(DnType::BusinessCategory, "Private Organization");
+		dn.push(DnType::OrganizationIdentifier, "VATDE-123456789");
+		dn.push(DnType::JurisdictionLocalityName, "Berlin");
+		dn.push(DnType::JurisdictionStateOrProvinceName, "Berlin");
+		dn.push(DnType::JurisdictionCountryName, "DE");
+		let key_pair = test_keys::ecdsa_p256();
+		let cert = params.self_signed(&key_pair).unwrap();
+
+		// The jurisdiction country must be a PrintableString, the rest default to UTF8String.
+		let (_, x509) = x509_parser::parse_x509_certificate(cert.der()).unwrap();
+		let country = x509
+			.subject()
+			.iter_attributes()
+			.find(|attr| {
+				attr.attr_type()
+					.iter()
+					.unwrap()
+					.eq([1, 3, 6, 1, 4, 1, 311, 60, 2, 1, 3])
+			})
+			.unwrap();
+		assert_eq!(
+			country.attr_value().tag(),
+			x509_parser::der_parser::asn1_rs::Tag::PrintableString
+		);
+
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		let names = params_from_cert
+			.distinguished_name
+			.iter()
+			.collect::<Vec<(_, _)>>();
+		assert_eq!(
+			names,
+			&[
+				(
+					&DnType::BusinessCategory,
+					&DnValue::Utf8String("Private Organization".to_owned())
+				),
+				(
+					&DnType::OrganizationIdentifier,
+					&DnValue::Utf8String("VATDE-123456789".to_owned())
+				),
+				(
+					&DnType::JurisdictionLocalityName,
+					&DnValue::Utf8String("Berlin".to_owned())
+				),
+				(
+					&DnType::JurisdictionStateOrProvinceName,
+					&DnValue::Utf8String("Berlin".to_owned())
+				),
+				(
+					&DnType::JurisdictionCountryName,
+					&DnValue::PrintableString("DE".try_into().unwrap())
+				),
+			]
+		);
+	}
+
 	#[cfg(feature = "x509-parser")]
 	#[test]
 	fn converts_from_ip() {
@@ -1367,7 +4823,7 @@ mod tests {
 		let ip_san = SanType::IpAddress(IpAddr::V4(ip));
 
 		let mut params = CertificateParams::new(vec!["crabs".to_owned()]).unwrap();
-		let ca_key = KeyPair::generate().unwrap();
+		let ca_key = test_keys::ecdsa_p256();
 
 		// Add the SAN we want to test the parsing for
 		params.subject_alt_names.push(ip_san.clone());
@@ -1382,6 +4838,102 @@ mod tests {
 		assert!(actual.subject_alt_names.contains(&ip_san));
 	}
 
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn rejects_trailing_bytes_after_ca_cert() {
+		let mut params = CertificateParams::new(vec!["crabs".to_owned()]).unwrap();
+		params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+		let ca_key = test_keys::ecdsa_p256();
+		let cert = params.self_signed(&ca_key).unwrap();
+
+		let mut der = cert.der().to_vec();
+		der.extend_from_slice(&[0x00, 0x00]);
+		let der = CertificateDer::from(der);
+
+		assert!(matches!(
+			CertificateParams::from_ca_cert_der(&der),
+			Err(Error::X509(_))
+		));
+		assert!(matches!(
+			Issuer::from_ca_cert_der(&der, &ca_key),
+			Err(Error::X509(_))
+		));
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn rejects_bad_ip_name_constraint_length() {
+		assert!(matches!(
+			CidrSubnet::from_octets(&[192, 0, 2, 0, 255]),
+			Err(Error::InvalidIpAddressOctetLength(5))
+		));
+		assert_eq!(
+			CidrSubnet::from_octets(&[192, 0, 2, 0, 255, 255, 255, 0]).unwrap(),
+			CidrSubnet::V4([192, 0, 2, 0], [255, 255, 255, 0])
+		);
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn name_constraints() {
+		let name_constraints = NameConstraints {
+			permitted_subtrees: vec![
+				GeneralSubtree::IpAddress(CidrSubnet::from_str("10.0.0.0/8").unwrap()),
+				GeneralSubtree::IpAddress(CidrSubnet::from_str("2001:db8::/32").unwrap()),
+				GeneralSubtree::DnsName(".corp.example".to_string()),
+				GeneralSubtree::Rfc822Name("corp.example".to_string()),
+				GeneralSubtree::URI(".corp.example".to_string()),
+			],
+			excluded_subtrees: vec![
+				GeneralSubtree::IpAddress(CidrSubnet::from_str("10.99.0.0/16").unwrap()),
+				GeneralSubtree::Rfc822Name("ceo@corp.example".to_string()),
+				GeneralSubtree::URI("legacy.corp.example".to_string()),
+			],
+		};
+		let params = CertificateParams {
+			is_ca: IsCa::Ca(BasicConstraints::Unconstrained),
+			name_constraints: Some(name_constraints.clone()),
+			..CertificateParams::default()
+		};
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let ext = cert
+			.extensions()
+			.unwrap()
+			.into_iter()
+			.find(|ext| ext.name() == Some("nameConstraints"))
+			.unwrap();
+		assert!(ext.criticality());
+		// The permitted IPv4 subtree: [0] { [7] address and mask }
+		assert!(ext
+			.value()
+			.windows(10)
+			.any(|w| w == [0x87, 0x08, 10, 0, 0, 0, 255, 0, 0, 0]));
+
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert_eq!(params_from_cert.name_constraints, Some(name_constraints));
+
+		let subnet = CidrSubnet::V4([10, 0, 0, 0], [255, 0, 255, 0]);
+		let params = CertificateParams {
+			name_constraints: Some(NameConstraints {
+				permitted_subtrees: Vec::new(),
+				excluded_subtrees: vec![GeneralSubtree::IpAddress(subnet)],
+			}),
+			..params
+		};
+		assert_eq!(
+			params.self_signed(&test_keys::ecdsa_p256()).unwrap_err(),
+			Error::InvalidSubnetMask(subnet)
+		);
+
+		assert!(CidrSubnet::V6([0; 16], [0; 16]).is_contiguous());
+		assert!(CidrSubnet::from_str("2001:db8::1/128")
+			.unwrap()
+			.is_contiguous());
+		assert!(!CidrSubnet::V6([0; 16], [0x7f; 16]).is_contiguous());
+		assert_eq!(CidrSubnet::from_str("10.0.0.0/33"), Err(()));
+		assert_eq!(CidrSubnet::from_str("2001:db8::/129"), Err(()));
+	}
+
 	#[cfg(feature = "x509-parser")]
 	mod test_key_identifier_from_ca {
 		use super::*;
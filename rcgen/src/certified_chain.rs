@@ -0,0 +1,320 @@
+use pki_types::CertificateDer;
+use time::OffsetDateTime;
+use yasna::tags::TAG_UTCTIME;
+use yasna::{ASN1Result, BERReader, Tag};
+
+use crate::{oid, Certificate, CertificateParams, CertifiedKey, Error, SigningKey};
+
+/// A leaf certificate with its key, together with the certificates that issued it
+///
+/// The issuers are ordered starting with the issuer of the leaf, each followed by its own
+/// issuer, as TLS expects them. Whether the chain ends with a root certificate is up to
+/// the caller, as the root is commonly left out when it's distributed separately.
+#[derive(PartialEq, Eq)]
+pub struct CertifiedChain<S: SigningKey> {
+	leaf: CertifiedKey<S>,
+	issuers: Vec<Certificate>,
+}
+
+impl<S: SigningKey> CertifiedChain<S> {
+	/// Creates a chain from the leaf and its issuers, in order
+	///
+	/// The chain isn't checked, use [`validate()`](Self::validate) for that.
+	pub fn new(leaf: CertifiedKey<S>, issuers: Vec<Certificate>) -> Self {
+		Self { leaf, issuers }
+	}
+
+	/// The leaf certificate and its key
+	pub fn leaf(&self) -> &CertifiedKey<S> {
+		&self.leaf
+	}
+
+	/// The issuers of the leaf certificate, starting with the one that signed it
+	pub fn issuers(&self) -> &[Certificate] {
+		&self.issuers
+	}
+
+	/// Splits the chain into the leaf and its issuers
+	pub fn into_parts(self) -> (CertifiedKey<S>, Vec<Certificate>) {
+		(self.leaf, self.issuers)
+	}
+
+	/// The certificates of the chain in DER format, starting with the leaf
+	///
+	/// This is the form rustls expects for a certificate chain.
+	pub fn as_der_chain(&self) -> Vec<CertificateDer<'static>> {
+		self.certificates().map(|cert| cert.der().clone()).collect()
+	}
+
+	/// The certificates of the chain in PEM format, starting with the leaf
+	#[cfg(feature = "pem")]
+	pub fn serialize_pem_chain(&self) -> String {
+		self.certificates().map(Certificate::pem).collect()
+	}
+
+	/// Checks that the certificates of the chain are linked together
+	///
+	/// The leaf certificate has to be for the public key of the leaf's key, and each
+	/// certificate has to
+	/// - name the subject of the next one as its issuer,
+	/// - have an authority key identifier equal to the subject key identifier of the next
+	///   one, if it has one, and
+	/// - have a validity period within the validity period of the next one.
+	///
+	/// Signatures aren't verified. Returns [`Error::InvalidChain`] describing the first
+	/// problem found.
+	pub fn validate(&self) -> Result<(), Error> {
+		let certs = self
+			.certificates()
+			.map(|cert| Parsed::from_der(cert.der()))
+			.collect::<Result<Vec<_>, _>>()?;
+
+		// Keys are always written with named curves, but other tools may not do so
+		let leaf_spki = crate::ec_params::normalize_spki(certs[0].spki)
+			.ok()
+			.flatten();
+		if leaf_spki.as_deref().unwrap_or(certs[0].spki)
+			!= self.leaf.signing_key.subject_public_key_info()
+		{
+			return Err(Error::InvalidChain(
+				"the leaf certificate is not for the leaf key".to_owned(),
+			));
+		}
+
+		for (i, (cert, issuer)) in certs.iter().zip(certs.iter().skip(1)).enumerate() {
+			let link = |problem| {
+				Error::InvalidChain(format!("certificate {i} {problem} certificate {}", i + 1))
+			};
+			if cert.issuer != issuer.subject {
+				return Err(link("names a different issuer than the subject of"));
+			}
+			if cert.authority_key_id.is_some() && cert.authority_key_id != issuer.key_id {
+				return Err(link("has an authority key identifier not matching"));
+			}
+			if cert.not_before < issuer.not_before || cert.not_after > issuer.not_after {
+				return Err(link("is valid outside of the validity period of"));
+			}
+		}
+		Ok(())
+	}
+
+	fn certificates(&self) -> impl Iterator<Item = &Certificate> {
+		std::iter::once(&self.leaf.cert).chain(&self.issuers)
+	}
+}
+
+/// The fields of a certificate relevant for the linkage of a chain
+struct Parsed<'a> {
+	/// The DER-encoded issuer name
+	issuer: &'a [u8],
+	/// The DER-encoded subject name
+	subject: &'a [u8],
+	not_before: OffsetDateTime,
+	not_after: OffsetDateTime,
+	/// The DER-encoded SubjectPublicKeyInfo
+	spki: &'a [u8],
+	key_id: Option<Vec<u8>>,
+	authority_key_id: Option<Vec<u8>>,
+}
+
+impl<'a> Parsed<'a> {
+	fn from_der(der: &'a CertificateDer<'_>) -> Result<Self, Error> {
+		let mut parsed =
+			crate::asn1::parse_der(der, Self::read).map_err(|_| Error::CouldNotParseCertificate)?;
+		for ext in CertificateParams::extensions_from_der(der)? {
+			if ext
+				.oid_components()
+				.eq(oid::SUBJECT_KEY_IDENTIFIER.iter().copied())
+			{
+				parsed.key_id = Some(
+					crate::asn1::parse_der(ext.value(), |reader| reader.read_bytes())
+						.map_err(|_| Error::CouldNotParseCertificate)?,
+				);
+			} else if ext
+				.oid_components()
+				.eq(oid::AUTHORITY_KEY_IDENTIFIER.iter().copied())
+			{
+				parsed.authority_key_id =
+					crate::asn1::parse_der(ext.value(), read_authority_key_id)
+						.map_err(|_| Error::CouldNotParseCertificate)?;
+			}
+		}
+		Ok(parsed)
+	}
+
+	fn read(reader: BERReader<'a, '_>) -> ASN1Result<Self> {
+		reader.read_sequence(|reader| {
+			let parsed = reader.next().read_sequence(|reader| {
+				// Skip the version, if present, the serial number and the signature algorithm
+				reader.read_optional(|reader| {
+					reader.read_tagged(Tag::context(0), |reader| reader.read_der())
+				})?;
+				reader.next().read_der()?;
+				reader.next().read_der()?;
+
+				let issuer = read_buffer(reader.next())?;
+				let (not_before, not_after) = reader.next().read_sequence(|reader| {
+					Ok((read_time(reader.next())?, read_time(reader.next())?))
+				})?;
+				let subject = read_buffer(reader.next())?;
+				let spki = read_buffer(reader.next())?;
+				// The unique identifiers and the extensions
+				while reader.read_optional(|reader| reader.read_der())?.is_some() {}
+
+				Ok(Self {
+					issuer,
+					subject,
+					not_before,
+					not_after,
+					spki,
+					key_id: None,
+					authority_key_id: None,
+				})
+			})?;
+			reader.next().read_der()?;
+			reader.next().read_der()?;
+			Ok(parsed)
+		})
+	}
+}
+
+/// Reads an element, returning its DER encoding
+fn read_buffer<'a>(reader: BERReader<'a, '_>) -> ASN1Result<&'a [u8]> {
+	Ok(reader.read_with_buffer(|reader| reader.read_der())?.1)
+}
+
+/// Reads a UTCTime or GeneralizedTime
+///
+/// In DER, both have to be in UTC and include the seconds, as RFC 5280 requires.
+fn read_time(reader: BERReader<'_, '_>) -> ASN1Result<OffsetDateTime> {
+	match reader.lookahead_tag()? {
+		TAG_UTCTIME => Ok(*reader.read_utctime()?.datetime()),
+		_ => Ok(*reader.read_generalized_time()?.datetime()),
+	}
+}
+
+/// Reads the key identifier of an AuthorityKeyIdentifier, the optional first field
+fn read_authority_key_id(reader: BERReader<'_, '_>) -> ASN1Result<Option<Vec<u8>>> {
+	reader.read_sequence(|reader| {
+		let key_id = reader.read_optional(|reader| {
+			reader.read_tagged_implicit(Tag::context(0), |reader| reader.read_bytes())
+		})?;
+		while reader.read_optional(|reader| reader.read_der())?.is_some() {}
+		Ok(key_id)
+	})
+}
+
+#[cfg(all(test, feature = "crypto"))]
+mod tests {
+	use time::{Date, Duration, Month, PrimitiveDateTime, Time};
+
+	use super::*;
+	use crate::{BasicConstraints, IsCa, Issuer, KeyPair, KeyUsagePurpose};
+
+	fn ca_params(name: &str) -> CertificateParams {
+		let mut params = CertificateParams::default();
+		params
+			.distinguished_name
+			.push(crate::DnType::CommonName, name);
+		params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+		params.key_usages = vec![KeyUsagePurpose::KeyCertSign];
+		params
+	}
+
+	#[test]
+	fn chain() {
+		let root_key = KeyPair::generate().unwrap();
+		let root_params = ca_params("Root");
+		let root = root_params.self_signed(&root_key).unwrap();
+		let root_issuer = Issuer::new(root_params, root_key);
+
+		let intermediate_key = KeyPair::generate().unwrap();
+		let intermediate_params = ca_params("Intermediate");
+		let intermediate = intermediate_params
+			.signed_by(&intermediate_key, &root_issuer)
+			.unwrap();
+		let intermediate_issuer = Issuer::new(intermediate_params, intermediate_key);
+
+		let leaf_key = KeyPair::generate().unwrap();
+		let mut leaf_params = CertificateParams::new(vec!["crabs.crabs".to_owned()]).unwrap();
+		let leaf = leaf_params
+			.signed_by(&leaf_key, &intermediate_issuer)
+			.unwrap();
+
+		let chain = CertifiedChain::new(
+			CertifiedKey {
+				cert: leaf.clone(),
+				signing_key: leaf_key,
+			},
+			vec![intermediate.clone(), root.clone()],
+		);
+		chain.validate().unwrap();
+		assert_eq!(
+			chain.as_der_chain(),
+			vec![
+				leaf.der().clone(),
+				intermediate.der().clone(),
+				root.der().clone()
+			]
+		);
+		#[cfg(feature = "pem")]
+		assert_eq!(
+			chain.serialize_pem_chain(),
+			[leaf.pem(), intermediate.pem(), root.pem()].concat()
+		);
+
+		let (leaf_key, issuers) = chain.into_parts();
+		let reversed = CertifiedChain::new(leaf_key, issuers.into_iter().rev().collect());
+		assert!(matches!(reversed.validate(), Err(Error::InvalidChain(_))));
+		let (leaf_key, issuers) = reversed.into_parts();
+
+		let other_key = CertifiedKey {
+			cert: leaf_key.cert,
+			signing_key: KeyPair::generate().unwrap(),
+		};
+		let chain = CertifiedChain::new(other_key, issuers.clone());
+		assert!(matches!(chain.validate(), Err(Error::InvalidChain(_))));
+
+		// A leaf outliving its issuer
+		leaf_params.not_after = CertificateParams::default().not_after + Duration::days(1);
+		let leaf_key = KeyPair::generate().unwrap();
+		let leaf = leaf_params
+			.signed_by(&leaf_key, &intermediate_issuer)
+			.unwrap();
+		let chain = CertifiedChain::new(
+			CertifiedKey {
+				cert: leaf,
+				signing_key: leaf_key,
+			},
+			vec![intermediate, root],
+		);
+		assert!(matches!(chain.validate(), Err(Error::InvalidChain(_))));
+	}
+
+	#[test]
+	fn times() {
+		let time = |tag, s: &str| {
+			let der = [&[tag, s.len() as u8], s.as_bytes()].concat();
+			crate::asn1::parse_der(&der, read_time).ok()
+		};
+		let at = |year, month, day, hour, minute, second| {
+			let date = Date::from_calendar_date(year, month, day).unwrap();
+			PrimitiveDateTime::new(date, Time::from_hms(hour, minute, second).unwrap()).assume_utc()
+		};
+		assert_eq!(
+			time(0x17, "491231235959Z"),
+			Some(at(2049, Month::December, 31, 23, 59, 59))
+		);
+		assert_eq!(
+			time(0x17, "500101000000Z"),
+			Some(at(1950, Month::January, 1, 0, 0, 0))
+		);
+		assert_eq!(
+			time(0x18, "40960101000000Z"),
+			Some(at(4096, Month::January, 1, 0, 0, 0))
+		);
+		assert_eq!(time(0x17, "500101000000"), None);
+		assert_eq!(time(0x18, "4096010100000Z"), None);
+		assert_eq!(time(0x17, "501301000000Z"), None);
+	}
+}
@@ -0,0 +1,220 @@
+use std::fmt;
+use std::time::Duration;
+
+use pki_types::{CertificateDer, ServerName, UnixTime};
+use time::OffsetDateTime;
+use webpki::{anchor_from_trusted_cert, EndEntityCert, KeyUsage, ALL_VERIFICATION_ALGS};
+
+/// Checks that `leaf` chains up to one of `roots` and is valid for `dns_name` at `at_time`.
+///
+/// The leaf is verified as a TLS server certificate using [rustls-webpki], with the
+/// signature algorithms provided by whichever crypto backend rcgen was built with.
+/// `intermediates` may be given in any order, and `roots` are the self-signed
+/// certificates to be used as trust anchors.
+///
+/// This is meant as a sanity check for freshly generated certificate hierarchies,
+/// e.g. in tests. It doesn't check revocation status.
+///
+/// Requires the `webpki` feature, and the `ring` or `aws_lc_rs` feature.
+///
+/// [rustls-webpki]: https://docs.rs/rustls-webpki
+pub fn verify_chain(
+	leaf: &CertificateDer<'_>,
+	intermediates: &[CertificateDer<'_>],
+	roots: &[CertificateDer<'_>],
+	dns_name: &str,
+	at_time: OffsetDateTime,
+) -> Result<(), ChainError> {
+	let name = ServerName::try_from(dns_name)
+		.map_err(|_| ChainError::InvalidDnsName(dns_name.to_owned()))?;
+	let time = u64::try_from(at_time.unix_timestamp())
+		.map(|secs| UnixTime::since_unix_epoch(Duration::from_secs(secs)))
+		.map_err(|_| ChainError::InvalidTime)?;
+
+	let anchors = roots
+		.iter()
+		.map(|root| {
+			anchor_from_trusted_cert(root).map_err(|e| ChainError::InvalidRoot(e.to_string()))
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let end_entity = EndEntityCert::try_from(leaf).map_err(ChainError::from)?;
+	end_entity
+		.verify_for_usage(
+			ALL_VERIFICATION_ALGS,
+			&anchors,
+			intermediates,
+			time,
+			KeyUsage::server_auth(),
+			None,
+			None,
+		)
+		.map_err(ChainError::from)?;
+	end_entity
+		.verify_is_valid_for_subject_name(&name)
+		.map_err(ChainError::from)?;
+
+	Ok(())
+}
+
+/// The reason a chain was rejected by [`verify_chain`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ChainError {
+	/// The given DNS name isn't syntactically valid
+	InvalidDnsName(String),
+	/// The validation time is before the Unix epoch
+	InvalidTime,
+	/// One of the roots couldn't be used as a trust anchor
+	InvalidRoot(String),
+	/// A certificate in the chain couldn't be parsed
+	MalformedCertificate(String),
+	/// The chain doesn't lead to any of the given roots
+	UnknownIssuer,
+	/// A certificate in the chain has a signature that doesn't verify
+	BadSignature,
+	/// A certificate in the chain is signed with an algorithm the crypto backend doesn't support
+	UnsupportedSignatureAlgorithm,
+	/// A certificate in the chain has expired at the validation time
+	Expired,
+	/// A certificate in the chain isn't valid yet at the validation time
+	NotYetValid,
+	/// The leaf isn't valid for the given DNS name
+	NotValidForName,
+	/// The leaf's extended key usage doesn't allow TLS server authentication
+	InvalidUsage,
+	/// The leaf is a CA certificate
+	CaUsedAsEndEntity,
+	/// An intermediate isn't a CA certificate
+	EndEntityUsedAsCa,
+	/// A path length constraint of an issuer was exceeded
+	PathLenConstraintViolated,
+	/// A name constraint of an issuer was violated
+	NameConstraintViolation,
+	/// Any other failure reported by webpki
+	Other(String),
+}
+
+impl From<webpki::Error> for ChainError {
+	fn from(err: webpki::Error) -> Self {
+		use webpki::Error as E;
+		match err {
+			E::BadDer
+			| E::BadDerTime
+			| E::MalformedExtensions
+			| E::ExtensionValueInvalid
+			| E::TrailingData(_)
+			| E::UnsupportedCertVersion
+			| E::UnsupportedCriticalExtension => Self::MalformedCertificate(err.to_string()),
+			E::UnknownIssuer => Self::UnknownIssuer,
+			E::InvalidSignatureForPublicKey => Self::BadSignature,
+			E::UnsupportedSignatureAlgorithmContext(_)
+			| E::UnsupportedSignatureAlgorithmForPublicKeyContext(_) => Self::UnsupportedSignatureAlgorithm,
+			E::CertExpired { .. } => Self::Expired,
+			E::CertNotValidYet { .. } => Self::NotYetValid,
+			E::CertNotValidForName(_) => Self::NotValidForName,
+			E::RequiredEkuNotFoundContext(_) | E::EmptyEkuExtension => Self::InvalidUsage,
+			E::CaUsedAsEndEntity => Self::CaUsedAsEndEntity,
+			E::EndEntityUsedAsCa => Self::EndEntityUsedAsCa,
+			E::PathLenConstraintViolated => Self::PathLenConstraintViolated,
+			E::NameConstraintViolation => Self::NameConstraintViolation,
+			other => Self::Other(other.to_string()),
+		}
+	}
+}
+
+impl fmt::Display for ChainError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		use self::ChainError::*;
+		match self {
+			InvalidDnsName(name) => write!(f, "Invalid DNS name: {name:?}")?,
+			InvalidTime => write!(f, "Validation time is before the Unix epoch")?,
+			InvalidRoot(e) => write!(f, "Root certificate can't be used as a trust anchor: {e}")?,
+			MalformedCertificate(e) => write!(f, "Malformed certificate in chain: {e}")?,
+			UnknownIssuer => write!(f, "Chain doesn't lead to a trusted root")?,
+			BadSignature => write!(f, "Invalid signature in chain")?,
+			UnsupportedSignatureAlgorithm => write!(f, "Unsupported signature algorithm in chain")?,
+			Expired => write!(f, "Certificate in chain has expired")?,
+			NotYetValid => write!(f, "Certificate in chain is not valid yet")?,
+			NotValidForName => write!(f, "Certificate is not valid for the given DNS name")?,
+			InvalidUsage => write!(f, "Certificate is not valid for TLS server authentication")?,
+			CaUsedAsEndEntity => write!(f, "CA certificate used as end entity")?,
+			EndEntityUsedAsCa => write!(f, "End entity certificate used as CA")?,
+			PathLenConstraintViolated => write!(f, "Path length constraint violated")?,
+			NameConstraintViolation => write!(f, "Name constraint violated")?,
+			Other(e) => write!(f, "Chain validation failed: {e}")?,
+		};
+		Ok(())
+	}
+}
+
+impl std::error::Error for ChainError {}
+
+#[cfg(test)]
+mod tests {
+	use time::Duration;
+
+	use super::*;
+	use crate::{
+		BasicConstraints, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa, Issuer,
+		KeyPair, KeyUsagePurpose,
+	};
+
+	fn ca_params(name: &str) -> CertificateParams {
+		let mut params = CertificateParams::new(Vec::new()).unwrap();
+		params.distinguished_name.push(DnType::CommonName, name);
+		params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+		params.key_usages = vec![KeyUsagePurpose::KeyCertSign];
+		params
+	}
+
+	#[test]
+	fn root_intermediate_leaf() {
+		let root_key = KeyPair::generate().unwrap();
+		let root_params = ca_params("Root");
+		let root = root_params.self_signed(&root_key).unwrap();
+		let root_issuer = Issuer::new(root_params, root_key);
+
+		let intermediate_key = KeyPair::generate().unwrap();
+		let intermediate_params = ca_params("Intermediate");
+		let intermediate = intermediate_params
+			.signed_by(&intermediate_key, &root_issuer)
+			.unwrap();
+		let intermediate_issuer = Issuer::new(intermediate_params, intermediate_key);
+
+		let leaf_key = KeyPair::generate().unwrap();
+		let mut leaf_params = CertificateParams::new(vec!["crabs.crabs".to_owned()]).unwrap();
+		leaf_params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+		let leaf = leaf_params
+			.signed_by(&leaf_key, &intermediate_issuer)
+			.unwrap();
+
+		let now = OffsetDateTime::now_utc();
+		let chain = [intermediate.der().clone()];
+		let roots = [root.der().clone()];
+		verify_chain(leaf.der(), &chain, &roots, "crabs.crabs", now).unwrap();
+
+		assert_eq!(
+			verify_chain(leaf.der(), &chain, &roots, "lobsters.crabs", now),
+			Err(ChainError::NotValidForName)
+		);
+		assert_eq!(
+			verify_chain(leaf.der(), &[], &roots, "crabs.crabs", now),
+			Err(ChainError::UnknownIssuer)
+		);
+		assert_eq!(
+			verify_chain(
+				leaf.der(),
+				&chain,
+				&roots,
+				"crabs.crabs",
+				now + Duration::days(365 * 5000)
+			),
+			Err(ChainError::Expired)
+		);
+		assert_eq!(
+			verify_chain(leaf.der(), &chain, &roots, "not a name", now),
+			Err(ChainError::InvalidDnsName("not a name".to_owned()))
+		);
+	}
+}
@@ -0,0 +1,263 @@
+//! Experimental composite ML-DSA signatures
+//!
+//! Implements [draft-ietf-lamps-pq-composite-sigs-02], which combines an ML-DSA key with a
+//! traditional key, so that certificates stay secure as long as either algorithm is unbroken.
+//! Both the OIDs and the encodings are still changing between draft versions.
+//!
+//! [draft-ietf-lamps-pq-composite-sigs-02]: https://www.ietf.org/archive/id/draft-ietf-lamps-pq-composite-sigs-02.html
+
+use yasna::models::ObjectIdentifier;
+
+use crate::sign_algo::algo::*;
+use crate::{Error, KeyPair, PublicKeyData, SignatureAlgorithm, SigningKey};
+
+/// A key pair for a composite signature algorithm, like [`PKCS_MLDSA44_ECDSA_P256_SHA256`]
+///
+/// It consists of an ML-DSA key pair and a traditional key pair, which both sign every
+/// message. The public key and the signature are the SEQUENCE of both component public keys
+/// and signatures as BIT STRINGs, ML-DSA first.
+///
+/// Requires the `composite_signatures_unstable` feature. The encoding follows
+/// [draft-ietf-lamps-pq-composite-sigs-02](https://www.ietf.org/archive/id/draft-ietf-lamps-pq-composite-sigs-02.html),
+/// and will change in minor releases to track the draft.
+#[derive(Debug)]
+pub struct CompositeKeyPair {
+	alg: &'static SignatureAlgorithm,
+	ml_dsa: KeyPair,
+	traditional: KeyPair,
+	public_key: Vec<u8>,
+}
+
+impl CompositeKeyPair {
+	/// Generates new random component key pairs for the composite algorithm `alg`
+	pub fn generate_for(alg: &'static SignatureAlgorithm) -> Result<Self, Error> {
+		let (ml_dsa, traditional) = components(alg).ok_or(Error::UnsupportedSignatureAlgorithm)?;
+		Self::from_components(
+			alg,
+			KeyPair::generate_for(ml_dsa)?,
+			KeyPair::generate_for(traditional)?,
+		)
+	}
+
+	/// Combines existing key pairs into a key pair for the composite algorithm `alg`
+	///
+	/// Returns [`Error::SignatureAlgorithmMismatch`] if the algorithms of the key pairs aren't
+	/// the components of `alg`.
+	pub fn from_components(
+		alg: &'static SignatureAlgorithm,
+		ml_dsa: KeyPair,
+		traditional: KeyPair,
+	) -> Result<Self, Error> {
+		let expected = components(alg).ok_or(Error::UnsupportedSignatureAlgorithm)?;
+		if (ml_dsa.algorithm(), traditional.algorithm()) != expected {
+			return Err(Error::SignatureAlgorithmMismatch);
+		}
+
+		let public_key = yasna::construct_der(|writer| {
+			writer.write_sequence(|writer| {
+				for key in [ml_dsa.der_bytes(), traditional.der_bytes()] {
+					writer.next().write_bitvec_bytes(key, key.len() * 8);
+				}
+			})
+		});
+
+		Ok(Self {
+			alg,
+			ml_dsa,
+			traditional,
+			public_key,
+		})
+	}
+
+	/// The ML-DSA component key pair
+	pub fn ml_dsa_key(&self) -> &KeyPair {
+		&self.ml_dsa
+	}
+
+	/// The traditional component key pair
+	pub fn traditional_key(&self) -> &KeyPair {
+		&self.traditional
+	}
+}
+
+impl SigningKey for CompositeKeyPair {
+	fn sign(&self, msg: &[u8]) -> Result<Vec<u8>, Error> {
+		let msg = domain_separated(self.alg, msg);
+		let signatures = [self.ml_dsa.sign(&msg)?, self.traditional.sign(&msg)?];
+		Ok(yasna::construct_der(|writer| {
+			writer.write_sequence(|writer| {
+				for signature in &signatures {
+					writer
+						.next()
+						.write_bitvec_bytes(signature, signature.len() * 8);
+				}
+			})
+		}))
+	}
+}
+
+impl PublicKeyData for CompositeKeyPair {
+	fn der_bytes(&self) -> &[u8] {
+		&self.public_key
+	}
+
+	fn algorithm(&self) -> &'static SignatureAlgorithm {
+		self.alg
+	}
+}
+
+/// The ML-DSA and traditional algorithms of a composite algorithm
+fn components(
+	alg: &'static SignatureAlgorithm,
+) -> Option<(&'static SignatureAlgorithm, &'static SignatureAlgorithm)> {
+	if alg == &PKCS_MLDSA44_ED25519 {
+		Some((&PKCS_ML_DSA_44, &PKCS_ED25519))
+	} else if alg == &PKCS_MLDSA44_ECDSA_P256_SHA256 {
+		Some((&PKCS_ML_DSA_44, &PKCS_ECDSA_P256_SHA256))
+	} else if alg == &PKCS_MLDSA65_ED25519 {
+		Some((&PKCS_ML_DSA_65, &PKCS_ED25519))
+	} else {
+		None
+	}
+}
+
+/// Prefixes `msg` with the DER-encoded OID of `alg`, which both components sign
+fn domain_separated(alg: &SignatureAlgorithm, msg: &[u8]) -> Vec<u8> {
+	let mut separated = yasna::construct_der(|writer| {
+		writer.write_oid(&ObjectIdentifier::from_slice(alg.oid_components))
+	});
+	separated.extend_from_slice(msg);
+	separated
+}
+
+#[cfg(test)]
+mod tests {
+	use aws_lc_rs::signature::{UnparsedPublicKey, VerificationAlgorithm};
+	use aws_lc_rs::unstable::signature::{ML_DSA_44, ML_DSA_65};
+
+	use super::*;
+	use crate::ring_like::signature::{ECDSA_P256_SHA256_ASN1, ED25519};
+	use crate::CertificateParams;
+
+	#[cfg(feature = "x509-parser")]
+	fn read_pair(der: &[u8]) -> [Vec<u8>; 2] {
+		crate::asn1::parse_der(der, |reader| {
+			reader.read_sequence(|reader| {
+				Ok([
+					reader.next().read_bitvec_bytes()?.0,
+					reader.next().read_bitvec_bytes()?.0,
+				])
+			})
+		})
+		.unwrap()
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn self_signed() {
+		for (alg, ml_dsa, traditional) in [
+			(
+				&PKCS_MLDSA44_ED25519,
+				&ML_DSA_44,
+				&ED25519 as &dyn VerificationAlgorithm,
+			),
+			(
+				&PKCS_MLDSA44_ECDSA_P256_SHA256,
+				&ML_DSA_44,
+				&ECDSA_P256_SHA256_ASN1,
+			),
+			(&PKCS_MLDSA65_ED25519, &ML_DSA_65, &ED25519),
+		] {
+			let key = CompositeKeyPair::generate_for(alg).unwrap();
+			let cert = CertificateParams::default().self_signed(&key).unwrap();
+			assert_eq!(cert.signature_algorithm(), alg);
+			verify_self_signed(cert.der(), &key, ml_dsa, traditional);
+		}
+	}
+
+	/// Checks that the public key of `der` is the one of `key`, and verifies its signature
+	/// with each component
+	#[cfg(feature = "x509-parser")]
+	fn verify_self_signed(
+		der: &[u8],
+		key: &CompositeKeyPair,
+		ml_dsa: &'static dyn VerificationAlgorithm,
+		traditional: &'static dyn VerificationAlgorithm,
+	) {
+		use x509_parser::prelude::FromDer;
+
+		let (_, parsed) = x509_parser::certificate::X509Certificate::from_der(der).unwrap();
+		let alg = key.algorithm();
+		assert_eq!(
+			parsed.signature_algorithm.algorithm.to_id_string(),
+			alg.oid_string()
+		);
+		let spki = &parsed.tbs_certificate.subject_pki;
+		assert_eq!(spki.algorithm.algorithm.to_id_string(), alg.oid_string());
+		assert!(spki.algorithm.parameters.is_none());
+		let public_keys = read_pair(&spki.subject_public_key.data);
+		assert_eq!(public_keys[0], key.ml_dsa_key().der_bytes());
+		assert_eq!(public_keys[1], key.traditional_key().der_bytes());
+
+		let signatures = read_pair(&parsed.signature_value.data);
+		let msg = domain_separated(alg, parsed.tbs_certificate.as_ref());
+		UnparsedPublicKey::new(ml_dsa, &public_keys[0])
+			.verify(&msg, &signatures[0])
+			.unwrap();
+		UnparsedPublicKey::new(traditional, &public_keys[1])
+			.verify(&msg, &signatures[1])
+			.unwrap();
+		// Each component signs the domain separated message, not the plain one
+		assert!(UnparsedPublicKey::new(traditional, &public_keys[1])
+			.verify(parsed.tbs_certificate.as_ref(), &signatures[1])
+			.is_err());
+	}
+
+	#[cfg(all(feature = "x509-parser", feature = "pem"))]
+	#[test]
+	fn fixture() {
+		// A self-signed id-MLDSA44-ECDSA-P256-SHA256 certificate, whose component signatures
+		// were verified with OpenSSL 3.5 over the domain separated TBS certificate
+		let der = include_bytes!("test_data/mldsa44_ecdsa_p256.cert.der");
+		let ml_dsa = KeyPair::from_pkcs8_pem_and_sign_algo(
+			include_str!("test_data/mldsa44.key.pem"),
+			&PKCS_ML_DSA_44,
+		)
+		.unwrap();
+		let traditional = KeyPair::from_pem(include_str!("test_data/p256.key.pem")).unwrap();
+		let key =
+			CompositeKeyPair::from_components(&PKCS_MLDSA44_ECDSA_P256_SHA256, ml_dsa, traditional)
+				.unwrap();
+		verify_self_signed(der, &key, &ML_DSA_44, &ECDSA_P256_SHA256_ASN1);
+
+		// Certificates signed with the fixture key again verify the same way
+		let params = crate::CertificateParams::from_ca_cert_der(&der[..].into()).unwrap();
+		let cert = params.self_signed(&key).unwrap();
+		verify_self_signed(cert.der(), &key, &ML_DSA_44, &ECDSA_P256_SHA256_ASN1);
+	}
+
+	#[test]
+	fn domain_separator() {
+		// The domain separator of id-MLDSA44-ECDSA-P256-SHA256 in the draft
+		let separated = domain_separated(&PKCS_MLDSA44_ECDSA_P256_SHA256, b"msg");
+		assert_eq!(
+			separated,
+			b"\x06\x0b\x60\x86\x48\x01\x86\xfa\x6b\x50\x08\x01\x04msg"
+		);
+	}
+
+	#[test]
+	fn mismatched_components() {
+		let ml_dsa = KeyPair::generate_for(&PKCS_ML_DSA_44).unwrap();
+		let traditional = KeyPair::generate_for(&PKCS_ECDSA_P256_SHA256).unwrap();
+		assert_eq!(
+			CompositeKeyPair::from_components(&PKCS_MLDSA44_ED25519, ml_dsa, traditional)
+				.unwrap_err(),
+			Error::SignatureAlgorithmMismatch
+		);
+		assert_eq!(
+			CompositeKeyPair::generate_for(&PKCS_ML_DSA_44).unwrap_err(),
+			Error::UnsupportedSignatureAlgorithm
+		);
+	}
+}
@@ -2,15 +2,19 @@
 use pem::Pem;
 use pki_types::CertificateRevocationListDer;
 use time::OffsetDateTime;
-use yasna::{DERWriter, Tag};
+use yasna::{DERWriter, DERWriterSeq, Tag};
 
-use crate::key_pair::sign_der;
+#[cfg(feature = "crypto")]
+use crate::key_pair::SubjectPublicKeyInfo;
+use crate::key_pair::{assemble_signed_der, check_tbs_der, sign_der, AsyncSigningKey};
+#[cfg(feature = "tracing")]
+use crate::trace;
 #[cfg(feature = "pem")]
 use crate::ENCODE_CONFIG;
 use crate::{
 	oid, write_distinguished_name, write_dt_utc_or_generalized,
 	write_x509_authority_key_identifier, write_x509_extension, Error, Issuer, KeyIdMethod,
-	KeyUsagePurpose, SerialNumber, SigningKey,
+	KeyUsagePurpose, Lint, PublicKeyData, SerialNumber, SignatureAlgorithm, SigningKey,
 };
 
 /// A certificate revocation list (CRL)
@@ -84,6 +88,40 @@ impl CertificateRevocationList {
 	pub fn der(&self) -> &CertificateRevocationListDer<'static> {
 		&self.der
 	}
+
+	/// Assembles a CRL from a TBSCertList and a signature produced outside of rcgen
+	///
+	/// `tbs_der` is the output of [`CertificateRevocationListParams::serialize_tbs_der()`]
+	/// and `signature` the signature over it, made with `signature_algorithm`. Returns
+	/// [`Error::SignatureAlgorithmMismatch`] if the TBSCertList names a different algorithm.
+	/// The signature itself isn't verified, see [`assemble_verified()`](Self::assemble_verified).
+	pub fn assemble(
+		tbs_der: &[u8],
+		signature_algorithm: &'static SignatureAlgorithm,
+		signature: &[u8],
+	) -> Result<Self, Error> {
+		check_tbs_der(tbs_der, signature_algorithm)?;
+		Ok(Self {
+			der: assemble_signed_der(tbs_der, signature_algorithm, signature).into(),
+		})
+	}
+
+	/// Like [`assemble()`](Self::assemble), but also verifies the signature with the public key
+	/// of the issuer
+	///
+	/// Returns [`Error::InvalidSignature`] if the signature doesn't verify, see
+	/// [`Certificate::assemble_verified()`](crate::Certificate::assemble_verified).
+	#[cfg(feature = "crypto")]
+	pub fn assemble_verified(
+		tbs_der: &[u8],
+		signature_algorithm: &'static SignatureAlgorithm,
+		signature: &[u8],
+		issuer_spki: &SubjectPublicKeyInfo,
+	) -> Result<Self, Error> {
+		let crl = Self::assemble(tbs_der, signature_algorithm, signature)?;
+		issuer_spki.verify_with_algorithm(signature_algorithm, tbs_der, signature)?;
+		Ok(crl)
+	}
 }
 
 impl From<CertificateRevocationList> for CertificateRevocationListDer<'static> {
@@ -93,8 +131,10 @@ impl From<CertificateRevocationList> for CertificateRevocationListDer<'static> {
 }
 
 /// A certificate revocation list (CRL) distribution point, to be included in a certificate's
-/// [distribution points extension](https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.13) or
+/// [distribution points extension](https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.13),
 /// a CRL's [issuing distribution point extension](https://datatracker.ietf.org/doc/html/rfc5280#section-5.2.5)
+/// or the [freshest CRL extension](https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.15)
+/// of either
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct CrlDistributionPoint {
 	/// One or more URI distribution point names, indicating a place the current CRL can
@@ -109,6 +149,49 @@ impl CrlDistributionPoint {
 			write_distribution_point_name_uris(writer.next(), &self.uris);
 		});
 	}
+
+	/// Parses the `CRLDistributionPoints` syntax shared by the CRL distribution points and
+	/// freshest CRL extensions, keeping the URIs of full names
+	///
+	/// Distribution points without any URI, like those only naming an LDAP directory name or
+	/// a CRL issuer, are skipped.
+	#[cfg(feature = "x509-parser")]
+	pub(crate) fn from_extension_value(value: &[u8]) -> Result<Vec<Self>, Error> {
+		use x509_parser::extensions::{CRLDistributionPoints, DistributionPointName, GeneralName};
+		use x509_parser::prelude::FromDer;
+
+		let (rest, points) =
+			CRLDistributionPoints::from_der(value).map_err(|_| Error::CouldNotParseCertificate)?;
+		if !rest.is_empty() {
+			return Err(Error::CouldNotParseCertificate);
+		}
+		Ok(points
+			.iter()
+			.filter_map(|point| match &point.distribution_point {
+				Some(DistributionPointName::FullName(names)) => Some(names),
+				_ => None,
+			})
+			.map(|names| Self {
+				uris: names
+					.iter()
+					.filter_map(|name| match name {
+						GeneralName::URI(uri) => Some(uri.to_string()),
+						_ => None,
+					})
+					.collect(),
+			})
+			.filter(|point| !point.uris.is_empty())
+			.collect())
+	}
+}
+
+/// Writes the `CRLDistributionPoints` syntax, a SEQUENCE of distribution points
+pub(crate) fn write_distribution_points(writer: DERWriter, points: &[CrlDistributionPoint]) {
+	writer.write_sequence(|writer| {
+		for point in points {
+			point.write_der(writer.next());
+		}
+	});
 }
 
 fn write_distribution_point_name_uris<'a>(
@@ -176,122 +259,483 @@ pub struct CertificateRevocationListParams {
 	pub revoked_certs: Vec<RevokedCertParams>,
 	/// Method to generate key identifiers from public keys
 	///
-	/// Defaults to SHA-256.
+	/// Defaults to SHA-256. It's only used if the subject key identifier of the issuer isn't
+	/// known, as it is for issuers parsed from a certificate, whose subject key identifier is
+	/// copied to the authority key identifier as is.
 	pub key_identifier_method: KeyIdMethod,
 }
 
 impl CertificateRevocationListParams {
 	/// Serializes the certificate revocation list (CRL).
 	///
-	/// Including a signature from the issuing certificate authority's key.
+	/// Including a signature from the issuing certificate authority's key. The parameters are
+	/// checked as with [`CrlValidation::Standard`], see
+	/// [`signed_by_validated()`](Self::signed_by_validated) for the other levels.
 	pub fn signed_by(
 		&self,
 		issuer: &Issuer<'_, impl SigningKey>,
 	) -> Result<CertificateRevocationList, Error> {
+		self.signed_by_validated(issuer, CrlValidation::Standard)
+	}
+
+	/// Like [`signed_by()`](Self::signed_by), but checks the parameters as given by
+	/// `validation` before serializing them
+	pub fn signed_by_validated(
+		&self,
+		issuer: &Issuer<'_, impl SigningKey>,
+		validation: CrlValidation,
+	) -> Result<CertificateRevocationList, Error> {
+		self.sign(issuer, validation, &CrlExtensions::default())
+	}
+
+	/// Adds the optional `extensions` to the CRL, like the freshest CRL of a base CRL
+	///
+	/// The returned [`CrlWithExtensions`] is signed like the parameters themselves.
+	pub fn with_extensions<'a>(&'a self, extensions: &'a CrlExtensions) -> CrlWithExtensions<'a> {
+		CrlWithExtensions {
+			params: self,
+			extensions,
+		}
+	}
+
+	fn sign(
+		&self,
+		issuer: &Issuer<'_, impl SigningKey>,
+		validation: CrlValidation,
+		extensions: &CrlExtensions,
+	) -> Result<CertificateRevocationList, Error> {
+		self.check(issuer, validation)?;
+		let sign = || {
+			sign_der(&issuer.signing_key, |writer| {
+				self.write_tbs(writer, issuer, extensions)
+			})
+		};
+		#[cfg(feature = "tracing")]
+		let der = trace::crl(self, issuer, sign)?;
+		#[cfg(not(feature = "tracing"))]
+		let der = sign()?;
+
+		Ok(CertificateRevocationList { der: der.into() })
+	}
+
+	/// Like [`signed_by()`](Self::signed_by), but signs with an [`AsyncSigningKey`]
+	pub async fn signed_by_async(
+		&self,
+		issuer: &Issuer<'_, impl AsyncSigningKey>,
+	) -> Result<CertificateRevocationList, Error> {
+		let tbs_der = self.serialize_tbs_der(issuer)?;
+		let signature = issuer.signing_key.sign(&tbs_der).await?;
+		let der = assemble_signed_der(&tbs_der, issuer.signing_key.algorithm(), &signature);
+		Ok(CertificateRevocationList { der: der.into() })
+	}
+
+	/// Serializes the TBSCertList, i.e. the part of the CRL covered by the signature
+	///
+	/// Like [`CertificateParams::serialize_tbs_der()`](crate::CertificateParams::serialize_tbs_der),
+	/// this allows signing with a key that isn't available to rcgen: sign the returned bytes with
+	/// the issuer's key, then pass the signature to [`CertificateRevocationList::assemble()`].
+	/// Only the public key of `issuer` is used, and the output only depends on the arguments.
+	///
+	/// The parameters are checked as with [`CrlValidation::Standard`], see
+	/// [`serialize_tbs_der_validated()`](Self::serialize_tbs_der_validated) for the other
+	/// levels.
+	pub fn serialize_tbs_der(
+		&self,
+		issuer: &Issuer<'_, impl PublicKeyData>,
+	) -> Result<Vec<u8>, Error> {
+		self.serialize_tbs_der_validated(issuer, CrlValidation::Standard)
+	}
+
+	/// Like [`serialize_tbs_der()`](Self::serialize_tbs_der), but checks the parameters as
+	/// given by `validation`, like [`signed_by_validated()`](Self::signed_by_validated)
+	pub fn serialize_tbs_der_validated(
+		&self,
+		issuer: &Issuer<'_, impl PublicKeyData>,
+		validation: CrlValidation,
+	) -> Result<Vec<u8>, Error> {
+		self.tbs_der(issuer, validation, &CrlExtensions::default())
+	}
+
+	fn tbs_der(
+		&self,
+		issuer: &Issuer<'_, impl PublicKeyData>,
+		validation: CrlValidation,
+		extensions: &CrlExtensions,
+	) -> Result<Vec<u8>, Error> {
+		self.check(issuer, validation)?;
+		yasna::try_construct_der(|writer| {
+			writer.write_sequence(|writer| self.write_tbs(writer, issuer, extensions))
+		})
+	}
+
+	/// Checks the parameters for combinations that are allowed to be serialized,
+	/// but that some relying parties reject
+	///
+	/// An empty result means that no problems were found. With [`CrlValidation::Strict`],
+	/// [`validate()`](Self::validate) fails instead for the problems reported here.
+	pub fn lint(&self) -> Vec<Lint> {
+		self.revoked_certs
+			.iter()
+			.filter(|revoked| revoked.revocation_time > self.this_update)
+			.map(|revoked| Lint::RevocationAfterThisUpdate(revoked.serial_number.clone()))
+			.collect()
+	}
+
+	/// Serializes one CRL per shard of `shards`, each with the revocations of its shard
+	///
+	/// The CRLs share all parameters but the revoked certificates and the issuing distribution
+	/// point, whose distribution point is the one of the shard. Its scope is taken from
+	/// [`issuing_distribution_point`](Self::issuing_distribution_point) if set. Every
+	/// revocation is in exactly one of the CRLs, which are returned in the order of
+	/// [`CrlShards::distribution_points`].
+	///
+	/// Returns [`Error::CrlShardMismatch`] if a certificate was issued with a distribution
+	/// point that isn't the one of its shard, per [`CrlShards::issued_with`].
+	pub fn signed_shards(
+		&self,
+		shards: &CrlShards,
+		issuer: &Issuer<'_, impl SigningKey>,
+	) -> Result<Vec<CertificateRevocationList>, Error> {
+		if shards.distribution_points.is_empty() {
+			return Err(Error::MissingCrlShards);
+		}
+
+		for revoked in &self.revoked_certs {
+			let serial = &revoked.serial_number;
+			let expected = shards
+				.distribution_point_for(serial)
+				.ok_or(Error::MissingCrlShards)?;
+			let mismatch = shards
+				.issued_with
+				.iter()
+				.any(|(issued, dp)| serial_key(issued) == serial_key(serial) && dp != expected);
+			if mismatch {
+				return Err(Error::CrlShardMismatch(serial.clone()));
+			}
+		}
+
+		let scope = self
+			.issuing_distribution_point
+			.as_ref()
+			.and_then(|idp| idp.scope);
+		(0..shards.distribution_points.len())
+			.map(|shard| {
+				let params = Self {
+					issuing_distribution_point: Some(CrlIssuingDistributionPoint {
+						distribution_point: shards.distribution_points[shard].clone(),
+						scope,
+					}),
+					revoked_certs: self
+						.revoked_certs
+						.iter()
+						.filter(|revoked| shards.shard_of(&revoked.serial_number) == Some(shard))
+						.cloned()
+						.collect(),
+					..self.clone()
+				};
+				params.signed_by(issuer)
+			})
+			.collect()
+	}
+
+	/// Sorts the revoked certificates by ascending serial number
+	///
+	/// Some CRL profiles recommend this order, which allows relying parties to look up
+	/// serial numbers with a binary search.
+	pub fn sort_revoked_certs(&mut self) {
+		self.revoked_certs
+			.sort_by(|a, b| serial_key(&a.serial_number).cmp(&serial_key(&b.serial_number)));
+	}
+
+	/// Checks that the parameters describe a well-formed CRL, as given by `validation`
+	///
+	/// This is what [`signed_by_validated()`](Self::signed_by_validated) does before
+	/// serializing the CRL, apart from checking the issuer.
+	pub fn validate(&self, validation: CrlValidation) -> Result<(), Error> {
+		if validation == CrlValidation::Unchecked {
+			return Ok(());
+		}
+
 		if self.next_update.le(&self.this_update) {
 			return Err(Error::InvalidCrlNextUpdate);
 		}
 
+		let mut serials = self
+			.revoked_certs
+			.iter()
+			.map(|revoked| &revoked.serial_number)
+			.collect::<Vec<_>>();
+		serials.sort_by_key(|serial| serial_key(serial));
+		if let Some(pair) = serials
+			.windows(2)
+			.find(|pair| serial_key(pair[0]) == serial_key(pair[1]))
+		{
+			return Err(Error::DuplicateRevokedSerialNumber(pair[0].clone()));
+		}
+
+		if validation == CrlValidation::Strict {
+			if let Some(Lint::RevocationAfterThisUpdate(serial)) = self.lint().into_iter().next() {
+				return Err(Error::RevocationAfterThisUpdate(serial));
+			}
+		}
+
+		Ok(())
+	}
+
+	fn check(
+		&self,
+		issuer: &Issuer<'_, impl PublicKeyData>,
+		validation: CrlValidation,
+	) -> Result<(), Error> {
+		self.validate(validation)?;
+
 		if !issuer.key_usages.is_empty() && !issuer.key_usages.contains(&KeyUsagePurpose::CrlSign) {
 			return Err(Error::IssuerNotCrlSigner);
 		}
 
-		Ok(CertificateRevocationList {
-			der: self.serialize_der(issuer)?.into(),
-		})
+		Ok(())
 	}
 
-	fn serialize_der(&self, issuer: &Issuer<'_, impl SigningKey>) -> Result<Vec<u8>, Error> {
-		sign_der(&issuer.signing_key, |writer| {
-			// Write CRL version.
-			// RFC 5280 §5.1.2.1:
-			//   This optional field describes the version of the encoded CRL.  When
-			//   extensions are used, as required by this profile, this field MUST be
-			//   present and MUST specify version 2 (the integer value is 1).
-			// RFC 5280 §5.2:
-			//   Conforming CRL issuers are REQUIRED to include the authority key
-			//   identifier (Section 5.2.1) and the CRL number (Section 5.2.3)
-			//   extensions in all CRLs issued.
-			writer.next().write_u8(1);
-
-			// Write algorithm identifier.
-			// RFC 5280 §5.1.2.2:
-			//   This field MUST contain the same algorithm identifier as the
-			//   signatureAlgorithm field in the sequence CertificateList
-			issuer
-				.signing_key
-				.algorithm()
-				.write_alg_ident(writer.next());
-
-			// Write issuer.
-			// RFC 5280 §5.1.2.3:
-			//   The issuer field MUST contain a non-empty X.500 distinguished name (DN).
-			write_distinguished_name(writer.next(), issuer.distinguished_name.as_ref());
-
-			// Write thisUpdate date.
-			// RFC 5280 §5.1.2.4:
-			//    This field indicates the issue date of this CRL.  thisUpdate may be
-			//    encoded as UTCTime or GeneralizedTime.
-			write_dt_utc_or_generalized(writer.next(), self.this_update);
-
-			// Write nextUpdate date.
-			// While OPTIONAL in the ASN.1 module, RFC 5280 §5.1.2.5 says:
-			//   Conforming CRL issuers MUST include the nextUpdate field in all CRLs.
-			write_dt_utc_or_generalized(writer.next(), self.next_update);
-
-			// Write revokedCertificates.
-			// RFC 5280 §5.1.2.6:
-			//   When there are no revoked certificates, the revoked certificates list
-			//   MUST be absent
-			if !self.revoked_certs.is_empty() {
-				writer.next().write_sequence(|writer| {
-					for revoked_cert in &self.revoked_certs {
-						revoked_cert.write_der(writer.next());
-					}
+	fn write_tbs(
+		&self,
+		writer: &mut DERWriterSeq,
+		issuer: &Issuer<'_, impl PublicKeyData>,
+		extensions: &CrlExtensions,
+	) -> Result<(), Error> {
+		// Write CRL version.
+		// RFC 5280 §5.1.2.1:
+		//   This optional field describes the version of the encoded CRL.  When
+		//   extensions are used, as required by this profile, this field MUST be
+		//   present and MUST specify version 2 (the integer value is 1).
+		// RFC 5280 §5.2:
+		//   Conforming CRL issuers are REQUIRED to include the authority key
+		//   identifier (Section 5.2.1) and the CRL number (Section 5.2.3)
+		//   extensions in all CRLs issued.
+		writer.next().write_u8(1);
+
+		// Write algorithm identifier.
+		// RFC 5280 §5.1.2.2:
+		//   This field MUST contain the same algorithm identifier as the
+		//   signatureAlgorithm field in the sequence CertificateList
+		issuer
+			.signing_key
+			.algorithm()
+			.write_alg_ident(writer.next());
+
+		// Write issuer.
+		// RFC 5280 §5.1.2.3:
+		//   The issuer field MUST contain a non-empty X.500 distinguished name (DN).
+		write_distinguished_name(writer.next(), issuer.distinguished_name.as_ref());
+
+		// Write thisUpdate date.
+		// RFC 5280 §5.1.2.4:
+		//    This field indicates the issue date of this CRL.  thisUpdate may be
+		//    encoded as UTCTime or GeneralizedTime.
+		write_dt_utc_or_generalized(writer.next(), self.this_update);
+
+		// Write nextUpdate date.
+		// While OPTIONAL in the ASN.1 module, RFC 5280 §5.1.2.5 says:
+		//   Conforming CRL issuers MUST include the nextUpdate field in all CRLs.
+		write_dt_utc_or_generalized(writer.next(), self.next_update);
+
+		// Write revokedCertificates.
+		// RFC 5280 §5.1.2.6:
+		//   When there are no revoked certificates, the revoked certificates list
+		//   MUST be absent
+		if !self.revoked_certs.is_empty() {
+			writer.next().write_sequence(|writer| {
+				for revoked_cert in &self.revoked_certs {
+					revoked_cert.write_der(writer.next());
+				}
+			});
+		}
+
+		// Write crlExtensions.
+		// RFC 5280 §5.1.2.7:
+		//   This field may only appear if the version is 2 (Section 5.1.2.1).  If
+		//   present, this field is a sequence of one or more CRL extensions.
+		// RFC 5280 §5.2:
+		//   Conforming CRL issuers are REQUIRED to include the authority key
+		//   identifier (Section 5.2.1) and the CRL number (Section 5.2.3)
+		//   extensions in all CRLs issued.
+		writer.next().write_tagged(Tag::context(0), |writer| {
+			writer.write_sequence(|writer| {
+				// Write authority key identifier.
+				write_x509_authority_key_identifier(
+					writer.next(),
+					Some(&issuer.authority_key_identifier(&self.key_identifier_method)),
+					None,
+					false,
+				);
+
+				// Write CRL number.
+				write_x509_extension(writer.next(), oid::CRL_NUMBER, false, |writer| {
+					writer.write_bigint_bytes(self.crl_number.as_ref(), true);
 				});
-			}
 
-			// Write crlExtensions.
-			// RFC 5280 §5.1.2.7:
-			//   This field may only appear if the version is 2 (Section 5.1.2.1).  If
-			//   present, this field is a sequence of one or more CRL extensions.
-			// RFC 5280 §5.2:
-			//   Conforming CRL issuers are REQUIRED to include the authority key
-			//   identifier (Section 5.2.1) and the CRL number (Section 5.2.3)
-			//   extensions in all CRLs issued.
-			writer.next().write_tagged(Tag::context(0), |writer| {
-				writer.write_sequence(|writer| {
-					// Write authority key identifier.
-					write_x509_authority_key_identifier(
+				// Write issuing distribution point (if present).
+				if let Some(issuing_distribution_point) = &self.issuing_distribution_point {
+					write_x509_extension(
 						writer.next(),
-						self.key_identifier_method
-							.derive(issuer.signing_key.subject_public_key_info()),
+						oid::CRL_ISSUING_DISTRIBUTION_POINT,
+						true,
+						|writer| {
+							issuing_distribution_point.write_der(writer);
+						},
 					);
+				}
 
-					// Write CRL number.
-					write_x509_extension(writer.next(), oid::CRL_NUMBER, false, |writer| {
-						writer.write_bigint_bytes(self.crl_number.as_ref(), true);
+				// Write freshest CRL (if present).
+				let freshest_crl = &extensions.freshest_crl;
+				if !freshest_crl.is_empty() {
+					write_x509_extension(writer.next(), oid::FRESHEST_CRL, false, |writer| {
+						write_distribution_points(writer, freshest_crl);
 					});
-
-					// Write issuing distribution point (if present).
-					if let Some(issuing_distribution_point) = &self.issuing_distribution_point {
-						write_x509_extension(
-							writer.next(),
-							oid::CRL_ISSUING_DISTRIBUTION_POINT,
-							true,
-							|writer| {
-								issuing_distribution_point.write_der(writer);
-							},
-						);
-					}
-				});
+				}
 			});
+		});
 
-			Ok(())
-		})
+		Ok(())
 	}
 }
 
+/// Optional extensions of a CRL, for [`CertificateRevocationListParams::with_extensions()`]
+///
+/// Start from [`CrlExtensions::default()`], which adds none, and set the fields as needed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CrlExtensions {
+	/// Where delta CRLs for this CRL's scope can be retrieved, written as the freshest CRL
+	/// extension described in RFC 5280 Section 5.2.6[^1]
+	///
+	/// Set it on base CRLs and leave it empty on delta CRLs. The extension is always
+	/// non-critical and omitted if the list is empty.
+	///
+	/// [^1]: <https://www.rfc-editor.org/rfc/rfc5280#section-5.2.6>
+	pub freshest_crl: Vec<CrlDistributionPoint>,
+}
+
+/// A CRL with optional extensions, from [`CertificateRevocationListParams::with_extensions()`]
+#[derive(Clone, Copy, Debug)]
+pub struct CrlWithExtensions<'a> {
+	params: &'a CertificateRevocationListParams,
+	extensions: &'a CrlExtensions,
+}
+
+impl CrlWithExtensions<'_> {
+	/// Like [`CertificateRevocationListParams::signed_by()`], with the extensions
+	pub fn signed_by(
+		&self,
+		issuer: &Issuer<'_, impl SigningKey>,
+	) -> Result<CertificateRevocationList, Error> {
+		self.signed_by_validated(issuer, CrlValidation::Standard)
+	}
+
+	/// Like [`CertificateRevocationListParams::signed_by_validated()`], with the extensions
+	pub fn signed_by_validated(
+		&self,
+		issuer: &Issuer<'_, impl SigningKey>,
+		validation: CrlValidation,
+	) -> Result<CertificateRevocationList, Error> {
+		self.params.sign(issuer, validation, self.extensions)
+	}
+
+	/// Like [`CertificateRevocationListParams::serialize_tbs_der()`], with the extensions
+	pub fn serialize_tbs_der(
+		&self,
+		issuer: &Issuer<'_, impl PublicKeyData>,
+	) -> Result<Vec<u8>, Error> {
+		self.serialize_tbs_der_validated(issuer, CrlValidation::Standard)
+	}
+
+	/// Like [`CertificateRevocationListParams::serialize_tbs_der_validated()`], with the
+	/// extensions
+	pub fn serialize_tbs_der_validated(
+		&self,
+		issuer: &Issuer<'_, impl PublicKeyData>,
+		validation: CrlValidation,
+	) -> Result<Vec<u8>, Error> {
+		self.params.tbs_der(issuer, validation, self.extensions)
+	}
+}
+
+/// The checks done on [`CertificateRevocationListParams`] before serialization, see
+/// [`CertificateRevocationListParams::signed_by_validated()`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CrlValidation {
+	/// Rejects a `next_update` that isn't after `this_update` and serial numbers that are
+	/// revoked more than once
+	///
+	/// Revocation times after `this_update` are only reported by
+	/// [`CertificateRevocationListParams::lint()`].
+	#[default]
+	Standard,
+	/// Like [`CrlValidation::Standard`], but also rejects revocation times after `this_update`
+	Strict,
+	/// Skips all checks of the parameters, to produce malformed CRLs as test fixtures
+	///
+	/// The issuer must still be allowed to sign CRLs.
+	Unchecked,
+}
+
+/// Revocations split across several CRLs, each published at its own distribution point
+///
+/// Certificates are assigned to a shard by their serial number, see [`CrlShards::shard_of()`].
+/// To have a certificate's revocation show up in the CRL it points to, issue it with the
+/// distribution point from [`CrlShards::distribution_point_for()`]. The CRLs are produced by
+/// [`CertificateRevocationListParams::signed_shards()`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CrlShards {
+	/// The distribution point of each shard, which the shard's CRL is published at
+	pub distribution_points: Vec<CrlDistributionPoint>,
+	/// The distribution points that certificates were issued with, if known
+	///
+	/// Used to check that the revocation of each of these certificates ends up in the CRL
+	/// the certificate points to.
+	pub issued_with: Vec<(SerialNumber, CrlDistributionPoint)>,
+}
+
+impl CrlShards {
+	/// Creates shards for the given distribution points, without any knowledge of the
+	/// distribution points certificates were issued with
+	pub fn new(distribution_points: Vec<CrlDistributionPoint>) -> Self {
+		Self {
+			distribution_points,
+			issued_with: Vec::new(),
+		}
+	}
+
+	/// Returns the index of the shard that revocations of `serial` belong to
+	///
+	/// This is the value of the serial number modulo the number of shards, which spreads
+	/// random serial numbers evenly. Returns `None` if there are no distribution points.
+	pub fn shard_of(&self, serial: &SerialNumber) -> Option<usize> {
+		let count = self.distribution_points.len() as u64;
+		if count == 0 {
+			return None;
+		}
+		let (_, bytes) = serial_key(serial);
+		let shard = bytes
+			.iter()
+			.fold(0, |rem, &byte| ((rem << 8) | u64::from(byte)) % count);
+		Some(shard as usize)
+	}
+
+	/// Returns the distribution point of the shard that revocations of `serial` belong to
+	///
+	/// Returns `None` if there are no distribution points.
+	pub fn distribution_point_for(&self, serial: &SerialNumber) -> Option<&CrlDistributionPoint> {
+		self.distribution_points.get(self.shard_of(serial)?)
+	}
+}
+
+/// The serial number as it is encoded, without leading zeros, and ordered by its value
+fn serial_key(serial: &SerialNumber) -> (usize, &[u8]) {
+	let bytes = serial.as_ref();
+	let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+	(bytes.len() - start, &bytes[start..])
+}
+
 /// A certificate revocation list (CRL) issuing distribution point, to be included in a CRL's
 /// [issuing distribution point extension](https://datatracker.ietf.org/doc/html/rfc5280#section-5.2.5).
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -403,3 +847,173 @@ impl RevokedCertParams {
 		})
 	}
 }
+
+#[cfg(all(test, feature = "crypto"))]
+mod tests {
+	use time::Duration;
+
+	use super::*;
+	use crate::{test_keys, CertificateParams, KeyPair};
+
+	fn params(revoked: &[(SerialNumber, OffsetDateTime)]) -> CertificateRevocationListParams {
+		let this_update = OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap();
+		CertificateRevocationListParams {
+			this_update,
+			next_update: this_update + Duration::weeks(1),
+			crl_number: SerialNumber::from(1),
+			issuing_distribution_point: None,
+			revoked_certs: revoked
+				.iter()
+				.map(|(serial_number, revocation_time)| RevokedCertParams {
+					serial_number: serial_number.clone(),
+					revocation_time: *revocation_time,
+					reason_code: None,
+					invalidity_date: None,
+				})
+				.collect(),
+			key_identifier_method: KeyIdMethod::Sha256,
+		}
+	}
+
+	fn issuer() -> Issuer<'static, KeyPair> {
+		Issuer::new(CertificateParams::default(), test_keys::ecdsa_p256())
+	}
+
+	#[test]
+	fn update_times_ordered() {
+		let mut params = params(&[]);
+		params.next_update = params.this_update;
+		assert_eq!(
+			params.signed_by(&issuer()).unwrap_err(),
+			Error::InvalidCrlNextUpdate
+		);
+
+		params
+			.signed_by_validated(&issuer(), CrlValidation::Unchecked)
+			.unwrap();
+
+		// Serializing for an external signature is checked the same way
+		assert_eq!(
+			params.serialize_tbs_der(&issuer()).unwrap_err(),
+			Error::InvalidCrlNextUpdate
+		);
+		let issuer = issuer();
+		let tbs_der = params
+			.serialize_tbs_der_validated(&issuer, CrlValidation::Unchecked)
+			.unwrap();
+		let signature = issuer.signing_key.sign(&tbs_der).unwrap();
+		let crl = CertificateRevocationList::assemble(
+			&tbs_der,
+			issuer.signing_key.algorithm(),
+			&signature,
+		)
+		.unwrap();
+		assert!(crl
+			.der()
+			.windows(tbs_der.len())
+			.any(|window| window == tbs_der));
+	}
+
+	#[test]
+	fn duplicate_serials() {
+		let now = OffsetDateTime::from_unix_timestamp(1_600_000_000).unwrap();
+		// Leading zeros aren't encoded, so these are the same serial number
+		let params = params(&[
+			(SerialNumber::from(42), now),
+			(SerialNumber::from(7), now),
+			(SerialNumber::from_slice(&[42]), now),
+		]);
+		let err = params.signed_by(&issuer()).unwrap_err();
+		assert!(
+			matches!(err, Error::DuplicateRevokedSerialNumber(ref s) if s.to_bytes().ends_with(&[42]))
+		);
+		assert!(err
+			.to_string()
+			.ends_with("2a is revoked more than once in the CRL"));
+
+		params
+			.signed_by_validated(&issuer(), CrlValidation::Unchecked)
+			.unwrap();
+	}
+
+	#[test]
+	fn future_revocations() {
+		let this_update = params(&[]).this_update;
+		let future = SerialNumber::from_slice(&[0x10, 0x01]);
+		let params = params(&[
+			(SerialNumber::from_slice(&[1]), this_update),
+			(future.clone(), this_update + Duration::days(1)),
+		]);
+		assert_eq!(
+			params.lint(),
+			vec![Lint::RevocationAfterThisUpdate(future.clone())]
+		);
+		params.signed_by(&issuer()).unwrap();
+
+		assert_eq!(
+			params.validate(CrlValidation::Strict),
+			Err(Error::RevocationAfterThisUpdate(future.clone()))
+		);
+		assert_eq!(
+			params
+				.signed_by_validated(&issuer(), CrlValidation::Strict)
+				.unwrap_err(),
+			Error::RevocationAfterThisUpdate(future)
+		);
+	}
+
+	#[test]
+	fn crl_shards() {
+		let dp = |n| CrlDistributionPoint {
+			uris: vec![format!("http://crl.example.com/{n}.crl")],
+		};
+		let mut shards = CrlShards::new(vec![dp(0), dp(1), dp(2)]);
+		assert_eq!(shards.shard_of(&SerialNumber::from(7)), Some(1));
+		assert_eq!(
+			shards.shard_of(&SerialNumber::from_slice(&[0x01, 0x00])),
+			Some(1)
+		);
+		assert_eq!(
+			shards.distribution_point_for(&SerialNumber::from(9)),
+			Some(&dp(0))
+		);
+
+		let now = OffsetDateTime::from_unix_timestamp(1_600_000_000).unwrap();
+		let params = params(&[(SerialNumber::from(7), now), (SerialNumber::from(8), now)]);
+		let crls = params.signed_shards(&shards, &issuer()).unwrap();
+		assert_eq!(crls.len(), 3);
+
+		shards.issued_with = vec![(SerialNumber::from_slice(&[7]), dp(1))];
+		params.signed_shards(&shards, &issuer()).unwrap();
+		shards.issued_with = vec![(SerialNumber::from_slice(&[7]), dp(2))];
+		assert_eq!(
+			params.signed_shards(&shards, &issuer()).unwrap_err(),
+			Error::CrlShardMismatch(SerialNumber::from(7))
+		);
+
+		let empty = CrlShards::new(Vec::new());
+		assert_eq!(empty.shard_of(&SerialNumber::from(7)), None);
+		assert_eq!(empty.distribution_point_for(&SerialNumber::from(7)), None);
+		assert_eq!(
+			params.signed_shards(&empty, &issuer()).unwrap_err(),
+			Error::MissingCrlShards
+		);
+	}
+
+	#[test]
+	fn sort_revoked_certs() {
+		let now = OffsetDateTime::from_unix_timestamp(1_600_000_000).unwrap();
+		let mut params = params(&[
+			(SerialNumber::from_slice(&[0x01, 0x00]), now),
+			(SerialNumber::from(0xff), now),
+			(SerialNumber::from_slice(&[0x02]), now),
+		]);
+		params.sort_revoked_certs();
+		let serials = params
+			.revoked_certs
+			.iter()
+			.map(|revoked| revoked.serial_number.to_string())
+			.collect::<Vec<_>>();
+		assert_eq!(serials, ["02", "00:00:00:00:00:00:00:ff", "01:00"]);
+	}
+}
@@ -4,22 +4,92 @@ use std::hash::Hash;
 use pem::Pem;
 use pki_types::CertificateSigningRequestDer;
 
+use crate::key_pair::{assemble_signed_der, write_subject_public_key_info};
+#[cfg(any(feature = "crypto", feature = "x509-parser"))]
+use crate::oid;
+#[cfg(any(feature = "crypto", feature = "x509-parser"))]
+use crate::ParseLimit;
 #[cfg(feature = "pem")]
 use crate::ENCODE_CONFIG;
 use crate::{
 	Certificate, CertificateParams, Error, Issuer, PublicKeyData, SignatureAlgorithm, SigningKey,
 };
 #[cfg(feature = "x509-parser")]
-use crate::{DistinguishedName, ExtendedKeyUsagePurpose, IsCa, KeyUsagePurpose, SanType};
+use crate::{
+	DistinguishedName, ExtendedKeyUsagePurpose, IsCa, KeyUsagePurpose, SanType, TlsFeature,
+};
+#[cfg(any(feature = "crypto", feature = "x509-parser"))]
+use crate::{ParseLimits, ParseOptions};
 
 /// A public key, extracted from a CSR
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct PublicKey {
-	raw: Vec<u8>,
-	alg: &'static SignatureAlgorithm,
+	pub(crate) raw: Vec<u8>,
+	pub(crate) alg: &'static SignatureAlgorithm,
+	/// Whether the key is identified by id-RSASSA-PSS without parameters
+	pub(crate) unrestricted_rsa_pss: bool,
 }
 
 impl PublicKey {
+	/// Determines the algorithm of a CSR's key from the OIDs and DER-encoded
+	/// AlgorithmIdentifiers of the key and of the CSR's signature
+	///
+	/// The key takes the algorithm of the signature, except that RSA keys keep their own
+	/// identifier: rsaEncryption keys that signed with RSASSA-PSS stay rsaEncryption keys,
+	/// and id-RSASSA-PSS keys keep their parameters, or the lack thereof. EC keys take the
+	/// algorithm for their curve, as ECDSA signatures don't identify it.
+	#[cfg(any(feature = "crypto", feature = "x509-parser"))]
+	pub(crate) fn from_request(
+		raw: Vec<u8>,
+		key_oid: &[u64],
+		key_alg: &[u8],
+		signature_oid: &[u64],
+		signature_alg: &[u8],
+	) -> Result<Self, Error> {
+		let signature_alg = match signature_oid {
+			oid::RSASSA_PSS => SignatureAlgorithm::from_rsa_pss_alg_ident(signature_alg)?
+				.ok_or(Error::UnsupportedSignatureAlgorithm)?,
+			_ => SignatureAlgorithm::from_oid(signature_oid)?,
+		};
+		let signed_with_pss = signature_alg.key_alg_oid() == oid::RSASSA_PSS;
+
+		let (alg, unrestricted_rsa_pss) = match key_oid {
+			oid::RSASSA_PSS => match SignatureAlgorithm::from_rsa_pss_alg_ident(key_alg)? {
+				Some(alg) if alg == signature_alg => (alg, false),
+				None if signed_with_pss => (signature_alg, true),
+				// The key is restricted to another algorithm than it signed with
+				_ => return Err(Error::InvalidCertificationRequestSignature),
+			},
+			oid::RSA_ENCRYPTION if signed_with_pss => {
+				let alg = SignatureAlgorithm::iter()
+					.find(|alg| {
+						alg.key_alg_oid() == oid::RSA_ENCRYPTION
+							&& alg.digest() == signature_alg.digest()
+					})
+					.ok_or(Error::UnsupportedSignatureAlgorithm)?;
+				(*alg, false)
+			},
+			oid::EC_PUBLIC_KEY => {
+				let alg = SignatureAlgorithm::iter()
+					.find(|alg| {
+						alg.oid_components == signature_alg.oid_components
+							&& yasna::construct_der(|writer| alg.write_oids_sign_alg(writer))
+								== key_alg
+					})
+					.copied()
+					.unwrap_or(signature_alg);
+				(alg, false)
+			},
+			_ => (signature_alg, false),
+		};
+
+		Ok(Self {
+			raw,
+			alg,
+			unrestricted_rsa_pss,
+		})
+	}
+
 	/// The algorithm used to generate the public key and sign the CSR.
 	pub fn algorithm(&self) -> &SignatureAlgorithm {
 		self.alg
@@ -27,6 +97,12 @@ impl PublicKey {
 }
 
 impl PublicKeyData for PublicKey {
+	fn subject_public_key_info(&self) -> Vec<u8> {
+		yasna::construct_der(|writer| {
+			write_subject_public_key_info(self, self.unrestricted_rsa_pss, writer)
+		})
+	}
+
 	fn der_bytes(&self) -> &[u8] {
 		&self.raw
 	}
@@ -62,6 +138,27 @@ impl CertificateSigningRequest {
 	pub fn der(&self) -> &CertificateSigningRequestDer<'static> {
 		&self.der
 	}
+
+	/// Assembles a CSR from a CertificationRequestInfo and a signature produced outside of rcgen
+	///
+	/// `tbs_der` is the output of [`CertificateParams::serialize_request_tbs_der()`] and
+	/// `signature` the signature over it, made with `signature_algorithm`. Unlike for
+	/// certificates, the CertificationRequestInfo doesn't name the signature algorithm, so only
+	/// its encoding is checked. The signature itself isn't verified.
+	pub fn assemble(
+		tbs_der: &[u8],
+		signature_algorithm: &'static SignatureAlgorithm,
+		signature: &[u8],
+	) -> Result<Self, Error> {
+		crate::asn1::parse_der(tbs_der, |reader| reader.read_der())
+			.map_err(|_| Error::CouldNotParseTbs)?;
+		if tbs_der.first() != Some(&0x30) {
+			return Err(Error::CouldNotParseTbs);
+		}
+		Ok(Self {
+			der: assemble_signed_der(tbs_der, signature_algorithm, signature).into(),
+		})
+	}
 }
 
 impl From<CertificateSigningRequest> for CertificateSigningRequestDer<'static> {
@@ -83,10 +180,33 @@ impl CertificateSigningRequestParams {
 	/// Parse and verify a certificate signing request from the ASCII PEM format
 	///
 	/// See [`from_der`](Self::from_der) for more details.
-	#[cfg(all(feature = "pem", feature = "x509-parser"))]
+	#[cfg(all(feature = "pem", any(feature = "crypto", feature = "x509-parser")))]
 	pub fn from_pem(pem_str: &str) -> Result<Self, Error> {
+		Self::from_pem_with_limits(pem_str, &ParseLimits::UNLIMITED)
+	}
+
+	/// Parse and verify a certificate signing request from the ASCII PEM format,
+	/// rejecting input that exceeds `limits`
+	///
+	/// See [`from_der_with_limits`](Self::from_der_with_limits) for more details.
+	#[cfg(all(feature = "pem", any(feature = "crypto", feature = "x509-parser")))]
+	pub fn from_pem_with_limits(pem_str: &str, limits: &ParseLimits) -> Result<Self, Error> {
+		Self::from_pem_with_options(pem_str, limits, &ParseOptions::default())
+	}
+
+	/// Parse and verify a certificate signing request from the ASCII PEM format,
+	/// rejecting input that exceeds `limits` or is refused by `options`
+	///
+	/// See [`from_der_with_options`](Self::from_der_with_options) for more details.
+	#[cfg(all(feature = "pem", any(feature = "crypto", feature = "x509-parser")))]
+	pub fn from_pem_with_options(
+		pem_str: &str,
+		limits: &ParseLimits,
+		options: &ParseOptions,
+	) -> Result<Self, Error> {
+		limits.check(ParseLimit::InputLength, pem_str.len())?;
 		let csr = pem::parse(pem_str).map_err(|_| Error::CouldNotParseCertificationRequest)?;
-		Self::from_der(&csr.contents().into())
+		Self::from_der_with_options(&csr.contents().into(), limits, options)
 	}
 
 	/// Parse and verify a certificate signing request from DER-encoded bytes
@@ -96,6 +216,12 @@ impl CertificateSigningRequestParams {
 	/// - `Key Usage` (see [`KeyUsagePurpose`])
 	/// - `Extended Key Usage` (see [`ExtendedKeyUsagePurpose`])
 	/// - `Basic Constraints` (see [`crate::BasicConstraints`])
+	/// - `TLS Feature` (see [`crate::TlsFeature`])
+	///
+	/// Subject alternative names of a form without a dedicated [`SanType`] variant, like
+	/// x400Address, are kept as [`SanType::Raw`] rather than rejected. Callers that only
+	/// accept the typed forms can set [`ParseOptions::reject_raw_names`] and use
+	/// [`from_der_with_options`](Self::from_der_with_options).
 	///
 	/// On encountering other extensions, this function will return [`Error::UnsupportedExtension`].
 	/// If the request's signature is invalid, it will return
@@ -105,34 +231,129 @@ impl CertificateSigningRequestParams {
 	/// PEM input. If you already have a byte slice containing DER, it can trivially be converted
 	/// into [`CertificateSigningRequestDer`] using the [`Into`] trait.
 	///
+	/// Without the `x509-parser` feature, a built-in parser is used, which requires the
+	/// `crypto` feature to verify the signature.
+	///
 	/// [`PemObject`]: pki_types::pem::PemObject
-	#[cfg(feature = "x509-parser")]
+	#[cfg(any(feature = "crypto", feature = "x509-parser"))]
 	pub fn from_der(csr: &CertificateSigningRequestDer<'_>) -> Result<Self, Error> {
+		Self::from_der_with_limits(csr, &ParseLimits::UNLIMITED)
+	}
+
+	/// Parse and verify a certificate signing request from DER-encoded bytes,
+	/// rejecting input that exceeds `limits`
+	///
+	/// The limits are checked before the signature is verified or any of the contents are
+	/// copied, and [`Error::ParseLimitExceeded`] is returned for the first one exceeded.
+	/// Otherwise, this behaves like [`from_der`](Self::from_der).
+	#[cfg(any(feature = "crypto", feature = "x509-parser"))]
+	pub fn from_der_with_limits(
+		csr: &CertificateSigningRequestDer<'_>,
+		limits: &ParseLimits,
+	) -> Result<Self, Error> {
+		Self::from_der_with_options(csr, limits, &ParseOptions::default())
+	}
+
+	/// Parse and verify a certificate signing request from DER-encoded bytes,
+	/// rejecting input that exceeds `limits` or is refused by `options`
+	///
+	/// With [`ParseOptions::reject_raw_names`], a subject alternative name that would be kept
+	/// as [`SanType::Raw`] results in [`Error::InvalidNameType`]. Otherwise, this behaves like
+	/// [`from_der_with_limits`](Self::from_der_with_limits).
+	#[cfg(any(feature = "crypto", feature = "x509-parser"))]
+	pub fn from_der_with_options(
+		csr: &CertificateSigningRequestDer<'_>,
+		limits: &ParseLimits,
+		options: &ParseOptions,
+	) -> Result<Self, Error> {
+		#[cfg(feature = "x509-parser")]
+		let parsed = Self::from_x509_parser(csr, limits)?;
+		#[cfg(not(feature = "x509-parser"))]
+		let parsed = crate::csr_parser::parse(csr, limits)?;
+
+		options.check_raw_names(&parsed.params.subject_alt_names)?;
+		Ok(parsed)
+	}
+
+	#[cfg(feature = "x509-parser")]
+	fn from_x509_parser(
+		csr: &CertificateSigningRequestDer<'_>,
+		limits: &ParseLimits,
+	) -> Result<Self, Error> {
+		use x509_parser::cri_attributes::ParsedCriAttribute;
 		use x509_parser::prelude::FromDer;
 
-		let csr = x509_parser::certification_request::X509CertificationRequest::from_der(csr)
-			.map_err(|_| Error::CouldNotParseCertificationRequest)?
-			.1;
-		csr.verify_signature()
-			.map_err(|_| Error::InvalidCertificationRequestSignature)?;
+		check_limits(csr, limits)?;
+		let signature_alg = crate::asn1::parse_der(csr, |reader| {
+			reader.read_sequence(|reader| {
+				reader.next().read_der()?;
+				let alg = reader.next().read_der()?;
+				reader.next().read_der()?;
+				Ok(alg)
+			})
+		})
+		.map_err(|_| Error::CouldNotParseCertificationRequest)?;
+		let (remainder, csr) =
+			x509_parser::certification_request::X509CertificationRequest::from_der(csr)
+				.map_err(|_| Error::CouldNotParseCertificationRequest)?;
+		if !remainder.is_empty() {
+			return Err(Error::CouldNotParseCertificationRequest);
+		}
+
+		let info = &csr.certification_request_info;
+		limits.check_name(&info.subject)?;
+		let extensions = info
+			.iter_attributes()
+			.find_map(|attr| match attr.parsed_attribute() {
+				ParsedCriAttribute::ExtensionRequest(requested) => Some(&requested.extensions),
+				_ => None,
+			});
+		// Keys with explicit EC domain parameters are verified, and kept, in their named form
+		let normalized_spki = crate::ec_params::normalize_spki(info.subject_pki.raw)?;
+		let subject_pki = match &normalized_spki {
+			Some(spki) => {
+				x509_parser::x509::SubjectPublicKeyInfo::from_der(spki)
+					.map_err(|_| Error::CouldNotParseCertificationRequest)?
+					.1
+			},
+			None => info.subject_pki.clone(),
+		};
+		verify_signature(&subject_pki, &csr, info.raw)?;
 		let alg_oid = csr
 			.signature_algorithm
 			.algorithm
 			.iter()
 			.ok_or(Error::CouldNotParseCertificationRequest)?
 			.collect::<Vec<_>>();
-		let alg = SignatureAlgorithm::from_oid(&alg_oid)?;
+		let key_oid = subject_pki
+			.algorithm
+			.algorithm
+			.iter()
+			.ok_or(Error::CouldNotParseCertificationRequest)?
+			.collect::<Vec<_>>();
+		let key_alg = crate::asn1::parse_der(subject_pki.raw, |reader| {
+			reader.read_sequence(|reader| {
+				let alg = reader.next().read_der()?;
+				reader.next().read_der()?;
+				Ok(alg)
+			})
+		})
+		.map_err(|_| Error::CouldNotParseCertificationRequest)?;
+		let public_key = PublicKey::from_request(
+			subject_pki.subject_public_key.data.to_vec(),
+			&key_oid,
+			&key_alg,
+			&alg_oid,
+			&signature_alg,
+		)?;
 
-		let info = &csr.certification_request_info;
 		let mut params = CertificateParams {
 			distinguished_name: DistinguishedName::from_name(&info.subject)?,
 			..CertificateParams::default()
 		};
-		let raw = info.subject_pki.subject_public_key.data.to_vec();
-
-		if let Some(extensions) = csr.requested_extensions() {
+		if let Some(extensions) = extensions {
 			for ext in extensions {
-				match ext {
+				match ext.parsed_extension() {
 					x509_parser::extensions::ParsedExtension::KeyUsage(key_usage) => {
 						// This x509 parser stores flags in reversed bit BIT STRING order
 						params.key_usages =
@@ -145,37 +366,25 @@ impl CertificateSigningRequestParams {
 								.push(SanType::try_from_general(name)?);
 						}
 					},
-					x509_parser::extensions::ParsedExtension::ExtendedKeyUsage(eku) => {
-						if eku.any {
-							params.insert_extended_key_usage(ExtendedKeyUsagePurpose::Any);
-						}
-						if eku.server_auth {
-							params.insert_extended_key_usage(ExtendedKeyUsagePurpose::ServerAuth);
-						}
-						if eku.client_auth {
-							params.insert_extended_key_usage(ExtendedKeyUsagePurpose::ClientAuth);
-						}
-						if eku.code_signing {
-							params.insert_extended_key_usage(ExtendedKeyUsagePurpose::CodeSigning);
-						}
-						if eku.email_protection {
-							params.insert_extended_key_usage(
-								ExtendedKeyUsagePurpose::EmailProtection,
-							);
-						}
-						if eku.time_stamping {
-							params.insert_extended_key_usage(ExtendedKeyUsagePurpose::TimeStamping);
-						}
-						if eku.ocsp_signing {
-							params.insert_extended_key_usage(ExtendedKeyUsagePurpose::OcspSigning);
-						}
-						if !eku.other.is_empty() {
-							return Err(Error::UnsupportedExtension);
+					x509_parser::extensions::ParsedExtension::ExtendedKeyUsage(_) => {
+						// Read the raw value, as the parsed one loses the order of the purposes
+						let purposes = ExtendedKeyUsagePurpose::from_extension_value(ext.value)
+							.map_err(|_| Error::CouldNotParseCertificationRequest)?;
+						for purpose in purposes {
+							params.insert_extended_key_usage(purpose);
 						}
 					},
 					x509_parser::extensions::ParsedExtension::BasicConstraints(bc) => {
 						params.is_ca = IsCa::from_basic_constraints(bc)?;
 					},
+					_ if ext
+						.oid
+						.iter()
+						.is_some_and(|oid| oid.eq(oid::TLS_FEATURE.iter().copied())) =>
+					{
+						params.tls_features = TlsFeature::from_extension_value(ext.value)
+							.map_err(|_| Error::CouldNotParseCertificationRequest)?;
+					},
 					_ => return Err(Error::UnsupportedExtension),
 				}
 			}
@@ -185,10 +394,7 @@ impl CertificateSigningRequestParams {
 		// * name_constraints
 		// and any other extensions.
 
-		Ok(Self {
-			params,
-			public_key: PublicKey { alg, raw },
-		})
+		Ok(Self { params, public_key })
 	}
 
 	/// Generate a new certificate based on the requested parameters, signed by the provided
@@ -200,16 +406,142 @@ impl CertificateSigningRequestParams {
 	///
 	/// Note that no validation of the `issuer` certificate is performed. Rcgen will not require
 	/// the certificate to be a CA certificate, or have key usage extensions that allow signing.
+	/// The [`IssuanceDefaults`](crate::IssuanceDefaults) of the `issuer` are applied as for
+	/// [`CertificateParams::signed_by()`].
 	///
 	/// The returned [`Certificate`] may be serialized using [`Certificate::der`] and
 	/// [`Certificate::pem`].
 	pub fn signed_by(&self, issuer: &Issuer<impl SigningKey>) -> Result<Certificate, Error> {
-		Ok(Certificate {
-			der: self
-				.params
-				.serialize_der_with_signer(&self.public_key, issuer)?,
+		self.params.signed_by(&self.public_key, issuer)
+	}
+}
+
+/// Checks the limits on the input length and on the number of attributes, extensions and
+/// subject alternative names of a DER-encoded certificate signing request
+///
+/// This only walks the structure of the request, so that input exceeding a limit is
+/// rejected before the request is parsed into owned values. Everything else is left to the
+/// parser, which also rejects the BER this accepts.
+#[cfg(any(feature = "crypto", feature = "x509-parser"))]
+pub(crate) fn check_limits(csr: &[u8], limits: &ParseLimits) -> Result<(), Error> {
+	use std::cell::Cell;
+
+	use yasna::{ASN1Error, ASN1ErrorKind, Tag};
+
+	limits.check(ParseLimit::InputLength, csr.len())?;
+	let exceeded = Cell::new(None);
+	let check = |limit, value| {
+		limits.check(limit, value).map_err(|_| {
+			exceeded.set(Some(limit));
+			ASN1Error::new(ASN1ErrorKind::Invalid)
 		})
+	};
+
+	let result = crate::asn1::parse_ber(csr, |reader| {
+		reader.read_sequence(|reader| {
+			reader.next().read_sequence(|reader| {
+				reader.next().read_der()?; // version
+				reader.next().read_der()?; // subject
+				reader.next().read_der()?; // subjectPKInfo
+				let mut attributes = 0;
+				reader
+					.next()
+					.read_tagged_implicit(Tag::context(0), |reader| {
+						reader.read_set_of(|reader| {
+							reader.read_sequence(|reader| {
+								let attribute_oid = reader.next().read_oid()?;
+								if attribute_oid.components() != oid::PKCS_9_AT_EXTENSION_REQUEST {
+									return reader.next().read_der().map(drop);
+								}
+								reader.next().read_set_of(|reader| {
+									let mut extensions = 0;
+									reader.read_sequence_of(|reader| {
+										reader.read_sequence(|reader| {
+											let ext_oid = reader.next().read_oid()?;
+											reader.read_optional(|reader| reader.read_bool())?;
+											let value = reader.next().read_bytes()?;
+											if ext_oid.components() != oid::SUBJECT_ALT_NAME {
+												return Ok(());
+											}
+											let mut names = 0;
+											crate::asn1::parse_ber(&value, |reader| {
+												reader.read_sequence_of(|reader| {
+													reader.read_der()?;
+													names += 1;
+													check(ParseLimit::SubjectAltNames, names)
+												})
+											})
+										})?;
+										extensions += 1;
+										check(ParseLimit::Extensions, extensions)
+									})
+								})
+							})?;
+							attributes += 1;
+							check(ParseLimit::Attributes, attributes)
+						})
+					})
+			})?;
+			reader.next().read_der()?; // signatureAlgorithm
+			reader.next().read_der()?; // signature
+			Ok(())
+		})
+	});
+
+	match (exceeded.get(), result) {
+		(Some(limit), _) => Err(Error::ParseLimitExceeded(limit)),
+		(None, Ok(())) => Ok(()),
+		(None, Err(_)) => Err(Error::CouldNotParseCertificationRequest),
+	}
+}
+
+/// Verifies the signature of a CSR parsed by x509-parser
+///
+/// x509-parser verifies signatures with `ring`, which doesn't support secp256k1, so those
+/// are verified with `aws-lc-rs` instead.
+#[cfg(feature = "x509-parser")]
+fn verify_signature(
+	spki: &x509_parser::x509::SubjectPublicKeyInfo<'_>,
+	csr: &x509_parser::certification_request::X509CertificationRequest<'_>,
+	signed: &[u8],
+) -> Result<(), Error> {
+	#[cfg(feature = "aws_lc_rs")]
+	{
+		use crate::ring_like::signature::{UnparsedPublicKey, ECDSA_P256K1_SHA256_ASN1};
+
+		let is_k256 = spki
+			.algorithm
+			.parameters
+			.as_ref()
+			.and_then(|params| params.as_oid().ok())
+			.is_some_and(|curve| {
+				curve
+					.iter()
+					.is_some_and(|curve| curve.eq(oid::EC_SECP_256_K1.iter().copied()))
+			});
+		if is_k256 {
+			let signed_with_sha256 = csr.signature_algorithm.algorithm.iter().is_some_and(|alg| {
+				alg.eq(crate::PKCS_ECDSA_K256_SHA256.oid_components.iter().copied())
+			});
+			if !signed_with_sha256 {
+				return Err(Error::InvalidCertificationRequestSignature);
+			}
+			return UnparsedPublicKey::new(
+				&ECDSA_P256K1_SHA256_ASN1,
+				&spki.subject_public_key.data,
+			)
+			.verify(signed, &csr.signature_value.data)
+			.map_err(|_| Error::InvalidCertificationRequestSignature);
+		}
 	}
+
+	x509_parser::verify::verify_signature(
+		spki,
+		&csr.signature_algorithm,
+		&csr.signature_value,
+		signed,
+	)
+	.map_err(|_| Error::InvalidCertificationRequestSignature)
 }
 
 #[cfg(all(test, feature = "x509-parser"))]
@@ -218,8 +550,9 @@ mod tests {
 	use x509_parser::prelude::{FromDer, ParsedExtension};
 
 	use crate::{
-		BasicConstraints, CertificateParams, CertificateSigningRequestParams,
-		ExtendedKeyUsagePurpose, IsCa, KeyPair, KeyUsagePurpose,
+		BasicConstraints, CertificateParams, CertificateSigningRequestParams, DnType, Error,
+		ExtendedKeyUsagePurpose, IsCa, Issuer, KeyPair, KeyUsagePurpose, ParseLimit, ParseLimits,
+		ParseOptions, SanType, TlsFeature,
 	};
 
 	#[test]
@@ -291,4 +624,241 @@ mod tests {
 
 		assert_eq!(csr_de.params.is_ca, params.is_ca);
 	}
+
+	#[test]
+	fn serialize_and_deserialize_eq_extended_key_usages() {
+		let params = CertificateParams {
+			extended_key_usages: vec![
+				ExtendedKeyUsagePurpose::Other(vec![1, 3, 6, 1, 4, 1, 55555, 1]),
+				ExtendedKeyUsagePurpose::ServerAuth,
+			],
+			..Default::default()
+		};
+		let key_pair = KeyPair::generate().unwrap();
+		let csr = params.serialize_request(&key_pair).unwrap();
+		let csr_de = CertificateSigningRequestParams::from_der(csr.der()).unwrap();
+
+		assert_eq!(
+			csr_de.params.extended_key_usages,
+			params.extended_key_usages
+		);
+	}
+
+	#[test]
+	fn serialize_and_deserialize_eq_tls_features() {
+		let params = CertificateParams {
+			tls_features: vec![TlsFeature::StatusRequest],
+			..Default::default()
+		};
+		let key_pair = KeyPair::generate().unwrap();
+		let csr = params.serialize_request(&key_pair).unwrap();
+		let csr_de = CertificateSigningRequestParams::from_der(csr.der()).unwrap();
+		assert_eq!(csr_de.params.tls_features, params.tls_features);
+
+		// The issuer honors the request when signing it
+		let issuer = Issuer::new(CertificateParams::default(), KeyPair::generate().unwrap());
+		let cert = csr_de.signed_by(&issuer).unwrap();
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert_eq!(params_from_cert.tls_features, params.tls_features);
+	}
+
+	#[test]
+	fn issuance_defaults() {
+		use crate::{
+			CrlDistributionPoint, CustomExtension, GeneralSubtree, IssuanceDefaults,
+			IssuanceValidation, MaxValidity, NameConstraints,
+		};
+
+		let key_pair = KeyPair::generate().unwrap();
+		let params = CertificateParams::new(vec!["host.corp.example".to_owned()]).unwrap();
+		let csr = params.serialize_request(&key_pair).unwrap();
+		let csr = CertificateSigningRequestParams::from_der(csr.der()).unwrap();
+
+		let ca_params = CertificateParams {
+			name_constraints: Some(NameConstraints {
+				permitted_subtrees: vec![GeneralSubtree::DnsName(".corp.example".to_string())],
+				excluded_subtrees: Vec::new(),
+			}),
+			..CertificateParams::default()
+		};
+		let extension =
+			CustomExtension::from_oid_content(&[1, 3, 6, 1, 4, 1, 32473, 1], vec![0x05, 0x00]);
+		let issuer =
+			Issuer::new(ca_params, KeyPair::generate().unwrap()).with_defaults(IssuanceDefaults {
+				crl_distribution_points: vec![CrlDistributionPoint {
+					uris: vec!["http://crl.example.com/ca.crl".to_string()],
+				}],
+				custom_extensions: vec![extension.clone()],
+				max_validity: Some(MaxValidity::Clamp(time::Duration::days(90))),
+				validation: IssuanceValidation::Strict,
+				..IssuanceDefaults::default()
+			});
+
+		let cert = csr.signed_by(&issuer).unwrap();
+		let params_from_cert = CertificateParams::from_ca_cert_der(cert.der()).unwrap();
+		assert_eq!(
+			params_from_cert.not_after - params_from_cert.not_before,
+			time::Duration::days(90)
+		);
+		let names = cert
+			.extensions()
+			.unwrap()
+			.into_iter()
+			.filter_map(|ext| ext.name())
+			.collect::<Vec<_>>();
+		assert!(names.contains(&"cRLDistributionPoints"));
+		assert!(cert
+			.extensions()
+			.unwrap()
+			.into_iter()
+			.any(|ext| ext.oid_components().eq(extension.oid_components())));
+
+		// Names outside of the constraints of the issuer are rejected
+		let params = CertificateParams::new(vec!["evil.com".to_owned()]).unwrap();
+		let csr = params.serialize_request(&key_pair).unwrap();
+		let csr = CertificateSigningRequestParams::from_der(csr.der()).unwrap();
+		assert!(matches!(
+			csr.signed_by(&issuer),
+			Err(Error::NameConstraintViolation(_))
+		));
+	}
+
+	#[test]
+	fn parse_limits() {
+		let key_pair = KeyPair::generate().unwrap();
+		let sans: Vec<_> = (0..100_000).map(|i| format!("{i}.example.com")).collect();
+		let params = CertificateParams::new(sans).unwrap();
+		let mut der = params.serialize_request(&key_pair).unwrap().der().to_vec();
+		// Corrupt the signature, to show that the limits are checked before it is verified
+		*der.last_mut().unwrap() ^= 1;
+		let der = der.into();
+
+		let mut limits = ParseLimits::default();
+		assert_eq!(
+			CertificateSigningRequestParams::from_der_with_limits(&der, &limits),
+			Err(Error::ParseLimitExceeded(ParseLimit::InputLength))
+		);
+		limits.max_input_len = usize::MAX;
+		assert_eq!(
+			CertificateSigningRequestParams::from_der_with_limits(&der, &limits),
+			Err(Error::ParseLimitExceeded(ParseLimit::SubjectAltNames))
+		);
+		limits.max_subject_alt_names = usize::MAX;
+		assert_eq!(
+			CertificateSigningRequestParams::from_der_with_limits(&der, &limits),
+			Err(Error::InvalidCertificationRequestSignature)
+		);
+
+		let mut params = CertificateParams::new(vec!["crabs.crabs".to_owned()]).unwrap();
+		params
+			.distinguished_name
+			.push(DnType::OrganizationName, "Crab widgits SE".repeat(100));
+		params.key_usages.push(KeyUsagePurpose::DigitalSignature);
+		let csr = params.serialize_request(&key_pair).unwrap();
+		let mut limits = ParseLimits::default();
+		assert_eq!(
+			CertificateSigningRequestParams::from_der_with_limits(csr.der(), &limits),
+			Err(Error::ParseLimitExceeded(ParseLimit::DnAttributeLength))
+		);
+		limits.max_dn_attribute_len = 2048;
+		limits.max_extensions = 1;
+		assert_eq!(
+			CertificateSigningRequestParams::from_der_with_limits(csr.der(), &limits),
+			Err(Error::ParseLimitExceeded(ParseLimit::Extensions))
+		);
+		limits.max_extensions = 2;
+		let parsed = CertificateSigningRequestParams::from_der_with_limits(csr.der(), &limits);
+		assert_eq!(
+			parsed.unwrap().params.distinguished_name,
+			params.distinguished_name
+		);
+	}
+
+	#[test]
+	fn reject_raw_names() {
+		let key_pair = KeyPair::generate().unwrap();
+		let mut params = CertificateParams::new(vec!["crabs.crabs".to_owned()]).unwrap();
+		params.subject_alt_names.push(SanType::Raw {
+			tag: 3,
+			der_value: vec![0x30, 0x06, 0x61, 0x04, 0x13, 0x02, b'U', b'S'],
+		});
+		let csr = params.serialize_request(&key_pair).unwrap();
+
+		let limits = ParseLimits::default();
+		let mut options = ParseOptions::default();
+		let parsed =
+			CertificateSigningRequestParams::from_der_with_options(csr.der(), &limits, &options);
+		assert_eq!(
+			parsed.unwrap().params.subject_alt_names,
+			params.subject_alt_names
+		);
+		options.reject_raw_names = true;
+		assert_eq!(
+			CertificateSigningRequestParams::from_der_with_options(csr.der(), &limits, &options),
+			Err(Error::InvalidNameType)
+		);
+	}
+
+	#[test]
+	fn rsa_pss_keys() {
+		use crate::{Issuer, PublicKeyData, PKCS_RSA_PSS_SHA256, PKCS_RSA_SHA384};
+
+		let issuer = Issuer::new(CertificateParams::default(), KeyPair::generate().unwrap());
+
+		// Generated with `openssl req` for `openssl genpkey -algorithm RSA-PSS` keys, with
+		// and without restricting the key with `-pkeyopt rsa_pss_keygen_md:sha256`
+		for (csr, spki) in [
+			(
+				&include_bytes!("test_data/rsa_pss.csr.der")[..],
+				&include_bytes!("test_data/rsa_pss.spki.der")[..],
+			),
+			(
+				include_bytes!("test_data/rsa_pss_sha256.csr.der"),
+				include_bytes!("test_data/rsa_pss_sha256.spki.der"),
+			),
+		] {
+			let csr = CertificateSigningRequestParams::from_der(&csr.into()).unwrap();
+			assert_eq!(csr.public_key.algorithm(), &PKCS_RSA_PSS_SHA256);
+			assert_eq!(csr.public_key.subject_public_key_info(), spki);
+
+			let cert = csr.signed_by(&issuer).unwrap();
+			let (_, cert) = x509_parser::parse_x509_certificate(cert.der()).unwrap();
+			assert_eq!(cert.tbs_certificate.subject_pki.raw, spki);
+		}
+
+		// rsaEncryption keys stay rsaEncryption keys when signing with RSASSA-PSS
+		let der = include_bytes!("test_data/rsa_signed_with_pss.csr.der");
+		let (_, parsed) = X509CertificationRequest::from_der(der).unwrap();
+		let csr = CertificateSigningRequestParams::from_der(&der[..].into()).unwrap();
+		assert_eq!(csr.public_key.algorithm(), &PKCS_RSA_SHA384);
+		assert_eq!(
+			csr.public_key.subject_public_key_info(),
+			parsed.certification_request_info.subject_pki.raw
+		);
+	}
+
+	#[test]
+	fn explicit_ec_parameters() {
+		use crate::{Issuer, PublicKeyData, PKCS_ECDSA_P256_SHA256};
+
+		// Generated with `openssl req` for an `openssl ecparam -genkey -param_enc explicit` key
+		let der = include_bytes!("test_data/p256_explicit.csr.der");
+		let csr = CertificateSigningRequestParams::from_der(&der[..].into()).unwrap();
+		assert_eq!(csr.public_key.algorithm(), &PKCS_ECDSA_P256_SHA256);
+
+		let issuer = Issuer::new(CertificateParams::default(), KeyPair::generate().unwrap());
+		let cert = csr.signed_by(&issuer).unwrap();
+		let (_, cert) = x509_parser::parse_x509_certificate(cert.der()).unwrap();
+		let spki = &cert.tbs_certificate.subject_pki;
+		assert_eq!(spki.raw, csr.public_key.subject_public_key_info());
+		assert_eq!(
+			spki.algorithm
+				.parameters
+				.as_ref()
+				.unwrap()
+				.as_oid()
+				.unwrap(),
+			x509_parser::oid_registry::OID_EC_P256
+		);
+	}
 }
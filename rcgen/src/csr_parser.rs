@@ -0,0 +1,610 @@
+//! A certificate signing request parser that doesn't depend on x509-parser
+//!
+//! It supports the same fields and extensions as the x509-parser based implementation of
+//! [`CertificateSigningRequestParams::from_der()`], and is used in its place when the
+//! `x509-parser` feature is disabled.
+
+use yasna::models::{ObjectIdentifier, TaggedDerValue};
+use yasna::{ASN1Error, ASN1ErrorKind, ASN1Result, BERReader, Tag};
+
+use crate::csr::PublicKey;
+use crate::ring_like::signature::{self, UnparsedPublicKey, VerificationAlgorithm};
+use crate::string::{BmpString, DnsName, UniversalString};
+use crate::{
+	ip_addr_from_octets, oid, BasicConstraints, CertificateParams, CertificateSigningRequestParams,
+	DigestAlgorithm, DistinguishedName, DnType, DnValue, Error, ExtendedKeyUsagePurpose, IsCa,
+	KeyUsagePurpose, OtherNameValue, ParseLimit, ParseLimits, SanType, SignatureAlgorithm,
+	TlsFeature,
+};
+
+/// The attributes of a distinguished name, by relative distinguished name
+type Rdns = Vec<Vec<(ObjectIdentifier, TaggedDerValue)>>;
+
+/// Parses and verifies a DER-encoded certificate signing request
+pub(crate) fn parse(
+	csr: &[u8],
+	limits: &ParseLimits,
+) -> Result<CertificateSigningRequestParams, Error> {
+	crate::csr::check_limits(csr, limits)?;
+	let request = crate::asn1::parse_der(csr, Request::read)
+		.map_err(|_| Error::CouldNotParseCertificationRequest)?;
+
+	for rdn in &request.rdns {
+		for (_, value) in rdn {
+			limits.check(ParseLimit::DnAttributeLength, value.value().len())?;
+		}
+	}
+
+	// Keys with explicit EC domain parameters are verified, and kept, in their named form
+	let normalized_spki = crate::ec_params::normalize_spki(request.spki)?;
+	let spki = normalized_spki.as_deref().unwrap_or(request.spki);
+	let ((key_oid, key_params), key_algorithm_der, key) = crate::asn1::parse_der(spki, |reader| {
+		reader.read_sequence(|reader| {
+			let (algorithm, der) = reader.next().read_with_buffer(read_algorithm_identifier)?;
+			Ok((algorithm, der, read_bytes_of_bits(reader.next())?))
+		})
+	})
+	.map_err(|_| Error::CouldNotParseCertificationRequest)?;
+
+	let mut sans = Vec::new();
+	for ext in &request.extensions {
+		if ext.oid.components() == oid::SUBJECT_ALT_NAME {
+			sans = crate::asn1::parse_der(&ext.value, |reader| {
+				reader.collect_sequence_of(|reader| reader.read_der())
+			})
+			.map_err(|_| Error::CouldNotParseCertificationRequest)?;
+		}
+	}
+
+	let (signature_oid, _) = &request.signature_algorithm;
+	let verification_algorithm = verification_algorithm(
+		key_oid.components(),
+		key_params
+			.as_ref()
+			.map(|params| params.components().as_slice()),
+		signature_oid.components(),
+		request.signature_algorithm_der,
+	)
+	.ok_or(Error::InvalidCertificationRequestSignature)?;
+	UnparsedPublicKey::new(verification_algorithm, &key)
+		.verify(request.info, &request.signature)
+		.map_err(|_| Error::InvalidCertificationRequestSignature)?;
+	let public_key = PublicKey::from_request(
+		key,
+		key_oid.components(),
+		key_algorithm_der,
+		signature_oid.components(),
+		request.signature_algorithm_der,
+	)?;
+
+	let mut params = CertificateParams {
+		distinguished_name: distinguished_name(&request.rdns)?,
+		..CertificateParams::default()
+	};
+	for ext in &request.extensions {
+		match ext.oid.components().as_slice() {
+			oid::KEY_USAGE => {
+				let flags = crate::asn1::parse_der(&ext.value, read_bits)
+					.map_err(|_| Error::CouldNotParseCertificationRequest)?;
+				let flags = match flags.as_slice() {
+					[] => 0,
+					[first] => u16::from(*first) << 8,
+					[first, second] => u16::from_be_bytes([*first, *second]),
+					_ => return Err(Error::CouldNotParseCertificationRequest),
+				};
+				params.key_usages = KeyUsagePurpose::from_u16(flags);
+			},
+			oid::SUBJECT_ALT_NAME => {
+				for name in &sans {
+					params.subject_alt_names.push(san(name)?);
+				}
+			},
+			oid::EXT_KEY_USAGE => {
+				let purposes = ExtendedKeyUsagePurpose::from_extension_value(&ext.value)
+					.map_err(|_| Error::CouldNotParseCertificationRequest)?;
+				for purpose in purposes {
+					params.insert_extended_key_usage(purpose);
+				}
+			},
+			oid::BASIC_CONSTRAINTS => params.is_ca = basic_constraints(&ext.value)?,
+			oid::TLS_FEATURE => {
+				params.tls_features = TlsFeature::from_extension_value(&ext.value)
+					.map_err(|_| Error::CouldNotParseCertificationRequest)?;
+			},
+			_ => return Err(Error::UnsupportedExtension),
+		}
+	}
+
+	Ok(CertificateSigningRequestParams { params, public_key })
+}
+
+/// The parts of a certificate signing request needed to verify and convert it
+struct Request<'a> {
+	/// The DER-encoded CertificationRequestInfo, which is what is signed
+	info: &'a [u8],
+	rdns: Rdns,
+	/// The DER-encoded SubjectPublicKeyInfo
+	spki: &'a [u8],
+	/// The extensions of the first extension request attribute
+	extensions: Vec<Extension>,
+	signature_algorithm: (ObjectIdentifier, Option<ObjectIdentifier>),
+	signature_algorithm_der: &'a [u8],
+	signature: Vec<u8>,
+}
+
+impl<'a> Request<'a> {
+	fn read(reader: BERReader<'a, '_>) -> ASN1Result<Self> {
+		reader.read_sequence(|reader| {
+			let ((rdns, spki, extensions), info) = reader.next().read_with_buffer(|reader| {
+				reader.read_sequence(|reader| {
+					if reader.next().read_u8()? != 0 {
+						return Err(ASN1Error::new(ASN1ErrorKind::Invalid));
+					}
+					let rdns = read_name(reader.next())?;
+					let (_, spki) = reader
+						.next()
+						.read_with_buffer(|reader| reader.read_tagged_der().map(drop))?;
+
+					// Read as SEQUENCE OF, which unlike a SET OF in DER doesn't require
+					// the attributes to be sorted, like x509-parser
+					let mut extensions = None;
+					reader
+						.next()
+						.read_tagged_implicit(Tag::context(0), |reader| {
+							reader.read_sequence_of(|reader| {
+								reader.read_sequence(|reader| {
+									let attribute_oid = reader.next().read_oid()?;
+									if extensions.is_some()
+										|| attribute_oid.components()
+											!= oid::PKCS_9_AT_EXTENSION_REQUEST
+									{
+										return reader.next().read_der().map(drop);
+									}
+									let mut values = reader.next().collect_set_of(|reader| {
+										reader.collect_sequence_of(Extension::read)
+									})?;
+									match (values.pop(), values.is_empty()) {
+										(Some(requested), true) => {
+											extensions = Some(requested);
+											Ok(())
+										},
+										_ => Err(ASN1Error::new(ASN1ErrorKind::Invalid)),
+									}
+								})
+							})
+						})?;
+					Ok((rdns, spki, extensions.unwrap_or_default()))
+				})
+			})?;
+			let (signature_algorithm, signature_algorithm_der) =
+				reader.next().read_with_buffer(read_algorithm_identifier)?;
+			let signature = read_bytes_of_bits(reader.next())?;
+			Ok(Self {
+				info,
+				rdns,
+				spki,
+				extensions,
+				signature_algorithm,
+				signature_algorithm_der,
+				signature,
+			})
+		})
+	}
+}
+
+/// An extension of the extension request attribute
+struct Extension {
+	oid: ObjectIdentifier,
+	value: Vec<u8>,
+}
+
+impl Extension {
+	fn read(reader: BERReader<'_, '_>) -> ASN1Result<Self> {
+		reader.read_sequence(|reader| {
+			let oid = reader.next().read_oid()?;
+			reader.read_optional(|reader| reader.read_bool())?;
+			let value = reader.next().read_bytes()?;
+			Ok(Self { oid, value })
+		})
+	}
+}
+
+fn read_name(reader: BERReader<'_, '_>) -> ASN1Result<Rdns> {
+	reader.collect_sequence_of(|reader| {
+		reader.collect_set_of(|reader| {
+			reader.read_sequence(|reader| {
+				Ok((reader.next().read_oid()?, reader.next().read_tagged_der()?))
+			})
+		})
+	})
+}
+
+fn distinguished_name(rdns: &Rdns) -> Result<DistinguishedName, Error> {
+	let mut dn = DistinguishedName::new();
+	for rdn in rdns {
+		let (attr_type, value) = match rdn.as_slice() {
+			[attr] => attr,
+			[] => return Err(Error::CouldNotParseCertificationRequest),
+			// no support for distinguished names with more than one attribute
+			_ => return Err(Error::CouldNotParseCertificate),
+		};
+
+		let dn_type = DnType::from_oid(attr_type.components());
+		let data = value.value();
+		let try_str = |data| std::str::from_utf8(data).map_err(|_| Error::CouldNotParseCertificate);
+		let dn_value = match tag_byte(value) {
+			Some(0x1e) => DnValue::BmpString(BmpString::from_utf16be(data.to_vec())?),
+			Some(0x16) => DnValue::Ia5String(try_str(data)?.try_into()?),
+			Some(0x13) => DnValue::PrintableString(try_str(data)?.try_into()?),
+			Some(0x14) => DnValue::TeletexString(try_str(data)?.try_into()?),
+			Some(0x1c) => DnValue::UniversalString(UniversalString::from_utf32be(data.to_vec())?),
+			Some(0x0c) => DnValue::Utf8String(try_str(data)?.to_owned()),
+			_ => return Err(Error::CouldNotParseCertificate),
+		};
+		dn.push_parsed(dn_type, dn_value);
+	}
+	Ok(dn)
+}
+
+/// Converts a DER-encoded `GeneralName`
+fn san(der: &[u8]) -> Result<SanType, Error> {
+	let name = crate::asn1::parse_der(der, |reader| reader.read_tagged_der())
+		.map_err(|_| Error::CouldNotParseCertificationRequest)?;
+	let contents = name.value();
+	let try_str =
+		|| std::str::from_utf8(contents).map_err(|_| Error::CouldNotParseCertificationRequest);
+	Ok(match tag_byte(&name) {
+		Some(0x81) => SanType::Rfc822Name(try_str()?.try_into()?),
+		Some(0x82) => SanType::DnsName(DnsName::raw(try_str()?.try_into()?)),
+		Some(0x86) => SanType::URI(try_str()?.try_into()?),
+		Some(0x87) => SanType::IpAddress(ip_addr_from_octets(contents)?),
+		Some(0xa0) => {
+			let (type_id, value) = crate::asn1::parse_der(der, |reader| {
+				reader.read_tagged_implicit(Tag::context(0), |reader| {
+					reader.read_sequence(|reader| {
+						Ok((reader.next().read_oid()?, reader.next().read_der()?))
+					})
+				})
+			})
+			.map_err(|_| Error::CouldNotParseCertificationRequest)?;
+			match OtherNameValue::from_der(&value) {
+				Some(value) => SanType::OtherName((type_id.components().clone(), value)),
+				// Keep values we can't represent as they are
+				None => SanType::Raw {
+					tag: 0,
+					der_value: contents.to_vec(),
+				},
+			}
+		},
+		Some(0xa4) => {
+			let name = crate::asn1::parse_der(contents, read_name)
+				.map_err(|_| Error::CouldNotParseCertificationRequest)
+				.and_then(|rdns| distinguished_name(&rdns));
+			match name {
+				Ok(dn) => SanType::DirectoryName(dn),
+				// Keep names we can't represent as they are
+				Err(_) => SanType::Raw {
+					tag: 4,
+					der_value: contents.to_vec(),
+				},
+			}
+		},
+		Some(0x88) => {
+			let oid = crate::asn1::parse_der(der, |reader| {
+				reader.read_tagged_implicit(Tag::context(8), |reader| reader.read_oid())
+			});
+			match oid {
+				Ok(oid) => SanType::RegisteredId(oid.components().clone()),
+				Err(_) => SanType::Raw {
+					tag: 8,
+					der_value: contents.to_vec(),
+				},
+			}
+		},
+		// Other context-specific forms
+		Some(tag) if tag & 0xc0 == 0x80 => SanType::Raw {
+			tag: tag & 0x1f,
+			der_value: contents.to_vec(),
+		},
+		_ => return Err(Error::CouldNotParseCertificationRequest),
+	})
+}
+
+fn basic_constraints(value: &[u8]) -> Result<IsCa, Error> {
+	let (ca, path_len) = crate::asn1::parse_der(value, |reader| {
+		reader.read_sequence(|reader| {
+			let ca = reader.read_optional(|reader| reader.read_bool())?;
+			let path_len = reader.read_optional(|reader| reader.read_u64())?;
+			Ok((ca.unwrap_or(false), path_len))
+		})
+	})
+	.map_err(|_| Error::CouldNotParseCertificationRequest)?;
+	let path_len = path_len
+		.map(|n| u8::try_from(n).map_err(|_| Error::CouldNotParseCertificate))
+		.transpose()?;
+
+	Ok(match (ca, path_len) {
+		(false, _) => IsCa::ExplicitNoCa,
+		(true, None) => IsCa::Ca(BasicConstraints::Unconstrained),
+		(true, Some(n)) => IsCa::Ca(BasicConstraints::Constrained(n)),
+	})
+}
+
+/// Reads the OID and the parameters, if they are an OID, of an `AlgorithmIdentifier`
+fn read_algorithm_identifier(
+	reader: BERReader<'_, '_>,
+) -> ASN1Result<(ObjectIdentifier, Option<ObjectIdentifier>)> {
+	reader.read_sequence(|reader| {
+		let algorithm = reader.next().read_oid()?;
+		let params = reader.read_optional(|reader| reader.read_oid())?;
+		if params.is_none() {
+			reader.read_optional(|reader| reader.read_der())?;
+		}
+		Ok((algorithm, params))
+	})
+}
+
+/// Picks the algorithm to verify the request's signature with
+///
+/// RSASSA-PSS signatures are told apart by the parameters of `signature_algorithm`, the
+/// DER-encoded AlgorithmIdentifier of the signature.
+fn verification_algorithm(
+	key: &[u64],
+	curve: Option<&[u64]>,
+	signature: &[u64],
+	signature_algorithm: &[u8],
+) -> Option<&'static dyn VerificationAlgorithm> {
+	const ECDSA_WITH_SHA256: &[u64] = &[1, 2, 840, 10045, 4, 3, 2];
+	const ECDSA_WITH_SHA384: &[u64] = &[1, 2, 840, 10045, 4, 3, 3];
+	#[cfg(feature = "aws_lc_rs")]
+	const ECDSA_WITH_SHA512: &[u64] = &[1, 2, 840, 10045, 4, 3, 4];
+	const SHA256_WITH_RSA: &[u64] = &[1, 2, 840, 113549, 1, 1, 11];
+	const SHA384_WITH_RSA: &[u64] = &[1, 2, 840, 113549, 1, 1, 12];
+	const SHA512_WITH_RSA: &[u64] = &[1, 2, 840, 113549, 1, 1, 13];
+
+	Some(match (key, curve, signature) {
+		(oid::EC_PUBLIC_KEY, Some(oid::EC_SECP_256_R1), ECDSA_WITH_SHA256) => {
+			&signature::ECDSA_P256_SHA256_ASN1
+		},
+		(oid::EC_PUBLIC_KEY, Some(oid::EC_SECP_256_R1), ECDSA_WITH_SHA384) => {
+			&signature::ECDSA_P256_SHA384_ASN1
+		},
+		(oid::EC_PUBLIC_KEY, Some(oid::EC_SECP_384_R1), ECDSA_WITH_SHA256) => {
+			&signature::ECDSA_P384_SHA256_ASN1
+		},
+		(oid::EC_PUBLIC_KEY, Some(oid::EC_SECP_384_R1), ECDSA_WITH_SHA384) => {
+			&signature::ECDSA_P384_SHA384_ASN1
+		},
+		#[cfg(feature = "aws_lc_rs")]
+		(oid::EC_PUBLIC_KEY, Some(oid::EC_SECP_521_R1), ECDSA_WITH_SHA256) => {
+			&signature::ECDSA_P521_SHA256_ASN1
+		},
+		#[cfg(feature = "aws_lc_rs")]
+		(oid::EC_PUBLIC_KEY, Some(oid::EC_SECP_521_R1), ECDSA_WITH_SHA384) => {
+			&signature::ECDSA_P521_SHA384_ASN1
+		},
+		#[cfg(feature = "aws_lc_rs")]
+		(oid::EC_PUBLIC_KEY, Some(oid::EC_SECP_521_R1), ECDSA_WITH_SHA512) => {
+			&signature::ECDSA_P521_SHA512_ASN1
+		},
+		#[cfg(feature = "aws_lc_rs")]
+		(oid::EC_PUBLIC_KEY, Some(oid::EC_SECP_256_K1), ECDSA_WITH_SHA256) => {
+			&signature::ECDSA_P256K1_SHA256_ASN1
+		},
+		(oid::ED25519, None, oid::ED25519) => &signature::ED25519,
+		(oid::RSA_ENCRYPTION, None, SHA256_WITH_RSA) => &signature::RSA_PKCS1_2048_8192_SHA256,
+		(oid::RSA_ENCRYPTION, None, SHA384_WITH_RSA) => &signature::RSA_PKCS1_2048_8192_SHA384,
+		(oid::RSA_ENCRYPTION, None, SHA512_WITH_RSA) => &signature::RSA_PKCS1_2048_8192_SHA512,
+		(oid::RSA_ENCRYPTION | oid::RSASSA_PSS, _, oid::RSASSA_PSS) => {
+			let alg = SignatureAlgorithm::from_rsa_pss_alg_ident(signature_algorithm).ok()??;
+			match alg.digest()? {
+				DigestAlgorithm::Sha256 => &signature::RSA_PSS_2048_8192_SHA256,
+				DigestAlgorithm::Sha384 => &signature::RSA_PSS_2048_8192_SHA384,
+				DigestAlgorithm::Sha512 => &signature::RSA_PSS_2048_8192_SHA512,
+				#[cfg(feature = "insecure_legacy_algorithms")]
+				DigestAlgorithm::Sha1 => return None,
+			}
+		},
+		_ => return None,
+	})
+}
+
+/// Reads a BIT STRING, which must be a whole number of bytes
+fn read_bytes_of_bits(reader: BERReader<'_, '_>) -> ASN1Result<Vec<u8>> {
+	match reader.read_bitvec_bytes()? {
+		(bytes, len) if len % 8 == 0 => Ok(bytes),
+		_ => Err(ASN1Error::new(ASN1ErrorKind::Invalid)),
+	}
+}
+
+/// Reads the bytes of a BIT STRING, with the unused bits of the last one set to zero
+fn read_bits(reader: BERReader<'_, '_>) -> ASN1Result<Vec<u8>> {
+	Ok(reader.read_bitvec_bytes()?.0)
+}
+
+/// The identifier octet of a value, for tag numbers that fit into it
+fn tag_byte(value: &TaggedDerValue) -> Option<u8> {
+	let tag = value.tag();
+	let number = u8::try_from(tag.tag_number)
+		.ok()
+		.filter(|number| *number < 0x1f)?;
+	Some((tag.tag_class as u8) << 6 | (value.pcbit() as u8) << 5 | number)
+}
+
+#[cfg(test)]
+mod tests {
+	use pki_types::CertificateSigningRequestDer;
+
+	use super::*;
+	use crate::{
+		CustomExtension, KeyPair, PublicKeyData, PKCS_ECDSA_P256_SHA256, PKCS_ECDSA_P384_SHA384,
+		PKCS_ED25519,
+	};
+	#[cfg(feature = "aws_lc_rs")]
+	use crate::{PKCS_ECDSA_K256_SHA256, PKCS_RSA_SHA256};
+
+	/// Requests covering the supported fields, extensions and algorithms
+	fn corpus() -> Vec<CertificateSigningRequestDer<'static>> {
+		let directory_name = {
+			let mut dn = DistinguishedName::new();
+			dn.push(DnType::CommonName, "Crab");
+			yasna::construct_der(|writer| crate::write_distinguished_name(writer, &dn))
+		};
+
+		let mut full = CertificateParams::new(vec![
+			"crabs.crabs".to_owned(),
+			"192.0.2.1".to_owned(),
+			"2001:db8::1".to_owned(),
+		])
+		.unwrap();
+		full.subject_alt_names.extend([
+			SanType::Rfc822Name("crab@crabs.crabs".try_into().unwrap()),
+			SanType::URI("https://crabs.crabs/".try_into().unwrap()),
+			SanType::OtherName((vec![1, 2, 3, 4], "crab".into())),
+			SanType::OtherName((
+				vec![1, 2, 3, 5],
+				OtherNameValue::Ia5String("crab".try_into().unwrap()),
+			)),
+			SanType::user_principal_name("crab@crabs.crabs"),
+			SanType::RegisteredId(vec![1, 3, 6, 1, 4, 1, 54392, 5, 1]),
+			SanType::Raw {
+				tag: 0,
+				der_value: vec![0x06, 0x03, 0x2a, 0x03, 0x04, 0xa0, 0x03, 0x02, 0x01, 0x05],
+			},
+			SanType::Raw {
+				tag: 4,
+				der_value: directory_name,
+			},
+			SanType::Raw {
+				tag: 8,
+				der_value: vec![0x2a, 0x03, 0x04],
+			},
+		]);
+		let dn = &mut full.distinguished_name;
+		dn.push(
+			DnType::CountryName,
+			DnValue::PrintableString("DE".try_into().unwrap()),
+		);
+		dn.push(
+			DnType::OrganizationName,
+			DnValue::BmpString("Crab".try_into().unwrap()),
+		);
+		dn.push(
+			DnType::OrganizationalUnitName,
+			DnValue::UniversalString("Crab widgits".try_into().unwrap()),
+		);
+		dn.push(
+			DnType::LocalityName,
+			DnValue::Ia5String("Krabi".try_into().unwrap()),
+		);
+		dn.push(
+			DnType::StateOrProvinceName,
+			DnValue::TeletexString("Tide".try_into().unwrap()),
+		);
+		dn.push(DnType::CustomDnType(vec![1, 2, 3, 4]), "custom");
+		full.key_usages = vec![
+			KeyUsagePurpose::DigitalSignature,
+			KeyUsagePurpose::KeyCertSign,
+			KeyUsagePurpose::DecipherOnly,
+		];
+		full.extended_key_usages = vec![
+			ExtendedKeyUsagePurpose::ServerAuth,
+			ExtendedKeyUsagePurpose::Other(vec![1, 2, 3, 4]),
+		];
+		full.is_ca = IsCa::Ca(BasicConstraints::Constrained(200));
+		full.tls_features = vec![TlsFeature::StatusRequest, TlsFeature::Other(1000)];
+
+		let mut all_params = vec![CertificateParams::default(), full];
+		for is_ca in [
+			IsCa::ExplicitNoCa,
+			IsCa::Ca(BasicConstraints::Unconstrained),
+			IsCa::Ca(BasicConstraints::Constrained(0)),
+		] {
+			all_params.push(CertificateParams {
+				is_ca,
+				key_usages: vec![KeyUsagePurpose::CrlSign],
+				..CertificateParams::default()
+			});
+		}
+		let mut unsupported = CertificateParams::default();
+		unsupported
+			.custom_extensions
+			.push(CustomExtension::from_oid_content(
+				&[1, 2, 3, 4],
+				vec![0x05, 0x00],
+			));
+		all_params.push(unsupported);
+
+		let algs = [
+			&PKCS_ECDSA_P256_SHA256,
+			&PKCS_ECDSA_P384_SHA384,
+			&PKCS_ED25519,
+			#[cfg(feature = "aws_lc_rs")]
+			&PKCS_ECDSA_K256_SHA256,
+			#[cfg(feature = "aws_lc_rs")]
+			&PKCS_RSA_SHA256,
+		];
+
+		let mut corpus = Vec::new();
+		for alg in algs {
+			let key = KeyPair::generate_for(alg).unwrap();
+			for params in &all_params {
+				corpus.push(params.serialize_request(&key).unwrap().der().clone());
+			}
+		}
+
+		// Keys identified by id-RSASSA-PSS, an rsaEncryption key signing with RSASSA-PSS and
+		// a key with explicit EC domain parameters
+		for der in [
+			&include_bytes!("test_data/rsa_pss.csr.der")[..],
+			include_bytes!("test_data/rsa_pss_sha256.csr.der"),
+			include_bytes!("test_data/rsa_signed_with_pss.csr.der"),
+			include_bytes!("test_data/p256_explicit.csr.der"),
+		] {
+			corpus.push(der.to_vec().into());
+		}
+
+		// A tampered signature, truncated input and trailing data
+		let mut der = corpus[0].to_vec();
+		*der.last_mut().unwrap() ^= 1;
+		corpus.push(der.clone().into());
+		corpus.push(der[..der.len() - 1].to_vec().into());
+		der.push(0);
+		corpus.push(der.into());
+		corpus
+	}
+
+	#[test]
+	fn round_trip() {
+		let key = KeyPair::generate().unwrap();
+		let mut params = CertificateParams::new(vec!["crabs.crabs".to_owned()]).unwrap();
+		params.key_usages = vec![KeyUsagePurpose::DigitalSignature];
+		params.is_ca = IsCa::ExplicitNoCa;
+		let csr = params.serialize_request(&key).unwrap();
+
+		let parsed = parse(csr.der(), &ParseLimits::default()).unwrap();
+		assert_eq!(parsed.params.distinguished_name, params.distinguished_name);
+		assert_eq!(parsed.params.subject_alt_names, params.subject_alt_names);
+		assert_eq!(parsed.params.key_usages, params.key_usages);
+		assert_eq!(parsed.params.is_ca, params.is_ca);
+		assert_eq!(parsed.public_key.der_bytes(), key.der_bytes());
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn same_as_x509_parser() {
+		let small = ParseLimits {
+			max_subject_alt_names: 4,
+			..ParseLimits::default()
+		};
+		for limits in [ParseLimits::default(), small] {
+			for csr in corpus() {
+				assert_eq!(
+					parse(&csr, &limits),
+					CertificateSigningRequestParams::from_der_with_limits(&csr, &limits),
+					"{csr:?}"
+				);
+			}
+		}
+	}
+}
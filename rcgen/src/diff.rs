@@ -0,0 +1,370 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use pki_types::CertificateDer;
+use time::{Duration, OffsetDateTime};
+
+use crate::name_constraints::{describe_dn, dn_eq};
+use crate::{dt_strip_nanos, CertificateParams, DistinguishedName, Error, IsCa, SanType};
+
+impl CertificateParams {
+	/// Lists the differences between a DER-encoded certificate and these parameters
+	///
+	/// This answers whether a deployed certificate still matches the parameters it should
+	/// have been issued from, so an empty list means that there is no need to re-issue it.
+	/// The following fields are compared:
+	///
+	/// * the subject, with attribute values compared ignoring their string type, case and
+	///   redundant whitespace
+	/// * the subject alternative names, key usages and extended key usages, ignoring order
+	/// * whether the certificate is a CA, treating [`IsCa::NoCa`] and [`IsCa::ExplicitNoCa`]
+	///   the same
+	/// * the validity period, which has to match exactly
+	///
+	/// Use [`diff_against_cert_with_options()`](Self::diff_against_cert_with_options) to also
+	/// compare the issuer, or to tolerate drift in the validity period.
+	pub fn diff_against_cert(&self, cert_der: &[u8]) -> Result<Vec<ParamsDiff>, Error> {
+		self.diff_against_cert_with_options(cert_der, &DiffOptions::default())
+	}
+
+	/// Lists the differences between a DER-encoded certificate and these parameters, as
+	/// configured by `options`
+	///
+	/// See [`diff_against_cert()`](Self::diff_against_cert) for the fields that are compared.
+	pub fn diff_against_cert_with_options(
+		&self,
+		cert_der: &[u8],
+		options: &DiffOptions,
+	) -> Result<Vec<ParamsDiff>, Error> {
+		let cert_der = CertificateDer::from(cert_der);
+		let x509 = crate::parse_x509_certificate(&cert_der)?;
+		let actual = CertificateParams::from_ca_cert_der(&cert_der)?;
+		let mut diffs = Vec::new();
+
+		if !dn_eq(&self.distinguished_name, &actual.distinguished_name) {
+			diffs.push(ParamsDiff::new(
+				DiffField::Subject,
+				describe_dn(&self.distinguished_name),
+				describe_dn(&actual.distinguished_name),
+			));
+		}
+
+		if let Some(issuer) = &options.issuer {
+			let actual_issuer = DistinguishedName::from_name(&x509.tbs_certificate.issuer)?;
+			if !dn_eq(issuer, &actual_issuer) {
+				diffs.push(ParamsDiff::new(
+					DiffField::Issuer,
+					describe_dn(issuer),
+					describe_dn(&actual_issuer),
+				));
+			}
+		}
+
+		if !same_elements(&self.subject_alt_names, &actual.subject_alt_names) {
+			diffs.push(ParamsDiff::new(
+				DiffField::SubjectAltNames,
+				describe_all(&self.subject_alt_names, describe_san),
+				describe_all(&actual.subject_alt_names, describe_san),
+			));
+		}
+
+		if !same_elements(&self.key_usages, &actual.key_usages) {
+			diffs.push(ParamsDiff::new(
+				DiffField::KeyUsages,
+				describe_all(&self.key_usages, |usage| format!("{usage:?}")),
+				describe_all(&actual.key_usages, |usage| format!("{usage:?}")),
+			));
+		}
+
+		if !same_elements(&self.extended_key_usages, &actual.extended_key_usages) {
+			diffs.push(ParamsDiff::new(
+				DiffField::ExtendedKeyUsages,
+				describe_all(&self.extended_key_usages, |usage| format!("{usage:?}")),
+				describe_all(&actual.extended_key_usages, |usage| format!("{usage:?}")),
+			));
+		}
+
+		let is_ca = |is_ca: IsCa| match is_ca {
+			IsCa::ExplicitNoCa => IsCa::NoCa,
+			other => other,
+		};
+		if is_ca(self.is_ca) != is_ca(actual.is_ca) {
+			diffs.push(ParamsDiff::new(
+				DiffField::IsCa,
+				format!("{:?}", self.is_ca),
+				format!("{:?}", actual.is_ca),
+			));
+		}
+
+		for (field, expected, actual) in [
+			(DiffField::NotBefore, self.not_before, actual.not_before),
+			(DiffField::NotAfter, self.not_after, actual.not_after),
+		] {
+			// Certificates can't hold fractions of a second
+			let expected = dt_strip_nanos(expected);
+			if (expected - actual).abs() > options.validity_tolerance {
+				diffs.push(ParamsDiff::new(
+					field,
+					describe_time(expected),
+					describe_time(actual),
+				));
+			}
+		}
+
+		Ok(diffs)
+	}
+}
+
+/// Options for [`CertificateParams::diff_against_cert_with_options()`]
+///
+/// ```
+/// # use rcgen::{DiffOptions, DistinguishedName, DnType};
+/// let mut issuer = DistinguishedName::new();
+/// issuer.push(DnType::CommonName, "Intermediate CA");
+///
+/// let mut options = DiffOptions::default();
+/// options.issuer = Some(issuer);
+/// options.validity_tolerance = time::Duration::minutes(5);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct DiffOptions {
+	/// The expected issuer of the certificate, which isn't compared if `None`
+	pub issuer: Option<DistinguishedName>,
+	/// How far `not_before` and `not_after` may be off before they count as different
+	pub validity_tolerance: Duration,
+}
+
+/// A difference between a certificate and the [`CertificateParams`] that should describe it
+///
+/// Returned by [`CertificateParams::diff_against_cert()`]. The values are human-readable
+/// descriptions, which are meant for logging rather than parsing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParamsDiff {
+	/// The field that differs
+	pub field: DiffField,
+	/// The value of the field in the parameters
+	pub expected: String,
+	/// The value of the field in the certificate
+	pub actual: String,
+}
+
+impl ParamsDiff {
+	fn new(field: DiffField, expected: String, actual: String) -> Self {
+		Self {
+			field,
+			expected,
+			actual,
+		}
+	}
+}
+
+/// A field compared by [`CertificateParams::diff_against_cert()`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DiffField {
+	/// The subject distinguished name
+	Subject,
+	/// The issuer distinguished name, see [`DiffOptions::issuer`]
+	Issuer,
+	/// The subject alternative names
+	SubjectAltNames,
+	/// The key usages
+	KeyUsages,
+	/// The extended key usages
+	ExtendedKeyUsages,
+	/// Whether the certificate is a CA, and its path length constraint
+	IsCa,
+	/// The start of the validity period
+	NotBefore,
+	/// The end of the validity period
+	NotAfter,
+}
+
+fn same_elements<T: Eq + Hash>(expected: &[T], actual: &[T]) -> bool {
+	expected.iter().collect::<HashSet<_>>() == actual.iter().collect::<HashSet<_>>()
+}
+
+/// Describes the elements of `values` in a stable order
+fn describe_all<T>(values: &[T], describe: impl Fn(&T) -> String) -> String {
+	let mut descriptions = values.iter().map(describe).collect::<Vec<_>>();
+	descriptions.sort();
+	descriptions.join(", ")
+}
+
+fn describe_san(san: &SanType) -> String {
+	match san {
+		SanType::DnsName(name) => format!("DNS:{}", name.as_str()),
+		SanType::Rfc822Name(email) => format!("email:{}", email.as_str()),
+		SanType::URI(uri) => format!("URI:{}", uri.as_str()),
+		SanType::IpAddress(addr) => format!("IP:{addr}"),
+		other => format!("{other:?}"),
+	}
+}
+
+fn describe_time(time: OffsetDateTime) -> String {
+	time.format(&time::format_description::well_known::Rfc3339)
+		.unwrap_or_else(|_| time.to_string())
+}
+
+#[cfg(all(test, feature = "crypto"))]
+mod tests {
+	use super::*;
+	use crate::{
+		date_time_ymd, test_keys, DnType, ExtendedKeyUsagePurpose, Issuer, KeyUsagePurpose,
+	};
+
+	fn params() -> CertificateParams {
+		let mut params = CertificateParams::new(vec![
+			"example.com".to_string(),
+			"www.example.com".to_string(),
+		])
+		.unwrap();
+		params.distinguished_name = DistinguishedName::new();
+		params
+			.distinguished_name
+			.push(DnType::OrganizationName, "Crab widgits SE");
+		params.key_usages = vec![KeyUsagePurpose::DigitalSignature];
+		params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+		params.not_before = date_time_ymd(2025, 1, 1);
+		params.not_after = date_time_ymd(2025, 4, 1);
+		params
+	}
+
+	fn fields(diffs: &[ParamsDiff]) -> Vec<DiffField> {
+		diffs.iter().map(|diff| diff.field).collect()
+	}
+
+	#[test]
+	fn unchanged() {
+		let params = params();
+		let cert = params.self_signed(&test_keys::ecdsa_p256()).unwrap();
+		assert_eq!(params.diff_against_cert(cert.der()).unwrap(), []);
+
+		// The order of the names doesn't matter, and neither does the string type of the DN
+		let mut reordered = params.clone();
+		reordered.subject_alt_names.reverse();
+		reordered.distinguished_name = DistinguishedName::new();
+		reordered.distinguished_name.push(
+			DnType::OrganizationName,
+			crate::DnValue::PrintableString("crab  WIDGITS se".try_into().unwrap()),
+		);
+		reordered.is_ca = IsCa::ExplicitNoCa;
+		assert_eq!(reordered.diff_against_cert(cert.der()).unwrap(), []);
+	}
+
+	#[test]
+	fn renamed() {
+		let cert = params().self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let mut params = params();
+		params.distinguished_name = DistinguishedName::new();
+		params
+			.distinguished_name
+			.push(DnType::OrganizationName, "Crab widgits AG");
+
+		let diffs = params.diff_against_cert(cert.der()).unwrap();
+		assert_eq!(
+			diffs,
+			[ParamsDiff::new(
+				DiffField::Subject,
+				"O=Crab widgits AG".to_string(),
+				"O=Crab widgits SE".to_string(),
+			)]
+		);
+	}
+
+	#[test]
+	fn added_san_and_changed_eku() {
+		let cert = params().self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let mut params = params();
+		params
+			.subject_alt_names
+			.push(SanType::DnsName("api.example.com".try_into().unwrap()));
+		params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+		params.is_ca = IsCa::Ca(crate::BasicConstraints::Unconstrained);
+
+		let diffs = params.diff_against_cert(cert.der()).unwrap();
+		assert_eq!(
+			fields(&diffs),
+			[
+				DiffField::SubjectAltNames,
+				DiffField::ExtendedKeyUsages,
+				DiffField::IsCa
+			]
+		);
+		assert_eq!(
+			diffs[0].expected,
+			"DNS:api.example.com, DNS:example.com, DNS:www.example.com"
+		);
+		assert_eq!(diffs[0].actual, "DNS:example.com, DNS:www.example.com");
+		assert_eq!(diffs[1].expected, "ClientAuth");
+		assert_eq!(diffs[1].actual, "ServerAuth");
+	}
+
+	#[test]
+	fn validity_tolerance() {
+		let cert = params().self_signed(&test_keys::ecdsa_p256()).unwrap();
+		let mut params = params();
+		params.not_before += Duration::seconds(30);
+		params.not_after -= Duration::seconds(90);
+
+		let diffs = params.diff_against_cert(cert.der()).unwrap();
+		assert_eq!(fields(&diffs), [DiffField::NotBefore, DiffField::NotAfter]);
+		assert_eq!(diffs[0].expected, "2025-01-01T00:00:30Z");
+		assert_eq!(diffs[0].actual, "2025-01-01T00:00:00Z");
+
+		let options = DiffOptions {
+			validity_tolerance: Duration::minutes(1),
+			..DiffOptions::default()
+		};
+		let diffs = params
+			.diff_against_cert_with_options(cert.der(), &options)
+			.unwrap();
+		assert_eq!(fields(&diffs), [DiffField::NotAfter]);
+	}
+
+	#[test]
+	fn other_issuer() {
+		let mut ca_dn = DistinguishedName::new();
+		ca_dn.push(DnType::CommonName, "Other CA");
+		let ca_params = CertificateParams {
+			distinguished_name: ca_dn,
+			..CertificateParams::default()
+		};
+		let other_ca = Issuer::new(ca_params, test_keys::ed25519());
+
+		let params = params();
+		let cert = params
+			.signed_by(&test_keys::ecdsa_p256(), &other_ca)
+			.unwrap();
+
+		let mut expected_issuer = DistinguishedName::new();
+		expected_issuer.push(DnType::CommonName, "Expected CA");
+		let options = DiffOptions {
+			issuer: Some(expected_issuer),
+			..DiffOptions::default()
+		};
+		let diffs = params
+			.diff_against_cert_with_options(cert.der(), &options)
+			.unwrap();
+		assert_eq!(
+			diffs,
+			[ParamsDiff::new(
+				DiffField::Issuer,
+				"CN=Expected CA".to_string(),
+				"CN=Other CA".to_string(),
+			)]
+		);
+
+		// The issuer is only compared if it's given
+		assert_eq!(params.diff_against_cert(cert.der()).unwrap(), []);
+	}
+
+	#[test]
+	fn not_a_certificate() {
+		assert_eq!(
+			params().diff_against_cert(b"\x30\x00"),
+			Err(Error::CouldNotParseCertificate)
+		);
+	}
+}
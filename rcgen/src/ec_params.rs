@@ -0,0 +1,260 @@
+//! Support for EC public keys with explicit domain parameters
+//!
+//! [RFC 5480](https://www.rfc-editor.org/rfc/rfc5480#section-2.1.1) forbids them in
+//! certificates, but some older tools still write the SpecifiedECDomain of
+//! [RFC 3279](https://www.rfc-editor.org/rfc/rfc3279#section-2.3.5) into keys and CSRs.
+//! Keys using the domain parameters of a supported curve are rewritten to use its named
+//! curve instead, so that they are handled, and issued certificates for, like any other key.
+
+use yasna::models::ObjectIdentifier;
+use yasna::BERReader;
+
+use crate::oid::{EC_PUBLIC_KEY, EC_SECP_256_R1, EC_SECP_384_R1, EC_SECP_521_R1, PRIME_FIELD};
+use crate::Error;
+
+/// The domain parameters of a named curve, as hexadecimal big-endian integers
+struct Curve {
+	oid: &'static [u64],
+	p: &'static str,
+	a: &'static str,
+	b: &'static str,
+	/// The base point in uncompressed form, without the leading `04`
+	g: &'static str,
+	n: &'static str,
+}
+
+/// The curves of [FIPS 186-4](https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.186-4.pdf#page=99),
+/// all of which have a cofactor of 1
+const CURVES: &[Curve] = &[
+	Curve {
+		oid: EC_SECP_256_R1,
+		p: "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff",
+		a: "ffffffff00000001000000000000000000000000fffffffffffffffffffffffc",
+		b: "5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b",
+		g: "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296\
+			4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5",
+		n: "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+	},
+	Curve {
+		oid: EC_SECP_384_R1,
+		p: "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe\
+			ffffffff0000000000000000ffffffff",
+		a: "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffe\
+			ffffffff0000000000000000fffffffc",
+		b: "b3312fa7e23ee7e4988e056be3f82d19181d9c6efe8141120314088f5013875a\
+			c656398d8a2ed19d2a85c8edd3ec2aef",
+		g: "aa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b9859f741e082542a38\
+			5502f25dbf55296c3a545e3872760ab73617de4a96262c6f5d9e98bf9292dc29\
+			f8f41dbd289a147ce9da3113b5f0b8c00a60b1ce1d7e819d7a431d7c90ea0e5f",
+		n: "ffffffffffffffffffffffffffffffffffffffffffffffffc7634d81f4372ddf\
+			581a0db248b0a77aecec196accc52973",
+	},
+	Curve {
+		oid: EC_SECP_521_R1,
+		p: "01ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\
+			ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\
+			ffff",
+		a: "01ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\
+			ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\
+			fffc",
+		b: "0051953eb9618e1c9a1f929a21a0b68540eea2da725b99b315f3b8b489918ef1\
+			09e156193951ec7e937b1652c0bd3bb1bf073573df883d2c34f1ef451fd46b50\
+			3f00",
+		g: "00c6858e06b70404e9cd9e3ecb662395b4429c648139053fb521f828af606b4d\
+			3dbaa14b5e77efe75928fe1dc127a2ffa8de3348b3c1856a429bf97e7e31c2e5\
+			bd66011839296a789a3bc0045c8a5fb42c7d1bd998f54449579b446817afbd17\
+			273e662c97ee72995ef42640c550b9013fad0761353c7086a272c24088be9476\
+			9fd16650",
+		n: "01ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\
+			fffa51868783bf2f966b7fcc0148f709a5d03bb5c9b8899c47aebb6fb71e9138\
+			6409",
+	},
+];
+
+/// Rewrites a DER-encoded SubjectPublicKeyInfo with explicit EC domain parameters to use
+/// the corresponding named curve
+///
+/// Returns `None` for all other keys, including malformed ones, which are left to the
+/// regular parsers to reject. Fails with [`Error::ExplicitEcParametersUnsupported`] if the
+/// domain parameters don't belong to any of the supported curves.
+pub(crate) fn normalize_spki(spki: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+	let Ok((parameters, public_key)) = crate::asn1::parse_der(spki, |reader| {
+		reader.read_sequence(|reader| {
+			let parameters = reader.next().read_sequence(|reader| {
+				let algorithm = reader.next().read_oid()?;
+				let parameters = reader.read_optional(|reader| reader.read_der())?;
+				Ok((algorithm.components().as_slice() == EC_PUBLIC_KEY).then_some(parameters))
+			})?;
+			let public_key = reader.next().read_der()?;
+			Ok((parameters.flatten(), public_key))
+		})
+	}) else {
+		return Ok(None);
+	};
+
+	// Named curves are OIDs, while explicit domain parameters are a SEQUENCE
+	let Some(parameters) = parameters.filter(|params| params.first() == Some(&0x30)) else {
+		return Ok(None);
+	};
+
+	let curve = named_curve(&parameters)?;
+	Ok(Some(yasna::construct_der(|writer| {
+		writer.write_sequence(|writer| {
+			writer.next().write_sequence(|writer| {
+				writer
+					.next()
+					.write_oid(&ObjectIdentifier::from_slice(EC_PUBLIC_KEY));
+				writer
+					.next()
+					.write_oid(&ObjectIdentifier::from_slice(curve));
+			});
+			writer.next().write_der(&public_key);
+		})
+	})))
+}
+
+/// Looks up the named curve of a DER-encoded SpecifiedECDomain
+fn named_curve(specified_domain: &[u8]) -> Result<&'static [u64], Error> {
+	let domain = crate::asn1::parse_der(specified_domain, |reader| {
+		reader.read_sequence(|reader| {
+			// ecpVer1, or ecdpVer2 and ecdpVer3 of SEC 1 version 2 which only differ in the seed
+			let version = reader.next().read_u8()?;
+			let p = reader.next().read_sequence(|reader| {
+				let field_type = reader.next().read_oid()?;
+				let p = read_unsigned(reader.next())?;
+				Ok((field_type.components().as_slice() == PRIME_FIELD).then_some(p))
+			})?;
+			let (a, b) = reader.next().read_sequence(|reader| {
+				let a = reader.next().read_bytes()?;
+				let b = reader.next().read_bytes()?;
+				reader.read_optional(|reader| reader.read_bitvec_bytes())?;
+				Ok((a, b))
+			})?;
+			let base = reader.next().read_bytes()?;
+			let order = read_unsigned(reader.next())?;
+			let cofactor = reader.read_optional(|reader| reader.read_u64())?;
+			// The hash algorithm of SEC 1 version 2 is only relevant for generating curves
+			reader.read_optional(|reader| reader.read_der())?;
+			Ok((version, p, a, b, base, order, cofactor))
+		})
+	})
+	.map_err(|_| Error::CouldNotParseKeyPair)?;
+
+	let (1..=3, Some(p), a, b, base, order, None | Some(1)) = domain else {
+		return Err(Error::ExplicitEcParametersUnsupported);
+	};
+
+	CURVES
+		.iter()
+		.find(|curve| {
+			eq_unsigned(&p, curve.p)
+				&& eq_unsigned(&a, curve.a)
+				&& eq_unsigned(&b, curve.b)
+				&& eq_unsigned(&order, curve.n)
+				&& is_base_point(&base, curve.g)
+		})
+		.map(|curve| curve.oid)
+		.ok_or(Error::ExplicitEcParametersUnsupported)
+}
+
+/// Reads a non-negative INTEGER
+fn read_unsigned(reader: BERReader<'_, '_>) -> yasna::ASN1Result<Vec<u8>> {
+	match reader.read_bigint_bytes()? {
+		(bytes, true) => Ok(bytes),
+		(_, false) => Err(yasna::ASN1Error::new(yasna::ASN1ErrorKind::Invalid)),
+	}
+}
+
+/// Checks whether the point in the format of SEC 1 section 2.3.3 is the base point `g`
+fn is_base_point(point: &[u8], g: &str) -> bool {
+	let g = decode_hex(g);
+	let (x, y) = g.split_at(g.len() / 2);
+	match point.split_first() {
+		Some((0x04, rest)) => rest == g.as_slice(),
+		// The compressed form only holds the parity of the y coordinate
+		Some((prefix @ (0x02 | 0x03), rest)) => {
+			rest == x && y.last().map(|last| 0x02 | (last & 1)) == Some(*prefix)
+		},
+		_ => false,
+	}
+}
+
+/// Compares big-endian integers, which may have different numbers of leading zeros
+fn eq_unsigned(value: &[u8], expected: &str) -> bool {
+	fn trim(bytes: &[u8]) -> &[u8] {
+		let zeros = bytes.iter().take_while(|byte| **byte == 0).count();
+		&bytes[zeros..]
+	}
+
+	trim(value) == trim(&decode_hex(expected))
+}
+
+/// Decodes the hexadecimal constants of [`CURVES`]
+#[allow(clippy::unwrap_used)]
+fn decode_hex(hex: &str) -> Vec<u8> {
+	hex.as_bytes()
+		.chunks(2)
+		.map(|pair| u8::from_str_radix(std::str::from_utf8(pair).unwrap(), 16).unwrap())
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn known_curves() {
+		// Generated with `openssl ecparam -genkey -param_enc explicit`
+		for (spki, curve_oid, point_len) in [
+			(
+				&include_bytes!("test_data/p256_explicit.spki.der")[..],
+				EC_SECP_256_R1,
+				65,
+			),
+			(
+				include_bytes!("test_data/p384_explicit.spki.der"),
+				EC_SECP_384_R1,
+				97,
+			),
+			(
+				include_bytes!("test_data/p521_explicit.spki.der"),
+				EC_SECP_521_R1,
+				133,
+			),
+		] {
+			let normalized = normalize_spki(spki).unwrap().unwrap();
+			let (curve, point) = crate::asn1::parse_der(&normalized, |reader| {
+				reader.read_sequence(|reader| {
+					let curve = reader.next().read_sequence(|reader| {
+						assert_eq!(reader.next().read_oid()?.components(), EC_PUBLIC_KEY);
+						reader.next().read_oid()
+					})?;
+					Ok((curve, reader.next().read_bitvec_bytes()?.0))
+				})
+			})
+			.unwrap();
+			assert_eq!(curve.components(), curve_oid);
+			assert_eq!(point.len(), point_len);
+			assert!(spki.ends_with(&point));
+		}
+	}
+
+	#[cfg(feature = "crypto")]
+	#[test]
+	fn other_keys_unchanged() {
+		let named = crate::test_keys::ecdsa_p256();
+		assert_eq!(
+			normalize_spki(&crate::PublicKeyData::subject_public_key_info(&named)).unwrap(),
+			None
+		);
+		assert_eq!(normalize_spki(b"\x30\x00").unwrap(), None);
+	}
+
+	#[test]
+	fn unknown_curve() {
+		assert_eq!(
+			normalize_spki(include_bytes!("test_data/brainpool_explicit.spki.der")),
+			Err(Error::ExplicitEcParametersUnsupported)
+		);
+	}
+}
@@ -0,0 +1,80 @@
+//! Ed448 key pairs, backed by OpenSSL as neither `ring` nor `aws-lc-rs` implement Ed448
+
+use std::fmt;
+
+use openssl::pkey::{Id, PKey, Private};
+use openssl::sign::{Signer, Verifier};
+
+use crate::Error;
+
+/// An Ed448 key pair as per [RFC 8410](https://www.rfc-editor.org/rfc/rfc8410)
+pub(crate) struct Ed448KeyPair {
+	key: PKey<Private>,
+	public_key: Vec<u8>,
+}
+
+impl Ed448KeyPair {
+	/// Generates a new key pair, along with its PKCS#8 serialization
+	///
+	/// Fails with [`Error::KeyGenerationUnavailable`] if OpenSSL can't generate Ed448 keys.
+	pub(crate) fn generate() -> Result<(Self, Vec<u8>), Error> {
+		let key = PKey::generate_ed448().map_err(|_| Error::KeyGenerationUnavailable)?;
+		let pkcs8 = key
+			.private_key_to_pkcs8()
+			.map_err(|_| Error::KeyGenerationUnavailable)?;
+		Ok((Self::new(key)?, pkcs8))
+	}
+
+	/// Parses the key pair from a PKCS#8 key
+	///
+	/// Fails with [`Error::CouldNotParseKeyPair`] for keys of other algorithms.
+	pub(crate) fn from_pkcs8(pkcs8: &[u8]) -> Result<Self, Error> {
+		let key = PKey::private_key_from_pkcs8(pkcs8).map_err(|_| Error::CouldNotParseKeyPair)?;
+		if key.id() != Id::ED448 {
+			return Err(Error::CouldNotParseKeyPair);
+		}
+		Self::new(key)
+	}
+
+	fn new(key: PKey<Private>) -> Result<Self, Error> {
+		let public_key = key
+			.raw_public_key()
+			.map_err(|_| Error::CouldNotParseKeyPair)?;
+		Ok(Self { key, public_key })
+	}
+
+	/// Signs `msg` as PureEdDSA, without a context
+	pub(crate) fn sign(&self, msg: &[u8]) -> Result<Vec<u8>, Error> {
+		Signer::new_without_digest(&self.key)
+			.and_then(|mut signer| signer.sign_oneshot_to_vec(msg))
+			.map_err(|_| Error::RingUnspecified)
+	}
+
+	/// The 57-byte raw public key
+	pub(crate) fn public_key(&self) -> &[u8] {
+		&self.public_key
+	}
+}
+
+/// Verifies the PureEdDSA `signature` of `msg` with the raw `public_key`
+///
+/// Fails with [`Error::InvalidSignature`] if it doesn't verify.
+pub(crate) fn verify(public_key: &[u8], msg: &[u8], signature: &[u8]) -> Result<(), Error> {
+	let public_key = PKey::public_key_from_raw_bytes(public_key, Id::ED448)
+		.map_err(|_| Error::InvalidPublicKey)?;
+	let valid = Verifier::new_without_digest(&public_key)
+		.and_then(|mut verifier| verifier.verify_oneshot(signature, msg))
+		.unwrap_or(false);
+	match valid {
+		true => Ok(()),
+		false => Err(Error::InvalidSignature),
+	}
+}
+
+impl fmt::Debug for Ed448KeyPair {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Ed448KeyPair")
+			.field("public_key", &self.public_key)
+			.finish()
+	}
+}
@@ -1,4 +1,7 @@
 use std::fmt;
+use std::net::IpAddr;
+
+use crate::{CidrSubnet, ExtensionId, ParseLimit, SerialNumber, SignatureAlgorithm};
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[non_exhaustive]
@@ -10,24 +13,78 @@ pub enum Error {
 	CouldNotParseCertificationRequest,
 	/// The given key pair couldn't be parsed
 	CouldNotParseKeyPair,
+	/// A private key was expected, but the input is a public key, like a "PUBLIC KEY" PEM block
+	ExpectedPrivateKeyFoundPublic,
+	/// A private key was expected, but the input is a certificate
+	ExpectedPrivateKeyFoundCertificate,
+	/// The given [`CaState`](crate::CaState) couldn't be parsed
+	CouldNotParseCaState,
+	/// The given to-be-signed structure couldn't be parsed
+	CouldNotParseTbs,
+	/// The serial number has already been issued by the CA
+	DuplicateSerialNumber(SerialNumber),
+	/// A counter of the [`CaState`](crate::CaState) reached its maximum, or the state holds
+	/// more or longer serial numbers than [`CaState::to_bytes()`](crate::CaState::to_bytes)
+	/// can represent
+	CaStateOverflow,
 	/// The CSR signature is invalid
-	#[cfg(feature = "x509-parser")]
+	#[cfg(any(feature = "crypto", feature = "x509-parser"))]
 	InvalidCertificationRequestSignature,
-	#[cfg(feature = "x509-parser")]
+	#[cfg(any(feature = "crypto", feature = "x509-parser"))]
 	/// Invalid subject alternative name type
 	InvalidNameType,
 	/// Invalid ASN.1 string
 	InvalidAsn1String(InvalidAsn1String),
 	/// An IP address was provided as a byte array, but the byte array was an invalid length.
 	InvalidIpAddressOctetLength(usize),
+	/// The mask of a name constraint subnet has a zero bit before a one bit, so it can't be
+	/// written as a prefix length
+	InvalidSubnetMask(CidrSubnet),
+	/// An attribute in
+	/// [`CertificateParams::subject_directory_attributes`](crate::CertificateParams::subject_directory_attributes)
+	/// has no values, or a raw value that isn't a single DER element
+	InvalidSubjectDirectoryAttribute(Vec<u64>),
+	/// The public key is well-formed DER, but not a valid key for its algorithm,
+	/// like an EC point that isn't on the curve
+	InvalidPublicKey,
+	/// The public key doesn't belong to the private key
+	PublicKeyMismatch,
+	/// The signature doesn't verify with the public key
+	InvalidSignature,
+	/// The key pair failed [`KeyPair::self_test()`](crate::KeyPair::self_test), for the given
+	/// reason
+	SelfTestFailed(String),
+	/// The EC public key specifies its curve with explicit domain parameters that don't
+	/// match any of the supported named curves
+	ExplicitEcParametersUnsupported,
 	/// There is no support for generating
 	/// keys for the given algorithm
 	KeyGenerationUnavailable,
-	#[cfg(feature = "x509-parser")]
+	/// The private key is encrypted with an unsupported scheme
+	UnsupportedKeyEncryption,
+	/// The private key couldn't be decrypted with the given password
+	IncorrectPassword,
+	/// The key pair can't be serialized in the requested format, like SEC1 for a key that
+	/// isn't an EC key
+	KeyFormatUnavailable,
+	#[cfg(feature = "jwk")]
+	/// A JSON Web Key lacks a member required for its key type, like the private key `d`
+	MissingJwkMember(String),
+	#[cfg(any(feature = "crypto", feature = "x509-parser"))]
 	/// Unsupported extension requested in CSR
 	UnsupportedExtension,
 	/// The requested signature algorithm is not supported
 	UnsupportedSignatureAlgorithm,
+	/// The signature algorithm doesn't match the one in the to-be-signed structure
+	SignatureAlgorithmMismatch,
+	/// The private key is a key for another algorithm than the requested one, like a P-384 key
+	/// for [`PKCS_ECDSA_P256_SHA256`](crate::PKCS_ECDSA_P256_SHA256)
+	KeyAlgorithmMismatch {
+		/// The requested algorithm
+		expected: &'static SignatureAlgorithm,
+		/// The algorithm that the key was detected as
+		found: &'static SignatureAlgorithm,
+	},
 	/// Unspecified `ring` error
 	RingUnspecified,
 	/// The `ring` library rejected the key upon loading
@@ -37,20 +94,135 @@ pub enum Error {
 	#[cfg(feature = "pem")]
 	/// Error from the pem crate
 	PemError(String),
+	#[cfg(feature = "pem")]
+	/// The PEM input contains no private key
+	MissingPrivateKey,
 	/// Error generated by a remote key operation
 	RemoteKeyError,
 	/// Unsupported field when generating a CSR
 	UnsupportedInCsr,
+	/// Parameter that requires an extension when generating a version 1 certificate
+	UnsupportedInV1Certificate,
+	/// An override in [`CertificateParams::extension_criticality`](crate::CertificateParams::extension_criticality)
+	/// violates the criticality that RFC 5280 requires for the extension
+	NonconformingCriticality(ExtensionId),
+	/// The signed certificate timestamps can't be written, for the given reason
+	InvalidSignedCertificateTimestamps(String),
+	/// The validity period of a certificate exceeds the maximum of its issuer
+	ValidityTooLong,
+	/// A name of a certificate violates the name constraints of its issuer, see
+	/// [`IssuanceValidation::Strict`](crate::IssuanceValidation::Strict)
+	NameConstraintViolation(String),
 	/// Invalid certificate revocation list (CRL) next update.
 	InvalidCrlNextUpdate,
 	/// CRL issuer specifies Key Usages that don't include cRLSign.
 	IssuerNotCrlSigner,
+	/// A serial number appears more than once in the revoked certificates of a CRL
+	DuplicateRevokedSerialNumber(SerialNumber),
+	/// A certificate was revoked after the thisUpdate time of the CRL revoking it
+	RevocationAfterThisUpdate(SerialNumber),
+	/// Sharded CRLs were requested without any shards
+	MissingCrlShards,
+	/// The revocation of a certificate belongs to another CRL shard than the one of the
+	/// distribution point it was issued with
+	CrlShardMismatch(SerialNumber),
 	#[cfg(not(feature = "crypto"))]
 	/// Missing serial number
 	MissingSerialNumber,
 	/// X509 parsing error
-	#[cfg(feature = "x509-parser")]
 	X509(String),
+	/// The certificates of a [`CertifiedChain`](crate::CertifiedChain) aren't linked together
+	InvalidChain(String),
+	/// The input exceeds one of the given [`ParseLimits`](crate::ParseLimits)
+	ParseLimitExceeded(ParseLimit),
+	/// The openssl configuration is malformed or uses an unsupported directive
+	#[cfg(feature = "openssl_conf")]
+	InvalidOpensslConf(String),
+}
+
+impl Error {
+	/// The category of the error, for handling errors without matching on every variant
+	///
+	/// Every variant belongs to exactly one category, which stays the same across minor
+	/// versions.
+	pub fn kind(&self) -> ErrorKind {
+		use self::Error::*;
+		match self {
+			CouldNotParseCertificate
+			| CouldNotParseCertificationRequest
+			| CouldNotParseKeyPair
+			| ExpectedPrivateKeyFoundPublic
+			| ExpectedPrivateKeyFoundCertificate
+			| CouldNotParseCaState
+			| CouldNotParseTbs
+			| ParseLimitExceeded(_)
+			| X509(_) => ErrorKind::ParseFailed,
+			#[cfg(feature = "pem")]
+			PemError(_) | MissingPrivateKey => ErrorKind::ParseFailed,
+			#[cfg(feature = "openssl_conf")]
+			InvalidOpensslConf(_) => ErrorKind::ParseFailed,
+			DuplicateSerialNumber(_)
+			| CaStateOverflow
+			| InvalidAsn1String(_)
+			| InvalidIpAddressOctetLength(_)
+			| InvalidSubnetMask(_)
+			| InvalidSubjectDirectoryAttribute(_)
+			| SignatureAlgorithmMismatch
+			| UnsupportedInCsr
+			| UnsupportedInV1Certificate
+			| NonconformingCriticality(_)
+			| InvalidSignedCertificateTimestamps(_)
+			| ValidityTooLong
+			| NameConstraintViolation(_)
+			| InvalidCrlNextUpdate
+			| IssuerNotCrlSigner
+			| DuplicateRevokedSerialNumber(_)
+			| RevocationAfterThisUpdate(_)
+			| MissingCrlShards
+			| CrlShardMismatch(_)
+			| InvalidChain(_)
+			| InvalidSignature
+			| IncorrectPassword => ErrorKind::InvalidInput,
+			#[cfg(any(feature = "crypto", feature = "x509-parser"))]
+			InvalidCertificationRequestSignature | UnsupportedExtension => ErrorKind::InvalidInput,
+			#[cfg(any(feature = "crypto", feature = "x509-parser"))]
+			InvalidNameType => ErrorKind::InvalidInput,
+			#[cfg(not(feature = "crypto"))]
+			MissingSerialNumber => ErrorKind::InvalidInput,
+			#[cfg(feature = "jwk")]
+			MissingJwkMember(_) => ErrorKind::InvalidInput,
+			UnsupportedSignatureAlgorithm
+			| KeyGenerationUnavailable
+			| UnsupportedKeyEncryption
+			| KeyFormatUnavailable
+			| ExplicitEcParametersUnsupported => ErrorKind::UnsupportedAlgorithm,
+			InvalidPublicKey
+			| PublicKeyMismatch
+			| SelfTestFailed(_)
+			| KeyAlgorithmMismatch { .. }
+			| RingKeyRejected(_) => ErrorKind::KeyRejected,
+			RingUnspecified | RemoteKeyError => ErrorKind::SigningFailed,
+			Time => ErrorKind::Internal,
+		}
+	}
+}
+
+/// The category of an [`Error`], as returned by [`Error::kind()`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+	/// The parameters, or the certificates and CSRs they came from, are invalid
+	InvalidInput,
+	/// The signature or key algorithm isn't supported
+	UnsupportedAlgorithm,
+	/// The key material was rejected by the cryptographic backend
+	KeyRejected,
+	/// A cryptographic operation like signing failed, locally or on a remote key
+	SigningFailed,
+	/// The input couldn't be parsed
+	ParseFailed,
+	/// An unexpected failure within rcgen or its dependencies
+	Internal,
 }
 
 impl fmt::Display for Error {
@@ -64,25 +236,78 @@ impl fmt::Display for Error {
 				request"
 			)?,
 			CouldNotParseKeyPair => write!(f, "Could not parse key pair")?,
-			#[cfg(feature = "x509-parser")]
+			ExpectedPrivateKeyFoundPublic => {
+				write!(f, "Expected a private key, but found a public key")?
+			},
+			ExpectedPrivateKeyFoundCertificate => {
+				write!(f, "Expected a private key, but found a certificate")?
+			},
+			CouldNotParseCaState => write!(f, "Could not parse CA state")?,
+			CouldNotParseTbs => write!(f, "Could not parse to-be-signed structure")?,
+			DuplicateSerialNumber(serial) => {
+				write!(f, "Serial number {serial} has already been issued")?
+			},
+			CaStateOverflow => write!(f, "The CA state overflowed")?,
+			#[cfg(any(feature = "crypto", feature = "x509-parser"))]
 			InvalidCertificationRequestSignature => write!(f, "Invalid CSR signature")?,
-			#[cfg(feature = "x509-parser")]
+			#[cfg(any(feature = "crypto", feature = "x509-parser"))]
 			InvalidNameType => write!(f, "Invalid subject alternative name type")?,
 			InvalidAsn1String(e) => write!(f, "{e}")?,
 			InvalidIpAddressOctetLength(actual) => {
 				write!(f, "Invalid IP address octet length of {actual} bytes")?
 			},
+			InvalidSubnetMask(subnet) => {
+				let (addr, mask) = match subnet {
+					CidrSubnet::V4(addr, mask) => (IpAddr::from(*addr), IpAddr::from(*mask)),
+					CidrSubnet::V6(addr, mask) => (IpAddr::from(*addr), IpAddr::from(*mask)),
+				};
+				write!(f, "The mask {mask} of subnet {addr} isn't contiguous")?
+			},
+			InvalidSubjectDirectoryAttribute(oid) => {
+				let oid = oid.iter().map(u64::to_string).collect::<Vec<_>>();
+				write!(
+					f,
+					"The subject directory attribute {} has no values or a malformed raw value",
+					oid.join(".")
+				)?
+			},
+			InvalidPublicKey => write!(f, "The public key is not valid for its algorithm")?,
+			PublicKeyMismatch => write!(f, "The public key doesn't belong to the private key")?,
+			SelfTestFailed(reason) => write!(f, "The key pair failed its self-test: {reason}")?,
+			InvalidSignature => write!(f, "The signature doesn't verify with the public key")?,
+			ExplicitEcParametersUnsupported => write!(
+				f,
+				"The explicit EC domain parameters don't match a supported named curve"
+			)?,
 			KeyGenerationUnavailable => write!(
 				f,
 				"There is no support for generating \
 				keys for the given algorithm"
 			)?,
+			UnsupportedKeyEncryption => {
+				write!(f, "The private key is encrypted with an unsupported scheme")?
+			},
+			IncorrectPassword => write!(f, "Incorrect password for the private key")?,
+			KeyFormatUnavailable => write!(
+				f,
+				"The key pair can't be serialized in the requested format"
+			)?,
+			#[cfg(feature = "jwk")]
+			MissingJwkMember(member) => write!(f, "The JWK has no \"{member}\" member")?,
 			UnsupportedSignatureAlgorithm => write!(
 				f,
 				"The requested signature algorithm \
 				is not supported"
 			)?,
-			#[cfg(feature = "x509-parser")]
+			SignatureAlgorithmMismatch => write!(
+				f,
+				"The signature algorithm doesn't match the to-be-signed structure"
+			)?,
+			KeyAlgorithmMismatch { expected, found } => write!(
+				f,
+				"Expected a key for {expected}, but found one for {found}"
+			)?,
+			#[cfg(any(feature = "crypto", feature = "x509-parser"))]
 			UnsupportedExtension => write!(f, "Unsupported extension requested in CSR")?,
 			RingUnspecified => write!(f, "Unspecified ring error")?,
 			RingKeyRejected(e) => write!(f, "Key rejected by ring: {e}")?,
@@ -91,16 +316,53 @@ impl fmt::Display for Error {
 			RemoteKeyError => write!(f, "Remote key error")?,
 			#[cfg(feature = "pem")]
 			PemError(e) => write!(f, "PEM error: {e}")?,
+			#[cfg(feature = "pem")]
+			MissingPrivateKey => write!(f, "The PEM input contains no private key")?,
 			UnsupportedInCsr => write!(f, "Certificate parameter unsupported in CSR")?,
+			UnsupportedInV1Certificate => write!(
+				f,
+				"Certificate parameter requires an extension, which version 1 certificates lack"
+			)?,
+			NonconformingCriticality(id) => write!(
+				f,
+				"The criticality of the {} extension violates RFC 5280",
+				id.name().unwrap_or("custom")
+			)?,
+			InvalidSignedCertificateTimestamps(reason) => {
+				write!(f, "Invalid signed certificate timestamps: {reason}")?
+			},
+			ValidityTooLong => write!(
+				f,
+				"The certificate validity period exceeds the maximum of its issuer"
+			)?,
+			NameConstraintViolation(e) => {
+				write!(f, "Name constraints of the issuer violated: {e}")?
+			},
 			InvalidCrlNextUpdate => write!(f, "Invalid CRL next update parameter")?,
 			IssuerNotCrlSigner => write!(
 				f,
 				"CRL issuer must specify no key usage, or key usage including cRLSign"
 			)?,
+			DuplicateRevokedSerialNumber(serial) => write!(
+				f,
+				"Serial number {serial} is revoked more than once in the CRL"
+			)?,
+			RevocationAfterThisUpdate(serial) => write!(
+				f,
+				"Serial number {serial} is revoked after the thisUpdate time of the CRL"
+			)?,
+			MissingCrlShards => write!(f, "At least one CRL shard is required")?,
+			CrlShardMismatch(serial) => write!(
+				f,
+				"Serial number {serial} was issued with the distribution point of another CRL shard"
+			)?,
 			#[cfg(not(feature = "crypto"))]
 			MissingSerialNumber => write!(f, "A serial number must be specified")?,
-			#[cfg(feature = "x509-parser")]
 			X509(e) => write!(f, "X.509 parsing error: {e}")?,
+			InvalidChain(e) => write!(f, "Invalid certificate chain: {e}")?,
+			ParseLimitExceeded(limit) => write!(f, "The input exceeds the limit on its {limit}")?,
+			#[cfg(feature = "openssl_conf")]
+			InvalidOpensslConf(e) => write!(f, "Invalid openssl configuration: {e}")?,
 		};
 		Ok(())
 	}
@@ -122,6 +384,8 @@ pub enum InvalidAsn1String {
 	TeletexString(String),
 	/// Invalid BmpString type
 	BmpString(String),
+	/// Invalid DNS name, see [`DnsName`](crate::string::DnsName)
+	DnsName(String),
 }
 
 impl fmt::Display for InvalidAsn1String {
@@ -133,6 +397,7 @@ impl fmt::Display for InvalidAsn1String {
 			BmpString(s) => write!(f, "Invalid BMPString: '{s}'")?,
 			UniversalString(s) => write!(f, "Invalid UniversalString: '{s}'")?,
 			TeletexString(s) => write!(f, "Invalid TeletexString: '{s}'")?,
+			DnsName(s) => write!(f, "Invalid DNS name: '{s}'")?,
 		};
 		Ok(())
 	}
@@ -146,3 +411,76 @@ impl fmt::Display for InvalidAsn1String {
 pub(crate) trait ExternalError<T>: Sized {
 	fn _err(self) -> Result<T, Error>;
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn kinds() {
+		for (error, kind) in [
+			(
+				Error::CouldNotParseCertificationRequest,
+				ErrorKind::ParseFailed,
+			),
+			(Error::CouldNotParseKeyPair, ErrorKind::ParseFailed),
+			(Error::ExpectedPrivateKeyFoundPublic, ErrorKind::ParseFailed),
+			(Error::X509(String::new()), ErrorKind::ParseFailed),
+			(
+				Error::InvalidAsn1String(InvalidAsn1String::Ia5String("é".into())),
+				ErrorKind::InvalidInput,
+			),
+			(
+				Error::InvalidIpAddressOctetLength(5),
+				ErrorKind::InvalidInput,
+			),
+			(
+				Error::InvalidSubnetMask(CidrSubnet::V4([10, 0, 0, 0], [255, 0, 255, 0])),
+				ErrorKind::InvalidInput,
+			),
+			(
+				Error::NameConstraintViolation(String::new()),
+				ErrorKind::InvalidInput,
+			),
+			(Error::ValidityTooLong, ErrorKind::InvalidInput),
+			(
+				Error::InvalidSignedCertificateTimestamps(String::new()),
+				ErrorKind::InvalidInput,
+			),
+			(
+				Error::NonconformingCriticality(ExtensionId::NameConstraints),
+				ErrorKind::InvalidInput,
+			),
+			(
+				Error::UnsupportedSignatureAlgorithm,
+				ErrorKind::UnsupportedAlgorithm,
+			),
+			(
+				Error::KeyGenerationUnavailable,
+				ErrorKind::UnsupportedAlgorithm,
+			),
+			(
+				Error::RingKeyRejected(String::new()),
+				ErrorKind::KeyRejected,
+			),
+			(Error::InvalidPublicKey, ErrorKind::KeyRejected),
+			(Error::SelfTestFailed(String::new()), ErrorKind::KeyRejected),
+			(Error::InvalidSignature, ErrorKind::InvalidInput),
+			(Error::RingUnspecified, ErrorKind::SigningFailed),
+			(Error::RemoteKeyError, ErrorKind::SigningFailed),
+			(Error::Time, ErrorKind::Internal),
+		] {
+			assert_eq!(error.kind(), kind, "{error:?}");
+		}
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn kinds_x509_parser() {
+		assert_eq!(Error::InvalidNameType.kind(), ErrorKind::InvalidInput);
+		assert_eq!(
+			Error::InvalidCertificationRequestSignature.kind(),
+			ErrorKind::InvalidInput
+		);
+	}
+}
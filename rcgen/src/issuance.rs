@@ -0,0 +1,430 @@
+use std::borrow::Cow;
+
+use time::Duration;
+
+use crate::{
+	oid, AccessMethod, CertificateParams, CrlDistributionPoint, CustomExtension, Error,
+	NameConstraints, PolicyInformation,
+};
+
+/// Parameters that an [`Issuer`](crate::Issuer) adds to every certificate it signs
+///
+/// Attach them with [`Issuer::with_defaults()`](crate::Issuer::with_defaults). They are
+/// merged into the [`CertificateParams`] of each certificate as follows:
+///
+/// - [`crl_distribution_points`](Self::crl_distribution_points),
+///   [`authority_info_access`](Self::authority_info_access) and
+///   [`certificate_policies`](Self::certificate_policies) are used if the parameters don't
+///   specify any, neither in the corresponding field nor as a custom extension. The
+///   parameters win.
+/// - [`custom_extensions`](Self::custom_extensions) are added, except for those whose OID
+///   the parameters already have, either as a custom extension or through a field like
+///   [`authority_info_access`](CertificateParams::authority_info_access). The parameters
+///   win.
+/// - [`max_validity`](Self::max_validity) limits the validity period of the parameters.
+///   The defaults win.
+/// - [`validation`](Self::validation) decides whether the names of the parameters are
+///   checked against the name constraints of the issuer.
+///
+/// Certificates whose parameters have
+/// [`use_issuer_defaults`](CertificateParams::use_issuer_defaults) set to `false` get
+/// neither the distribution points nor the extensions, but are still subject to the maximum
+/// validity and the validation, as they are limits rather than defaults.
+///
+/// ```
+/// # use rcgen::{CrlDistributionPoint, IssuanceDefaults, MaxValidity};
+/// let mut defaults = IssuanceDefaults::default();
+/// defaults.crl_distribution_points = vec![CrlDistributionPoint {
+///     uris: vec!["http://crl.example.com/intermediate.crl".to_string()],
+/// }];
+/// defaults.max_validity = Some(MaxValidity::Reject(time::Duration::days(398)));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct IssuanceDefaults {
+	/// CRL distribution points for certificates that don't specify their own
+	pub crl_distribution_points: Vec<CrlDistributionPoint>,
+	/// Authority information access, like the OCSP responder of the issuer, for certificates
+	/// that don't specify their own
+	pub authority_info_access: Vec<(AccessMethod, String)>,
+	/// Certificate policies for certificates that don't specify their own
+	///
+	/// The extension is marked as critical if
+	/// [`certificate_policies_critical`](CertificateParams::certificate_policies_critical)
+	/// is set on the parameters.
+	pub certificate_policies: Vec<PolicyInformation>,
+	/// Extensions added to every certificate that don't have a dedicated field
+	pub custom_extensions: Vec<CustomExtension>,
+	/// The longest validity period a certificate may have
+	pub max_validity: Option<MaxValidity>,
+	/// The checks done on the parameters of every certificate
+	pub validation: IssuanceValidation,
+}
+
+/// The longest validity period of the certificates an issuer signs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MaxValidity {
+	/// Fails with [`Error::ValidityTooLong`] for certificates valid for longer
+	Reject(Duration),
+	/// Moves `not_after` earlier for certificates valid for longer
+	Clamp(Duration),
+}
+
+/// The checks an [`Issuer`](crate::Issuer) does on the parameters of the certificates it signs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IssuanceValidation {
+	/// Only enforces the [`max_validity`](IssuanceDefaults::max_validity)
+	#[default]
+	Standard,
+	/// Like [`IssuanceValidation::Standard`], but also rejects certificates with names that
+	/// violate the [`name_constraints`](CertificateParams::name_constraints) of the issuer
+	///
+	/// The subject, and the DNS, email, URI and IP address subject alternative names are
+	/// checked as per [RFC 5280 section 4.2.1.10], and the first violation is returned as
+	/// [`Error::NameConstraintViolation`]. As is commonly done, DNS subtrees with a leading
+	/// period only contain the subdomains, like the email and URI subtrees do.
+	///
+	/// [RFC 5280 section 4.2.1.10]: https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.10
+	Strict,
+}
+
+impl IssuanceDefaults {
+	/// Returns `params` with the defaults merged in, after checking them against the name
+	/// constraints of the issuer if the validation is strict
+	pub(crate) fn apply<'a>(
+		&self,
+		params: &'a CertificateParams,
+		name_constraints: Option<&NameConstraints>,
+	) -> Result<Cow<'a, CertificateParams>, Error> {
+		let mut params = Cow::Borrowed(params);
+
+		if params.use_issuer_defaults {
+			if !self.crl_distribution_points.is_empty()
+				&& !has_extension(&params, oid::CRL_DISTRIBUTION_POINTS)
+			{
+				params.to_mut().crl_distribution_points = self.crl_distribution_points.clone();
+			}
+
+			if !self.authority_info_access.is_empty()
+				&& !has_extension(&params, oid::AUTHORITY_INFO_ACCESS)
+			{
+				params.to_mut().authority_info_access = self.authority_info_access.clone();
+			}
+
+			if !self.certificate_policies.is_empty()
+				&& !has_extension(&params, oid::CERTIFICATE_POLICIES)
+			{
+				params.to_mut().certificate_policies = self.certificate_policies.clone();
+			}
+
+			for extension in &self.custom_extensions {
+				let oid = extension.oid_components().collect::<Vec<_>>();
+				if !has_extension(&params, &oid) {
+					params.to_mut().custom_extensions.push(extension.clone());
+				}
+			}
+		}
+
+		match self.max_validity {
+			Some(MaxValidity::Reject(max)) if params.not_after - params.not_before > max => {
+				return Err(Error::ValidityTooLong);
+			},
+			Some(MaxValidity::Clamp(max)) if params.not_after - params.not_before > max => {
+				let not_after = params.not_before + max;
+				params.to_mut().not_after = not_after;
+			},
+			_ => {},
+		}
+
+		if let (IssuanceValidation::Strict, Some(constraints)) = (self.validation, name_constraints)
+		{
+			crate::name_constraints::check(constraints, &params)?;
+		}
+
+		Ok(params)
+	}
+}
+
+/// Whether `params` have the extension with the given OID, either as a custom extension or
+/// through one of the fields that issuer defaults exist for
+fn has_extension(params: &CertificateParams, oid: &[u64]) -> bool {
+	let typed = match oid {
+		oid::CRL_DISTRIBUTION_POINTS => !params.crl_distribution_points.is_empty(),
+		oid::AUTHORITY_INFO_ACCESS => !params.authority_info_access.is_empty(),
+		oid::CERTIFICATE_POLICIES => !params.certificate_policies.is_empty(),
+		_ => false,
+	};
+	typed
+		|| params
+			.custom_extensions
+			.iter()
+			.any(|own| own.oid_components().eq(oid.iter().copied()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::date_time_ymd;
+
+	fn dp(uri: &str) -> CrlDistributionPoint {
+		CrlDistributionPoint {
+			uris: vec![uri.to_string()],
+		}
+	}
+
+	fn defaults() -> IssuanceDefaults {
+		IssuanceDefaults {
+			crl_distribution_points: vec![dp("http://crl.example.com/ca.crl")],
+			authority_info_access: vec![(AccessMethod::Ocsp, "http://ocsp.example.com".into())],
+			certificate_policies: vec![PolicyInformation {
+				oid: vec![2, 23, 140, 1, 2, 1],
+				qualifiers: Vec::new(),
+			}],
+			custom_extensions: vec![
+				CustomExtension::from_oid_content(&[1, 3, 6, 1, 4, 1, 32473, 1], vec![0x05, 0x00]),
+				CustomExtension::from_oid_content(&[1, 3, 6, 1, 4, 1, 32473, 2], vec![0x05, 0x00]),
+			],
+			max_validity: None,
+			validation: IssuanceValidation::Standard,
+		}
+	}
+
+	#[test]
+	fn params_win_for_extensions() {
+		let defaults = defaults();
+
+		let params = CertificateParams::default();
+		let merged = defaults.apply(&params, None).unwrap();
+		assert_eq!(
+			merged.crl_distribution_points,
+			defaults.crl_distribution_points
+		);
+		assert_eq!(merged.custom_extensions, defaults.custom_extensions);
+
+		let own = CustomExtension::from_oid_content(&[1, 3, 6, 1, 4, 1, 32473, 2], vec![0x01]);
+		let params = CertificateParams {
+			crl_distribution_points: vec![dp("http://crl.example.com/own.crl")],
+			custom_extensions: vec![own.clone()],
+			..CertificateParams::default()
+		};
+		let merged = defaults.apply(&params, None).unwrap();
+		assert_eq!(
+			merged.crl_distribution_points,
+			params.crl_distribution_points
+		);
+		assert_eq!(
+			merged.custom_extensions,
+			[own, defaults.custom_extensions[0].clone()]
+		);
+	}
+
+	#[test]
+	fn params_win_for_typed_extensions() {
+		let mut defaults = defaults();
+		let params = CertificateParams::default();
+		let merged = defaults.apply(&params, None).unwrap();
+		assert_eq!(merged.authority_info_access, defaults.authority_info_access);
+		assert_eq!(merged.certificate_policies, defaults.certificate_policies);
+
+		// The fields of the parameters win over both the typed and the custom defaults
+		defaults.custom_extensions = vec![
+			CustomExtension::from_oid_content(oid::AUTHORITY_INFO_ACCESS, vec![0x30, 0x00]),
+			CustomExtension::from_oid_content(oid::CERTIFICATE_POLICIES, vec![0x30, 0x00]),
+		];
+		let params = CertificateParams {
+			authority_info_access: vec![(
+				AccessMethod::CaIssuers,
+				"http://example.com/ca.der".into(),
+			)],
+			certificate_policies: vec![PolicyInformation::any_policy(Vec::new())],
+			..CertificateParams::default()
+		};
+		let merged = defaults.apply(&params, None).unwrap();
+		assert_eq!(merged.authority_info_access, params.authority_info_access);
+		assert_eq!(merged.certificate_policies, params.certificate_policies);
+		assert!(merged.custom_extensions.is_empty());
+
+		// And so do their custom extensions
+		let params = CertificateParams {
+			custom_extensions: defaults.custom_extensions.clone(),
+			..CertificateParams::default()
+		};
+		let merged = defaults.apply(&params, None).unwrap();
+		assert!(merged.authority_info_access.is_empty());
+		assert!(merged.certificate_policies.is_empty());
+		assert_eq!(merged.custom_extensions, params.custom_extensions);
+	}
+
+	#[test]
+	fn opt_out() {
+		let mut defaults = defaults();
+		defaults.max_validity = Some(MaxValidity::Reject(Duration::days(1)));
+		let mut params = CertificateParams {
+			use_issuer_defaults: false,
+			..CertificateParams::default()
+		};
+		assert_eq!(
+			defaults.apply(&params, None).unwrap_err(),
+			Error::ValidityTooLong
+		);
+
+		params.not_after = params.not_before + Duration::hours(1);
+		assert!(matches!(
+			defaults.apply(&params, None).unwrap(),
+			Cow::Borrowed(merged) if *merged == params
+		));
+	}
+
+	#[test]
+	fn max_validity() {
+		let params = CertificateParams {
+			not_before: date_time_ymd(2025, 1, 1),
+			not_after: date_time_ymd(2026, 1, 1),
+			..CertificateParams::default()
+		};
+
+		let mut defaults = IssuanceDefaults {
+			max_validity: Some(MaxValidity::Reject(Duration::days(365))),
+			..IssuanceDefaults::default()
+		};
+		assert!(matches!(
+			defaults.apply(&params, None).unwrap(),
+			Cow::Borrowed(_)
+		));
+		defaults.max_validity = Some(MaxValidity::Reject(Duration::days(90)));
+		assert_eq!(
+			defaults.apply(&params, None).unwrap_err(),
+			Error::ValidityTooLong
+		);
+
+		defaults.max_validity = Some(MaxValidity::Clamp(Duration::days(90)));
+		let merged = defaults.apply(&params, None).unwrap();
+		assert_eq!(merged.not_before, params.not_before);
+		assert_eq!(merged.not_after, date_time_ymd(2025, 4, 1));
+	}
+
+	#[cfg(feature = "crypto")]
+	#[test]
+	fn applied_when_signing() {
+		use crate::{test_keys, Issuer};
+
+		let issuer = Issuer::new(CertificateParams::default(), test_keys::ecdsa_p256())
+			.with_defaults(defaults());
+		let key = test_keys::ed25519();
+		let cert = CertificateParams::default()
+			.signed_by(&key, &issuer)
+			.unwrap();
+		let names = cert
+			.extensions()
+			.unwrap()
+			.into_iter()
+			.map(|ext| {
+				ext.oid_components()
+					.map(|c| c.to_string())
+					.collect::<Vec<_>>()
+					.join(".")
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(
+			names,
+			[
+				"2.5.29.31",
+				"1.3.6.1.5.5.7.1.1",
+				"2.5.29.32",
+				"1.3.6.1.4.1.32473.1",
+				"1.3.6.1.4.1.32473.2"
+			]
+		);
+
+		let mut defaults = defaults();
+		defaults.custom_extensions.clear();
+		let issuer = issuer.with_defaults(defaults);
+		let cert = CertificateParams::default()
+			.signed_by(&key, &issuer)
+			.unwrap();
+		assert_eq!(cert.extensions().unwrap().len(), 3);
+
+		// Self-signed certificates don't have an issuer with defaults
+		let cert = CertificateParams::default().self_signed(&key).unwrap();
+		assert_eq!(cert.extensions().unwrap().len(), 0);
+	}
+
+	#[cfg(feature = "crypto")]
+	#[test]
+	fn strict_validation() {
+		use crate::{test_keys, GeneralSubtree, Issuer};
+
+		let ca_params = CertificateParams {
+			name_constraints: Some(NameConstraints {
+				permitted_subtrees: vec![GeneralSubtree::DnsName(".corp.example".to_string())],
+				excluded_subtrees: Vec::new(),
+			}),
+			..CertificateParams::default()
+		};
+		let issuer = Issuer::new(ca_params, test_keys::ecdsa_p256());
+		let key = test_keys::ed25519();
+		let evil = CertificateParams::new(vec!["evil.com".to_string()]).unwrap();
+		let host = CertificateParams::new(vec!["host.corp.example".to_string()]).unwrap();
+
+		// Standard validation signs whatever it is given
+		assert!(evil.signed_by(&key, &issuer).is_ok());
+
+		let issuer = issuer.with_defaults(IssuanceDefaults {
+			validation: IssuanceValidation::Strict,
+			..IssuanceDefaults::default()
+		});
+		assert!(matches!(
+			evil.signed_by(&key, &issuer),
+			Err(Error::NameConstraintViolation(_))
+		));
+		assert!(host.signed_by(&key, &issuer).is_ok());
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn unsupported_name_constraints() {
+		use crate::{test_keys, CustomExtension, Issuer};
+
+		// Permits the DNS names under .corp.example and the registered ID 1.2.3, which
+		// can't be represented as a `GeneralSubtree`
+		let name_constraints = [
+			&[0x30, 0x19, 0xa0, 0x17, 0x30, 0x0f, 0x82, 0x0d][..],
+			b".corp.example",
+			&[0x30, 0x04, 0x88, 0x02, 0x2a, 0x03],
+		]
+		.concat();
+		let ca_params = CertificateParams {
+			custom_extensions: vec![CustomExtension::from_oid_content(
+				&[2, 5, 29, 30],
+				name_constraints,
+			)],
+			..CertificateParams::default()
+		};
+		let ca_key = test_keys::ecdsa_p256();
+		let ca_cert = ca_params.self_signed(&ca_key).unwrap();
+
+		assert!(matches!(
+			CertificateParams::from_ca_cert_der(ca_cert.der()),
+			Err(Error::X509(_))
+		));
+		let issuer = Issuer::from_ca_cert_der(ca_cert.der(), ca_key).unwrap();
+		assert_eq!(
+			issuer.unsupported_name_constraints(),
+			["RegisteredID(1.2.3)"]
+		);
+		assert_eq!(
+			issuer.name_constraints().unwrap().permitted_subtrees,
+			[crate::GeneralSubtree::DnsName(".corp.example".to_string())]
+		);
+
+		let key = test_keys::ed25519();
+		let host = CertificateParams::new(vec!["host.corp.example".to_string()]).unwrap();
+		assert!(host.signed_by(&key, &issuer).is_ok());
+
+		let issuer = issuer.with_defaults(IssuanceDefaults {
+			validation: IssuanceValidation::Strict,
+			..IssuanceDefaults::default()
+		});
+		assert!(matches!(host.signed_by(&key, &issuer), Err(Error::X509(_))));
+	}
+}
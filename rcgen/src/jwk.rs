@@ -0,0 +1,560 @@
+//! Key pairs as JSON Web Keys of [RFC 7517](https://www.rfc-editor.org/rfc/rfc7517)
+
+use std::collections::HashMap;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use pki_types::PrivatePkcs8KeyDer;
+use yasna::models::ObjectIdentifier;
+use yasna::Tag;
+
+use crate::key_pair::SecretBytes;
+use crate::ring_like::digest::{self, SHA256};
+use crate::sign_algo::algo::*;
+use crate::{oid, Error, KeyPair, PublicKeyData, SignatureAlgorithm};
+
+impl KeyPair {
+	/// Parses the key pair from a private JSON Web Key
+	///
+	/// Supports the key types of [RFC 7518](https://www.rfc-editor.org/rfc/rfc7518#section-6),
+	/// [RFC 8037](https://www.rfc-editor.org/rfc/rfc8037), and
+	/// [RFC 8812](https://www.rfc-editor.org/rfc/rfc8812#section-3.1): `EC` keys with the
+	/// `P-256`, `P-384`, `P-521`, or `secp256k1` curves, `OKP` keys with the `Ed25519` curve,
+	/// and `RSA` keys with all CRT parameters. The signature algorithm follows from the curve for EC keys, and from
+	/// the `alg` member for RSA keys, defaulting to `RS256`. Other members, like `kid`, are
+	/// ignored.
+	///
+	/// Fails with [`Error::MissingJwkMember`] if a member of the private key is missing, like
+	/// for a public JWK, and with [`Error::UnsupportedSignatureAlgorithm`] for other key types
+	/// and curves.
+	pub fn from_jwk(json: &str) -> Result<Self, Error> {
+		let jwk = Jwk::parse(json)?;
+		let (pkcs8, alg) = match jwk.member("kty")? {
+			"EC" => {
+				let (curve, alg) = match jwk.member("crv")? {
+					"P-256" => (oid::EC_SECP_256_R1, &PKCS_ECDSA_P256_SHA256),
+					"P-384" => (oid::EC_SECP_384_R1, &PKCS_ECDSA_P384_SHA384),
+					"P-521" => (oid::EC_SECP_521_R1, &PKCS_ECDSA_P521_SHA512),
+					"secp256k1" => (oid::EC_SECP_256_K1, &PKCS_ECDSA_K256_SHA256),
+					_ => return Err(Error::UnsupportedSignatureAlgorithm),
+				};
+				let mut public_key = vec![0x04];
+				public_key.extend(jwk.bytes("x")?);
+				public_key.extend(jwk.bytes("y")?);
+				let private_key = jwk.bytes("d")?;
+				let ec_private_key = yasna::construct_der(|writer| {
+					writer.write_sequence(|writer| {
+						writer.next().write_u8(1);
+						writer.next().write_bytes(&private_key);
+						writer.next().write_tagged(Tag::context(1), |writer| {
+							writer.write_bitvec_bytes(&public_key, public_key.len() * 8)
+						});
+					})
+				});
+				let pkcs8 = write_pkcs8(&ec_private_key, |writer| {
+					writer
+						.next()
+						.write_oid(&ObjectIdentifier::from_slice(oid::EC_PUBLIC_KEY));
+					writer
+						.next()
+						.write_oid(&ObjectIdentifier::from_slice(curve));
+				});
+				(pkcs8, alg)
+			},
+			"OKP" => {
+				if jwk.member("crv")? != "Ed25519" {
+					return Err(Error::UnsupportedSignatureAlgorithm);
+				}
+				let seed = jwk.bytes("d")?;
+				let public_key = jwk.bytes("x")?;
+				return Self::from_raw_ed25519_seed_and_public_key(
+					seed.as_slice()
+						.try_into()
+						.map_err(|_| Error::CouldNotParseKeyPair)?,
+					public_key
+						.as_slice()
+						.try_into()
+						.map_err(|_| Error::CouldNotParseKeyPair)?,
+				);
+			},
+			"RSA" => {
+				let alg = match jwk.0.get("alg").map(String::as_str) {
+					None | Some("RS256") => &PKCS_RSA_SHA256,
+					Some("RS384") => &PKCS_RSA_SHA384,
+					Some("RS512") => &PKCS_RSA_SHA512,
+					Some("PS256") => &PKCS_RSA_PSS_SHA256,
+					Some("PS384") => &PKCS_RSA_PSS_SHA384,
+					Some("PS512") => &PKCS_RSA_PSS_SHA512,
+					Some(_) => return Err(Error::UnsupportedSignatureAlgorithm),
+				};
+				// The RSAPrivateKey of RFC 8017
+				let mut integers = Vec::new();
+				for member in ["n", "e", "d", "p", "q", "dp", "dq", "qi"] {
+					integers.push(jwk.bytes(member)?);
+				}
+				let rsa_private_key = yasna::construct_der(|writer| {
+					writer.write_sequence(|writer| {
+						writer.next().write_u8(0);
+						for integer in &integers {
+							writer.next().write_bigint_bytes(integer, true);
+						}
+					})
+				});
+				let pkcs8 = write_pkcs8(&rsa_private_key, |writer| {
+					writer
+						.next()
+						.write_oid(&ObjectIdentifier::from_slice(oid::RSA_ENCRYPTION));
+					writer.next().write_null();
+				});
+				(pkcs8, alg)
+			},
+			_ => return Err(Error::UnsupportedSignatureAlgorithm),
+		};
+
+		// The backends check that the public key matches the private key
+		Self::from_pkcs8_der_and_sign_algo(&PrivatePkcs8KeyDer::from(&pkcs8[..]), alg)
+	}
+
+	/// Returns the public key as a JSON Web Key
+	///
+	/// The JWK only has the members required for the key type: `kty`, `crv`, `x`, and `y`
+	/// for ECDSA keys, `kty`, `crv`, and `x` for Ed25519 keys, and `kty`, `n`, and `e` for RSA
+	/// keys. The members are in lexicographic order without whitespace, so the JWK is also the
+	/// input of its [RFC 7638](https://www.rfc-editor.org/rfc/rfc7638) thumbprint. Fails with
+	/// [`Error::KeyFormatUnavailable`] for other key types.
+	pub fn public_key_jwk(&self) -> Result<String, Error> {
+		public_key_jwk(self.algorithm(), self.der_bytes())
+	}
+
+	/// Returns the [RFC 7638](https://www.rfc-editor.org/rfc/rfc7638) SHA-256 thumbprint of
+	/// the public key, encoded as base64url without padding
+	///
+	/// This is the thumbprint used in ACME key authorizations of
+	/// [RFC 8555](https://www.rfc-editor.org/rfc/rfc8555#section-8.1).
+	pub fn public_key_jwk_thumbprint(&self) -> Result<String, Error> {
+		let jwk = self.public_key_jwk()?;
+		Ok(URL_SAFE_NO_PAD.encode(digest::digest(&SHA256, jwk.as_bytes())))
+	}
+}
+
+/// Encodes the raw public key of the given algorithm as a JWK
+fn public_key_jwk(alg: &SignatureAlgorithm, public_key: &[u8]) -> Result<String, Error> {
+	let jwk = match alg.key_alg_oid() {
+		oid::EC_PUBLIC_KEY => {
+			let crv = match alg.curve_oid() {
+				Some(oid::EC_SECP_256_R1) => "P-256",
+				Some(oid::EC_SECP_384_R1) => "P-384",
+				Some(oid::EC_SECP_521_R1) => "P-521",
+				Some(oid::EC_SECP_256_K1) => "secp256k1",
+				_ => return Err(Error::KeyFormatUnavailable),
+			};
+			// The uncompressed point, with both coordinates padded to the field size
+			let Some((0x04, coordinates)) = public_key.split_first() else {
+				return Err(Error::InvalidPublicKey);
+			};
+			let (x, y) = coordinates.split_at(coordinates.len() / 2);
+			format!(
+				r#"{{"crv":"{crv}","kty":"EC","x":"{}","y":"{}"}}"#,
+				URL_SAFE_NO_PAD.encode(x),
+				URL_SAFE_NO_PAD.encode(y)
+			)
+		},
+		oid::ED25519 => format!(
+			r#"{{"crv":"Ed25519","kty":"OKP","x":"{}"}}"#,
+			URL_SAFE_NO_PAD.encode(public_key)
+		),
+		oid::RSA_ENCRYPTION | oid::RSASSA_PSS => {
+			// The RSAPublicKey of RFC 8017
+			let (n, e) = crate::asn1::parse_der(public_key, |reader| {
+				reader.read_sequence(|reader| {
+					let n = reader.next().read_bigint_bytes()?;
+					let e = reader.next().read_bigint_bytes()?;
+					Ok((n, e))
+				})
+			})
+			.map_err(|_| Error::InvalidPublicKey)?;
+			format!(
+				r#"{{"e":"{}","kty":"RSA","n":"{}"}}"#,
+				URL_SAFE_NO_PAD.encode(unsigned(&e.0)),
+				URL_SAFE_NO_PAD.encode(unsigned(&n.0))
+			)
+		},
+		_ => return Err(Error::KeyFormatUnavailable),
+	};
+	Ok(jwk)
+}
+
+/// Strips the leading zero bytes of a positive DER integer, as JWKs encode RSA parameters
+/// as unsigned big-endian integers
+fn unsigned(bytes: &[u8]) -> &[u8] {
+	let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+	&bytes[zeros..]
+}
+
+/// Writes a PKCS#8 PrivateKeyInfo with the algorithm-specific `private_key`
+fn write_pkcs8(
+	private_key: &[u8],
+	algorithm: impl FnOnce(&mut yasna::DERWriterSeq<'_>),
+) -> SecretBytes {
+	SecretBytes::from(yasna::construct_der(|writer| {
+		writer.write_sequence(|writer| {
+			writer.next().write_u8(0);
+			writer.next().write_sequence(algorithm);
+			writer.next().write_bytes(private_key);
+		})
+	}))
+}
+
+/// The members with string values of a JSON Web Key
+struct Jwk(HashMap<String, String>);
+
+impl Jwk {
+	fn parse(json: &str) -> Result<Self, Error> {
+		let mut parser = JsonParser {
+			input: json.as_bytes(),
+			pos: 0,
+		};
+		let members = parser.object().ok_or(Error::CouldNotParseKeyPair)?;
+		match parser.peek() {
+			None => Ok(Self(members)),
+			Some(_) => Err(Error::CouldNotParseKeyPair),
+		}
+	}
+
+	fn member(&self, name: &str) -> Result<&str, Error> {
+		self.0
+			.get(name)
+			.map(String::as_str)
+			.ok_or_else(|| Error::MissingJwkMember(name.to_owned()))
+	}
+
+	/// Decodes a base64url-encoded member
+	fn bytes(&self, name: &str) -> Result<Vec<u8>, Error> {
+		URL_SAFE_NO_PAD
+			.decode(self.member(name)?)
+			.map_err(|_| Error::CouldNotParseKeyPair)
+	}
+}
+
+/// A parser for the JSON objects of JWKs, which keeps the members with string values and
+/// skips the others, like `key_ops` arrays
+struct JsonParser<'a> {
+	input: &'a [u8],
+	pos: usize,
+}
+
+impl JsonParser<'_> {
+	/// The maximum nesting of skipped arrays and objects
+	const MAX_DEPTH: usize = 16;
+
+	fn object(&mut self) -> Option<HashMap<String, String>> {
+		let mut members = HashMap::new();
+		self.expect(b'{')?;
+		if self.eat(b'}') {
+			return Some(members);
+		}
+		loop {
+			let name = self.string()?;
+			self.expect(b':')?;
+			if self.peek()? == b'"' {
+				let value = self.string()?;
+				// Duplicate members are ambiguous
+				if members.insert(name, value).is_some() {
+					return None;
+				}
+			} else {
+				self.skip_value(0)?;
+			}
+			if self.eat(b'}') {
+				return Some(members);
+			}
+			self.expect(b',')?;
+		}
+	}
+
+	fn string(&mut self) -> Option<String> {
+		self.expect(b'"')?;
+		let mut string = Vec::new();
+		loop {
+			let byte = *self.input.get(self.pos)?;
+			self.pos += 1;
+			match byte {
+				b'"' => return String::from_utf8(string).ok(),
+				b'\\' => {
+					let escaped = *self.input.get(self.pos)?;
+					self.pos += 1;
+					let c = match escaped {
+						b'"' | b'\\' | b'/' => char::from(escaped),
+						b'b' => '\u{8}',
+						b'f' => '\u{c}',
+						b'n' => '\n',
+						b'r' => '\r',
+						b't' => '\t',
+						b'u' => {
+							let hex = self.input.get(self.pos..self.pos + 4)?;
+							self.pos += 4;
+							let code =
+								u32::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?;
+							char::from_u32(code)?
+						},
+						_ => return None,
+					};
+					string.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+				},
+				0..=0x1f => return None,
+				_ => string.push(byte),
+			}
+		}
+	}
+
+	fn skip_value(&mut self, depth: usize) -> Option<()> {
+		if depth > Self::MAX_DEPTH {
+			return None;
+		}
+		match self.peek()? {
+			b'"' => {
+				self.string()?;
+			},
+			b'[' => {
+				self.pos += 1;
+				if !self.eat(b']') {
+					loop {
+						self.skip_value(depth + 1)?;
+						if self.eat(b']') {
+							break;
+						}
+						self.expect(b',')?;
+					}
+				}
+			},
+			b'{' => {
+				self.pos += 1;
+				if !self.eat(b'}') {
+					loop {
+						self.string()?;
+						self.expect(b':')?;
+						self.skip_value(depth + 1)?;
+						if self.eat(b'}') {
+							break;
+						}
+						self.expect(b',')?;
+					}
+				}
+			},
+			// Numbers and literals
+			_ => {
+				let start = self.pos;
+				while let Some(b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'+' | b'-' | b'.') =
+					self.input.get(self.pos)
+				{
+					self.pos += 1;
+				}
+				if self.pos == start {
+					return None;
+				}
+			},
+		}
+		Some(())
+	}
+
+	/// Skips whitespace and returns the next byte
+	fn peek(&mut self) -> Option<u8> {
+		while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.input.get(self.pos) {
+			self.pos += 1;
+		}
+		self.input.get(self.pos).copied()
+	}
+
+	fn eat(&mut self, byte: u8) -> bool {
+		let found = self.peek() == Some(byte);
+		if found {
+			self.pos += 1;
+		}
+		found
+	}
+
+	fn expect(&mut self, byte: u8) -> Option<()> {
+		self.eat(byte).then_some(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn rfc7638_rsa() {
+		// The example key of RFC 7638 section 3.1
+		let n = URL_SAFE_NO_PAD
+			.decode(
+				"0vx7agoebGcQSuuPiLJXZptN9nndrQmbXEps2aiAFbWhM78LhWx4cbbfAAtVT86zwu1RK7aPFFxuhDR1L6tSoc\
+				_BJECPebWKRXjBZCiFV4n3oknjhMstn64tZ_2W-5JsGY4Hc5n9yBXArwl93lqt7_RN5w6Cf0h4QyQ5v-65YGjQR0\
+				_FDW2QvzqY368QQMicAtaSqzs8KJZgnYb9c7d0zgdAZHzu6qMQvRL5hajrn1n91CbOpbISD08qNLyrdkt-bFTWhAI\
+				4vMQFh6WeZu0fM4lFd2NcRwr3XPksINHaQ-G_xBniIqbw0Ls1jF44-csFCur-kEgU8awapJzKnqDKgw",
+			)
+			.unwrap();
+		let public_key = yasna::construct_der(|writer| {
+			writer.write_sequence(|writer| {
+				writer.next().write_bigint_bytes(&n, true);
+				writer.next().write_u32(65537);
+			})
+		});
+
+		let jwk = public_key_jwk(&PKCS_RSA_SHA256, &public_key).unwrap();
+		assert!(jwk.starts_with(r#"{"e":"AQAB","kty":"RSA","n":"0vx7agoebGcQSuuPiLJXZptN9nnd"#));
+		assert_eq!(
+			URL_SAFE_NO_PAD.encode(digest::digest(&SHA256, jwk.as_bytes())),
+			"NzbLsXh8uDCcd-6MNwXF4W_7noWXFZAfHkxZsRGC9Xs"
+		);
+	}
+
+	#[test]
+	fn rfc8037_ed25519() {
+		// The example key of RFC 8037 appendix A
+		let seed = URL_SAFE_NO_PAD
+			.decode("nWGxne_9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A")
+			.unwrap();
+		let key_pair = KeyPair::from_raw_ed25519_seed(seed.as_slice().try_into().unwrap()).unwrap();
+		assert_eq!(key_pair.algorithm(), &PKCS_ED25519);
+
+		assert_eq!(
+			key_pair.public_key_jwk().unwrap(),
+			r#"{"crv":"Ed25519","kty":"OKP","x":"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"}"#
+		);
+		assert_eq!(
+			key_pair.public_key_jwk_thumbprint().unwrap(),
+			"kPrK_qmxVWaYVA9wwBF6Iuo3vVzz7TxHCTwXBygrS4k"
+		);
+	}
+
+	#[cfg(feature = "pem")]
+	#[test]
+	fn ecdsa() {
+		let key_pair = KeyPair::from_pem(include_str!("test_data/p256.key.pem")).unwrap();
+		assert_eq!(key_pair.algorithm(), &PKCS_ECDSA_P256_SHA256);
+
+		// Computed with Python's cryptography package
+		assert_eq!(
+			key_pair.public_key_jwk().unwrap(),
+			r#"{"crv":"P-256","kty":"EC","x":"Om_iaaSsLOT_ukEmScVSwbfSmKz_sw5m_PLewBamcHo","y":"wAhjEHsRu8fnNN-nFmNRi_Y95Qbr62w1DcJ_bGfZU88"}"#
+		);
+		assert_eq!(
+			key_pair.public_key_jwk_thumbprint().unwrap(),
+			"Uj3KE6gfA1QwlsQ7wkTYCia8ywh0P68V5M8lDGA_LFI"
+		);
+	}
+
+	#[cfg(feature = "pem")]
+	#[test]
+	fn from_jwk() {
+		// Converted from the PEM files with Python's cryptography package
+		for (jwk, pem, alg) in [
+			(
+				include_str!("test_data/p256.jwk.json"),
+				include_str!("test_data/p256.key.pem"),
+				&PKCS_ECDSA_P256_SHA256,
+			),
+			(
+				include_str!("test_data/ed25519.jwk.json"),
+				include_str!("test_data/ed25519.key.pem"),
+				&PKCS_ED25519,
+			),
+			(
+				include_str!("test_data/rsa2048.jwk.json"),
+				include_str!("test_data/rsa2048.key.pem"),
+				&PKCS_RSA_PSS_SHA256,
+			),
+		] {
+			let key_pair = KeyPair::from_jwk(jwk).unwrap();
+			assert_eq!(key_pair.algorithm(), alg);
+			assert_eq!(
+				key_pair.public_key_raw(),
+				KeyPair::from_pem(pem).unwrap().public_key_raw()
+			);
+
+			// The public JWK has the same public members
+			let public = Jwk::parse(&key_pair.public_key_jwk().unwrap()).unwrap();
+			let private = Jwk::parse(jwk).unwrap();
+			for (name, value) in &public.0 {
+				assert_eq!(private.0.get(name), Some(value));
+			}
+		}
+	}
+
+	#[test]
+	fn from_jwk_roundtrip() {
+		let key_pair = KeyPair::generate_for(&PKCS_ECDSA_P384_SHA384).unwrap();
+		let jwk = key_pair.public_key_jwk().unwrap();
+		// A public JWK has no private key
+		assert_eq!(
+			KeyPair::from_jwk(&jwk).unwrap_err(),
+			Error::MissingJwkMember("d".to_owned())
+		);
+
+		let sec1 = key_pair.serialize_sec1_der().unwrap();
+		let private_key = crate::asn1::parse_der(&sec1, |reader| {
+			reader.read_sequence(|reader| {
+				reader.next().read_u8()?;
+				let private_key = reader.next().read_bytes()?;
+				reader.next().read_der()?;
+				reader.next().read_der()?;
+				Ok(private_key)
+			})
+		})
+		.unwrap();
+		let private_jwk = format!(
+			r#"{}, "d": "{}"}}"#,
+			jwk.strip_suffix('}').unwrap(),
+			URL_SAFE_NO_PAD.encode(private_key)
+		);
+		let parsed = KeyPair::from_jwk(&private_jwk).unwrap();
+		assert_eq!(parsed.algorithm(), &PKCS_ECDSA_P384_SHA384);
+		assert_eq!(parsed.public_key_raw(), key_pair.public_key_raw());
+	}
+
+	#[test]
+	fn from_jwk_errors() {
+		let ed25519 = include_str!("test_data/ed25519.jwk.json");
+		// Mismatched public key
+		let other = ed25519.replace(
+			"wwFmBPYnZVYEkGeTq0qKlvYs_scP2mvwgsZv6IEfw7A",
+			"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo",
+		);
+		assert_eq!(
+			KeyPair::from_jwk(&other).unwrap_err(),
+			Error::PublicKeyMismatch
+		);
+		// Unknown curves and key types
+		for jwk in [
+			ed25519.replace("Ed25519", "Ed448"),
+			ed25519.replace("OKP", "oct"),
+		] {
+			assert_eq!(
+				KeyPair::from_jwk(&jwk).unwrap_err(),
+				Error::UnsupportedSignatureAlgorithm
+			);
+		}
+		// Malformed JSON
+		for json in [
+			"",
+			"{",
+			r#"{"kty": "OKP",}"#,
+			r#"{"kty": "OKP"} {}"#,
+			r#"{"kty": "OKP", "kty": "EC"}"#,
+			r#"{"kty": "OKP", "x": [[[[[[[[[[[[[[[[[[[[]]]]]]]]]]]]]]]]]]]}"#,
+		] {
+			assert_eq!(
+				KeyPair::from_jwk(json).unwrap_err(),
+				Error::CouldNotParseKeyPair,
+				"{json}"
+			);
+		}
+	}
+
+	#[test]
+	fn json_escapes() {
+		let jwk = Jwk::parse(r#"{"k\u0074y": "O\"K\\P\/\n", "ext": false, "n": -1.5e3}"#).unwrap();
+		assert_eq!(jwk.member("kty").unwrap(), "O\"K\\P/\n");
+		assert_eq!(jwk.0.len(), 1);
+	}
+}
@@ -29,56 +29,112 @@ println!("{}", signing_key.serialize_pem());
 #![forbid(unsafe_code)]
 #![forbid(non_ascii_idents)]
 #![deny(missing_docs)]
+#![cfg_attr(not(test), deny(clippy::unwrap_used, clippy::expect_used))]
 #![cfg_attr(rcgen_docsrs, feature(doc_cfg))]
 #![warn(unreachable_pub)]
 
 use std::borrow::Cow;
-use std::collections::HashMap;
 use std::fmt;
 use std::hash::Hash;
 use std::net::IpAddr;
-#[cfg(feature = "x509-parser")]
+#[cfg(any(feature = "crypto", feature = "x509-parser"))]
 use std::net::{Ipv4Addr, Ipv6Addr};
 use std::ops::Deref;
 
+pub use ca_state::CaState;
 pub use certificate::{
-	date_time_ymd, Attribute, BasicConstraints, Certificate, CertificateParams, CidrSubnet,
-	CustomExtension, DnType, ExtendedKeyUsagePurpose, GeneralSubtree, IsCa, NameConstraints,
+	date_time_ymd, AccessMethod, Attribute, AttributeValue, BasicConstraints, Certificate,
+	CertificateParams, CertificateVersion, CidrSubnet, CustomExtension, DnType,
+	ExtendedKeyUsagePurpose, ExtensionId, ExtensionRef, GeneralSubtree, IsCa, NameConstraints,
+	NetscapeCertType, PolicyConstraints, PolicyInformation, PolicyQualifier, TlsFeature,
 };
+pub use certified_chain::CertifiedChain;
+#[cfg(all(feature = "webpki", any(feature = "ring", feature = "aws_lc_rs")))]
+pub use chain::{verify_chain, ChainError};
+#[cfg(all(feature = "composite_signatures_unstable", not(feature = "fips")))]
+pub use composite::CompositeKeyPair;
 pub use crl::{
 	CertificateRevocationList, CertificateRevocationListParams, CrlDistributionPoint,
-	CrlIssuingDistributionPoint, CrlScope, RevocationReason, RevokedCertParams,
+	CrlExtensions, CrlIssuingDistributionPoint, CrlScope, CrlShards, CrlValidation,
+	CrlWithExtensions, RevocationReason, RevokedCertParams,
 };
 pub use csr::{CertificateSigningRequest, CertificateSigningRequestParams, PublicKey};
-pub use error::{Error, InvalidAsn1String};
+#[cfg(feature = "x509-parser")]
+pub use diff::{DiffField, DiffOptions, ParamsDiff};
+pub use error::{Error, ErrorKind, InvalidAsn1String};
+pub use issuance::{IssuanceDefaults, IssuanceValidation, MaxValidity};
+pub use key_pair::{
+	AsyncSigningKey, CompressedEcKey, PublicKeyData, PublicKeyHandle, SigningKey,
+	SubjectPublicKeyInfo, UnsignedKey,
+};
 #[cfg(feature = "crypto")]
-pub use key_pair::KeyPair;
+pub use key_pair::{KeyPair, KeyProvenance, RngSource};
 #[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
-pub use key_pair::RsaKeySize;
-pub use key_pair::{PublicKeyData, SigningKey, SubjectPublicKeyInfo};
+pub use key_pair::{RsaKeyOptions, RsaKeySize, RsaPublicExponent};
+pub use limits::{ParseLimit, ParseLimits, ParseOptions};
+pub use lint::Lint;
 #[cfg(feature = "pem")]
 use pem::Pem;
 use pki_types::CertificateDer;
 #[cfg(feature = "crypto")]
 use ring_like::digest;
 pub use sign_algo::algo::*;
-pub use sign_algo::SignatureAlgorithm;
+pub use sign_algo::{DigestAlgorithm, SignatureAlgorithm};
 use time::{OffsetDateTime, Time};
 use yasna::models::{GeneralizedTime, ObjectIdentifier, UTCTime};
-use yasna::tags::{TAG_BMPSTRING, TAG_TELETEXSTRING, TAG_UNIVERSALSTRING};
+use yasna::tags::{TAG_BMPSTRING, TAG_PRINTABLESTRING, TAG_TELETEXSTRING, TAG_UNIVERSALSTRING};
 use yasna::{DERWriter, Tag};
 
-use crate::string::{BmpString, Ia5String, PrintableString, TeletexString, UniversalString};
+use crate::string::{
+	BmpString, DnsName, Ia5String, PrintableString, TeletexString, UniversalString,
+};
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impls;
+mod asn1;
+mod ca_state;
 mod certificate;
+mod certified_chain;
+#[cfg(all(feature = "webpki", any(feature = "ring", feature = "aws_lc_rs")))]
+mod chain;
+#[cfg(all(feature = "composite_signatures_unstable", not(feature = "fips")))]
+mod composite;
 mod crl;
 mod csr;
+#[cfg(all(feature = "crypto", any(test, not(feature = "x509-parser"))))]
+mod csr_parser;
+#[cfg(feature = "x509-parser")]
+mod diff;
+mod ec_params;
+#[cfg(all(feature = "crypto", feature = "ed448"))]
+mod ed448;
 mod error;
+mod issuance;
+#[cfg(feature = "jwk")]
+mod jwk;
 mod key_pair;
+mod limits;
+mod lint;
+mod name_constraints;
 mod oid;
+#[cfg(feature = "openssh")]
+mod openssh;
+#[cfg(feature = "openssl_conf")]
+mod openssl_conf;
+#[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
+mod pbes2;
+#[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
+mod pkcs12;
+#[cfg(feature = "pkcs12")]
+mod pkcs12_import;
 mod ring_like;
+pub mod sign;
 mod sign_algo;
 pub mod string;
+#[cfg(all(feature = "crypto", any(test, feature = "test_keys")))]
+pub mod test_keys;
+#[cfg(feature = "tracing")]
+mod trace;
 
 /// Type-alias for the old name of [`Error`].
 #[deprecated(
@@ -157,7 +213,12 @@ impl<'a, S: SigningKey> CertifiedIssuer<'a, S> {
 	) -> Result<Self, Error> {
 		Ok(Self {
 			certificate: params.signed_by(&signing_key, issuer)?,
-			issuer: Issuer::new(params, signing_key),
+			issuer: Issuer {
+				certificate_issuer: Some(Cow::Owned(
+					issuer.distinguished_name.clone().into_owned(),
+				)),
+				..Issuer::new(params, signing_key)
+			},
 		})
 	}
 
@@ -196,17 +257,46 @@ impl<'a, S> AsRef<Certificate> for CertifiedIssuer<'a, S> {
 pub struct Issuer<'a, S> {
 	distinguished_name: Cow<'a, DistinguishedName>,
 	key_identifier_method: Cow<'a, KeyIdMethod>,
+	/// The issuer of the issuer's certificate, if it isn't self-issued
+	certificate_issuer: Option<Cow<'a, DistinguishedName>>,
+	serial_number: Option<Cow<'a, SerialNumber>>,
 	key_usages: Cow<'a, [KeyUsagePurpose]>,
+	name_constraints: Option<Cow<'a, NameConstraints>>,
+	/// The name constraint subtrees of the issuer's certificate that couldn't be represented
+	unsupported_name_constraints: Vec<String>,
+	defaults: IssuanceDefaults,
 	signing_key: S,
 }
 
-impl<'a, S: SigningKey> Issuer<'a, S> {
+impl<K: PublicKeyData> Issuer<'static, UnsignedKey<K>> {
+	/// Create an issuer that only has the public key of the issuing certificate
+	///
+	/// Certificates and CRLs issued by it have the issuer name and authority key identifier
+	/// of the CA described by `params` and `public_key`, but an empty signature, which never
+	/// verifies. Use this to build structurally valid but untrusted chains for testing
+	/// verifiers, never for anything that should be trusted.
+	pub fn new_unsigned_for_tests(params: CertificateParams, public_key: K) -> Self {
+		Self::new(params, UnsignedKey(public_key))
+	}
+}
+
+impl<'a, S: PublicKeyData> Issuer<'a, S> {
 	/// Create a new issuer from the given parameters and signing key.
+	///
+	/// The issuer's certificate is assumed to be self-issued, which matters for authority key
+	/// identifiers in the [`AuthorityKeyIdMode::IssuerAndSerialNumber`] form. Use
+	/// [`CertifiedIssuer::signed_by`] or [`from_ca_cert_der`](Self::from_ca_cert_der) for
+	/// intermediate CAs.
 	pub fn new(params: CertificateParams, signing_key: S) -> Self {
 		Self {
 			distinguished_name: Cow::Owned(params.distinguished_name),
 			key_identifier_method: Cow::Owned(params.key_identifier_method),
+			certificate_issuer: None,
+			serial_number: params.serial_number.map(Cow::Owned),
 			key_usages: Cow::Owned(params.key_usages),
+			name_constraints: params.name_constraints.map(Cow::Owned),
+			unsupported_name_constraints: Vec::new(),
+			defaults: IssuanceDefaults::default(),
 			signing_key,
 		}
 	}
@@ -219,7 +309,12 @@ impl<'a, S: SigningKey> Issuer<'a, S> {
 		Self {
 			distinguished_name: Cow::Borrowed(&params.distinguished_name),
 			key_identifier_method: Cow::Borrowed(&params.key_identifier_method),
+			certificate_issuer: None,
+			serial_number: params.serial_number.as_ref().map(Cow::Borrowed),
 			key_usages: Cow::Borrowed(&params.key_usages),
+			name_constraints: params.name_constraints.as_ref().map(Cow::Borrowed),
+			unsupported_name_constraints: Vec::new(),
+			defaults: IssuanceDefaults::default(),
 			signing_key,
 		}
 	}
@@ -229,8 +324,22 @@ impl<'a, S: SigningKey> Issuer<'a, S> {
 	/// See [`from_ca_cert_der`](Self::from_ca_cert_der) for more details.
 	#[cfg(all(feature = "pem", feature = "x509-parser"))]
 	pub fn from_ca_cert_pem(pem_str: &str, signing_key: S) -> Result<Self, Error> {
+		Self::from_ca_cert_pem_with_limits(pem_str, signing_key, &ParseLimits::UNLIMITED)
+	}
+
+	/// Parses an existing CA certificate from the ASCII PEM format, rejecting input that
+	/// exceeds `limits`
+	///
+	/// See [`from_ca_cert_der_with_limits`](Self::from_ca_cert_der_with_limits) for more details.
+	#[cfg(all(feature = "pem", feature = "x509-parser"))]
+	pub fn from_ca_cert_pem_with_limits(
+		pem_str: &str,
+		signing_key: S,
+		limits: &ParseLimits,
+	) -> Result<Self, Error> {
+		limits.check(ParseLimit::InputLength, pem_str.len())?;
 		let certificate = pem::parse(pem_str).map_err(|_| Error::CouldNotParseCertificate)?;
-		Self::from_ca_cert_der(&certificate.contents().into(), signing_key)
+		Self::from_ca_cert_der_with_limits(&certificate.contents().into(), signing_key, limits)
 	}
 
 	/// Parses an existing CA certificate from the DER format.
@@ -239,32 +348,150 @@ impl<'a, S: SigningKey> Issuer<'a, S> {
 	/// for the presence of the `BasicConstraints` extension, or perform any other
 	/// validation.
 	///
+	/// The name constraints of the certificate are kept for
+	/// [`IssuanceValidation::Strict`]. Subtrees on forms other than DNS names, email
+	/// addresses, directory names, URIs and IP addresses can't be enforced, so they are
+	/// skipped and listed by
+	/// [`unsupported_name_constraints()`](Self::unsupported_name_constraints), and an issuer
+	/// with any of them refuses to sign with [`IssuanceValidation::Strict`].
+	///
 	/// If you already have a byte slice containing DER, it can trivially be converted into
 	/// [`CertificateDer`] using the [`Into`] trait.
 	#[cfg(feature = "x509-parser")]
 	pub fn from_ca_cert_der(ca_cert: &CertificateDer<'_>, signing_key: S) -> Result<Self, Error> {
-		let (_remainder, x509) = x509_parser::parse_x509_certificate(ca_cert)
-			.map_err(|_| Error::CouldNotParseCertificate)?;
+		Self::from_ca_cert_der_with_limits(ca_cert, signing_key, &ParseLimits::UNLIMITED)
+	}
+
+	/// Parses an existing CA certificate from the DER format, rejecting input that exceeds
+	/// `limits`
+	///
+	/// The limits on the input length, the number of extensions and the length of the
+	/// distinguished name attributes apply, and [`Error::ParseLimitExceeded`] is returned for
+	/// the first one exceeded. Otherwise, this behaves like
+	/// [`from_ca_cert_der`](Self::from_ca_cert_der).
+	#[cfg(feature = "x509-parser")]
+	pub fn from_ca_cert_der_with_limits(
+		ca_cert: &CertificateDer<'_>,
+		signing_key: S,
+		limits: &ParseLimits,
+	) -> Result<Self, Error> {
+		limits.check(ParseLimit::InputLength, ca_cert.len())?;
+		let x509 = parse_x509_certificate(ca_cert)?;
+		limits.check(ParseLimit::Extensions, x509.extensions().len())?;
+		limits.check_name(&x509.tbs_certificate.subject)?;
+		let (name_constraints, unsupported_name_constraints) =
+			NameConstraints::from_x509_lenient(&x509)?;
+		#[cfg(feature = "tracing")]
+		for subtree in &unsupported_name_constraints {
+			tracing::warn!(%subtree, "skipping unsupported name constraint subtree");
+		}
 
 		Ok(Self {
 			key_usages: Cow::Owned(KeyUsagePurpose::from_x509(&x509)?),
 			key_identifier_method: Cow::Owned(KeyIdMethod::from_x509(&x509)?),
+			certificate_issuer: Some(Cow::Owned(DistinguishedName::from_name(
+				&x509.tbs_certificate.issuer,
+			)?)),
+			serial_number: Some(Cow::Owned(SerialNumber::from_x509(&x509)?)),
 			distinguished_name: Cow::Owned(DistinguishedName::from_name(
 				&x509.tbs_certificate.subject,
 			)?),
+			name_constraints: name_constraints.map(Cow::Owned),
+			unsupported_name_constraints,
+			defaults: IssuanceDefaults::default(),
 			signing_key,
 		})
 	}
 
+	/// Attaches parameters that are merged into every certificate this issuer signs
+	///
+	/// See [`IssuanceDefaults`] for how they are merged. Self-signed certificates aren't
+	/// affected, as they aren't signed through an existing issuer.
+	pub fn with_defaults(mut self, defaults: IssuanceDefaults) -> Self {
+		self.defaults = defaults;
+		self
+	}
+
+	/// The parameters merged into every certificate this issuer signs
+	pub fn defaults(&self) -> &IssuanceDefaults {
+		&self.defaults
+	}
+
 	/// Allowed key usages for this issuer.
 	pub fn key_usages(&self) -> &[KeyUsagePurpose] {
 		&self.key_usages
 	}
 
+	/// The name constraints of this issuer, which [`IssuanceValidation::Strict`] enforces
+	pub fn name_constraints(&self) -> Option<&NameConstraints> {
+		self.name_constraints.as_deref()
+	}
+
+	/// The name constraint subtrees of the issuer's certificate that were skipped when
+	/// parsing it, as they are on forms that can't be enforced
+	///
+	/// They are described in the notation of x509-parser, like `RegisteredID(1.2.3)`. An
+	/// issuer with any of them fails to sign with [`IssuanceValidation::Strict`].
+	pub fn unsupported_name_constraints(&self) -> &[String] {
+		&self.unsupported_name_constraints
+	}
+
+	/// Returns `params` with the [`IssuanceDefaults`] of this issuer merged in
+	///
+	/// With [`IssuanceValidation::Strict`], issuers whose name constraints couldn't all be
+	/// parsed refuse to sign, as these couldn't be enforced.
+	pub(crate) fn apply_defaults<'p>(
+		&self,
+		params: &'p CertificateParams,
+	) -> Result<Cow<'p, CertificateParams>, Error> {
+		if let (IssuanceValidation::Strict, Some(subtree)) = (
+			self.defaults.validation,
+			self.unsupported_name_constraints.first(),
+		) {
+			return Err(Error::X509(format!(
+				"unsupported name constraint subtree: {subtree}"
+			)));
+		}
+		self.defaults
+			.apply(params, self.name_constraints.as_deref())
+	}
+
 	/// Yield a reference to the signing key.
 	pub fn key(&self) -> &S {
 		&self.signing_key
 	}
+
+	/// The key identifier to use as authority key identifier of certificates and CRLs
+	///
+	/// This is the subject key identifier of the issuer's certificate as is, if known, so that
+	/// it matches no matter how the issuer computed it. Otherwise, it's derived from the
+	/// issuer's public key using `method`.
+	fn authority_key_identifier(&self, method: &KeyIdMethod) -> Vec<u8> {
+		match self.key_identifier_method.as_ref() {
+			KeyIdMethod::PreSpecified(key_id) if !key_id.is_empty() => key_id.clone(),
+			_ => method.derive(&self.signing_key),
+		}
+	}
+
+	/// The issuer name and serial number of the issuer's certificate, as used in the
+	/// [`AuthorityKeyIdMode::IssuerAndSerialNumber`] form of authority key identifiers
+	///
+	/// Without a known serial number, it's derived from the issuer's public key, the same way
+	/// as when the issuer's certificate was generated.
+	fn authority_cert_issuer_and_serial(&self) -> Result<(&DistinguishedName, Vec<u8>), Error> {
+		let name = self
+			.certificate_issuer
+			.as_deref()
+			.unwrap_or(&self.distinguished_name);
+		let serial = match &self.serial_number {
+			Some(serial) => serial.to_bytes(),
+			#[cfg(feature = "crypto")]
+			None => certificate::derive_serial_number(&self.signing_key),
+			#[cfg(not(feature = "crypto"))]
+			None => return Err(Error::MissingSerialNumber),
+		};
+		Ok((name, serial))
+	}
 }
 
 impl<'a, S> fmt::Debug for Issuer<'a, S> {
@@ -274,19 +501,59 @@ impl<'a, S> fmt::Debug for Issuer<'a, S> {
 		let Issuer {
 			distinguished_name,
 			key_identifier_method,
+			certificate_issuer,
+			serial_number,
 			key_usages,
+			name_constraints,
+			unsupported_name_constraints,
+			defaults,
 			signing_key: _,
 		} = self;
 
 		f.debug_struct("Issuer")
 			.field("distinguished_name", distinguished_name)
 			.field("key_identifier_method", key_identifier_method)
+			.field("certificate_issuer", certificate_issuer)
+			.field("serial_number", serial_number)
 			.field("key_usages", key_usages)
+			.field("name_constraints", name_constraints)
+			.field("unsupported_name_constraints", unsupported_name_constraints)
+			.field("defaults", defaults)
 			.field("signing_key", &"[elided]")
 			.finish()
 	}
 }
 
+/// Parses a DER-encoded certificate, rejecting trailing data after the certificate.
+#[cfg(feature = "x509-parser")]
+fn parse_x509_certificate<'a>(
+	der: &'a CertificateDer<'_>,
+) -> Result<x509_parser::certificate::X509Certificate<'a>, Error> {
+	let (remainder, x509) =
+		x509_parser::parse_x509_certificate(der).map_err(|_| Error::CouldNotParseCertificate)?;
+	if !remainder.is_empty() {
+		return Err(Error::X509(format!(
+			"{} trailing bytes after certificate",
+			remainder.len()
+		)));
+	}
+	Ok(x509)
+}
+
+/// The arcs of an OID parsed by x509-parser, if it can be written again
+///
+/// x509-parser decodes a first subidentifier of 120 or more as a first arc above 2, which
+/// yasna panics on when writing it.
+#[cfg(feature = "x509-parser")]
+fn oid_arcs(oid: &x509_parser::der_parser::asn1_rs::Oid<'_>) -> Option<Vec<u64>> {
+	let arcs = oid.iter()?.collect::<Vec<_>>();
+	match arcs[..] {
+		[0 | 1, second, ..] if second < 40 => Some(arcs),
+		[2, second, ..] if second < u64::MAX - 80 => Some(arcs),
+		_ => None,
+	}
+}
+
 // https://tools.ietf.org/html/rfc5280#section-4.1.1
 
 // Example certs usable as reference:
@@ -308,14 +575,52 @@ const ENCODE_CONFIG: pem::EncodeConfig = {
 pub enum SanType {
 	/// Also known as E-Mail address
 	Rfc822Name(Ia5String),
-	DnsName(Ia5String),
+	/// A DNS name, normalized when converted from a string, see [`DnsName`]
+	DnsName(DnsName),
 	URI(Ia5String),
 	IpAddress(IpAddr),
 	OtherName((Vec<u64>, OtherNameValue)),
+	/// A directoryName, a distinguished name like the subject of a certificate
+	DirectoryName(DistinguishedName),
+	/// A registeredID, an OID identifying the subject, like an application in some
+	/// industrial automation profiles
+	RegisteredId(Vec<u64>),
+	/// A `GeneralName` of any form, given as its context-specific tag number and content octets
+	///
+	/// This covers the forms that have no dedicated variant, like x400Address, or
+	/// otherName values that aren't UTF-8 strings, and is what the parser falls back to for them.
+	/// `der_value` is written verbatim as the content of the `[tag]` element:
+	///
+	/// - Most forms of [RFC 5280§4.2.1.6] are tagged implicitly, so `der_value` is the content of
+	///   the underlying type without its tag and length. For the SEQUENCE based otherName (`[0]`),
+	///   x400Address (`[3]`) and ediPartyName (`[5]`), that's the encoded SEQUENCE members.
+	/// - directoryName (`[4]`) is tagged explicitly as it's a CHOICE, so `der_value` is the
+	///   complete DER encoding of the `Name`, including its SEQUENCE tag and length.
+	///
+	/// Tags 0, 3, 4 and 5 are written as constructed elements, all others as primitive ones.
+	///
+	/// [RFC 5280§4.2.1.6]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.2.1.6
+	Raw {
+		/// The context-specific tag number of the `GeneralName` form
+		tag: u8,
+		/// The content octets of the tagged element
+		der_value: Vec<u8>,
+	},
 }
 
 impl SanType {
-	#[cfg(all(test, feature = "x509-parser"))]
+	/// A Microsoft user principal name (UPN), like `user@corp.example`, as needed for smart
+	/// card logon to Active Directory
+	///
+	/// This is an otherName with the OID 1.3.6.1.4.1.311.20.2.3 and a UTF-8 value.
+	pub fn user_principal_name(upn: impl Into<String>) -> Self {
+		Self::OtherName((
+			oid::MS_USER_PRINCIPAL_NAME.to_vec(),
+			OtherNameValue::Utf8String(upn.into()),
+		))
+	}
+
+	#[cfg(feature = "x509-parser")]
 	fn from_x509(x509: &x509_parser::certificate::X509Certificate<'_>) -> Result<Vec<Self>, Error> {
 		let sans = x509
 			.subject_alternative_name()
@@ -332,13 +637,31 @@ impl SanType {
 		}
 		Ok(subject_alt_names)
 	}
+
+	#[cfg(feature = "x509-parser")]
+	fn issuer_alt_names_from_x509(
+		x509: &x509_parser::certificate::X509Certificate<'_>,
+	) -> Result<Vec<Self>, Error> {
+		let ext = x509
+			.get_extension_unique(&x509_parser::oid_registry::OID_X509_EXT_ISSUER_ALT_NAME)
+			.map_err(|_| Error::CouldNotParseCertificate)?;
+		let names = match ext.map(|ext| ext.parsed_extension()) {
+			Some(x509_parser::extensions::ParsedExtension::IssuerAlternativeName(ian)) => {
+				&ian.general_names
+			},
+			Some(_) => return Err(Error::CouldNotParseCertificate),
+			None => return Ok(Vec::new()),
+		};
+
+		names.iter().map(Self::try_from_general).collect()
+	}
 }
 
 /// An `OtherName` value, defined in [RFC 5280§4.1.2.4].
 ///
-/// While the standard specifies this could be any ASN.1 type rcgen limits
-/// the value to a UTF-8 encoded string as this will cover the most common
-/// use cases, for instance smart card user principal names (UPN).
+/// The standard allows any ASN.1 type, as defined by the OID of the name. The common string
+/// types have their own variants, like UTF-8 for smart card user principal names (UPN), see
+/// [`SanType::user_principal_name()`]. Other types can be given as DER.
 ///
 /// [RFC 5280§4.1.2.4]: https://datatracker.ietf.org/doc/html/rfc5280#section-4.1.2.4
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
@@ -346,14 +669,44 @@ impl SanType {
 pub enum OtherNameValue {
 	/// A string encoded using UTF-8
 	Utf8String(String),
+	/// An ASCII string
+	Ia5String(Ia5String),
+	/// A value of any other type, as its complete DER encoding including tag and length
+	///
+	/// This is written verbatim, so it must be a single, well-formed DER element.
+	Der(Vec<u8>),
 }
 
 impl OtherNameValue {
 	fn write_der(&self, writer: DERWriter) {
 		writer.write_tagged(Tag::context(0), |writer| match self {
 			OtherNameValue::Utf8String(s) => writer.write_utf8_string(s),
+			OtherNameValue::Ia5String(s) => writer.write_ia5_string(s.as_str()),
+			OtherNameValue::Der(der) => writer.write_der(der),
 		});
 	}
+
+	/// Parses the `[0] EXPLICIT` element of an otherName
+	///
+	/// Returns `None` if it's not well-formed DER, so the name can be kept as
+	/// [`SanType::Raw`].
+	#[cfg(any(feature = "crypto", feature = "x509-parser"))]
+	fn from_der(explicit: &[u8]) -> Option<Self> {
+		let der = crate::asn1::parse_der(explicit, |reader| {
+			reader.read_tagged(Tag::context(0), |reader| reader.read_der())
+		})
+		.ok()?;
+		Some(match der.first() {
+			Some(&0x0c) => {
+				Self::Utf8String(crate::asn1::parse_der(&der, |r| r.read_utf8string()).ok()?)
+			},
+			Some(&0x16) => {
+				let s = crate::asn1::parse_der(&der, |reader| reader.read_ia5_string()).ok()?;
+				Self::Ia5String(s.try_into().ok()?)
+			},
+			_ => Self::Der(der),
+		})
+	}
 }
 
 impl<T> From<T> for OtherNameValue
@@ -365,7 +718,7 @@ where
 	}
 }
 
-#[cfg(feature = "x509-parser")]
+#[cfg(any(feature = "crypto", feature = "x509-parser"))]
 fn ip_addr_from_octets(octets: &[u8]) -> Result<IpAddr, Error> {
 	if let Ok(ipv6_octets) = <&[u8; 16]>::try_from(octets) {
 		Ok(Ipv6Addr::from(*ipv6_octets).into())
@@ -379,36 +732,64 @@ fn ip_addr_from_octets(octets: &[u8]) -> Result<IpAddr, Error> {
 impl SanType {
 	#[cfg(feature = "x509-parser")]
 	fn try_from_general(name: &x509_parser::extensions::GeneralName<'_>) -> Result<Self, Error> {
-		use x509_parser::der_parser::asn1_rs::{self, FromDer, Tag, TaggedExplicit};
 		Ok(match name {
 			x509_parser::extensions::GeneralName::RFC822Name(name) => {
 				SanType::Rfc822Name((*name).try_into()?)
 			},
 			x509_parser::extensions::GeneralName::DNSName(name) => {
-				SanType::DnsName((*name).try_into()?)
+				SanType::DnsName(DnsName::raw((*name).try_into()?))
 			},
 			x509_parser::extensions::GeneralName::URI(name) => SanType::URI((*name).try_into()?),
 			x509_parser::extensions::GeneralName::IPAddress(octets) => {
 				SanType::IpAddress(ip_addr_from_octets(octets)?)
 			},
 			x509_parser::extensions::GeneralName::OtherName(oid, value) => {
-				let oid = oid.iter().ok_or(Error::CouldNotParseCertificate)?;
-				// We first remove the explicit tag ([0] EXPLICIT)
-				let (_, other_name) = TaggedExplicit::<asn1_rs::Any, _, 0>::from_der(value)
-					.map_err(|_| Error::CouldNotParseCertificate)?;
-				let other_name = other_name.into_inner();
-
-				let other_name_value = match other_name.tag() {
-					Tag::Utf8String => OtherNameValue::Utf8String(
-						std::str::from_utf8(other_name.data)
-							.map_err(|_| Error::CouldNotParseCertificate)?
-							.to_owned(),
-					),
-					_ => return Err(Error::CouldNotParseCertificate),
-				};
-				SanType::OtherName((oid.collect(), other_name_value))
+				match (oid.iter(), OtherNameValue::from_der(value)) {
+					(Some(components), Some(value)) => {
+						SanType::OtherName((components.collect(), value))
+					},
+					// Keep values we can't represent as they are
+					_ => {
+						let mut der_value = yasna::construct_der(|writer| {
+							writer.write_tagged_implicit(yasna::tags::TAG_OID, |writer| {
+								writer.write_bytes(oid.as_bytes())
+							})
+						});
+						der_value.extend_from_slice(value);
+						SanType::Raw { tag: 0, der_value }
+					},
+				}
+			},
+			x509_parser::extensions::GeneralName::X400Address(any) => SanType::Raw {
+				tag: 3,
+				der_value: any.data.to_vec(),
+			},
+			x509_parser::extensions::GeneralName::EDIPartyName(any) => SanType::Raw {
+				tag: 5,
+				der_value: any.data.to_vec(),
+			},
+			x509_parser::extensions::GeneralName::DirectoryName(name) => {
+				match DistinguishedName::from_name(name) {
+					Ok(dn) => SanType::DirectoryName(dn),
+					// Keep names we can't represent as they are, like ones with multi-valued RDNs
+					Err(_) => SanType::Raw {
+						tag: 4,
+						der_value: name.as_raw().to_vec(),
+					},
+				}
+			},
+			x509_parser::extensions::GeneralName::RegisteredID(oid) => match oid.iter() {
+				Some(components) => SanType::RegisteredId(components.collect()),
+				// Keep OIDs with arcs that don't fit into a u64 as they are
+				None => SanType::Raw {
+					tag: 8,
+					der_value: oid.as_bytes().to_vec(),
+				},
+			},
+			x509_parser::extensions::GeneralName::Invalid(tag, data) => SanType::Raw {
+				tag: u8::try_from(tag.0).map_err(|_| Error::InvalidNameType)?,
+				der_value: data.to_vec(),
 			},
-			_ => return Err(Error::InvalidNameType),
 		})
 	}
 
@@ -418,8 +799,10 @@ impl SanType {
 		const TAG_OTHER_NAME: u64 = 0;
 		const TAG_RFC822_NAME: u64 = 1;
 		const TAG_DNS_NAME: u64 = 2;
+		const TAG_DIRECTORY_NAME: u64 = 4;
 		const TAG_URI: u64 = 6;
 		const TAG_IP_ADDRESS: u64 = 7;
+		const TAG_REGISTERED_ID: u64 = 8;
 
 		match self {
 			SanType::Rfc822Name(_name) => TAG_RFC822_NAME,
@@ -427,6 +810,9 @@ impl SanType {
 			SanType::URI(_name) => TAG_URI,
 			SanType::IpAddress(_addr) => TAG_IP_ADDRESS,
 			Self::OtherName(_oid) => TAG_OTHER_NAME,
+			Self::DirectoryName(_name) => TAG_DIRECTORY_NAME,
+			Self::RegisteredId(_oid) => TAG_REGISTERED_ID,
+			Self::Raw { tag, .. } => u64::from(*tag),
 		}
 	}
 }
@@ -458,20 +844,21 @@ where
 	}
 }
 
-#[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
 /**
 Distinguished name used e.g. for the issuer and subject fields of a certificate
 
 A distinguished name is a set of (attribute type, attribute value) tuples.
 
-This datastructure keeps them ordered by insertion order.
+This datastructure keeps them ordered by insertion order. Names parsed from existing
+certificates keep their attributes exactly as encoded, including repeated attribute
+types and the ASN.1 string type of each value, so they serialize to the same bytes.
 
 See also the RFC 5280 sections on the [issuer](https://tools.ietf.org/html/rfc5280#section-4.1.2.4)
 and [subject](https://tools.ietf.org/html/rfc5280#section-4.1.2.6) fields.
 */
 pub struct DistinguishedName {
-	entries: HashMap<DnType, DnValue>,
-	order: Vec<DnType>,
+	entries: Vec<(DnType, DnValue)>,
 }
 
 impl DistinguishedName {
@@ -480,8 +867,13 @@ impl DistinguishedName {
 		Self::default()
 	}
 	/// Obtains the attribute value for the given attribute type
+	///
+	/// If a parsed name contains the attribute type more than once, the first value is returned.
 	pub fn get(&self, ty: &DnType) -> Option<&DnValue> {
-		self.entries.get(ty)
+		self.entries
+			.iter()
+			.find(|(ty_e, _)| ty_e == ty)
+			.map(|(_, value)| value)
 	}
 	/// Removes the attribute with the specified DnType
 	///
@@ -489,11 +881,9 @@ impl DistinguishedName {
 	/// when no attribute with the specified DnType was
 	/// found.
 	pub fn remove(&mut self, ty: DnType) -> bool {
-		let removed = self.entries.remove(&ty).is_some();
-		if removed {
-			self.order.retain(|ty_o| &ty != ty_o);
-		}
-		removed
+		let len = self.entries.len();
+		self.entries.retain(|(ty_e, _)| ty_e != &ty);
+		self.entries.len() != len
 	}
 	/// Inserts or updates an attribute that consists of type and name
 	///
@@ -505,20 +895,35 @@ impl DistinguishedName {
 	/// assert_eq!(dn.get(&DnType::OrganizationName), Some(&DnValue::Utf8String("Crab widgits SE".to_string())));
 	/// assert_eq!(dn.get(&DnType::CommonName), Some(&DnValue::PrintableString("Master Cert".try_into().unwrap())));
 	/// ```
+	///
+	/// Plain strings for attribute types that are restricted to PrintableString,
+	/// like [`DnType::JurisdictionCountryName`], are stored as a PrintableString if possible.
 	pub fn push(&mut self, ty: DnType, s: impl Into<DnValue>) {
-		if !self.entries.contains_key(&ty) {
-			self.order.push(ty.clone());
+		let mut value = s.into();
+		if let (DnType::JurisdictionCountryName, DnValue::Utf8String(s)) = (&ty, &value) {
+			if let Ok(s) = PrintableString::try_from(s.as_str()) {
+				value = DnValue::PrintableString(s);
+			}
+		}
+
+		match self.entries.iter_mut().find(|(ty_e, _)| ty_e == &ty) {
+			Some((_, existing)) => *existing = value,
+			None => self.entries.push((ty, value)),
 		}
-		self.entries.insert(ty, s.into());
 	}
 	/// Iterate over the entries
 	pub fn iter(&self) -> DistinguishedNameIterator<'_> {
 		DistinguishedNameIterator {
-			distinguished_name: self,
-			iter: self.order.iter(),
+			iter: self.entries.iter(),
 		}
 	}
 
+	/// Appends a parsed attribute as is, even if its type is already present
+	#[cfg(any(feature = "x509-parser", feature = "crypto"))]
+	fn push_parsed(&mut self, ty: DnType, value: DnValue) {
+		self.entries.push((ty, value));
+	}
+
 	#[cfg(feature = "x509-parser")]
 	fn from_name(name: &x509_parser::x509::X509Name) -> Result<Self, Error> {
 		use x509_parser::der_parser::asn1_rs::Tag;
@@ -526,23 +931,19 @@ impl DistinguishedName {
 		let mut dn = DistinguishedName::new();
 		for rdn in name.iter() {
 			let mut rdn_iter = rdn.iter();
-			let dn_opt = rdn_iter.next();
-			let attr = if let Some(dn) = dn_opt {
-				if rdn_iter.next().is_some() {
-					// no support for distinguished names with more than one attribute
-					return Err(Error::CouldNotParseCertificate);
-				} else {
-					dn
-				}
-			} else {
-				panic!("x509-parser distinguished name set is empty");
+			let Some(attr) = rdn_iter.next() else {
+				// RFC 5280 requires at least one attribute per RDN, but a
+				// malformed input may contain an empty SET.
+				return Err(Error::X509("empty relative distinguished name".to_string()));
 			};
+			if rdn_iter.next().is_some() {
+				// no support for distinguished names with more than one attribute
+				return Err(Error::CouldNotParseCertificate);
+			}
 
-			let attr_type_oid = attr
-				.attr_type()
-				.iter()
-				.ok_or(Error::CouldNotParseCertificate)?;
-			let dn_type = DnType::from_oid(&attr_type_oid.collect::<Vec<_>>());
+			let attr_type_oid =
+				oid_arcs(attr.attr_type()).ok_or(Error::CouldNotParseCertificate)?;
+			let dn_type = DnType::from_oid(&attr_type_oid);
 			let data = attr.attr_value().data;
 			let try_str =
 				|data| std::str::from_utf8(data).map_err(|_| Error::CouldNotParseCertificate);
@@ -558,7 +959,7 @@ impl DistinguishedName {
 				_ => return Err(Error::CouldNotParseCertificate),
 			};
 
-			dn.push(dn_type, dn_value);
+			dn.push_parsed(dn_type, dn_value);
 		}
 		Ok(dn)
 	}
@@ -569,17 +970,14 @@ Iterator over [`DistinguishedName`] entries
 */
 #[derive(Clone, Debug)]
 pub struct DistinguishedNameIterator<'a> {
-	distinguished_name: &'a DistinguishedName,
-	iter: std::slice::Iter<'a, DnType>,
+	iter: std::slice::Iter<'a, (DnType, DnValue)>,
 }
 
 impl<'a> Iterator for DistinguishedNameIterator<'a> {
 	type Item = (&'a DnType, &'a DnValue);
 
 	fn next(&mut self) -> Option<Self::Item> {
-		self.iter
-			.next()
-			.and_then(|ty| self.distinguished_name.entries.get(ty).map(|v| (ty, v)))
+		self.iter.next().map(|(ty, value)| (ty, value))
 	}
 }
 
@@ -637,7 +1035,7 @@ impl KeyUsagePurpose {
 
 	/// Parse a collection of key usages from a [`u16`] representing the value
 	/// of a KeyUsage BIT STRING as defined by RFC 5280.
-	#[cfg(feature = "x509-parser")]
+	#[cfg(any(feature = "crypto", feature = "x509-parser"))]
 	fn from_u16(value: u16) -> Vec<Self> {
 		[
 			KeyUsagePurpose::DigitalSignature,
@@ -674,6 +1072,12 @@ impl KeyUsagePurpose {
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 #[non_exhaustive]
 pub enum KeyIdMethod {
+	/// RFC 5280 method 1 - the SHA-1 digest of the subjectPublicKey BIT STRING value.
+	///
+	/// Unlike the other methods, this doesn't hash the whole subject public key info. It's
+	/// the method of most existing CAs, e.g. of `subjectKeyIdentifier = hash` in openssl.
+	#[cfg(feature = "crypto")]
+	Sha1,
 	/// RFC 7093 method 1 - a truncated SHA256 digest.
 	#[cfg(feature = "crypto")]
 	Sha256,
@@ -687,30 +1091,65 @@ pub enum KeyIdMethod {
 	PreSpecified(Vec<u8>),
 }
 
+/// The form of the authority key identifier extension of a certificate
+///
+/// [RFC 5280 §4.2.1.1](https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.1) allows to
+/// identify the issuer's key by its key identifier, by the issuer name and serial number of
+/// the issuer's certificate, or by both. The latter are taken from the [`Issuer`].
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub enum AuthorityKeyIdMode {
+	/// Only the `keyIdentifier` field, as required by RFC 5280 for conforming CAs
+	#[default]
+	KeyIdentifier,
+	/// Only the `authorityCertIssuer` and `authorityCertSerialNumber` fields
+	IssuerAndSerialNumber,
+	/// All three fields
+	Both,
+}
+
+impl AuthorityKeyIdMode {
+	fn key_identifier(self) -> bool {
+		matches!(self, Self::KeyIdentifier | Self::Both)
+	}
+
+	fn issuer_and_serial_number(self) -> bool {
+		matches!(self, Self::IssuerAndSerialNumber | Self::Both)
+	}
+}
+
 impl KeyIdMethod {
 	#[cfg(feature = "x509-parser")]
 	fn from_x509(x509: &x509_parser::certificate::X509Certificate<'_>) -> Result<Self, Error> {
-		let key_identifier_method =
-			x509.iter_extensions()
-				.find_map(|ext| match ext.parsed_extension() {
-					x509_parser::extensions::ParsedExtension::SubjectKeyIdentifier(key_id) => {
-						Some(KeyIdMethod::PreSpecified(key_id.0.into()))
-					},
-					_ => None,
-				});
+		use x509_parser::extensions::ParsedExtension;
+		use x509_parser::oid_registry::OID_X509_EXT_SUBJECT_KEY_IDENTIFIER;
+
+		let ski = x509
+			.get_extension_unique(&OID_X509_EXT_SUBJECT_KEY_IDENTIFIER)
+			.map_err(|e| Error::X509(format!("subject key identifier: {e}")))?;
 
-		Ok(match key_identifier_method {
-			Some(method) => method,
+		Ok(match ski.map(|ext| ext.parsed_extension()) {
+			Some(ParsedExtension::SubjectKeyIdentifier(key_id)) if !key_id.0.is_empty() => {
+				KeyIdMethod::PreSpecified(key_id.0.into())
+			},
+			Some(_) => {
+				return Err(Error::X509(
+					"malformed subject key identifier extension".to_string(),
+				))
+			},
 			None => {
+				// Without a subject key identifier to copy, the key identifier
+				// has to be derived from the public key, which requires a digest.
 				#[cfg(not(feature = "crypto"))]
-				return Err(Error::UnsupportedSignatureAlgorithm);
+				return Err(Error::X509(
+					"missing subject key identifier extension".to_string(),
+				));
 				#[cfg(feature = "crypto")]
 				KeyIdMethod::Sha256
 			},
 		})
 	}
 
-	/// Derive a key identifier for the provided subject public key info using the key ID method.
+	/// Derive a key identifier for the provided public key using the key ID method.
 	///
 	/// Typically this is a truncated hash over the raw subject public key info, but may
 	/// be a pre-specified value.
@@ -718,9 +1157,11 @@ impl KeyIdMethod {
 	/// This key identifier is used in the SubjectKeyIdentifier and AuthorityKeyIdentifier
 	/// X.509v3 extensions.
 	#[allow(unused_variables)]
-	pub(crate) fn derive(&self, subject_public_key_info: impl AsRef<[u8]>) -> Vec<u8> {
+	pub(crate) fn derive(&self, key: &(impl PublicKeyData + ?Sized)) -> Vec<u8> {
 		#[cfg_attr(not(feature = "crypto"), expect(clippy::let_unit_value))]
 		let digest_method = match &self {
+			#[cfg(feature = "crypto")]
+			Self::Sha1 => &digest::SHA1_FOR_LEGACY_USE_ONLY,
 			#[cfg(feature = "crypto")]
 			Self::Sha256 => &digest::SHA256,
 			#[cfg(feature = "crypto")]
@@ -733,7 +1174,10 @@ impl KeyIdMethod {
 		};
 		#[cfg(feature = "crypto")]
 		{
-			let digest = digest::digest(digest_method, subject_public_key_info.as_ref());
+			let digest = match self {
+				Self::Sha1 => digest::digest(digest_method, key.der_bytes()),
+				_ => digest::digest(digest_method, &key.subject_public_key_info()),
+			};
 			digest.as_ref()[0..20].to_vec()
 		}
 	}
@@ -747,6 +1191,8 @@ fn dt_strip_nanos(dt: OffsetDateTime) -> OffsetDateTime {
 	// therefore, it needs to be stripped of nanoseconds fully.
 	// [1]: https://tools.ietf.org/html/rfc5280#section-4.1.2.5.2
 	// TODO: handle leap seconds if dt becomes leap second aware
+	// The components come from a valid time, so they are in range
+	#[allow(clippy::expect_used)]
 	let time =
 		Time::from_hms(dt.hour(), dt.minute(), dt.second()).expect("invalid or out-of-range time");
 	dt.replace_time(time)
@@ -789,9 +1235,12 @@ fn write_distinguished_name(writer: DERWriter, dn: &DistinguishedName) {
 
 						DnValue::Ia5String(s) => writer.next().write_ia5_string(s.as_str()),
 
-						DnValue::PrintableString(s) => {
-							writer.next().write_printable_string(s.as_str())
-						},
+						// yasna's `write_printable_string` rejects '?', which is a valid character
+						DnValue::PrintableString(s) => writer
+							.next()
+							.write_tagged_implicit(TAG_PRINTABLESTRING, |writer| {
+								writer.write_bytes(s.as_str().as_bytes())
+							}),
 						DnValue::TeletexString(s) => writer
 							.next()
 							.write_tagged_implicit(TAG_TELETEXSTRING, |writer| {
@@ -839,7 +1288,14 @@ fn write_x509_extension(
 }
 
 /// Serializes an X.509v3 authority key identifier extension according to RFC 5280.
-fn write_x509_authority_key_identifier(writer: DERWriter, aki: Vec<u8>) {
+///
+/// `cert_issuer` is the issuer name and serial number of the issuer's certificate.
+fn write_x509_authority_key_identifier(
+	writer: DERWriter,
+	key_id: Option<&[u8]>,
+	cert_issuer: Option<(&DistinguishedName, &[u8])>,
+	critical: bool,
+) {
 	// Write Authority Key Identifier
 	// RFC 5280 states:
 	//   'The keyIdentifier field of the authorityKeyIdentifier extension MUST
@@ -850,11 +1306,29 @@ fn write_x509_authority_key_identifier(writer: DERWriter, aki: Vec<u8>) {
 	// In addition, for CRLs:
 	//    'Conforming CRL issuers MUST use the key identifier method, and MUST
 	//     include this extension in all CRLs issued.'
-	write_x509_extension(writer, oid::AUTHORITY_KEY_IDENTIFIER, false, |writer| {
+	write_x509_extension(writer, oid::AUTHORITY_KEY_IDENTIFIER, critical, |writer| {
 		writer.write_sequence(|writer| {
-			writer
-				.next()
-				.write_tagged_implicit(Tag::context(0), |writer| writer.write_bytes(&aki))
+			if let Some(key_id) = key_id {
+				writer
+					.next()
+					.write_tagged_implicit(Tag::context(0), |writer| writer.write_bytes(key_id));
+			}
+			if let Some((name, serial)) = cert_issuer {
+				writer
+					.next()
+					.write_tagged_implicit(Tag::context(1), |writer| {
+						writer.write_sequence(|writer| {
+							writer.next().write_tagged(Tag::context(4), |writer| {
+								write_distinguished_name(writer, name)
+							})
+						})
+					});
+				writer
+					.next()
+					.write_tagged_implicit(Tag::context(2), |writer| {
+						writer.write_bigint_bytes(serial, true)
+					});
+			}
 		});
 	});
 }
@@ -889,6 +1363,15 @@ impl SerialNumber {
 	pub fn len(&self) -> usize {
 		self.inner.len()
 	}
+
+	#[cfg(feature = "x509-parser")]
+	fn from_x509(x509: &x509_parser::certificate::X509Certificate<'_>) -> Result<Self, Error> {
+		// The DER encoding of an INTEGER always has at least one content octet.
+		match x509.raw_serial() {
+			[] => Err(Error::X509("empty serial number".to_string())),
+			raw => Ok(Self::from_slice(raw)),
+		}
+	}
 }
 
 impl fmt::Display for SerialNumber {
@@ -981,6 +1464,360 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn signature_algo_metadata() {
+		for alg in SignatureAlgorithm::iter() {
+			assert_eq!(format!("{alg:?}"), format!("PKCS_{}", alg.name()));
+		}
+
+		let alg = &PKCS_ECDSA_P384_SHA384;
+		assert_eq!(alg.name(), "ECDSA_P384_SHA384");
+		assert_eq!(alg.oid_string(), "1.2.840.10045.4.3.3");
+		assert_eq!(alg.key_oid_string(), "1.2.840.10045.2.1");
+		assert_eq!(alg.digest(), Some(DigestAlgorithm::Sha384));
+
+		assert_eq!(PKCS_RSA_SHA512.digest(), Some(DigestAlgorithm::Sha512));
+		assert_eq!(PKCS_RSA_SHA512.key_oid_string(), "1.2.840.113549.1.1.1");
+		assert_eq!(PKCS_ED25519.digest(), None);
+		assert_eq!(PKCS_ED25519.oid_string(), "1.3.101.112");
+		assert_eq!(PKCS_ED448.oid_string(), "1.3.101.113");
+		assert_eq!(PKCS_ED448.key_oid_string(), "1.3.101.113");
+	}
+
+	#[test]
+	fn signature_algo_from_str() {
+		for alg in SignatureAlgorithm::iter() {
+			for s in [alg.name().to_owned(), format!("{alg:?}"), alg.to_string()] {
+				assert_eq!(s.parse::<&SignatureAlgorithm>(), Ok(*alg), "{s}");
+			}
+
+			// Algorithms sharing an OID resolve to the same one
+			let by_oid = alg.oid_string().parse::<&SignatureAlgorithm>().unwrap();
+			assert_eq!(by_oid.oid_string(), alg.oid_string());
+			assert_eq!(SignatureAlgorithm::from_oid(alg.oid_components), Ok(by_oid));
+		}
+
+		assert_eq!(
+			"1.2.840.10045.4.3.2".parse::<&SignatureAlgorithm>(),
+			Ok(&PKCS_ECDSA_P256_SHA256)
+		);
+		assert_eq!(
+			"1.2.840.113549.1.1.10".parse::<&SignatureAlgorithm>(),
+			Ok(&PKCS_RSA_PSS_SHA256)
+		);
+		for s in [
+			"",
+			"ECDSA",
+			"ecdsa_p256_sha256",
+			"1.2.840.113549.1.1.5",
+			"1.2.x",
+		] {
+			assert_eq!(
+				s.parse::<&SignatureAlgorithm>(),
+				Err(Error::UnsupportedSignatureAlgorithm),
+				"{s}"
+			);
+		}
+	}
+
+	#[test]
+	fn signature_algo_from_str_all_constants() {
+		let algs: &[&SignatureAlgorithm] = &[
+			&PKCS_RSA_SHA256,
+			&PKCS_RSA_SHA384,
+			&PKCS_RSA_SHA512,
+			&PKCS_RSA_PSS_SHA256,
+			&PKCS_RSA_PSS_SHA384,
+			&PKCS_RSA_PSS_SHA512,
+			&PKCS_ECDSA_P256_SHA256,
+			&PKCS_ECDSA_P256_SHA384,
+			&PKCS_ECDSA_P384_SHA256,
+			&PKCS_ECDSA_P384_SHA384,
+			&PKCS_ECDSA_P521_SHA256,
+			&PKCS_ECDSA_P521_SHA384,
+			&PKCS_ECDSA_P521_SHA512,
+			&PKCS_ECDSA_K256_SHA256,
+			&PKCS_ED25519,
+			&PKCS_ED448,
+			#[cfg(feature = "insecure_legacy_algorithms")]
+			&PKCS_RSA_SHA1,
+			#[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
+			&PKCS_ML_DSA_44,
+			#[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
+			&PKCS_ML_DSA_65,
+			#[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
+			&PKCS_ML_DSA_87,
+			#[cfg(all(feature = "composite_signatures_unstable", not(feature = "fips")))]
+			&PKCS_MLDSA44_ED25519,
+			#[cfg(all(feature = "composite_signatures_unstable", not(feature = "fips")))]
+			&PKCS_MLDSA44_ECDSA_P256_SHA256,
+			#[cfg(all(feature = "composite_signatures_unstable", not(feature = "fips")))]
+			&PKCS_MLDSA65_ED25519,
+		];
+		for alg in algs {
+			for s in [
+				alg.name().to_owned(),
+				format!("PKCS_{}", alg.name()),
+				alg.to_string(),
+			] {
+				let parsed = s.parse::<&SignatureAlgorithm>().unwrap();
+				// Compare the names, as `PartialEq` only looks at the OIDs
+				assert_eq!(parsed.name(), alg.name(), "{s}");
+				assert_eq!(parsed, *alg, "{s}");
+			}
+		}
+	}
+
+	#[test]
+	fn signature_algo_display() {
+		// These strings are relied upon by tooling, don't change them
+		for (alg, display) in [
+			(&PKCS_RSA_SHA256, "RSA PKCS#1 SHA-256"),
+			(&PKCS_RSA_SHA384, "RSA PKCS#1 SHA-384"),
+			(&PKCS_RSA_SHA512, "RSA PKCS#1 SHA-512"),
+			(&PKCS_RSA_PSS_SHA256, "RSA-PSS SHA-256"),
+			(&PKCS_RSA_PSS_SHA384, "RSA-PSS SHA-384"),
+			(&PKCS_RSA_PSS_SHA512, "RSA-PSS SHA-512"),
+			(&PKCS_ECDSA_P256_SHA256, "ECDSA P-256 with SHA-256"),
+			(&PKCS_ECDSA_P384_SHA384, "ECDSA P-384 with SHA-384"),
+			(&PKCS_ECDSA_P256_SHA384, "ECDSA P-256 with SHA-384"),
+			(&PKCS_ECDSA_P384_SHA256, "ECDSA P-384 with SHA-256"),
+			(&PKCS_ECDSA_P521_SHA256, "ECDSA P-521 with SHA-256"),
+			(&PKCS_ECDSA_P521_SHA384, "ECDSA P-521 with SHA-384"),
+			(&PKCS_ECDSA_P521_SHA512, "ECDSA P-521 with SHA-512"),
+			(&PKCS_ECDSA_K256_SHA256, "ECDSA secp256k1 with SHA-256"),
+			(&PKCS_ED25519, "Ed25519"),
+			(&PKCS_ED448, "Ed448"),
+			#[cfg(feature = "insecure_legacy_algorithms")]
+			(&PKCS_RSA_SHA1, "RSA PKCS#1 SHA-1"),
+			#[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
+			(&PKCS_ML_DSA_44, "ML-DSA-44"),
+			#[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
+			(&PKCS_ML_DSA_65, "ML-DSA-65"),
+			#[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
+			(&PKCS_ML_DSA_87, "ML-DSA-87"),
+			#[cfg(all(feature = "composite_signatures_unstable", not(feature = "fips")))]
+			(
+				&PKCS_MLDSA44_ECDSA_P256_SHA256,
+				"Composite ML-DSA-44 and ECDSA P-256 with SHA-256",
+			),
+		] {
+			assert_eq!(alg.to_string(), display);
+		}
+	}
+
+	#[test]
+	fn signature_algo_supported() {
+		let supported = SignatureAlgorithm::supported().collect::<Vec<_>>();
+		let generate = supported
+			.iter()
+			.copied()
+			.filter(|alg| alg.can_generate())
+			.collect::<Vec<_>>();
+		let load = supported
+			.iter()
+			.copied()
+			.filter(|alg| alg.can_load())
+			.collect::<Vec<_>>();
+		for alg in SignatureAlgorithm::iter() {
+			assert_eq!(alg.is_supported(), supported.contains(alg), "{alg:?}");
+		}
+
+		#[cfg(all(feature = "ring", not(feature = "aws_lc_rs")))]
+		{
+			let ec_ed: &[&SignatureAlgorithm] = &[
+				&PKCS_ECDSA_P256_SHA256,
+				&PKCS_ECDSA_P384_SHA384,
+				&PKCS_ED25519,
+				#[cfg(feature = "ed448")]
+				&PKCS_ED448,
+			];
+			let rsa: &[&SignatureAlgorithm] = &[
+				&PKCS_RSA_SHA256,
+				&PKCS_RSA_SHA384,
+				&PKCS_RSA_SHA512,
+				&PKCS_RSA_PSS_SHA256,
+				&PKCS_RSA_PSS_SHA384,
+				&PKCS_RSA_PSS_SHA512,
+			];
+			assert_eq!(supported, [rsa, ec_ed].concat());
+			assert_eq!(generate, ec_ed);
+			assert_eq!(load, supported);
+		}
+
+		#[cfg(feature = "aws_lc_rs")]
+		{
+			let loadable: &[&SignatureAlgorithm] = &[
+				&PKCS_RSA_SHA256,
+				&PKCS_RSA_SHA384,
+				&PKCS_RSA_SHA512,
+				&PKCS_RSA_PSS_SHA256,
+				&PKCS_RSA_PSS_SHA384,
+				&PKCS_RSA_PSS_SHA512,
+				&PKCS_ECDSA_P256_SHA256,
+				&PKCS_ECDSA_P384_SHA384,
+				&PKCS_ECDSA_P521_SHA256,
+				&PKCS_ECDSA_P521_SHA384,
+				&PKCS_ECDSA_P521_SHA512,
+				&PKCS_ECDSA_K256_SHA256,
+				&PKCS_ED25519,
+				#[cfg(feature = "ed448")]
+				&PKCS_ED448,
+			];
+			let generate_only: &[&SignatureAlgorithm] = &[
+				#[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
+				&PKCS_ML_DSA_44,
+				#[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
+				&PKCS_ML_DSA_65,
+				#[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
+				&PKCS_ML_DSA_87,
+			];
+			assert_eq!(supported, [loadable, generate_only].concat());
+			assert_eq!(generate, supported);
+			assert_eq!(load, loadable);
+		}
+
+		#[cfg(not(feature = "crypto"))]
+		{
+			assert!(supported.is_empty());
+			assert!(generate.is_empty());
+			assert!(load.is_empty());
+		}
+	}
+
+	#[cfg(feature = "crypto")]
+	#[test]
+	fn certificate_signature_algorithm() {
+		let key_pair = KeyPair::generate_for(&PKCS_ED25519).unwrap();
+		let cert = CertificateParams::default().self_signed(&key_pair).unwrap();
+		assert_eq!(cert.signature_algorithm(), &PKCS_ED25519);
+	}
+
+	#[cfg(feature = "x509-parser")]
+	#[test]
+	fn distinguished_name_round_trip() {
+		use x509_parser::prelude::FromDer;
+		use x509_parser::x509::X509Name;
+		use yasna::tags::{TAG_IA5STRING, TAG_PRINTABLESTRING, TAG_UTF8STRING};
+
+		const C: &[u64] = &[2, 5, 4, 6];
+		const ST: &[u64] = &[2, 5, 4, 8];
+		const L: &[u64] = &[2, 5, 4, 7];
+		const O: &[u64] = &[2, 5, 4, 10];
+		const OU: &[u64] = &[2, 5, 4, 11];
+		const CN: &[u64] = &[2, 5, 4, 3];
+		const SERIAL: &[u64] = &[2, 5, 4, 5];
+		const BUSINESS_CATEGORY: &[u64] = &[2, 5, 4, 15];
+		const JURISDICTION_C: &[u64] = &[1, 3, 6, 1, 4, 1, 311, 60, 2, 1, 3];
+		const EMAIL: &[u64] = &[1, 2, 840, 113549, 1, 9, 1];
+
+		let utf16 = |s: &str| {
+			s.encode_utf16()
+				.flat_map(u16::to_be_bytes)
+				.collect::<Vec<_>>()
+		};
+		let utf32 = |s: &str| s.chars().flat_map(|c| u32::from(c).to_be_bytes()).collect();
+		let printable = |s: &str| (TAG_PRINTABLESTRING, s.as_bytes().to_vec());
+		let teletex = |s: &str| (TAG_TELETEXSTRING, s.as_bytes().to_vec());
+		let utf8 = |s: &str| (TAG_UTF8STRING, s.as_bytes().to_vec());
+
+		type Attr = (&'static [u64], (yasna::Tag, Vec<u8>));
+		let names: [Vec<Attr>; 6] = [
+			// Entrust.net, with T61String for everything but the organization
+			vec![
+				(O, printable("Entrust.net")),
+				(
+					OU,
+					teletex("www.entrust.net/CPS_2048 incorp. by ref. (limits liab.)"),
+				),
+				(OU, teletex("(c) 1999 Entrust.net Limited")),
+				(CN, teletex("Entrust.net Certification Authority (2048)")),
+			],
+			// VeriSign, with two organizational units
+			vec![
+				(C, printable("US")),
+				(O, printable("VeriSign, Inc.")),
+				(OU, printable("VeriSign Trust Network")),
+				(
+					OU,
+					printable("(c) 2006 VeriSign, Inc. - For authorized use only"),
+				),
+				(
+					CN,
+					printable("VeriSign Class 3 Public Primary Certification Authority - G5"),
+				),
+			],
+			// Thawte, with an IA5String email address
+			vec![
+				(C, printable("ZA")),
+				(ST, printable("Western Cape")),
+				(L, printable("Cape Town")),
+				(O, printable("Thawte Consulting cc")),
+				(OU, printable("Certification Services Division")),
+				(CN, printable("Thawte Premium Server CA")),
+				(
+					EMAIL,
+					(TAG_IA5STRING, b"premium-server@thawte.com".to_vec()),
+				),
+			],
+			// Mixed PrintableString and UTF8String
+			vec![
+				(C, printable("US")),
+				(O, utf8("Let's Encrypt")),
+				(CN, printable("R3")),
+			],
+			// EV subject with a UTF8String jurisdiction country
+			vec![
+				(BUSINESS_CATEGORY, utf8("Private Organization")),
+				(JURISDICTION_C, utf8("DE")),
+				(SERIAL, printable("HRB 12345")),
+				(C, printable("DE")),
+				(O, utf8("Krabben GmbH")),
+			],
+			// BMPString and UniversalString
+			vec![
+				(C, printable("CN")),
+				(O, (TAG_BMPSTRING, utf16("Crab Widgits Ltd"))),
+				(CN, (TAG_UNIVERSALSTRING, utf32("Crab Root CA"))),
+			],
+		];
+
+		for attrs in names {
+			let der = yasna::construct_der(|writer| {
+				writer.write_sequence(|writer| {
+					for (oid, (tag, value)) in &attrs {
+						writer.next().write_set(|writer| {
+							writer.next().write_sequence(|writer| {
+								writer.next().write_oid(&ObjectIdentifier::from_slice(oid));
+								writer.next().write_tagged_implicit(*tag, |writer| {
+									writer.write_bytes(value)
+								});
+							});
+						});
+					}
+				});
+			});
+
+			let (_, name) = X509Name::from_der(&der).unwrap();
+			let dn = DistinguishedName::from_name(&name).unwrap();
+			assert_eq!(dn.iter().count(), attrs.len());
+			let serialized = yasna::construct_der(|writer| write_distinguished_name(writer, &dn));
+			assert_eq!(serialized, der, "{dn:?}");
+		}
+
+		// Found by the ca_cert_der fuzzer: x509-parser reads the OID as 4.11.15228.243.7,
+		// which can't be written again
+		let der = [
+			0x30, 0x12, 0x31, 0x10, 0x30, 0x0e, 0x06, 0x05, 0xab, 0xfb, 0xfc, 0xf3, 0x07, 0x0c,
+			0x05, 0x43, 0x72, 0x61, 0x62, 0x73,
+		];
+		let (_, name) = X509Name::from_der(&der).unwrap();
+		assert_eq!(
+			DistinguishedName::from_name(&name).unwrap_err(),
+			Error::CouldNotParseCertificate
+		);
+	}
+
 	#[cfg(feature = "x509-parser")]
 	mod test_ip_address_from_octets {
 		use std::net::IpAddr;
@@ -0,0 +1,140 @@
+use std::fmt;
+
+#[cfg(any(feature = "crypto", feature = "x509-parser"))]
+use crate::Error;
+#[cfg(any(feature = "crypto", feature = "x509-parser"))]
+use crate::SanType;
+
+/// Upper bounds on untrusted input accepted by the `*_with_limits` parsing functions
+///
+/// The defaults are generous for real-world certificates, requests and keys, while keeping
+/// crafted input from causing large allocations or slow parses. Each limit can be adjusted
+/// individually:
+///
+/// ```
+/// let mut limits = rcgen::ParseLimits::default();
+/// limits.max_subject_alt_names = 16;
+/// ```
+///
+/// Exceeding a limit results in [`Error::ParseLimitExceeded`](crate::Error::ParseLimitExceeded).
+/// What well-formed input is accepted is decided by [`ParseOptions`] instead.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ParseLimits {
+	/// Maximum size in bytes of the input, applied to both the PEM text and the DER
+	pub max_input_len: usize,
+	/// Maximum number of subject alternative names
+	pub max_subject_alt_names: usize,
+	/// Maximum number of extensions
+	pub max_extensions: usize,
+	/// Maximum number of attributes of a certificate signing request
+	pub max_attributes: usize,
+	/// Maximum size in bytes of the value of a single distinguished name attribute
+	pub max_dn_attribute_len: usize,
+}
+
+impl ParseLimits {
+	/// No limits at all, as used by the parsing functions without a `_with_limits` suffix
+	#[cfg(any(feature = "crypto", feature = "x509-parser"))]
+	pub(crate) const UNLIMITED: Self = Self {
+		max_input_len: usize::MAX,
+		max_subject_alt_names: usize::MAX,
+		max_extensions: usize::MAX,
+		max_attributes: usize::MAX,
+		max_dn_attribute_len: usize::MAX,
+	};
+
+	#[cfg(any(feature = "crypto", feature = "x509-parser"))]
+	pub(crate) fn check(&self, limit: ParseLimit, value: usize) -> Result<(), Error> {
+		let max = match limit {
+			ParseLimit::InputLength => self.max_input_len,
+			ParseLimit::SubjectAltNames => self.max_subject_alt_names,
+			ParseLimit::Extensions => self.max_extensions,
+			ParseLimit::Attributes => self.max_attributes,
+			ParseLimit::DnAttributeLength => self.max_dn_attribute_len,
+		};
+		if value > max {
+			return Err(Error::ParseLimitExceeded(limit));
+		}
+		Ok(())
+	}
+
+	#[cfg(feature = "x509-parser")]
+	pub(crate) fn check_name(&self, name: &x509_parser::x509::X509Name<'_>) -> Result<(), Error> {
+		for attr in name.iter_attributes() {
+			self.check(ParseLimit::DnAttributeLength, attr.attr_value().data.len())?;
+		}
+		Ok(())
+	}
+}
+
+impl Default for ParseLimits {
+	fn default() -> Self {
+		Self {
+			max_input_len: 64 * 1024,
+			max_subject_alt_names: 1024,
+			max_extensions: 64,
+			max_attributes: 16,
+			max_dn_attribute_len: 1024,
+		}
+	}
+}
+
+/// Policies on the contents of the input accepted by the `*_with_options` parsing functions
+///
+/// Unlike [`ParseLimits`], these don't bound the resources spent on parsing, but decide which
+/// well-formed input is accepted. The defaults accept everything rcgen can represent:
+///
+/// ```
+/// let mut options = rcgen::ParseOptions::default();
+/// options.reject_raw_names = true;
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ParseOptions {
+	/// Whether to reject alternative names that would be kept as
+	/// [`SanType::Raw`](crate::SanType::Raw), like an x400Address, with
+	/// [`Error::InvalidNameType`](crate::Error::InvalidNameType)
+	///
+	/// This is off by default, so that a name rcgen can't interpret doesn't make the whole
+	/// input unusable.
+	pub reject_raw_names: bool,
+}
+
+impl ParseOptions {
+	#[cfg(any(feature = "crypto", feature = "x509-parser"))]
+	pub(crate) fn check_raw_names(&self, names: &[SanType]) -> Result<(), Error> {
+		if self.reject_raw_names && names.iter().any(|name| matches!(name, SanType::Raw { .. })) {
+			return Err(Error::InvalidNameType);
+		}
+		Ok(())
+	}
+}
+
+/// The limit of [`ParseLimits`] that was exceeded
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseLimit {
+	/// [`ParseLimits::max_input_len`]
+	InputLength,
+	/// [`ParseLimits::max_subject_alt_names`]
+	SubjectAltNames,
+	/// [`ParseLimits::max_extensions`]
+	Extensions,
+	/// [`ParseLimits::max_attributes`]
+	Attributes,
+	/// [`ParseLimits::max_dn_attribute_len`]
+	DnAttributeLength,
+}
+
+impl fmt::Display for ParseLimit {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(match self {
+			Self::InputLength => "input length",
+			Self::SubjectAltNames => "number of subject alternative names",
+			Self::Extensions => "number of extensions",
+			Self::Attributes => "number of attributes",
+			Self::DnAttributeLength => "distinguished name attribute length",
+		})
+	}
+}
@@ -0,0 +1,276 @@
+use std::fmt;
+
+use crate::oid::{EC_PUBLIC_KEY, RSA_ENCRYPTION};
+use crate::{
+	KeyUsagePurpose, PolicyInformation, PolicyQualifier, SerialNumber, SignatureAlgorithm,
+};
+
+/// A problem with certificate or CRL parameters that doesn't prevent serialization,
+/// but that some validators are known to reject
+///
+/// Returned by [`CertificateParams::lint()`](crate::CertificateParams::lint) and
+/// [`CertificateRevocationListParams::lint()`](crate::CertificateRevocationListParams::lint).
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Lint {
+	/// A key usage was requested that the subject key's algorithm can't perform,
+	/// like keyEncipherment on an Ed25519 key
+	KeyUsageIncompatibleWithKey {
+		/// The offending key usage
+		usage: KeyUsagePurpose,
+		/// The algorithm of the subject key
+		algorithm: &'static SignatureAlgorithm,
+	},
+	/// encipherOnly or decipherOnly was requested without keyAgreement,
+	/// in which case its meaning is undefined
+	KeyUsageRequiresKeyAgreement(KeyUsagePurpose),
+	/// The revocation time of the certificate with this serial number is after the
+	/// thisUpdate time of the CRL, i.e. in the future from the point of view of the CRL
+	RevocationAfterThisUpdate(SerialNumber),
+	/// The certificate policy with this OID appears more than once, which RFC 5280 forbids
+	DuplicateCertificatePolicy(Vec<u64>),
+	/// A user notice has an explicit text of this many characters, more than the 200 that
+	/// RFC 5280 allows
+	ExplicitTextTooLong(usize),
+	/// A user notice has a notice reference, which RFC 5280 recommends against as its
+	/// notices are rarely available to relying parties
+	NoticeReference,
+	/// The policy constraints extension has neither constraint, which RFC 5280 forbids
+	EmptyPolicyConstraints,
+}
+
+impl fmt::Display for Lint {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		use self::Lint::*;
+		match self {
+			KeyUsageIncompatibleWithKey { usage, algorithm } => write!(
+				f,
+				"Key usage {} is not applicable to {algorithm} keys",
+				key_usage_name(*usage)
+			)?,
+			KeyUsageRequiresKeyAgreement(usage) => write!(
+				f,
+				"Key usage {} is only meaningful together with keyAgreement",
+				key_usage_name(*usage)
+			)?,
+			RevocationAfterThisUpdate(serial) => write!(
+				f,
+				"Serial number {serial} is revoked after the thisUpdate time of the CRL"
+			)?,
+			DuplicateCertificatePolicy(oid) => {
+				let oid = oid.iter().map(u64::to_string).collect::<Vec<_>>();
+				write!(
+					f,
+					"Certificate policy {} appears more than once",
+					oid.join(".")
+				)?
+			},
+			ExplicitTextTooLong(len) => write!(
+				f,
+				"User notice explicit text has {len} characters, more than the allowed 200"
+			)?,
+			NoticeReference => write!(f, "User notice has a notice reference")?,
+			EmptyPolicyConstraints => write!(f, "Policy constraints without any constraint")?,
+		};
+		Ok(())
+	}
+}
+
+/// Checks that the key usages are consistent with each other and with the subject key
+pub(crate) fn lint_key_usages(
+	usages: &[KeyUsagePurpose],
+	algorithm: &'static SignatureAlgorithm,
+	lints: &mut Vec<Lint>,
+) {
+	let key_alg = algorithm.key_alg_oid();
+	// Only rsaEncryption keys can encrypt; id-RSASSA-PSS keys are restricted to signing.
+	let can_encrypt = key_alg == RSA_ENCRYPTION;
+	let can_agree = key_alg == EC_PUBLIC_KEY;
+
+	for &usage in usages {
+		let compatible = match usage {
+			KeyUsagePurpose::KeyEncipherment | KeyUsagePurpose::DataEncipherment => can_encrypt,
+			KeyUsagePurpose::KeyAgreement => can_agree,
+			_ => true,
+		};
+		if !compatible {
+			lints.push(Lint::KeyUsageIncompatibleWithKey { usage, algorithm });
+		}
+	}
+
+	if !usages.contains(&KeyUsagePurpose::KeyAgreement) {
+		for &usage in usages {
+			if matches!(
+				usage,
+				KeyUsagePurpose::EncipherOnly | KeyUsagePurpose::DecipherOnly
+			) {
+				lints.push(Lint::KeyUsageRequiresKeyAgreement(usage));
+			}
+		}
+	}
+}
+
+/// Checks the certificate policies for violations of RFC 5280
+pub(crate) fn lint_certificate_policies(policies: &[PolicyInformation], lints: &mut Vec<Lint>) {
+	for (i, policy) in policies.iter().enumerate() {
+		if policies[..i].iter().any(|other| other.oid == policy.oid) {
+			lints.push(Lint::DuplicateCertificatePolicy(policy.oid.clone()));
+		}
+
+		for qualifier in &policy.qualifiers {
+			let PolicyQualifier::UserNotice {
+				organization,
+				explicit_text,
+				..
+			} = qualifier
+			else {
+				continue;
+			};
+			if organization.is_some() {
+				lints.push(Lint::NoticeReference);
+			}
+			match explicit_text.as_ref().map(|text| text.chars().count()) {
+				Some(len) if len > 200 => lints.push(Lint::ExplicitTextTooLong(len)),
+				_ => {},
+			}
+		}
+	}
+}
+
+/// The name of the key usage bit as written in RFC 5280
+fn key_usage_name(usage: KeyUsagePurpose) -> &'static str {
+	match usage {
+		KeyUsagePurpose::DigitalSignature => "digitalSignature",
+		KeyUsagePurpose::ContentCommitment => "contentCommitment",
+		KeyUsagePurpose::KeyEncipherment => "keyEncipherment",
+		KeyUsagePurpose::DataEncipherment => "dataEncipherment",
+		KeyUsagePurpose::KeyAgreement => "keyAgreement",
+		KeyUsagePurpose::KeyCertSign => "keyCertSign",
+		KeyUsagePurpose::CrlSign => "cRLSign",
+		KeyUsagePurpose::EncipherOnly => "encipherOnly",
+		KeyUsagePurpose::DecipherOnly => "decipherOnly",
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{PKCS_ECDSA_P256_SHA256, PKCS_ED25519, PKCS_RSA_PSS_SHA256, PKCS_RSA_SHA256};
+
+	fn lint(usages: &[KeyUsagePurpose], algorithm: &'static SignatureAlgorithm) -> Vec<Lint> {
+		let mut lints = Vec::new();
+		lint_key_usages(usages, algorithm, &mut lints);
+		lints
+	}
+
+	#[test]
+	fn encipherment_requires_rsa() {
+		let usages = [
+			KeyUsagePurpose::DigitalSignature,
+			KeyUsagePurpose::KeyEncipherment,
+			KeyUsagePurpose::DataEncipherment,
+		];
+		assert!(lint(&usages, &PKCS_RSA_SHA256).is_empty());
+		for algorithm in [&PKCS_ED25519, &PKCS_ECDSA_P256_SHA256, &PKCS_RSA_PSS_SHA256] {
+			assert_eq!(
+				lint(&usages, algorithm),
+				vec![
+					Lint::KeyUsageIncompatibleWithKey {
+						usage: KeyUsagePurpose::KeyEncipherment,
+						algorithm,
+					},
+					Lint::KeyUsageIncompatibleWithKey {
+						usage: KeyUsagePurpose::DataEncipherment,
+						algorithm,
+					},
+				]
+			);
+		}
+	}
+
+	#[test]
+	fn key_agreement_requires_ec() {
+		let usages = [KeyUsagePurpose::KeyAgreement];
+		assert!(lint(&usages, &PKCS_ECDSA_P256_SHA256).is_empty());
+		for algorithm in [&PKCS_ED25519, &PKCS_RSA_SHA256] {
+			assert_eq!(
+				lint(&usages, algorithm),
+				vec![Lint::KeyUsageIncompatibleWithKey {
+					usage: KeyUsagePurpose::KeyAgreement,
+					algorithm,
+				}]
+			);
+		}
+	}
+
+	#[test]
+	fn encipher_only_requires_key_agreement() {
+		let usages = [
+			KeyUsagePurpose::DigitalSignature,
+			KeyUsagePurpose::DecipherOnly,
+		];
+		let lints = lint(&usages, &PKCS_ECDSA_P256_SHA256);
+		assert_eq!(
+			lints,
+			vec![Lint::KeyUsageRequiresKeyAgreement(
+				KeyUsagePurpose::DecipherOnly
+			)]
+		);
+		assert_eq!(
+			lints[0].to_string(),
+			"Key usage decipherOnly is only meaningful together with keyAgreement"
+		);
+
+		let usages = [KeyUsagePurpose::KeyAgreement, KeyUsagePurpose::EncipherOnly];
+		assert!(lint(&usages, &PKCS_ECDSA_P256_SHA256).is_empty());
+	}
+
+	#[test]
+	fn certificate_policies() {
+		let notice = |organization: Option<&str>, text: &str| PolicyQualifier::UserNotice {
+			organization: organization.map(str::to_owned),
+			notice_numbers: vec![1],
+			explicit_text: Some(text.to_owned()),
+		};
+		let policy = |oid: &[u64], qualifiers| PolicyInformation {
+			oid: oid.to_vec(),
+			qualifiers,
+		};
+		let lint = |policies: &[PolicyInformation]| {
+			let mut lints = Vec::new();
+			lint_certificate_policies(policies, &mut lints);
+			lints
+		};
+
+		let compliant = [
+			policy(
+				&[2, 23, 140, 1, 2, 1],
+				vec![
+					PolicyQualifier::CpsUri("https://crabs.example/cps".to_owned()),
+					notice(None, &"🦀".repeat(200)),
+				],
+			),
+			PolicyInformation::any_policy(Vec::new()),
+		];
+		assert!(lint(&compliant).is_empty());
+
+		let policies = [
+			policy(&[2, 23, 140, 1, 2, 1], Vec::new()),
+			policy(&[2, 23, 140, 1, 2, 1], vec![notice(None, &"a".repeat(201))]),
+			policy(&[1, 2, 3], vec![notice(Some("Crabs"), "Crabs only")]),
+		];
+		let lints = lint(&policies);
+		assert_eq!(
+			lints,
+			vec![
+				Lint::DuplicateCertificatePolicy(vec![2, 23, 140, 1, 2, 1]),
+				Lint::ExplicitTextTooLong(201),
+				Lint::NoticeReference,
+			]
+		);
+		assert_eq!(
+			lints[0].to_string(),
+			"Certificate policy 2.23.140.1.2.1 appears more than once"
+		);
+	}
+}
@@ -0,0 +1,619 @@
+//! Checking the names of a certificate against the name constraints of its issuer
+//!
+//! The matching rules are the ones of
+//! [RFC 5280 section 4.2.1.10](https://www.rfc-editor.org/rfc/rfc5280#section-4.2.1.10).
+
+use std::net::IpAddr;
+
+use crate::{
+	oid, CertificateParams, CidrSubnet, DistinguishedName, DnType, DnValue, Error, GeneralSubtree,
+	NameConstraints, SanType,
+};
+
+/// Checks the subject and subject alternative names of `params` against `constraints`
+///
+/// Names given as [`SanType::Raw`], [`SanType::OtherName`] and [`SanType::RegisteredId`] are
+/// not checked, as no subtree can constrain them.
+pub(crate) fn check(
+	constraints: &NameConstraints,
+	params: &CertificateParams,
+) -> Result<(), Error> {
+	let dn = &params.distinguished_name;
+	if dn.iter().next().is_some() {
+		check_dn(constraints, dn)?;
+	}
+
+	let mut has_email = false;
+	for san in &params.subject_alt_names {
+		match san {
+			SanType::DnsName(name) => check_dns(constraints, name.as_str())?,
+			SanType::Rfc822Name(email) => {
+				has_email = true;
+				check_email(constraints, email.as_str())?;
+			},
+			SanType::URI(uri) => check_name(
+				constraints,
+				|| format!("URI {uri}"),
+				|subtree| match subtree {
+					GeneralSubtree::URI(base) => {
+						Some(uri_host(uri.as_str()).is_some_and(|host| host_within(host, base)))
+					},
+					_ => None,
+				},
+			)?,
+			SanType::IpAddress(addr) => check_name(
+				constraints,
+				|| format!("IP address {addr}"),
+				|subtree| match subtree {
+					GeneralSubtree::IpAddress(subnet) => Some(ip_within(*addr, subnet)),
+					_ => None,
+				},
+			)?,
+			SanType::DirectoryName(name) => check_dn(constraints, name)?,
+			SanType::OtherName(_) | SanType::RegisteredId(_) | SanType::Raw { .. } => {},
+		}
+	}
+
+	// Legacy email addresses in the subject are constrained like rfc822Name SANs, unless
+	// there are any of them
+	if !has_email {
+		let email_type = DnType::CustomDnType(oid::EMAIL_ADDRESS.to_vec());
+		for (ty, value) in dn.iter() {
+			if let (true, Some(email)) = (*ty == email_type, dn_value_text(value)) {
+				check_email(constraints, &email)?;
+			}
+		}
+	}
+
+	Ok(())
+}
+
+fn check_dn(constraints: &NameConstraints, dn: &DistinguishedName) -> Result<(), Error> {
+	check_name(
+		constraints,
+		|| format!("directory name {}", describe_dn(dn)),
+		|subtree| match subtree {
+			GeneralSubtree::DirectoryName(base) => Some(dn_within(dn, base)),
+			_ => None,
+		},
+	)
+}
+
+fn check_dns(constraints: &NameConstraints, name: &str) -> Result<(), Error> {
+	check_name(
+		constraints,
+		|| format!("DNS name {name}"),
+		|subtree| match subtree {
+			GeneralSubtree::DnsName(base) => Some(dns_within(name, base)),
+			_ => None,
+		},
+	)?;
+
+	// A wildcard also covers names in excluded subtrees one label below its parent domain
+	if let Some(parent) = name.strip_prefix("*.") {
+		for subtree in &constraints.excluded_subtrees {
+			let GeneralSubtree::DnsName(base) = subtree else {
+				continue;
+			};
+			let covered =
+				base.trim_start_matches('.')
+					.split_once('.')
+					.is_some_and(|(label, rest)| {
+						!label.is_empty() && rest.eq_ignore_ascii_case(parent)
+					});
+			if covered {
+				return Err(excluded(&format!("DNS name {name}"), subtree));
+			}
+		}
+	}
+
+	Ok(())
+}
+
+fn check_email(constraints: &NameConstraints, email: &str) -> Result<(), Error> {
+	check_name(
+		constraints,
+		|| format!("email address {email}"),
+		|subtree| match subtree {
+			GeneralSubtree::Rfc822Name(base) => Some(email_within(email, base)),
+			_ => None,
+		},
+	)
+}
+
+/// Checks a name against the subtrees of its form, for which `within` returns `Some`
+///
+/// A name violates the constraints if it's within any of the excluded subtrees, or if there
+/// are permitted subtrees of its form and it's not within any of them.
+fn check_name(
+	constraints: &NameConstraints,
+	name: impl Fn() -> String,
+	within: impl Fn(&GeneralSubtree) -> Option<bool>,
+) -> Result<(), Error> {
+	if let Some(subtree) = constraints
+		.excluded_subtrees
+		.iter()
+		.find(|subtree| within(subtree) == Some(true))
+	{
+		return Err(excluded(&name(), subtree));
+	}
+
+	let mut permitted = constraints
+		.permitted_subtrees
+		.iter()
+		.filter_map(|subtree| Some((subtree, within(subtree)?)))
+		.peekable();
+	if permitted.peek().is_none() {
+		return Ok(());
+	}
+	let mut subtrees = Vec::new();
+	for (subtree, within) in permitted {
+		if within {
+			return Ok(());
+		}
+		subtrees.push(describe_subtree(subtree));
+	}
+
+	Err(Error::NameConstraintViolation(format!(
+		"{} is not within any of the permitted subtrees {}",
+		name(),
+		subtrees.join(", ")
+	)))
+}
+
+fn excluded(name: &str, subtree: &GeneralSubtree) -> Error {
+	Error::NameConstraintViolation(format!(
+		"{name} is within the excluded subtree {}",
+		describe_subtree(subtree)
+	))
+}
+
+/// A DNS name is within a subtree if it can be formed by adding labels to its left
+///
+/// As commonly done for DNS names, and as required for email addresses and URIs, a subtree
+/// with a leading period only contains the subdomains, not the domain itself.
+fn dns_within(name: &str, base: &str) -> bool {
+	if base.is_empty() {
+		return true;
+	}
+
+	let name = name.to_ascii_lowercase();
+	let base = base.to_ascii_lowercase();
+	match base.starts_with('.') {
+		true => name.ends_with(&base),
+		false => name == base || name.ends_with(&format!(".{base}")),
+	}
+}
+
+/// An email address is within a subtree that is either the mailbox itself, all mailboxes
+/// on a host, or all mailboxes on the subdomains of a domain if it has a leading period
+fn email_within(email: &str, base: &str) -> bool {
+	let Some((local, host)) = email.rsplit_once('@') else {
+		return false;
+	};
+
+	match base.rsplit_once('@') {
+		Some((base_local, base_host)) => {
+			local == base_local && host.eq_ignore_ascii_case(base_host)
+		},
+		None => host_within(host, base),
+	}
+}
+
+/// A host is within a subtree that is either the host itself, or any subdomain of a domain
+/// if it has a leading period
+fn host_within(host: &str, base: &str) -> bool {
+	match base.starts_with('.') {
+		true => {
+			host.len() > base.len()
+				&& host
+					.to_ascii_lowercase()
+					.ends_with(&base.to_ascii_lowercase())
+		},
+		false => host.eq_ignore_ascii_case(base),
+	}
+}
+
+/// The host of a URI with an authority component, without user info and port
+fn uri_host(uri: &str) -> Option<&str> {
+	let (_scheme, rest) = uri.split_once("://")?;
+	let authority = rest.split(['/', '?', '#']).next()?;
+	let host_port = authority
+		.rsplit_once('@')
+		.map_or(authority, |(_, host)| host);
+	let host = match host_port.starts_with('[') {
+		// IP literals aren't domain names, which are all a subtree can constrain
+		true => return None,
+		false => host_port
+			.split_once(':')
+			.map_or(host_port, |(host, _port)| host),
+	};
+	(!host.is_empty()).then_some(host)
+}
+
+fn ip_within(addr: IpAddr, subnet: &CidrSubnet) -> bool {
+	fn masked_eq(addr: &[u8], base: &[u8], mask: &[u8]) -> bool {
+		addr.iter()
+			.zip(base)
+			.zip(mask)
+			.all(|((addr, base), mask)| addr & mask == base & mask)
+	}
+
+	match (addr, subnet) {
+		(IpAddr::V4(addr), CidrSubnet::V4(base, mask)) => masked_eq(&addr.octets(), base, mask),
+		(IpAddr::V6(addr), CidrSubnet::V6(base, mask)) => masked_eq(&addr.octets(), base, mask),
+		_ => false,
+	}
+}
+
+/// A distinguished name is within a subtree if it starts with the attributes of the subtree
+///
+/// Attribute values are compared ignoring case and redundant whitespace.
+fn dn_within(dn: &DistinguishedName, base: &DistinguishedName) -> bool {
+	let mut attributes = dn.iter();
+	base.iter().all(|(base_ty, base_value)| {
+		attributes.next().is_some_and(|(ty, value)| {
+			ty == base_ty
+				&& match (dn_value_text(value), dn_value_text(base_value)) {
+					(Some(value), Some(base)) => normalize(&value) == normalize(&base),
+					_ => value == base_value,
+				}
+		})
+	})
+}
+
+/// Compares distinguished names attribute by attribute, like [`dn_within()`]
+#[cfg(feature = "x509-parser")]
+pub(crate) fn dn_eq(dn: &DistinguishedName, other: &DistinguishedName) -> bool {
+	dn.iter().count() == other.iter().count() && dn_within(dn, other)
+}
+
+fn normalize(value: &str) -> String {
+	value
+		.split_whitespace()
+		.collect::<Vec<_>>()
+		.join(" ")
+		.to_lowercase()
+}
+
+fn dn_value_text(value: &DnValue) -> Option<String> {
+	Some(match value {
+		DnValue::BmpString(s) => {
+			let units = s
+				.as_bytes()
+				.chunks(2)
+				.map(|pair| u16::from_be_bytes([pair[0], pair[1]]));
+			char::decode_utf16(units).collect::<Result<_, _>>().ok()?
+		},
+		DnValue::Ia5String(s) => s.to_string(),
+		DnValue::PrintableString(s) => s.to_string(),
+		DnValue::TeletexString(s) => s.to_string(),
+		DnValue::UniversalString(s) => s
+			.as_bytes()
+			.chunks(4)
+			.map(|quad| char::from_u32(u32::from_be_bytes([quad[0], quad[1], quad[2], quad[3]])))
+			.collect::<Option<_>>()?,
+		DnValue::Utf8String(s) => s.clone(),
+	})
+}
+
+pub(crate) fn describe_dn(dn: &DistinguishedName) -> String {
+	let attributes = dn
+		.iter()
+		.map(|(ty, value)| {
+			let ty = match ty {
+				DnType::CountryName => "C".to_owned(),
+				DnType::LocalityName => "L".to_owned(),
+				DnType::StateOrProvinceName => "ST".to_owned(),
+				DnType::OrganizationName => "O".to_owned(),
+				DnType::OrganizationalUnitName => "OU".to_owned(),
+				DnType::CommonName => "CN".to_owned(),
+				other => other
+					.to_oid()
+					.components()
+					.iter()
+					.map(|c| c.to_string())
+					.collect::<Vec<_>>()
+					.join("."),
+			};
+			let value = dn_value_text(value).unwrap_or_else(|| "?".to_owned());
+			format!("{ty}={value}")
+		})
+		.collect::<Vec<_>>();
+	attributes.join(", ")
+}
+
+fn describe_subtree(subtree: &GeneralSubtree) -> String {
+	match subtree {
+		GeneralSubtree::Rfc822Name(base) => format!("email {base:?}"),
+		GeneralSubtree::DnsName(base) => format!("DNS {base:?}"),
+		GeneralSubtree::DirectoryName(base) => format!("directory name {:?}", describe_dn(base)),
+		GeneralSubtree::URI(base) => format!("URI {base:?}"),
+		GeneralSubtree::IpAddress(subnet) => {
+			let (addr, prefix) = match subnet {
+				CidrSubnet::V4(addr, mask) => (IpAddr::from(*addr), prefix_len(mask)),
+				CidrSubnet::V6(addr, mask) => (IpAddr::from(*addr), prefix_len(mask)),
+			};
+			format!("IP {addr}/{prefix}")
+		},
+	}
+}
+
+fn prefix_len(mask: &[u8]) -> u32 {
+	mask.iter().map(|byte| byte.count_ones()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+	use std::str::FromStr;
+
+	use super::*;
+
+	#[test]
+	fn dns_names() {
+		for (name, base, within) in [
+			("corp.example", "corp.example", true),
+			("host.corp.example", "corp.example", true),
+			("a.b.corp.example", "corp.example", true),
+			("HOST.Corp.Example", "corp.EXAMPLE", true),
+			("evilcorp.example", "corp.example", false),
+			("corp.example.com", "corp.example", false),
+			("example", "corp.example", false),
+			// A leading period only permits subdomains
+			("host.corp.example", ".corp.example", true),
+			("corp.example", ".corp.example", false),
+			("*.corp.example", ".corp.example", true),
+			// The empty name contains all names
+			("anything.example", "", true),
+		] {
+			assert_eq!(dns_within(name, base), within, "{name} within {base}");
+		}
+	}
+
+	#[test]
+	fn email_addresses() {
+		for (email, base, within) in [
+			("alice@corp.example", "alice@corp.example", true),
+			("alice@CORP.example", "alice@corp.example", true),
+			("Alice@corp.example", "alice@corp.example", false),
+			("bob@corp.example", "alice@corp.example", false),
+			("alice@corp.example", "corp.example", true),
+			("alice@mail.corp.example", "corp.example", false),
+			("alice@mail.corp.example", ".corp.example", true),
+			("alice@corp.example", ".corp.example", false),
+			("alice@evilcorp.example", ".corp.example", false),
+			("not-an-address", "corp.example", false),
+		] {
+			assert_eq!(email_within(email, base), within, "{email} within {base}");
+		}
+	}
+
+	#[test]
+	fn uris() {
+		for (uri, base, within) in [
+			("https://corp.example/path", "corp.example", true),
+			("https://user@corp.example:8443/", "corp.example", true),
+			("https://CORP.example?q", "corp.example", true),
+			("https://www.corp.example/", "corp.example", false),
+			("https://www.corp.example/", ".corp.example", true),
+			("https://corp.example/", ".corp.example", false),
+			("https://evilcorp.example/", ".corp.example", false),
+			("urn:corp.example", "corp.example", false),
+			("https://[2001:db8::1]/", "corp.example", false),
+		] {
+			let host_within = uri_host(uri).is_some_and(|host| host_within(host, base));
+			assert_eq!(host_within, within, "{uri} within {base}");
+		}
+	}
+
+	#[test]
+	fn ip_addresses() {
+		for (addr, subnet, within) in [
+			("192.0.2.1", "192.0.2.0/24", true),
+			("192.0.3.1", "192.0.2.0/24", false),
+			("192.0.2.1", "192.0.2.1/32", true),
+			("192.0.2.1", "0.0.0.0/0", true),
+			("2001:db8::1", "2001:db8::/32", true),
+			("2001:db9::1", "2001:db8::/32", false),
+			// Different address families never match
+			("192.0.2.1", "::/0", false),
+			("::ffff:192.0.2.1", "192.0.2.0/24", false),
+		] {
+			let addr = IpAddr::from_str(addr).unwrap();
+			let subnet = CidrSubnet::from_str(subnet).unwrap();
+			assert_eq!(ip_within(addr, &subnet), within, "{addr} within {subnet:?}");
+		}
+	}
+
+	#[test]
+	fn directory_names() {
+		fn dn(attributes: &[(DnType, &str)]) -> DistinguishedName {
+			let mut dn = DistinguishedName::new();
+			for (ty, value) in attributes {
+				dn.push(ty.clone(), *value);
+			}
+			dn
+		}
+
+		let base = dn(&[
+			(DnType::CountryName, "US"),
+			(DnType::OrganizationName, "Corp  Inc"),
+		]);
+		for (name, within) in [
+			(
+				dn(&[
+					(DnType::CountryName, "US"),
+					(DnType::OrganizationName, "Corp Inc"),
+					(DnType::CommonName, "host"),
+				]),
+				true,
+			),
+			(
+				dn(&[
+					(DnType::CountryName, "us"),
+					(DnType::OrganizationName, " CORP INC "),
+				]),
+				true,
+			),
+			(dn(&[(DnType::CountryName, "US")]), false),
+			(
+				dn(&[
+					(DnType::OrganizationName, "Corp Inc"),
+					(DnType::CountryName, "US"),
+				]),
+				false,
+			),
+			(
+				dn(&[
+					(DnType::CountryName, "US"),
+					(DnType::OrganizationName, "Evil Inc"),
+				]),
+				false,
+			),
+		] {
+			assert_eq!(dn_within(&name, &base), within, "{name:?}");
+		}
+		assert!(dn_within(&base, &DistinguishedName::new()));
+
+		let mut printable = DistinguishedName::new();
+		printable.push(
+			DnType::CountryName,
+			DnValue::PrintableString("US".try_into().unwrap()),
+		);
+		printable.push(DnType::OrganizationName, "corp inc");
+		assert!(dn_within(&printable, &base));
+	}
+
+	#[test]
+	fn permitted_and_excluded() {
+		let constraints = NameConstraints {
+			permitted_subtrees: vec![
+				GeneralSubtree::DnsName(".corp.example".to_owned()),
+				GeneralSubtree::IpAddress(CidrSubnet::from_str("10.0.0.0/8").unwrap()),
+			],
+			excluded_subtrees: vec![GeneralSubtree::DnsName("secret.corp.example".to_owned())],
+		};
+		let check_sans = |sans: &[&str]| {
+			let params =
+				CertificateParams::new(sans.iter().map(|san| san.to_string()).collect::<Vec<_>>())
+					.unwrap();
+			check(&constraints, &params)
+		};
+
+		check_sans(&["www.corp.example", "10.1.2.3"]).unwrap();
+		// No subtrees of the form constrain email addresses
+		let mut params = CertificateParams::new(vec!["www.corp.example".to_owned()]).unwrap();
+		params.subject_alt_names.push(SanType::Rfc822Name(
+			"alice@evil.example".try_into().unwrap(),
+		));
+		check(&constraints, &params).unwrap();
+
+		assert_eq!(
+			check_sans(&["www.corp.example", "evil.com"]),
+			Err(Error::NameConstraintViolation(
+				"DNS name evil.com is not within any of the permitted subtrees DNS \".corp.example\""
+					.to_owned()
+			))
+		);
+		assert_eq!(
+			check_sans(&["a.secret.corp.example"]),
+			Err(Error::NameConstraintViolation(
+				"DNS name a.secret.corp.example is within the excluded subtree DNS \"secret.corp.example\""
+					.to_owned()
+			))
+		);
+		assert_eq!(
+			check_sans(&["*.corp.example"]),
+			Err(Error::NameConstraintViolation(
+				"DNS name *.corp.example is within the excluded subtree DNS \"secret.corp.example\""
+					.to_owned()
+			))
+		);
+		assert_eq!(
+			check_sans(&["192.0.2.1"]),
+			Err(Error::NameConstraintViolation(
+				"IP address 192.0.2.1 is not within any of the permitted subtrees IP 10.0.0.0/8"
+					.to_owned()
+			))
+		);
+	}
+
+	#[test]
+	fn subject() {
+		let mut base = DistinguishedName::new();
+		base.push(DnType::OrganizationName, "Corp");
+		let constraints = NameConstraints {
+			permitted_subtrees: vec![
+				GeneralSubtree::DirectoryName(base),
+				GeneralSubtree::Rfc822Name("corp.example".to_owned()),
+			],
+			excluded_subtrees: Vec::new(),
+		};
+
+		let mut params = CertificateParams {
+			distinguished_name: DistinguishedName::new(),
+			..CertificateParams::default()
+		};
+		check(&constraints, &params).unwrap();
+
+		params
+			.distinguished_name
+			.push(DnType::OrganizationName, "Evil");
+		assert_eq!(
+			check(&constraints, &params),
+			Err(Error::NameConstraintViolation(
+				"directory name O=Evil is not within any of the permitted subtrees directory name \"O=Corp\""
+					.to_owned()
+			))
+		);
+
+		// Email addresses in the subject are checked unless there are rfc822Name SANs
+		params.distinguished_name = DistinguishedName::new();
+		params
+			.distinguished_name
+			.push(DnType::OrganizationName, "Corp");
+		params.distinguished_name.push(
+			DnType::CustomDnType(oid::EMAIL_ADDRESS.to_vec()),
+			DnValue::Ia5String("alice@evil.example".try_into().unwrap()),
+		);
+		assert!(check(&constraints, &params).is_err());
+		params.subject_alt_names.push(SanType::Rfc822Name(
+			"alice@corp.example".try_into().unwrap(),
+		));
+		check(&constraints, &params).unwrap();
+	}
+
+	#[test]
+	fn directory_name_sans() {
+		let dn = |org: &str| {
+			let mut dn = DistinguishedName::new();
+			dn.push(DnType::OrganizationName, org);
+			dn
+		};
+		let constraints = NameConstraints {
+			permitted_subtrees: vec![GeneralSubtree::DirectoryName(dn("Corp"))],
+			excluded_subtrees: Vec::new(),
+		};
+
+		let mut within = dn("Corp");
+		within.push(DnType::CommonName, "Alice");
+		let params = CertificateParams {
+			distinguished_name: DistinguishedName::new(),
+			subject_alt_names: vec![SanType::DirectoryName(within)],
+			..CertificateParams::default()
+		};
+		check(&constraints, &params).unwrap();
+
+		let params = CertificateParams {
+			subject_alt_names: vec![SanType::DirectoryName(dn("Evil"))],
+			..params
+		};
+		assert_eq!(
+			check(&constraints, &params),
+			Err(Error::NameConstraintViolation(
+				"directory name O=Evil is not within any of the permitted subtrees directory name \"O=Corp\""
+					.to_owned()
+			))
+		);
+	}
+}
@@ -1,6 +1,9 @@
 /// pkcs-9-at-extensionRequest in [RFC 2985](https://www.rfc-editor.org/rfc/rfc2985#appendix-A)
 pub(crate) const PKCS_9_AT_EXTENSION_REQUEST: &[u64] = &[1, 2, 840, 113549, 1, 9, 14];
 
+/// pkcs-9-at-emailAddress in [RFC 2985](https://www.rfc-editor.org/rfc/rfc2985#appendix-A)
+pub(crate) const EMAIL_ADDRESS: &[u64] = &[1, 2, 840, 113549, 1, 9, 1];
+
 /// id-at-countryName in [RFC 5280](https://www.rfc-editor.org/rfc/rfc5280#appendix-A)
 pub(crate) const COUNTRY_NAME: &[u64] = &[2, 5, 4, 6];
 /// id-at-localityName in [RFC 5280](https://www.rfc-editor.org/rfc/rfc5280#appendix-A)
@@ -13,6 +16,23 @@ pub(crate) const ORG_NAME: &[u64] = &[2, 5, 4, 10];
 pub(crate) const ORG_UNIT_NAME: &[u64] = &[2, 5, 4, 11];
 /// id-at-commonName in [RFC 5280](https://www.rfc-editor.org/rfc/rfc5280#appendix-A)
 pub(crate) const COMMON_NAME: &[u64] = &[2, 5, 4, 3];
+/// id-at-businessCategory in [X.520](https://www.itu.int/rec/T-REC-X.520)
+pub(crate) const BUSINESS_CATEGORY: &[u64] = &[2, 5, 4, 15];
+/// id-at-organizationIdentifier in [X.520](https://www.itu.int/rec/T-REC-X.520)
+pub(crate) const ORG_IDENTIFIER: &[u64] = &[2, 5, 4, 97];
+/// jurisdictionOfIncorporationLocalityName in the [CA/Browser Forum EV Guidelines](https://cabforum.org/working-groups/server/extended-validation/guidelines/)
+pub(crate) const JURISDICTION_LOCALITY_NAME: &[u64] = &[1, 3, 6, 1, 4, 1, 311, 60, 2, 1, 1];
+/// jurisdictionOfIncorporationStateOrProvinceName in the [CA/Browser Forum EV Guidelines](https://cabforum.org/working-groups/server/extended-validation/guidelines/)
+pub(crate) const JURISDICTION_STATE_OR_PROVINCE_NAME: &[u64] =
+	&[1, 3, 6, 1, 4, 1, 311, 60, 2, 1, 2];
+/// jurisdictionOfIncorporationCountryName in the [CA/Browser Forum EV Guidelines](https://cabforum.org/working-groups/server/extended-validation/guidelines/)
+pub(crate) const JURISDICTION_COUNTRY_NAME: &[u64] = &[1, 3, 6, 1, 4, 1, 311, 60, 2, 1, 3];
+/// szOID_NT_PRINCIPAL_NAME, the otherName of a [user principal name](https://learn.microsoft.com/en-us/windows/security/identity-protection/smart-cards/smart-card-certificate-requirements-and-enumeration)
+pub(crate) const MS_USER_PRINCIPAL_NAME: &[u64] = &[1, 3, 6, 1, 4, 1, 311, 20, 2, 3];
+/// szOID_ENROLL_CERTTYPE_EXTENSION, the [certificate template name](https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-wcce/3aec3e50-511a-42f9-a5be-ed14e3c3b8b4) of AD CS
+pub(crate) const MS_CERTIFICATE_TEMPLATE_NAME: &[u64] = &[1, 3, 6, 1, 4, 1, 311, 20, 2];
+/// szOID_CERTIFICATE_TEMPLATE, the [certificate template information](https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-wcce/9da866e5-9ce9-4a83-9064-0d20af8b2ccf) of AD CS
+pub(crate) const MS_CERTIFICATE_TEMPLATE: &[u64] = &[1, 3, 6, 1, 4, 1, 311, 21, 7];
 
 /// id-ecPublicKey in [RFC 5480](https://datatracker.ietf.org/doc/html/rfc5480#appendix-A)
 pub(crate) const EC_PUBLIC_KEY: &[u64] = &[1, 2, 840, 10045, 2, 1];
@@ -21,9 +41,16 @@ pub(crate) const EC_SECP_256_R1: &[u64] = &[1, 2, 840, 10045, 3, 1, 7];
 /// secp384r1 in [RFC 5480](https://datatracker.ietf.org/doc/html/rfc5480#appendix-A)
 pub(crate) const EC_SECP_384_R1: &[u64] = &[1, 3, 132, 0, 34];
 /// secp521r1 in [RFC 5480](https://datatracker.ietf.org/doc/html/rfc5480#appendix-A)
-/// Currently this is only supported with the `aws_lc_rs` feature
-#[cfg(feature = "aws_lc_rs")]
 pub(crate) const EC_SECP_521_R1: &[u64] = &[1, 3, 132, 0, 35];
+/// secp256k1 in [RFC 5480](https://datatracker.ietf.org/doc/html/rfc5480#appendix-A)
+pub(crate) const EC_SECP_256_K1: &[u64] = &[1, 3, 132, 0, 10];
+/// prime-field in [RFC 3279](https://www.rfc-editor.org/rfc/rfc3279#section-2.3.5)
+pub(crate) const PRIME_FIELD: &[u64] = &[1, 2, 840, 10045, 1, 1];
+
+/// id-Ed25519 in [RFC 8410](https://tools.ietf.org/html/rfc8410#section-3)
+pub(crate) const ED25519: &[u64] = &[1, 3, 101, 112];
+/// id-Ed448 in [RFC 8410](https://tools.ietf.org/html/rfc8410#section-3)
+pub(crate) const ED448: &[u64] = &[1, 3, 101, 113];
 
 #[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
 pub(crate) const ML_DSA_44: &[u64] = &[2, 16, 840, 1, 101, 3, 4, 3, 17];
@@ -32,18 +59,43 @@ pub(crate) const ML_DSA_65: &[u64] = &[2, 16, 840, 1, 101, 3, 4, 3, 18];
 #[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
 pub(crate) const ML_DSA_87: &[u64] = &[2, 16, 840, 1, 101, 3, 4, 3, 19];
 
+/// id-MLDSA44-Ed25519-SHA512 in [draft-ietf-lamps-pq-composite-sigs-02](https://www.ietf.org/archive/id/draft-ietf-lamps-pq-composite-sigs-02.html#name-algorithm-identifiers)
+#[cfg(all(feature = "composite_signatures_unstable", not(feature = "fips")))]
+pub(crate) const MLDSA44_ED25519_SHA512: &[u64] = &[2, 16, 840, 1, 114027, 80, 8, 1, 3];
+/// id-MLDSA44-ECDSA-P256-SHA256 in [draft-ietf-lamps-pq-composite-sigs-02](https://www.ietf.org/archive/id/draft-ietf-lamps-pq-composite-sigs-02.html#name-algorithm-identifiers)
+#[cfg(all(feature = "composite_signatures_unstable", not(feature = "fips")))]
+pub(crate) const MLDSA44_ECDSA_P256_SHA256: &[u64] = &[2, 16, 840, 1, 114027, 80, 8, 1, 4];
+/// id-MLDSA65-Ed25519-SHA512 in [draft-ietf-lamps-pq-composite-sigs-02](https://www.ietf.org/archive/id/draft-ietf-lamps-pq-composite-sigs-02.html#name-algorithm-identifiers)
+#[cfg(all(feature = "composite_signatures_unstable", not(feature = "fips")))]
+pub(crate) const MLDSA65_ED25519_SHA512: &[u64] = &[2, 16, 840, 1, 114027, 80, 8, 1, 10];
+
 /// rsaEncryption in [RFC 4055](https://www.rfc-editor.org/rfc/rfc4055#section-6)
 pub(crate) const RSA_ENCRYPTION: &[u64] = &[1, 2, 840, 113549, 1, 1, 1];
 
 /// id-RSASSA-PSS in [RFC 4055](https://www.rfc-editor.org/rfc/rfc4055#section-6)
 pub(crate) const RSASSA_PSS: &[u64] = &[1, 2, 840, 113549, 1, 1, 10];
 
+/// id-mgf1 in [RFC 4055](https://www.rfc-editor.org/rfc/rfc4055#section-6)
+pub(crate) const MGF1: &[u64] = &[1, 2, 840, 113549, 1, 1, 8];
+
+/// id-sha256 in [RFC 4055](https://www.rfc-editor.org/rfc/rfc4055#section-2.1)
+pub(crate) const SHA256: &[u64] = &[2, 16, 840, 1, 101, 3, 4, 2, 1];
+
+/// id-sha384 in [RFC 4055](https://www.rfc-editor.org/rfc/rfc4055#section-2.1)
+pub(crate) const SHA384: &[u64] = &[2, 16, 840, 1, 101, 3, 4, 2, 2];
+
+/// id-sha512 in [RFC 4055](https://www.rfc-editor.org/rfc/rfc4055#section-2.1)
+pub(crate) const SHA512: &[u64] = &[2, 16, 840, 1, 101, 3, 4, 2, 3];
+
 /// id-ce-keyUsage in [RFC 5280](https://tools.ietf.org/html/rfc5280#appendix-A.2)
 pub(crate) const KEY_USAGE: &[u64] = &[2, 5, 29, 15];
 
 /// id-ce-subjectAltName in [RFC 5280](https://tools.ietf.org/html/rfc5280#appendix-A.2)
 pub(crate) const SUBJECT_ALT_NAME: &[u64] = &[2, 5, 29, 17];
 
+/// id-ce-issuerAltName in [RFC 5280](https://tools.ietf.org/html/rfc5280#appendix-A.2)
+pub(crate) const ISSUER_ALT_NAME: &[u64] = &[2, 5, 29, 18];
+
 /// id-ce-basicConstraints in [RFC 5280](https://tools.ietf.org/html/rfc5280#appendix-A.2)
 pub(crate) const BASIC_CONSTRAINTS: &[u64] = &[2, 5, 29, 19];
 
@@ -56,12 +108,18 @@ pub(crate) const AUTHORITY_KEY_IDENTIFIER: &[u64] = &[2, 5, 29, 35];
 /// id-ce-extKeyUsage in [RFC 5280](https://www.rfc-editor.org/rfc/rfc5280#appendix-A)
 pub(crate) const EXT_KEY_USAGE: &[u64] = &[2, 5, 29, 37];
 
+/// id-ce-subjectDirectoryAttributes in [RFC 5280](https://www.rfc-editor.org/rfc/rfc5280#appendix-A)
+pub(crate) const SUBJECT_DIRECTORY_ATTRIBUTES: &[u64] = &[2, 5, 29, 9];
+
 /// id-ce-nameConstraints in [RFC 5280](https://www.rfc-editor.org/rfc/rfc5280#appendix-A)
 pub(crate) const NAME_CONSTRAINTS: &[u64] = &[2, 5, 29, 30];
 
 /// id-ce-cRLDistributionPoints in [RFC 5280](https://www.rfc-editor.org/rfc/rfc5280#appendix-A)
 pub(crate) const CRL_DISTRIBUTION_POINTS: &[u64] = &[2, 5, 29, 31];
 
+/// id-ce-freshestCRL in [RFC 5280](https://www.rfc-editor.org/rfc/rfc5280#appendix-A)
+pub(crate) const FRESHEST_CRL: &[u64] = &[2, 5, 29, 46];
+
 /// id-pe-acmeIdentifier in
 /// [IANA SMI Numbers registry](https://www.iana.org/assignments/smi-numbers/smi-numbers.xhtml#smi-numbers-1.3.6.1.5.5.7.1)
 pub(crate) const PE_ACME: &[u64] = &[1, 3, 6, 1, 5, 5, 7, 1, 31];
@@ -77,3 +135,109 @@ pub(crate) const CRL_INVALIDITY_DATE: &[u64] = &[2, 5, 29, 24];
 
 /// id-ce-issuingDistributionPoint in [RFC 5280](https://www.rfc-editor.org/rfc/rfc5280#appendix-A)
 pub(crate) const CRL_ISSUING_DISTRIBUTION_POINT: &[u64] = &[2, 5, 29, 28];
+
+/// id-pe-authorityInfoAccess in [RFC 5280](https://www.rfc-editor.org/rfc/rfc5280#appendix-A)
+pub(crate) const AUTHORITY_INFO_ACCESS: &[u64] = &[1, 3, 6, 1, 5, 5, 7, 1, 1];
+
+/// id-ad-ocsp in [RFC 5280](https://www.rfc-editor.org/rfc/rfc5280#appendix-A)
+pub(crate) const AD_OCSP: &[u64] = &[1, 3, 6, 1, 5, 5, 7, 48, 1];
+
+/// id-ad-caIssuers in [RFC 5280](https://www.rfc-editor.org/rfc/rfc5280#appendix-A)
+pub(crate) const AD_CA_ISSUERS: &[u64] = &[1, 3, 6, 1, 5, 5, 7, 48, 2];
+
+/// id-ce-policyConstraints in [RFC 5280](https://www.rfc-editor.org/rfc/rfc5280#appendix-A)
+pub(crate) const POLICY_CONSTRAINTS: &[u64] = &[2, 5, 29, 36];
+
+/// id-ce-inhibitAnyPolicy in [RFC 5280](https://www.rfc-editor.org/rfc/rfc5280#appendix-A)
+pub(crate) const INHIBIT_ANY_POLICY: &[u64] = &[2, 5, 29, 54];
+
+/// id-pe-tlsfeature in [RFC 7633](https://www.rfc-editor.org/rfc/rfc7633#section-6)
+pub(crate) const TLS_FEATURE: &[u64] = &[1, 3, 6, 1, 5, 5, 7, 1, 24];
+
+/// id-pkix-ocsp-nocheck in [RFC 6960](https://www.rfc-editor.org/rfc/rfc6960#section-4.2.2.2.1)
+pub(crate) const OCSP_NO_CHECK: &[u64] = &[1, 3, 6, 1, 5, 5, 7, 48, 1, 5];
+
+/// netscape-cert-type of the [Netscape certificate extensions](https://web.archive.org/web/19970605184238/http://home.netscape.com/eng/security/comm4-cert-exts.html)
+pub(crate) const NETSCAPE_CERT_TYPE: &[u64] = &[2, 16, 840, 1, 113730, 1, 1];
+
+/// netscape-comment of the [Netscape certificate extensions](https://web.archive.org/web/19970605184238/http://home.netscape.com/eng/security/comm4-cert-exts.html)
+pub(crate) const NETSCAPE_COMMENT: &[u64] = &[2, 16, 840, 1, 113730, 1, 13];
+
+/// The precertificate poison extension of
+/// [RFC 6962](https://www.rfc-editor.org/rfc/rfc6962#section-3.1)
+pub(crate) const CT_POISON: &[u64] = &[1, 3, 6, 1, 4, 1, 11129, 2, 4, 3];
+
+/// The SCT list extension of [RFC 6962](https://www.rfc-editor.org/rfc/rfc6962#section-3.3)
+pub(crate) const CT_SCT_LIST: &[u64] = &[1, 3, 6, 1, 4, 1, 11129, 2, 4, 2];
+
+/// id-ce-certificatePolicies in [RFC 5280](https://www.rfc-editor.org/rfc/rfc5280#appendix-A)
+pub(crate) const CERTIFICATE_POLICIES: &[u64] = &[2, 5, 29, 32];
+
+/// anyPolicy in [RFC 5280](https://www.rfc-editor.org/rfc/rfc5280#appendix-A)
+pub(crate) const ANY_POLICY: &[u64] = &[2, 5, 29, 32, 0];
+
+/// id-qt-cps in [RFC 5280](https://www.rfc-editor.org/rfc/rfc5280#appendix-A)
+pub(crate) const QT_CPS: &[u64] = &[1, 3, 6, 1, 5, 5, 7, 2, 1];
+
+/// id-qt-unotice in [RFC 5280](https://www.rfc-editor.org/rfc/rfc5280#appendix-A)
+pub(crate) const QT_UNOTICE: &[u64] = &[1, 3, 6, 1, 5, 5, 7, 2, 2];
+
+/// id-data in [RFC 5652](https://www.rfc-editor.org/rfc/rfc5652#section-4)
+#[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
+pub(crate) const PKCS_7_DATA: &[u64] = &[1, 2, 840, 113549, 1, 7, 1];
+
+/// pkcs8ShroudedKeyBag in [RFC 7292](https://www.rfc-editor.org/rfc/rfc7292#appendix-D)
+#[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
+pub(crate) const PKCS_12_SHROUDED_KEY_BAG: &[u64] = &[1, 2, 840, 113549, 1, 12, 10, 1, 2];
+
+/// certBag in [RFC 7292](https://www.rfc-editor.org/rfc/rfc7292#appendix-D)
+#[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
+pub(crate) const PKCS_12_CERT_BAG: &[u64] = &[1, 2, 840, 113549, 1, 12, 10, 1, 3];
+
+/// x509Certificate in [RFC 7292](https://www.rfc-editor.org/rfc/rfc7292#appendix-D)
+#[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
+pub(crate) const PKCS_9_X509_CERTIFICATE: &[u64] = &[1, 2, 840, 113549, 1, 9, 22, 1];
+
+/// pkcs-9-at-friendlyName in [RFC 2985](https://www.rfc-editor.org/rfc/rfc2985#appendix-A)
+#[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
+pub(crate) const PKCS_9_FRIENDLY_NAME: &[u64] = &[1, 2, 840, 113549, 1, 9, 20];
+
+/// pkcs-9-at-localKeyId in [RFC 2985](https://www.rfc-editor.org/rfc/rfc2985#appendix-A)
+#[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
+pub(crate) const PKCS_9_LOCAL_KEY_ID: &[u64] = &[1, 2, 840, 113549, 1, 9, 21];
+
+/// id-PBES2 in [RFC 8018](https://www.rfc-editor.org/rfc/rfc8018#appendix-A.4)
+#[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
+pub(crate) const PBES2: &[u64] = &[1, 2, 840, 113549, 1, 5, 13];
+
+/// id-PBKDF2 in [RFC 8018](https://www.rfc-editor.org/rfc/rfc8018#appendix-A.2)
+#[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
+pub(crate) const PBKDF2: &[u64] = &[1, 2, 840, 113549, 1, 5, 12];
+
+/// id-hmacWithSHA1 in [RFC 8018](https://www.rfc-editor.org/rfc/rfc8018#appendix-B.1.1)
+#[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
+pub(crate) const HMAC_WITH_SHA1: &[u64] = &[1, 2, 840, 113549, 2, 7];
+
+/// id-hmacWithSHA256 in [RFC 8018](https://www.rfc-editor.org/rfc/rfc8018#appendix-B.1.2)
+#[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
+pub(crate) const HMAC_WITH_SHA256: &[u64] = &[1, 2, 840, 113549, 2, 9];
+
+/// id-hmacWithSHA384 in [RFC 8018](https://www.rfc-editor.org/rfc/rfc8018#appendix-B.1.2)
+#[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
+pub(crate) const HMAC_WITH_SHA384: &[u64] = &[1, 2, 840, 113549, 2, 10];
+
+/// id-hmacWithSHA512 in [RFC 8018](https://www.rfc-editor.org/rfc/rfc8018#appendix-B.1.2)
+#[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
+pub(crate) const HMAC_WITH_SHA512: &[u64] = &[1, 2, 840, 113549, 2, 11];
+
+/// id-aes128-CBC in [RFC 3565](https://www.rfc-editor.org/rfc/rfc3565#section-4.1)
+#[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
+pub(crate) const AES_128_CBC: &[u64] = &[2, 16, 840, 1, 101, 3, 4, 1, 2];
+
+/// id-aes192-CBC in [RFC 3565](https://www.rfc-editor.org/rfc/rfc3565#section-4.1)
+#[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
+pub(crate) const AES_192_CBC: &[u64] = &[2, 16, 840, 1, 101, 3, 4, 1, 22];
+
+/// id-aes256-CBC in [RFC 3565](https://www.rfc-editor.org/rfc/rfc3565#section-4.1)
+#[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
+pub(crate) const AES_256_CBC: &[u64] = &[2, 16, 840, 1, 101, 3, 4, 1, 42];
@@ -0,0 +1,161 @@
+//! Public keys in the OpenSSH `authorized_keys` format
+
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+
+use crate::{oid, Error, KeyPair, PublicKeyData, SignatureAlgorithm};
+
+impl KeyPair {
+	/// Returns the public key in the format of OpenSSH's `authorized_keys` files
+	///
+	/// The line consists of the key type, like `ssh-ed25519`, the base64-encoded public key in
+	/// the wire format of [RFC 4253](https://www.rfc-editor.org/rfc/rfc4253#section-6.6),
+	/// [RFC 5656](https://www.rfc-editor.org/rfc/rfc5656#section-3.1), or
+	/// [RFC 8709](https://www.rfc-editor.org/rfc/rfc8709#section-4), and `comment`, which is
+	/// left out if it's empty.
+	///
+	/// Ed25519, ECDSA with the P-256, P-384, or P-521 curves, and RSA keys are supported.
+	/// Fails with [`Error::KeyFormatUnavailable`] for other key types, which SSH has no key
+	/// type for, including RSA-PSS keys.
+	pub fn public_key_openssh(&self, comment: &str) -> Result<String, Error> {
+		let (key_type, blob) = public_key_blob(self.algorithm(), self.der_bytes())?;
+		let mut line = format!("{key_type} {}", BASE64_STANDARD.encode(blob));
+		if !comment.is_empty() {
+			line.push(' ');
+			line.push_str(comment);
+		}
+		Ok(line)
+	}
+}
+
+/// Encodes the raw public key of the given algorithm in the SSH wire format
+///
+/// Returns the key type along with the encoding.
+fn public_key_blob(
+	alg: &SignatureAlgorithm,
+	public_key: &[u8],
+) -> Result<(&'static str, Vec<u8>), Error> {
+	let mut blob = Vec::new();
+	let key_type = match alg.key_alg_oid() {
+		oid::ED25519 => {
+			write_string(&mut blob, b"ssh-ed25519");
+			write_string(&mut blob, public_key);
+			"ssh-ed25519"
+		},
+		oid::EC_PUBLIC_KEY => {
+			let (key_type, curve) = match alg.curve_oid() {
+				Some(oid::EC_SECP_256_R1) => ("ecdsa-sha2-nistp256", "nistp256"),
+				Some(oid::EC_SECP_384_R1) => ("ecdsa-sha2-nistp384", "nistp384"),
+				Some(oid::EC_SECP_521_R1) => ("ecdsa-sha2-nistp521", "nistp521"),
+				_ => return Err(Error::KeyFormatUnavailable),
+			};
+			write_string(&mut blob, key_type.as_bytes());
+			write_string(&mut blob, curve.as_bytes());
+			// The uncompressed point, as it's stored in the subject public key info
+			write_string(&mut blob, public_key);
+			key_type
+		},
+		oid::RSA_ENCRYPTION => {
+			// The RSAPublicKey of RFC 8017. Its positive INTEGERs are minimal two's complement,
+			// like the `mpint` of RFC 4251.
+			let (n, e) = crate::asn1::parse_der(public_key, |reader| {
+				reader.read_sequence(|reader| {
+					let n = reader.next().read_bigint_bytes()?;
+					let e = reader.next().read_bigint_bytes()?;
+					Ok((n, e))
+				})
+			})
+			.map_err(|_| Error::InvalidPublicKey)?;
+			let ((n, true), (e, true)) = (n, e) else {
+				return Err(Error::InvalidPublicKey);
+			};
+			write_string(&mut blob, b"ssh-rsa");
+			write_string(&mut blob, &e);
+			write_string(&mut blob, &n);
+			"ssh-rsa"
+		},
+		_ => return Err(Error::KeyFormatUnavailable),
+	};
+	Ok((key_type, blob))
+}
+
+/// Writes a `string` of RFC 4251, prefixed by its length
+fn write_string(blob: &mut Vec<u8>, value: &[u8]) {
+	blob.extend_from_slice(&(value.len() as u32).to_be_bytes());
+	blob.extend_from_slice(value);
+}
+
+#[cfg(all(test, feature = "pem"))]
+mod tests {
+	use super::*;
+	use crate::ring_like::digest::{self, SHA256};
+	use crate::sign_algo::algo::*;
+
+	/// The fingerprint that `ssh-keygen -l -f` prints for the public key
+	fn fingerprint(line: &str) -> String {
+		let blob = BASE64_STANDARD
+			.decode(line.split(' ').nth(1).unwrap())
+			.unwrap();
+		let hash = digest::digest(&SHA256, &blob);
+		format!(
+			"SHA256:{}",
+			base64::prelude::BASE64_STANDARD_NO_PAD.encode(hash)
+		)
+	}
+
+	#[test]
+	fn ssh_keygen() {
+		// The lines are from `ssh-keygen -y -f`, and the fingerprints from `ssh-keygen -l -f`
+		let keys: &[(&str, &str, &str)] = &[
+			(
+				include_str!("test_data/ed25519.key.pem"),
+				"ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIMMBZgT2J2VWBJBnk6tKipb2LP7HD9pr8ILGb+iBH8Ow",
+				"SHA256:joaPSL47JdxFU4uvm0XpBARR+LpDajZpUrNETKPaoEQ",
+			),
+			(
+				include_str!("test_data/p256.key.pem"),
+				"ecdsa-sha2-nistp256 AAAAE2VjZHNhLXNoYTItbmlzdHAyNTYAAAAIbmlzdHAyNTYAAABBBDpv4mmkrCzk/7pBJknFUsG30pis/7MOZvzy3sAWpnB6wAhjEHsRu8fnNN+nFmNRi/Y95Qbr62w1DcJ/bGfZU88=",
+				"SHA256:LaM2iipBFL87Q80Wzn2/qRNQZbllbX1ppt/dK8+7QEw",
+			),
+			(
+				include_str!("test_data/p384.key.pem"),
+				"ecdsa-sha2-nistp384 AAAAE2VjZHNhLXNoYTItbmlzdHAzODQAAAAIbmlzdHAzODQAAABhBCDspQxmS3N6T7TM6yLZPvZfMZLOVo7hLYxXU/Selvg4/CiTZQATh9cIM9Kh1MKaXzd9Nhd5oMHiFzThhClOyuNgoxPA4uFlXe89TS8zTmtlHcx/5lynTkiYJqlWq8wzog==",
+				"SHA256:0S+rb6qC9r0eVD6sJ5bxACHmKCWCzggmV6hyUkbdDII",
+			),
+			#[cfg(feature = "aws_lc_rs")]
+			(
+				include_str!("test_data/p521.key.pem"),
+				"ecdsa-sha2-nistp521 AAAAE2VjZHNhLXNoYTItbmlzdHA1MjEAAAAIbmlzdHA1MjEAAACFBAH8Kgeh46mHK+owQvQ2yOEOC/rXNwgBLSRwhRRxHwahgYk07GJEuMFF510iPCOEtZdpWt8QigXkDSICHBj0bK5VnQCgITpMYJUySB9thBmmGVykusOQfzu7xM+SRrdbmrT9eHG4GS3c3KKhyU4N5wwzpDsSmsolTNi9L7ZnqEmt0g4cCg==",
+				"SHA256:TpodhvD9YezIvXLYmaSNtvYIuAy2zQKyQXhCE2GdEvk",
+			),
+			(
+				include_str!("test_data/rsa2048.key.pem"),
+				"ssh-rsa AAAAB3NzaC1yc2EAAAADAQABAAABAQDCPcvveNGw91g3+LzHsrN+drn2U0Os5lQ84pNuK0iPcdZ917y4tqF/+LJwPtCK/LJpttkDtSG/dWsA0l70PUw1axYuR47wLVI+D602OtkbLYGDtGPMgNPfZ7lAnUtmOHaE9q6hKRNv/oY5WUmeTNxtGcEa0GBR6Mgmc1rBhbxMHPCGR5rU9GtXgTPMjx9hsD4Soz7vAKr8PYOfwpftgO+wexYKF1gJR5flWmj/S3DprTMXqtrdjYfLf0BoH2+ECzd6GXMrSHY1gjKFKDGK+r1HfJYgv3pTWk5lIW8f6G9kYAmIbHWKHp8yGKeKHbTOOJud/ZOeU1q0YifQ2BDS2UAb",
+				"SHA256:KZ1xp75htze8/P5hsK2MdhRfHtwkBB3GztVA7fYR6og",
+			),
+		];
+
+		for (pem, expected, expected_fingerprint) in keys {
+			let key_pair = KeyPair::from_pem(pem).unwrap();
+			let line = key_pair.public_key_openssh("").unwrap();
+			assert_eq!(line, *expected);
+			assert_eq!(fingerprint(&line), *expected_fingerprint);
+			assert_eq!(
+				key_pair.public_key_openssh("crab@example").unwrap(),
+				format!("{expected} crab@example")
+			);
+		}
+	}
+
+	#[test]
+	fn unavailable() {
+		assert_eq!(
+			public_key_blob(&PKCS_ECDSA_K256_SHA256, &[4; 65]).err(),
+			Some(Error::KeyFormatUnavailable)
+		);
+		assert_eq!(
+			public_key_blob(&PKCS_RSA_PSS_SHA256, &[0x30, 0]).err(),
+			Some(Error::KeyFormatUnavailable)
+		);
+	}
+}
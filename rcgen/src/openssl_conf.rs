@@ -0,0 +1,786 @@
+use std::net::IpAddr;
+
+use crate::{
+	oid, AccessMethod, AuthorityKeyIdMode, BasicConstraints, CertificateParams,
+	CrlDistributionPoint, Error, ExtendedKeyUsagePurpose, ExtensionId, IsCa, KeyUsagePurpose,
+	OtherNameValue, PolicyInformation, PolicyQualifier, SanType,
+};
+
+impl CertificateParams {
+	/// Parses the x509v3 extension directives of an openssl configuration section
+	///
+	/// `conf` holds the directives of the section that would be passed to `openssl req
+	/// -extensions` or `openssl x509 -extensions`, either bare or under a `[section]` header,
+	/// followed by the sections they refer to with `@section`. For example:
+	///
+	/// ```text
+	/// [v3_req]
+	/// basicConstraints = CA:FALSE
+	/// keyUsage = nonRepudiation, digitalSignature, keyEncipherment
+	/// subjectAltName = @alt_names
+	///
+	/// [alt_names]
+	/// DNS.1 = example.com
+	/// IP.1 = 192.0.2.1
+	/// ```
+	///
+	/// The following directives are supported:
+	///
+	/// * `subjectAltName` with `DNS`, `IP`, `email`, `URI` and UTF-8 `otherName` entries
+	/// * `keyUsage` and `extendedKeyUsage`
+	/// * `basicConstraints`
+	/// * `crlDistributionPoints` and `freshestCRL` with URIs, either directly or as the
+	///   `fullname` of a distribution point section
+	/// * `authorityInfoAccess` with `OCSP` and `caIssuers` URIs
+	/// * `certificatePolicies`, including policy sections with `CPS` and `userNotice`
+	///   qualifiers carrying an `explicitText`, which is written as UTF8String like openssl
+	///   does with the `UTF8:` prefix
+	/// * `subjectKeyIdentifier` with `hash` or `none`, and `authorityKeyIdentifier`, where
+	///   `issuer:always` selects the [`AuthorityKeyIdMode`] with the issuer name and serial
+	///
+	/// Any other directive, like `nsComment`, results in [`Error::InvalidOpensslConf`].
+	/// Variable expansion with `$` isn't supported either.
+	///
+	/// Like with openssl, the extensions are critical if and only if their directive has the
+	/// `critical` flag, which is recorded in [`CertificateParams::extension_criticality`].
+	/// Serialization fails with [`Error::NonconformingCriticality`] if that violates RFC 5280,
+	/// like for a critical authority key identifier. The subject key identifier is emitted
+	/// whenever basic constraints are, unless it's `none`. All other fields keep their default
+	/// values.
+	pub fn from_openssl_conf_section(conf: &str) -> Result<Self, Error> {
+		let conf = Conf::parse(conf)?;
+		let mut params = Self::default();
+		let mut seen = Vec::new();
+
+		for (name, value) in conf.extension_section() {
+			if seen.contains(&name) {
+				return Err(conf_error(format!("duplicate directive `{name}`")));
+			}
+			seen.push(name);
+
+			let (critical, value) = match value.strip_prefix("critical") {
+				Some(rest) if rest.trim_start().starts_with(',') => {
+					(true, rest.trim_start()[1..].trim_start())
+				},
+				Some("") => (true, ""),
+				_ => (false, value.as_str()),
+			};
+
+			let id = match name.as_str() {
+				"subjectAltName" => {
+					for (name, value) in conf.values(value)? {
+						params.subject_alt_names.push(general_name(name, value)?);
+					}
+					ExtensionId::SubjectAltName
+				},
+				"keyUsage" => {
+					for (name, value) in conf.values(value)? {
+						let usage = key_usage(name, value)?;
+						if !params.key_usages.contains(&usage) {
+							params.key_usages.push(usage);
+						}
+					}
+					ExtensionId::KeyUsage
+				},
+				"extendedKeyUsage" => {
+					for (name, value) in conf.values(value)? {
+						params.insert_extended_key_usage(extended_key_usage(name, value)?);
+					}
+					ExtensionId::ExtendedKeyUsage
+				},
+				"basicConstraints" => {
+					params.is_ca = basic_constraints(conf.values(value)?)?;
+					ExtensionId::BasicConstraints
+				},
+				"crlDistributionPoints" => {
+					for (name, value) in conf.values(value)? {
+						params
+							.crl_distribution_points
+							.push(conf.distribution_point(name, value)?);
+					}
+					ExtensionId::CrlDistributionPoints
+				},
+				"freshestCRL" => {
+					for (name, value) in conf.values(value)? {
+						params
+							.freshest_crl
+							.push(conf.distribution_point(name, value)?);
+					}
+					ExtensionId::FreshestCrl
+				},
+				"authorityInfoAccess" => {
+					for (name, value) in conf.values(value)? {
+						params
+							.authority_info_access
+							.push(access_description(name, value)?);
+					}
+					ExtensionId::AuthorityInfoAccess
+				},
+				"certificatePolicies" => {
+					params.certificate_policies = conf.certificate_policies(value)?;
+					params.certificate_policies_critical = critical;
+					ExtensionId::CertificatePolicies
+				},
+				"subjectKeyIdentifier" => {
+					match value {
+						"hash" => params.use_subject_key_identifier_extension = true,
+						"none" => params.use_subject_key_identifier_extension = false,
+						_ => {
+							return Err(conf_error(format!(
+								"unsupported subjectKeyIdentifier `{value}`, only `hash` and `none` are supported"
+							)))
+						},
+					}
+					ExtensionId::SubjectKeyIdentifier
+				},
+				"authorityKeyIdentifier" => {
+					match authority_key_identifier(conf.values(value)?)? {
+						Some(mode) => {
+							params.use_authority_key_identifier_extension = true;
+							params.authority_key_identifier_mode = mode;
+						},
+						None => params.use_authority_key_identifier_extension = false,
+					}
+					ExtensionId::AuthorityKeyIdentifier
+				},
+				_ => return Err(conf_error(format!("unsupported directive `{name}`"))),
+			};
+			params.extension_criticality.insert(id, critical);
+		}
+
+		Ok(params)
+	}
+}
+
+/// The sections of an openssl configuration file, in order of appearance
+struct Conf {
+	sections: Vec<(String, Vec<(String, String)>)>,
+}
+
+impl Conf {
+	fn parse(conf: &str) -> Result<Self, Error> {
+		// Directives before the first header belong to the unnamed default section
+		let mut sections = vec![(String::new(), Vec::new())];
+		for (i, line) in conf.lines().enumerate() {
+			let line = strip_comment(line).trim();
+			if line.is_empty() {
+				continue;
+			}
+
+			if let Some(header) = line.strip_prefix('[') {
+				let Some(name) = header.strip_suffix(']') else {
+					return Err(conf_error(format!(
+						"malformed section header on line {}",
+						i + 1
+					)));
+				};
+				sections.push((name.trim().to_owned(), Vec::new()));
+				continue;
+			}
+
+			let Some((name, value)) = line.split_once('=') else {
+				return Err(conf_error(format!(
+					"expected `name = value` on line {}",
+					i + 1
+				)));
+			};
+			let value = value.trim();
+			let value = match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+				Some(unquoted) => unquoted,
+				None => value,
+			};
+			if let Some((_, entries)) = sections.last_mut() {
+				entries.push((name.trim().to_owned(), value.to_owned()));
+			}
+		}
+
+		Ok(Self { sections })
+	}
+
+	/// The directives of the default section, or of the first named one if it's empty
+	fn extension_section(&self) -> &[(String, String)] {
+		match self.sections.as_slice() {
+			[(_, default), (_, first), ..] if default.is_empty() => first,
+			[(_, default), ..] => default,
+			[] => &[],
+		}
+	}
+
+	fn section(&self, name: &str) -> Result<&[(String, String)], Error> {
+		self.sections
+			.iter()
+			.find(|(section, _)| section == name)
+			.map(|(_, entries)| entries.as_slice())
+			.ok_or_else(|| conf_error(format!("section `{name}` not found")))
+	}
+
+	/// The `name:value` pairs of a directive, either listed inline or in an `@section`
+	///
+	/// Inline entries without a value, like the key usages, have an empty value.
+	fn values<'a>(&'a self, value: &'a str) -> Result<Vec<(&'a str, &'a str)>, Error> {
+		if let Some(section) = value.strip_prefix('@') {
+			return Ok(self
+				.section(section.trim())?
+				.iter()
+				.map(|(name, value)| (name.as_str(), value.as_str()))
+				.collect());
+		}
+
+		Ok(parse_list(value)
+			.map(|entry| match entry.split_once(':') {
+				Some((name, value)) => (name.trim(), value.trim()),
+				None => (entry, ""),
+			})
+			.collect())
+	}
+
+	fn distribution_point(&self, name: &str, value: &str) -> Result<CrlDistributionPoint, Error> {
+		// A name without a value refers to a distribution point section
+		if value.is_empty() {
+			let mut uris = Vec::new();
+			for (name, value) in self.section(name.trim_start_matches('@'))? {
+				if name != "fullname" {
+					return Err(conf_error(format!(
+						"unsupported distribution point field `{name}`"
+					)));
+				}
+				for (name, value) in self.values(value)? {
+					uris.push(uri(name, value)?);
+				}
+			}
+			return Ok(CrlDistributionPoint { uris });
+		}
+
+		Ok(CrlDistributionPoint {
+			uris: vec![uri(name, value)?],
+		})
+	}
+
+	fn certificate_policies(&self, value: &str) -> Result<Vec<PolicyInformation>, Error> {
+		let mut policies = Vec::new();
+		for entry in parse_list(value) {
+			// Only affects the encoding of notice references, which aren't supported
+			if entry == "ia5org" {
+				continue;
+			}
+			match entry.strip_prefix('@') {
+				Some(section) => policies.push(self.policy_section(section.trim())?),
+				None => policies.push(PolicyInformation {
+					oid: policy_oid(entry)?,
+					qualifiers: Vec::new(),
+				}),
+			}
+		}
+		Ok(policies)
+	}
+
+	fn policy_section(&self, name: &str) -> Result<PolicyInformation, Error> {
+		let mut policy = None;
+		let mut qualifiers = Vec::new();
+		for (name, value) in self.section(name)? {
+			match base_name(name) {
+				"policyIdentifier" => policy = Some(policy_oid(value)?),
+				"CPS" => qualifiers.push(PolicyQualifier::CpsUri(value.clone())),
+				"userNotice" => {
+					let Some(section) = value.strip_prefix('@') else {
+						return Err(conf_error(format!(
+							"userNotice must refer to a section, not `{value}`"
+						)));
+					};
+					let mut explicit_text = None;
+					for (name, value) in self.section(section.trim())? {
+						match name.as_str() {
+							"explicitText" => {
+								let text = value.strip_prefix("UTF8:").unwrap_or(value);
+								explicit_text = Some(text.to_owned());
+							},
+							_ => {
+								return Err(conf_error(format!(
+									"unsupported user notice field `{name}`"
+								)))
+							},
+						}
+					}
+					if explicit_text.is_none() {
+						return Err(conf_error("userNotice without explicitText"));
+					}
+					qualifiers.push(PolicyQualifier::UserNotice {
+						organization: None,
+						notice_numbers: Vec::new(),
+						explicit_text,
+					});
+				},
+				_ => return Err(conf_error(format!("unsupported policy field `{name}`"))),
+			}
+		}
+
+		match policy {
+			Some(oid) => Ok(PolicyInformation { oid, qualifiers }),
+			None => Err(conf_error(format!(
+				"policy section `{name}` has no policyIdentifier"
+			))),
+		}
+	}
+}
+
+fn general_name(name: &str, value: &str) -> Result<SanType, Error> {
+	Ok(match base_name(name) {
+		"DNS" => SanType::DnsName(value.try_into()?),
+		"email" => SanType::Rfc822Name(value.try_into()?),
+		"URI" => SanType::URI(value.try_into()?),
+		"IP" => SanType::IpAddress(
+			value
+				.parse::<IpAddr>()
+				.map_err(|_| conf_error(format!("invalid IP address `{value}`")))?,
+		),
+		"otherName" => {
+			let other_name = value.split_once(';').and_then(|(oid, value)| {
+				let value = value
+					.strip_prefix("UTF8:")
+					.or_else(|| value.strip_prefix("UTF8String:"))?;
+				Some((parse_oid(oid.trim())?, OtherNameValue::from(value)))
+			});
+			match other_name {
+				Some(other_name) => SanType::OtherName(other_name),
+				None => {
+					return Err(conf_error(format!(
+						"unsupported otherName `{value}`, only UTF8 values are supported"
+					)))
+				},
+			}
+		},
+		_ => {
+			return Err(conf_error(format!(
+				"unsupported general name type `{name}`"
+			)))
+		},
+	})
+}
+
+fn uri(name: &str, value: &str) -> Result<String, Error> {
+	match base_name(name) {
+		"URI" => Ok(value.to_owned()),
+		_ => Err(conf_error(format!(
+			"unsupported general name type `{name}`, only URIs are supported"
+		))),
+	}
+}
+
+fn key_usage(name: &str, value: &str) -> Result<KeyUsagePurpose, Error> {
+	if !value.is_empty() {
+		return Err(conf_error(format!(
+			"unexpected value for key usage `{name}`"
+		)));
+	}
+
+	Ok(match name {
+		"digitalSignature" | "Digital Signature" => KeyUsagePurpose::DigitalSignature,
+		"nonRepudiation" | "Non Repudiation" => KeyUsagePurpose::ContentCommitment,
+		"keyEncipherment" | "Key Encipherment" => KeyUsagePurpose::KeyEncipherment,
+		"dataEncipherment" | "Data Encipherment" => KeyUsagePurpose::DataEncipherment,
+		"keyAgreement" | "Key Agreement" => KeyUsagePurpose::KeyAgreement,
+		"keyCertSign" | "Certificate Sign" => KeyUsagePurpose::KeyCertSign,
+		"cRLSign" | "CRL Sign" => KeyUsagePurpose::CrlSign,
+		"encipherOnly" | "Encipher Only" => KeyUsagePurpose::EncipherOnly,
+		"decipherOnly" | "Decipher Only" => KeyUsagePurpose::DecipherOnly,
+		_ => return Err(conf_error(format!("unknown key usage `{name}`"))),
+	})
+}
+
+fn extended_key_usage(name: &str, value: &str) -> Result<ExtendedKeyUsagePurpose, Error> {
+	if !value.is_empty() {
+		return Err(conf_error(format!(
+			"unexpected value for extended key usage `{name}`"
+		)));
+	}
+
+	Ok(match name {
+		"serverAuth" | "TLS Web Server Authentication" => ExtendedKeyUsagePurpose::ServerAuth,
+		"clientAuth" | "TLS Web Client Authentication" => ExtendedKeyUsagePurpose::ClientAuth,
+		"codeSigning" | "Code Signing" => ExtendedKeyUsagePurpose::CodeSigning,
+		"emailProtection" | "E-mail Protection" => ExtendedKeyUsagePurpose::EmailProtection,
+		"timeStamping" | "Time Stamping" => ExtendedKeyUsagePurpose::TimeStamping,
+		"OCSPSigning" | "OCSP Signing" => ExtendedKeyUsagePurpose::OcspSigning,
+		"anyExtendedKeyUsage" | "Any Extended Key Usage" => ExtendedKeyUsagePurpose::Any,
+		_ => match parse_oid(name) {
+			Some(oid) => ExtendedKeyUsagePurpose::Other(oid),
+			None => return Err(conf_error(format!("unknown extended key usage `{name}`"))),
+		},
+	})
+}
+
+fn basic_constraints(values: Vec<(&str, &str)>) -> Result<IsCa, Error> {
+	let mut ca = false;
+	let mut path_len = None;
+	for (name, value) in values {
+		match name {
+			"CA" => ca = parse_bool(value)?,
+			"pathlen" => {
+				path_len = Some(
+					value
+						.parse::<u8>()
+						.map_err(|_| conf_error(format!("invalid pathlen `{value}`")))?,
+				)
+			},
+			_ => {
+				return Err(conf_error(format!(
+					"unsupported basicConstraints field `{name}`"
+				)))
+			},
+		}
+	}
+
+	Ok(match (ca, path_len) {
+		(true, Some(path_len)) => IsCa::Ca(BasicConstraints::Constrained(path_len)),
+		(true, None) => IsCa::Ca(BasicConstraints::Unconstrained),
+		(false, None) => IsCa::ExplicitNoCa,
+		(false, Some(_)) => return Err(conf_error("pathlen requires CA:TRUE")),
+	})
+}
+
+fn access_description(name: &str, value: &str) -> Result<(AccessMethod, String), Error> {
+	let Some((method, location)) = name.split_once(';') else {
+		return Err(conf_error(format!(
+			"expected `method;URI:location` in authorityInfoAccess, got `{name}`"
+		)));
+	};
+	let method = match method.trim() {
+		"OCSP" => AccessMethod::Ocsp,
+		"caIssuers" | "CA Issuers" => AccessMethod::CaIssuers,
+		other => AccessMethod::Other(
+			parse_oid(other)
+				.ok_or_else(|| conf_error(format!("unknown access method `{other}`")))?,
+		),
+	};
+	Ok((method, uri(location.trim(), value)?))
+}
+
+fn authority_key_identifier(
+	values: Vec<(&str, &str)>,
+) -> Result<Option<AuthorityKeyIdMode>, Error> {
+	let (mut key_id, mut issuer) = (false, false);
+	for (name, value) in values {
+		match (name, value) {
+			("keyid", "" | "always") => key_id = true,
+			// Only used if the issuer has no key identifier, which can't happen with rcgen
+			("issuer", "") => {},
+			("issuer", "always") => issuer = true,
+			("none", "") => (key_id, issuer) = (false, false),
+			_ => {
+				return Err(conf_error(format!(
+					"unsupported authorityKeyIdentifier `{name}:{value}`"
+				)))
+			},
+		}
+	}
+	Ok(match (key_id, issuer) {
+		(false, false) => None,
+		(true, false) => Some(AuthorityKeyIdMode::KeyIdentifier),
+		(false, true) => Some(AuthorityKeyIdMode::IssuerAndSerialNumber),
+		(true, true) => Some(AuthorityKeyIdMode::Both),
+	})
+}
+
+fn policy_oid(value: &str) -> Result<Vec<u64>, Error> {
+	match value {
+		"anyPolicy" | "X509v3 Any Policy" => Ok(oid::ANY_POLICY.to_vec()),
+		_ => parse_oid(value).ok_or_else(|| conf_error(format!("invalid policy `{value}`"))),
+	}
+}
+
+fn parse_bool(value: &str) -> Result<bool, Error> {
+	match value {
+		"TRUE" | "true" | "Y" | "y" | "YES" | "yes" => Ok(true),
+		"FALSE" | "false" | "N" | "n" | "NO" | "no" => Ok(false),
+		_ => Err(conf_error(format!("invalid boolean `{value}`"))),
+	}
+}
+
+fn parse_oid(value: &str) -> Option<Vec<u64>> {
+	let components = value
+		.split('.')
+		.map(|c| c.parse().ok())
+		.collect::<Option<Vec<u64>>>()?;
+	match components.as_slice() {
+		[0..=2, _, ..] => Some(components),
+		_ => None,
+	}
+}
+
+/// The entries of a comma-separated list
+fn parse_list(value: &str) -> impl Iterator<Item = &str> {
+	value
+		.split(',')
+		.map(str::trim)
+		.filter(|entry| !entry.is_empty())
+}
+
+/// Strips the `.1` suffix used to make names unique within a section, e.g. in `DNS.1`
+fn base_name(name: &str) -> &str {
+	match name.split_once('.') {
+		Some((base, _)) => base,
+		None => name,
+	}
+}
+
+fn strip_comment(line: &str) -> &str {
+	let mut quoted = false;
+	for (i, c) in line.char_indices() {
+		match c {
+			'"' => quoted = !quoted,
+			'#' if !quoted => return &line[..i],
+			_ => {},
+		}
+	}
+	line
+}
+
+fn conf_error(msg: impl Into<String>) -> Error {
+	Error::InvalidOpensslConf(msg.into())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn v3_req() {
+		let params = CertificateParams::from_openssl_conf_section(
+			r#"
+			[ v3_req ]
+			# Extensions to add to a certificate request
+			basicConstraints = CA:FALSE
+			keyUsage = nonRepudiation, digitalSignature, keyEncipherment
+			extendedKeyUsage = serverAuth, clientAuth, 1.3.6.1.5.5.7.3.17
+			subjectAltName = @alt_names
+
+			[ alt_names ]
+			DNS.1 = example.com
+			DNS.2 = *.example.com
+			IP.1 = 192.0.2.1
+			IP.2 = 2001:db8::1
+			email.1 = admin@example.com
+			URI.1 = "https://example.com/"
+			"#,
+		)
+		.unwrap();
+
+		assert_eq!(params.is_ca, IsCa::ExplicitNoCa);
+		assert_eq!(
+			params.key_usages,
+			vec![
+				KeyUsagePurpose::ContentCommitment,
+				KeyUsagePurpose::DigitalSignature,
+				KeyUsagePurpose::KeyEncipherment,
+			]
+		);
+		assert_eq!(
+			params.extended_key_usages,
+			vec![
+				ExtendedKeyUsagePurpose::ServerAuth,
+				ExtendedKeyUsagePurpose::ClientAuth,
+				ExtendedKeyUsagePurpose::Other(vec![1, 3, 6, 1, 5, 5, 7, 3, 17]),
+			]
+		);
+		assert_eq!(
+			params.subject_alt_names,
+			vec![
+				SanType::DnsName("example.com".try_into().unwrap()),
+				SanType::DnsName("*.example.com".try_into().unwrap()),
+				SanType::IpAddress("192.0.2.1".parse().unwrap()),
+				SanType::IpAddress("2001:db8::1".parse().unwrap()),
+				SanType::Rfc822Name("admin@example.com".try_into().unwrap()),
+				SanType::URI("https://example.com/".try_into().unwrap()),
+			]
+		);
+	}
+
+	#[test]
+	fn v3_ca() {
+		let params = CertificateParams::from_openssl_conf_section(
+			"subjectKeyIdentifier = hash
+			authorityKeyIdentifier = keyid:always, issuer
+			basicConstraints = critical, CA:true, pathlen:0
+			keyUsage = critical, cRLSign, keyCertSign
+			crlDistributionPoints = URI:http://crl.example.com/a.crl, URI:http://crl.example.com/b.crl
+			freshestCRL = URI:http://crl.example.com/delta.crl
+			authorityInfoAccess = OCSP;URI:http://ocsp.example.com/, 1.2.3.4;URI:http://example.com/
+			subjectAltName = DNS:ca.example.com, otherName:1.3.6.1.4.1.311.20.2.3;UTF8:ca@example.com",
+		)
+		.unwrap();
+
+		assert!(params.use_authority_key_identifier_extension);
+		assert_eq!(
+			params.authority_key_identifier_mode,
+			AuthorityKeyIdMode::KeyIdentifier
+		);
+		assert_eq!(
+			params.extension_criticality,
+			[
+				(ExtensionId::SubjectKeyIdentifier, false),
+				(ExtensionId::AuthorityKeyIdentifier, false),
+				(ExtensionId::BasicConstraints, true),
+				(ExtensionId::KeyUsage, true),
+				(ExtensionId::CrlDistributionPoints, false),
+				(ExtensionId::FreshestCrl, false),
+				(ExtensionId::AuthorityInfoAccess, false),
+				(ExtensionId::SubjectAltName, false),
+			]
+			.into()
+		);
+		assert!(params.use_subject_key_identifier_extension);
+		assert_eq!(params.is_ca, IsCa::Ca(BasicConstraints::Constrained(0)));
+		assert_eq!(
+			params.key_usages,
+			vec![KeyUsagePurpose::CrlSign, KeyUsagePurpose::KeyCertSign]
+		);
+		assert_eq!(
+			params.crl_distribution_points,
+			vec![
+				CrlDistributionPoint {
+					uris: vec!["http://crl.example.com/a.crl".to_owned()],
+				},
+				CrlDistributionPoint {
+					uris: vec!["http://crl.example.com/b.crl".to_owned()],
+				},
+			]
+		);
+		assert_eq!(
+			params.freshest_crl,
+			vec![CrlDistributionPoint {
+				uris: vec!["http://crl.example.com/delta.crl".to_owned()],
+			}]
+		);
+		assert_eq!(
+			params.authority_info_access,
+			vec![
+				(AccessMethod::Ocsp, "http://ocsp.example.com/".to_owned()),
+				(
+					AccessMethod::Other(vec![1, 2, 3, 4]),
+					"http://example.com/".to_owned()
+				),
+			]
+		);
+		assert_eq!(
+			params.subject_alt_names,
+			vec![
+				SanType::DnsName("ca.example.com".try_into().unwrap()),
+				SanType::OtherName((
+					vec![1, 3, 6, 1, 4, 1, 311, 20, 2, 3],
+					OtherNameValue::Utf8String("ca@example.com".to_owned())
+				)),
+			]
+		);
+	}
+
+	#[test]
+	fn certificate_policies() {
+		let params = CertificateParams::from_openssl_conf_section(
+			r#"
+			[ policies ]
+			certificatePolicies = critical, ia5org, 2.23.140.1.2.1, @polsect
+
+			[ polsect ]
+			policyIdentifier = 1.3.5.8
+			CPS.1 = "http://my.host.example.com/"
+			userNotice.1 = @notice
+
+			[ notice ]
+			explicitText = "UTF8:Explicit Text Here"
+			"#,
+		)
+		.unwrap();
+
+		assert!(params.certificate_policies_critical);
+		assert_eq!(
+			params.extension_criticality,
+			[(ExtensionId::CertificatePolicies, true)].into()
+		);
+		assert_eq!(
+			params.certificate_policies,
+			vec![
+				PolicyInformation {
+					oid: vec![2, 23, 140, 1, 2, 1],
+					qualifiers: Vec::new(),
+				},
+				PolicyInformation {
+					oid: vec![1, 3, 5, 8],
+					qualifiers: vec![
+						PolicyQualifier::CpsUri("http://my.host.example.com/".to_owned()),
+						PolicyQualifier::UserNotice {
+							organization: None,
+							notice_numbers: Vec::new(),
+							explicit_text: Some("Explicit Text Here".to_owned()),
+						},
+					],
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn key_identifiers() {
+		for (aki, mode) in [
+			("keyid, issuer:always", Some(AuthorityKeyIdMode::Both)),
+			(
+				"issuer:always",
+				Some(AuthorityKeyIdMode::IssuerAndSerialNumber),
+			),
+			("none", None),
+		] {
+			let params = CertificateParams::from_openssl_conf_section(&format!(
+				"subjectKeyIdentifier = none\nauthorityKeyIdentifier = {aki}"
+			))
+			.unwrap();
+			assert!(!params.use_subject_key_identifier_extension);
+			assert_eq!(
+				params.use_authority_key_identifier_extension,
+				mode.is_some()
+			);
+			assert_eq!(
+				params.authority_key_identifier_mode,
+				mode.unwrap_or_default()
+			);
+		}
+	}
+
+	#[test]
+	fn errors() {
+		for (conf, msg) in [
+			(
+				"nsComment = \"OpenSSL Generated\"",
+				"unsupported directive `nsComment`",
+			),
+			(
+				"keyUsage = digitalSignature, sign",
+				"unknown key usage `sign`",
+			),
+			(
+				"subjectAltName = @alt_names",
+				"section `alt_names` not found",
+			),
+			(
+				"subjectAltName = dirName:dir_sect",
+				"unsupported general name type `dirName`",
+			),
+			(
+				"basicConstraints = CA:FALSE, pathlen:1",
+				"pathlen requires CA:TRUE",
+			),
+			(
+				"keyUsage = digitalSignature\nkeyUsage = cRLSign",
+				"duplicate directive `keyUsage`",
+			),
+			(
+				"[v3_req\nkeyUsage = cRLSign",
+				"malformed section header on line 1",
+			),
+		] {
+			assert_eq!(
+				CertificateParams::from_openssl_conf_section(conf),
+				Err(Error::InvalidOpensslConf(msg.to_owned())),
+				"{conf}"
+			);
+		}
+	}
+}
@@ -0,0 +1,221 @@
+//! Password-based encryption with PBES2 of [RFC 8018](https://www.rfc-editor.org/rfc/rfc8018#section-6.2)
+//!
+//! Keys are derived with PBKDF2-HMAC-SHA256 and data is encrypted with AES-256-CBC, which is
+//! what current versions of OpenSSL and Java use by default. Decryption also supports the
+//! other SHA hashes for PBKDF2, and AES-128 and AES-192.
+
+use std::num::NonZeroU32;
+
+use yasna::models::ObjectIdentifier;
+use yasna::DERWriter;
+
+use crate::error::ExternalError;
+use crate::oid;
+use crate::ring_like::cipher::{
+	Algorithm, DecryptionContext, PaddedBlockDecryptingKey, PaddedBlockEncryptingKey,
+	UnboundCipherKey, AES_128, AES_192, AES_256,
+};
+use crate::ring_like::iv::FixedLength;
+use crate::ring_like::pbkdf2;
+use crate::ring_like::rand::{SecureRandom, SystemRandom};
+use crate::Error;
+
+/// Encrypts `plaintext` with a key derived from `password`
+///
+/// Returns the DER-encoded AlgorithmIdentifier describing the encryption, and the ciphertext.
+pub(crate) fn encrypt(
+	password: &[u8],
+	plaintext: &[u8],
+	iterations: NonZeroU32,
+) -> Result<(Vec<u8>, Vec<u8>), Error> {
+	let mut salt = [0; 16];
+	SystemRandom::new().fill(&mut salt)._err()?;
+	let mut key = [0; 32];
+	pbkdf2::derive(
+		pbkdf2::PBKDF2_HMAC_SHA256,
+		iterations,
+		&salt,
+		password,
+		&mut key,
+	);
+
+	let cipher = PaddedBlockEncryptingKey::cbc_pkcs7(UnboundCipherKey::new(&AES_256, &key)._err()?)
+		._err()?;
+	let mut ciphertext = plaintext.to_vec();
+	let context = cipher.encrypt(&mut ciphertext)._err()?;
+	let iv = <&[u8]>::try_from(&context)._err()?;
+
+	let algorithm = yasna::construct_der(|writer| write_algorithm(writer, &salt, iterations, iv));
+	Ok((algorithm, ciphertext))
+}
+
+/// Encrypts a PKCS#8 private key into an EncryptedPrivateKeyInfo of
+/// [RFC 5958](https://www.rfc-editor.org/rfc/rfc5958#section-3)
+pub(crate) fn encrypt_private_key(
+	password: &[u8],
+	pkcs8: &[u8],
+	iterations: NonZeroU32,
+) -> Result<Vec<u8>, Error> {
+	let (algorithm, ciphertext) = encrypt(password, pkcs8, iterations)?;
+	Ok(yasna::construct_der(|writer| {
+		writer.write_sequence(|writer| {
+			writer.next().write_der(&algorithm);
+			writer.next().write_bytes(&ciphertext);
+		})
+	}))
+}
+
+/// Decrypts the PKCS#8 private key of an EncryptedPrivateKeyInfo
+///
+/// Fails with [`Error::IncorrectPassword`] if the decrypted data isn't DER, which is what
+/// happens for all but a vanishingly small fraction of wrong passwords.
+pub(crate) fn decrypt_private_key(password: &[u8], der: &[u8]) -> Result<Vec<u8>, Error> {
+	let (algorithm, parameters, mut ciphertext) = crate::asn1::parse_der(der, |reader| {
+		reader.read_sequence(|reader| {
+			let (algorithm, parameters) = reader.next().read_sequence(|reader| {
+				let algorithm = reader.next().read_oid()?;
+				Ok((algorithm, reader.next().read_der()?))
+			})?;
+			Ok((algorithm, parameters, reader.next().read_bytes()?))
+		})
+	})
+	.map_err(|_| Error::CouldNotParseKeyPair)?;
+	if algorithm.components().as_slice() != oid::PBES2 {
+		return Err(Error::UnsupportedKeyEncryption);
+	}
+
+	let params = Params::from_der(&parameters)?;
+	let mut key = vec![0; params.key_len];
+	pbkdf2::derive(
+		params.prf,
+		params.iterations,
+		&params.salt,
+		password,
+		&mut key,
+	);
+
+	let cipher =
+		PaddedBlockDecryptingKey::cbc_pkcs7(UnboundCipherKey::new(params.cipher, &key)._err()?)
+			._err()?;
+	let plaintext = cipher
+		.decrypt(
+			&mut ciphertext,
+			DecryptionContext::Iv128(FixedLength::from(params.iv)),
+		)
+		.map_err(|_| Error::IncorrectPassword)?;
+	crate::asn1::parse_der(plaintext, |reader| reader.read_der())
+		.map_err(|_| Error::IncorrectPassword)?;
+	// Decrypted in place, so that no other copy of the private key is left behind
+	let len = plaintext.len();
+	ciphertext.truncate(len);
+	Ok(ciphertext)
+}
+
+/// The parameters of PBES2 with PBKDF2 and AES-CBC
+struct Params {
+	salt: Vec<u8>,
+	iterations: NonZeroU32,
+	prf: pbkdf2::Algorithm,
+	cipher: &'static Algorithm,
+	key_len: usize,
+	iv: [u8; 16],
+}
+
+impl Params {
+	fn from_der(der: &[u8]) -> Result<Self, Error> {
+		let (kdf, salt, iterations, key_len, prf, cipher, iv) =
+			crate::asn1::parse_der(der, |reader| {
+				reader.read_sequence(|reader| {
+					let (kdf, salt, iterations, key_len, prf) =
+						reader.next().read_sequence(|reader| {
+							let kdf = reader.next().read_oid()?;
+							let (salt, iterations, key_len, prf) =
+								reader.next().read_sequence(|reader| {
+									let salt = reader.next().read_bytes()?;
+									let iterations = reader.next().read_u32()?;
+									let key_len =
+										reader.read_optional(|reader| reader.read_u64())?;
+									let prf = reader.read_optional(|reader| {
+										reader.read_sequence(|reader| {
+											let prf = reader.next().read_oid()?;
+											reader.read_optional(|reader| reader.read_null())?;
+											Ok(prf)
+										})
+									})?;
+									Ok((salt, iterations, key_len, prf))
+								})?;
+							Ok((kdf, salt, iterations, key_len, prf))
+						})?;
+					let (cipher, iv) = reader.next().read_sequence(|reader| {
+						let cipher = reader.next().read_oid()?;
+						Ok((cipher, reader.next().read_bytes()?))
+					})?;
+					Ok((kdf, salt, iterations, key_len, prf, cipher, iv))
+				})
+			})
+			.map_err(|_| Error::CouldNotParseKeyPair)?;
+
+		if kdf.components().as_slice() != oid::PBKDF2 {
+			return Err(Error::UnsupportedKeyEncryption);
+		}
+		// The PRF defaults to HMAC-SHA1
+		let prf = match prf.as_ref().map(|prf| prf.components().as_slice()) {
+			None | Some(oid::HMAC_WITH_SHA1) => pbkdf2::PBKDF2_HMAC_SHA1,
+			Some(oid::HMAC_WITH_SHA256) => pbkdf2::PBKDF2_HMAC_SHA256,
+			Some(oid::HMAC_WITH_SHA384) => pbkdf2::PBKDF2_HMAC_SHA384,
+			Some(oid::HMAC_WITH_SHA512) => pbkdf2::PBKDF2_HMAC_SHA512,
+			Some(_) => return Err(Error::UnsupportedKeyEncryption),
+		};
+		let (cipher, cipher_key_len) = match cipher.components().as_slice() {
+			oid::AES_128_CBC => (&AES_128, 16),
+			oid::AES_192_CBC => (&AES_192, 24),
+			oid::AES_256_CBC => (&AES_256, 32),
+			_ => return Err(Error::UnsupportedKeyEncryption),
+		};
+		if key_len.is_some_and(|len| len != cipher_key_len as u64) {
+			return Err(Error::CouldNotParseKeyPair);
+		}
+
+		Ok(Self {
+			salt,
+			iterations: NonZeroU32::new(iterations).ok_or(Error::CouldNotParseKeyPair)?,
+			prf,
+			cipher,
+			key_len: cipher_key_len,
+			iv: iv.try_into().map_err(|_| Error::CouldNotParseKeyPair)?,
+		})
+	}
+}
+
+fn write_algorithm(writer: DERWriter, salt: &[u8], iterations: NonZeroU32, iv: &[u8]) {
+	writer.write_sequence(|writer| {
+		writer
+			.next()
+			.write_oid(&ObjectIdentifier::from_slice(oid::PBES2));
+		writer.next().write_sequence(|writer| {
+			// keyDerivationFunc
+			writer.next().write_sequence(|writer| {
+				writer
+					.next()
+					.write_oid(&ObjectIdentifier::from_slice(oid::PBKDF2));
+				writer.next().write_sequence(|writer| {
+					writer.next().write_bytes(salt);
+					writer.next().write_u32(iterations.get());
+					writer.next().write_sequence(|writer| {
+						writer
+							.next()
+							.write_oid(&ObjectIdentifier::from_slice(oid::HMAC_WITH_SHA256));
+						writer.next().write_null();
+					});
+				});
+			});
+			// encryptionScheme
+			writer.next().write_sequence(|writer| {
+				writer
+					.next()
+					.write_oid(&ObjectIdentifier::from_slice(oid::AES_256_CBC));
+				writer.next().write_bytes(iv);
+			});
+		});
+	});
+}
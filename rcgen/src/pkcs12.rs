@@ -0,0 +1,250 @@
+//! Exporting certificates and their private keys as PKCS#12 files of
+//! [RFC 7292](https://www.rfc-editor.org/rfc/rfc7292)
+
+use std::num::NonZeroU32;
+
+use yasna::models::ObjectIdentifier;
+use yasna::{DERWriter, Tag};
+
+use crate::error::ExternalError;
+use crate::ring_like::digest::{self, SHA256};
+use crate::ring_like::hmac;
+use crate::ring_like::rand::{SecureRandom, SystemRandom};
+use crate::{oid, pbes2, Certificate, CertifiedChain, CertifiedKey, Error, KeyPair};
+
+/// The iteration count for both the key encryption and the MAC, which is the default of
+/// Java's `keytool`
+const ITERATIONS: NonZeroU32 = match NonZeroU32::new(10_000) {
+	Some(iterations) => iterations,
+	None => unreachable!(),
+};
+
+impl CertifiedKey<KeyPair> {
+	/// Serializes the certificate and its private key as a PKCS#12 file, also known as `.p12`
+	/// or `.pfx` file
+	///
+	/// The private key is encrypted with PBES2, using PBKDF2-HMAC-SHA256 and AES-256-CBC,
+	/// and the file is integrity protected by an HMAC-SHA256 MAC, both keyed with `password`.
+	/// The certificate is stored unencrypted. If given, `friendly_name` is the alias of the
+	/// entry, as shown by `keytool -list`.
+	///
+	/// Requires the `aws_lc_rs` feature, as `ring` doesn't support AES-CBC.
+	pub fn serialize_pkcs12(
+		&self,
+		password: &str,
+		friendly_name: Option<&str>,
+	) -> Result<Vec<u8>, Error> {
+		serialize(self, &[], password, friendly_name)
+	}
+}
+
+impl CertifiedChain<KeyPair> {
+	/// Serializes the leaf certificate, its private key, and the certificates of its issuers
+	/// as a PKCS#12 file
+	///
+	/// The issuers are stored in the order of the chain. See
+	/// [`CertifiedKey::serialize_pkcs12()`] for the rest.
+	pub fn serialize_pkcs12(
+		&self,
+		password: &str,
+		friendly_name: Option<&str>,
+	) -> Result<Vec<u8>, Error> {
+		serialize(self.leaf(), self.issuers(), password, friendly_name)
+	}
+}
+
+/// Serializes `leaf` with its private key and the certificates of its `issuers`
+fn serialize(
+	leaf: &CertifiedKey<KeyPair>,
+	issuers: &[Certificate],
+	password: &str,
+	friendly_name: Option<&str>,
+) -> Result<Vec<u8>, Error> {
+	let cert = leaf.cert.der();
+	// Links the certificate with its private key
+	let local_key_id = digest::digest(&SHA256, cert);
+	let attributes = Attributes {
+		friendly_name,
+		local_key_id: local_key_id.as_ref(),
+	};
+
+	let shrouded_key = pbes2::encrypt_private_key(
+		password.as_bytes(),
+		&leaf.signing_key.serialize_der(),
+		ITERATIONS,
+	)?;
+
+	let auth_safe = yasna::construct_der(|writer| {
+		writer.write_sequence(|writer| {
+			let certs = yasna::construct_der(|writer| {
+				writer.write_sequence(|writer| {
+					write_cert_bag(writer.next(), cert, Some(&attributes));
+					for issuer in issuers {
+						write_cert_bag(writer.next(), issuer.der(), None);
+					}
+				})
+			});
+			write_data(writer.next(), &certs);
+
+			let keys = yasna::construct_der(|writer| {
+				writer.write_sequence(|writer| {
+					write_bag(
+						writer.next(),
+						oid::PKCS_12_SHROUDED_KEY_BAG,
+						&shrouded_key,
+						Some(&attributes),
+					);
+				})
+			});
+			write_data(writer.next(), &keys);
+		})
+	});
+
+	let mut salt = [0; 16];
+	SystemRandom::new().fill(&mut salt)._err()?;
+	let mac_key = mac_key(password, &salt, ITERATIONS);
+	let mac = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, &mac_key), &auth_safe);
+
+	Ok(yasna::construct_der(|writer| {
+		writer.write_sequence(|writer| {
+			writer.next().write_u8(3);
+			write_data(writer.next(), &auth_safe);
+			// MacData
+			writer.next().write_sequence(|writer| {
+				writer.next().write_sequence(|writer| {
+					writer.next().write_sequence(|writer| {
+						writer
+							.next()
+							.write_oid(&ObjectIdentifier::from_slice(oid::SHA256));
+						writer.next().write_null();
+					});
+					writer.next().write_bytes(mac.as_ref());
+				});
+				writer.next().write_bytes(&salt);
+				writer.next().write_u32(ITERATIONS.get());
+			});
+		})
+	}))
+}
+
+/// The attributes of the bags of the end-entity certificate and its private key
+struct Attributes<'a> {
+	friendly_name: Option<&'a str>,
+	local_key_id: &'a [u8],
+}
+
+/// Writes a ContentInfo of type data
+fn write_data(writer: DERWriter, content: &[u8]) {
+	writer.write_sequence(|writer| {
+		writer
+			.next()
+			.write_oid(&ObjectIdentifier::from_slice(oid::PKCS_7_DATA));
+		writer
+			.next()
+			.write_tagged(Tag::context(0), |writer| writer.write_bytes(content));
+	});
+}
+
+fn write_cert_bag(writer: DERWriter, cert: &[u8], attributes: Option<&Attributes<'_>>) {
+	let cert_bag = yasna::construct_der(|writer| {
+		writer.write_sequence(|writer| {
+			writer
+				.next()
+				.write_oid(&ObjectIdentifier::from_slice(oid::PKCS_9_X509_CERTIFICATE));
+			writer
+				.next()
+				.write_tagged(Tag::context(0), |writer| writer.write_bytes(cert));
+		})
+	});
+	write_bag(writer, oid::PKCS_12_CERT_BAG, &cert_bag, attributes);
+}
+
+/// Writes a SafeBag with the DER-encoded `value`
+fn write_bag(writer: DERWriter, bag_id: &[u64], value: &[u8], attributes: Option<&Attributes<'_>>) {
+	writer.write_sequence(|writer| {
+		writer
+			.next()
+			.write_oid(&ObjectIdentifier::from_slice(bag_id));
+		writer
+			.next()
+			.write_tagged(Tag::context(0), |writer| writer.write_der(value));
+		let Some(attributes) = attributes else {
+			return;
+		};
+		writer.next().write_set(|writer| {
+			if let Some(friendly_name) = attributes.friendly_name {
+				write_attribute(writer.next(), oid::PKCS_9_FRIENDLY_NAME, |writer| {
+					writer.write_bmp_string(friendly_name)
+				});
+			}
+			write_attribute(writer.next(), oid::PKCS_9_LOCAL_KEY_ID, |writer| {
+				writer.write_bytes(attributes.local_key_id)
+			});
+		});
+	});
+}
+
+fn write_attribute(writer: DERWriter, attr_type: &[u64], value: impl FnOnce(DERWriter)) {
+	writer.write_sequence(|writer| {
+		writer
+			.next()
+			.write_oid(&ObjectIdentifier::from_slice(attr_type));
+		writer.next().write_set(|writer| value(writer.next()));
+	});
+}
+
+/// Derives the key of the HMAC-SHA256 MAC as described in
+/// [RFC 7292 appendix B](https://www.rfc-editor.org/rfc/rfc7292#appendix-B.2)
+fn mac_key(password: &str, salt: &[u8], iterations: NonZeroU32) -> [u8; 32] {
+	/// The block size of SHA-256
+	const V: usize = 64;
+	/// The diversifier for MAC keys
+	const ID: u8 = 3;
+
+	fn repeat_to_blocks(input: &[u8]) -> impl Iterator<Item = &u8> {
+		input.iter().cycle().take(input.len().div_ceil(V) * V)
+	}
+
+	// The password is a null-terminated BMPString
+	let mut password = password
+		.encode_utf16()
+		.flat_map(u16::to_be_bytes)
+		.collect::<Vec<_>>();
+	password.extend([0, 0]);
+	let input = repeat_to_blocks(salt)
+		.chain(repeat_to_blocks(&password))
+		.copied()
+		.collect::<Vec<_>>();
+
+	// The key fits into a single hash output, so the input never needs to be updated
+	let mut context = digest::Context::new(&SHA256);
+	context.update(&[ID; V]);
+	context.update(&input);
+	let mut hash = context.finish();
+	for _ in 1..iterations.get() {
+		hash = digest::digest(&SHA256, hash.as_ref());
+	}
+
+	let mut key = [0; 32];
+	key.copy_from_slice(hash.as_ref());
+	key
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn mac_key_derivation() {
+		// Computed with OpenSSL's PKCS12_key_gen_utf8()
+		let key = mac_key("password", b"saltsalt", NonZeroU32::new(2048).unwrap());
+		assert_eq!(
+			key,
+			[
+				0xf0, 0x08, 0xb6, 0x83, 0xad, 0x83, 0x36, 0x6a, 0x7a, 0x91, 0xc5, 0xed, 0xb7, 0xeb,
+				0x6b, 0x75, 0xaf, 0x71, 0xee, 0xb3, 0xe4, 0x9b, 0x6a, 0x97, 0xc3, 0xd4, 0xbd, 0x69,
+				0xa0, 0x63, 0x2a, 0x24,
+			]
+		);
+	}
+}
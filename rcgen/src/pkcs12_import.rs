@@ -0,0 +1,146 @@
+//! Importing key pairs and certificates from PKCS#12 files of
+//! [RFC 7292](https://www.rfc-editor.org/rfc/rfc7292)
+
+use std::sync::OnceLock;
+
+use openssl::error::ErrorStack;
+use openssl::pkcs12::Pkcs12;
+use openssl::provider::Provider;
+use pki_types::CertificateDer;
+
+use crate::key_pair::SecretBytes;
+use crate::{Error, KeyPair};
+
+impl KeyPair {
+	/// Parses a PKCS#12 file, also known as `.p12` or `.pfx` file, returning its private key
+	/// and its certificates
+	///
+	/// The certificate of the key comes first, followed by the other certificates in the
+	/// order OpenSSL returns them, which for files exported by OpenSSL or Windows are the
+	/// certificates of its issuers.
+	///
+	/// The file is parsed and decrypted by OpenSSL, so every scheme it implements is
+	/// supported, like the legacy schemes of OpenSSL 1.1 and Windows, PBES1 with SHA-1 and
+	/// 3DES or RC2, and PBES2 with PBKDF2 and AES-CBC, as exported by OpenSSL 3. As RC2 is
+	/// only in the legacy provider of OpenSSL 3, that provider is loaded on first use, which
+	/// makes its algorithms available to the rest of the process too. Schemes OpenSSL
+	/// doesn't implement fail with [`Error::UnsupportedKeyEncryption`], and a wrong
+	/// `password` with [`Error::IncorrectPassword`].
+	///
+	/// Requires the `pkcs12` feature.
+	pub fn from_pkcs12(
+		der: &[u8],
+		password: &str,
+	) -> Result<(Self, Vec<CertificateDer<'static>>), Error> {
+		let pkcs12 = Pkcs12::from_der(der).map_err(|_| Error::CouldNotParseKeyPair)?;
+		// OpenSSL takes the password as C string
+		if password.contains('\0') {
+			return Err(Error::IncorrectPassword);
+		}
+
+		load_legacy_provider();
+		let parsed = pkcs12.parse2(password).map_err(parse_error)?;
+		let key = parsed.pkey.ok_or(Error::CouldNotParseKeyPair)?;
+		let pkcs8 = SecretBytes::from(
+			key.private_key_to_pkcs8()
+				.map_err(|_| Error::CouldNotParseKeyPair)?,
+		);
+		let key_pair = KeyPair::try_from(&pkcs8[..])?;
+
+		let certs = parsed
+			.cert
+			.into_iter()
+			.chain(parsed.ca.into_iter().flatten())
+			.map(|cert| match cert.to_der() {
+				Ok(der) => Ok(CertificateDer::from(der)),
+				Err(_) => Err(Error::CouldNotParseCertificate),
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+		Ok((key_pair, certs))
+	}
+}
+
+/// Loads the legacy provider of OpenSSL 3 for RC2, keeping the default provider available
+///
+/// The provider stays loaded for the lifetime of the process. If it isn't installed, only
+/// the files using its algorithms fail to parse.
+fn load_legacy_provider() {
+	static LEGACY: OnceLock<Option<Provider>> = OnceLock::new();
+	LEGACY.get_or_init(|| Provider::try_load(None, "legacy", true).ok());
+}
+
+/// Maps the errors of OpenSSL's PKCS#12 parser to those of [`KeyPair::from_pkcs12()`]
+///
+/// OpenSSL reports a wrong password as failing MAC verification, or, for files without a
+/// MAC, as failing decryption, while unknown schemes and ciphers are reported as
+/// unsupported or unknown algorithms.
+fn parse_error(errors: ErrorStack) -> Error {
+	let reasons = errors
+		.errors()
+		.iter()
+		.filter_map(|error| error.reason())
+		.collect::<Vec<_>>();
+	if reasons.iter().any(|reason| {
+		reason.contains("unsupported") || reason.contains("unknown") || reason.contains("fetch")
+	}) {
+		return Error::UnsupportedKeyEncryption;
+	}
+	if reasons
+		.iter()
+		.any(|reason| reason.contains("mac verify") || reason.contains("decrypt"))
+	{
+		return Error::IncorrectPassword;
+	}
+	Error::CouldNotParseKeyPair
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn import() {
+		// Exported with `openssl pkcs12 -export -legacy`, like OpenSSL 1.1 does by default: the
+		// certificates with RC2-40, the key with 3DES, and a SHA-1 MAC
+		let legacy = include_bytes!("test_data/rsa2048.legacy.p12");
+		// Exported with OpenSSL 3 defaults: PBES2 with AES-256-CBC and a SHA-256 MAC
+		let aes = include_bytes!("test_data/p256.aes.p12");
+		// Exported with `-keypbe PBE-SHA1-RC2-128 -certpbe PBE-SHA1-3DES`, without CA certificate
+		let rc2 = include_bytes!("test_data/p256.rc2.p12");
+		// Exported with `-keypbe camellia-256-cbc`
+		let camellia = include_bytes!("test_data/p256.camellia.p12");
+
+		for (der, alg, cert_count) in [
+			(&legacy[..], &crate::PKCS_RSA_SHA256, 2),
+			(aes, &crate::PKCS_ECDSA_P256_SHA256, 2),
+			(rc2, &crate::PKCS_ECDSA_P256_SHA256, 1),
+			(camellia, &crate::PKCS_ECDSA_P256_SHA256, 1),
+		] {
+			let (key_pair, certs) = KeyPair::from_pkcs12(der, "crabs").unwrap();
+			assert_eq!(key_pair.algorithm(), alg);
+			assert_eq!(certs.len(), cert_count);
+			// The certificate of the key comes first, followed by the one of the CA
+			let spki = key_pair.subject_public_key_info_der();
+			let contains_spki = |cert: &CertificateDer<'_>| {
+				cert.windows(spki.len()).any(|window| window == &spki[..])
+			};
+			assert!(contains_spki(&certs[0]));
+			assert!(!certs[1..].iter().any(contains_spki));
+
+			for password in ["crab", "crabs\0"] {
+				assert_eq!(
+					KeyPair::from_pkcs12(der, password).err(),
+					Some(Error::IncorrectPassword)
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn import_invalid() {
+		assert_eq!(
+			KeyPair::from_pkcs12(b"not a PKCS#12 file", "crabs").err(),
+			Some(Error::CouldNotParseKeyPair)
+		);
+	}
+}
@@ -46,5 +46,29 @@ pub(crate) fn rsa_key_pair_public_modulus_len(kp: &signature::RsaKeyPair) -> usi
 	}
 }
 
+/// Checks that `point` is a valid public key for the ECDH algorithm `alg`
+///
+/// Neither backend exposes public key validation for ECDSA keys, but both validate the peer
+/// key, including that the point is on the curve, when parsing it for key agreement.
+#[cfg(feature = "crypto")]
+pub(crate) fn ec_public_key_is_valid(
+	alg: &'static agreement::Algorithm,
+	point: &[u8],
+) -> Result<bool, Error> {
+	#[cfg(all(feature = "ring", not(feature = "aws_lc_rs")))]
+	{
+		let rng = rand::SystemRandom::new();
+		let private_key = agreement::EphemeralPrivateKey::generate(alg, &rng)._err()?;
+		let public_key = agreement::UnparsedPublicKey::new(alg, point);
+		Ok(agreement::agree_ephemeral(private_key, &public_key, |_| ()).is_ok())
+	}
+
+	#[cfg(feature = "aws_lc_rs")]
+	{
+		let public_key = agreement::UnparsedPublicKey::new(alg, point);
+		Ok(agreement::ParsedPublicKey::try_from(&public_key).is_ok())
+	}
+}
+
 #[cfg(all(feature = "crypto", not(any(feature = "ring", feature = "aws_lc_rs"))))]
 compile_error!("At least one of the 'ring' or 'aws_lc_rs' features must be activated when the 'crypto' feature is enabled");
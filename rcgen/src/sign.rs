@@ -0,0 +1,94 @@
+//! Signing of arbitrary X.509-style structures
+//!
+//! Certificates, CRLs and CSRs all share the same outer structure: a SEQUENCE of the signed
+//! data, the signature algorithm and the signature. Many adjacent formats, like OCSP
+//! responses, reuse it, and [`signed_sequence()`] produces it for any signed data.
+
+use crate::key_pair::assemble_signed_der;
+use crate::{Error, SigningKey};
+
+/// Signs `tbs_der` with `key` and wraps both into a signed SEQUENCE
+///
+/// `tbs_der` is the DER encoding of the to-be-signed data. The result is the DER encoding of
+///
+/// ```text
+/// SEQUENCE {
+///     tbs                 ANY,
+///     signatureAlgorithm  AlgorithmIdentifier,
+///     signature           BIT STRING
+/// }
+/// ```
+///
+/// where the algorithm identifier is the one of `key`'s
+/// [`algorithm()`](crate::PublicKeyData::algorithm). If the to-be-signed data contains an
+/// algorithm identifier itself, it's up to the caller to make it match.
+///
+/// Returns [`Error::CouldNotParseTbs`] if `tbs_der` isn't a single DER-encoded value.
+pub fn signed_sequence(key: &(impl SigningKey + ?Sized), tbs_der: &[u8]) -> Result<Vec<u8>, Error> {
+	crate::asn1::parse_der(tbs_der, |reader| reader.read_der())
+		.map_err(|_| Error::CouldNotParseTbs)?;
+	let signature = key.sign(tbs_der)?;
+	Ok(assemble_signed_der(tbs_der, key.algorithm(), &signature))
+}
+
+#[cfg(all(test, feature = "crypto"))]
+mod tests {
+	use super::*;
+	use crate::ring_like::signature::{self, UnparsedPublicKey};
+	use crate::test_keys;
+
+	#[test]
+	fn sign_and_verify() {
+		// An arbitrary structure that isn't a certificate
+		let tbs = yasna::construct_der(|writer| {
+			writer.write_sequence(|writer| {
+				writer.next().write_u8(7);
+				writer.next().write_utf8_string("signed manifest");
+			})
+		});
+
+		for (key, verification_alg) in [
+			(
+				test_keys::ecdsa_p256(),
+				&signature::ECDSA_P256_SHA256_ASN1 as &dyn signature::VerificationAlgorithm,
+			),
+			(test_keys::ed25519(), &signature::ED25519),
+		] {
+			let signed = signed_sequence(&key, &tbs).unwrap();
+			let (signed_tbs, alg, sig) = crate::asn1::parse_der(&signed, |reader| {
+				reader.read_sequence(|reader| {
+					let tbs = reader.next().read_der()?;
+					let alg = reader.next().read_der()?;
+					let (sig, bits) = reader.next().read_bitvec_bytes()?;
+					assert_eq!(bits, sig.len() * 8);
+					Ok((tbs, alg, sig))
+				})
+			})
+			.unwrap();
+
+			assert_eq!(signed_tbs, tbs);
+			assert_eq!(
+				alg,
+				yasna::construct_der(|writer| key.algorithm().write_alg_ident(writer))
+			);
+			UnparsedPublicKey::new(verification_alg, key.public_key_raw())
+				.verify(&tbs, &sig)
+				.unwrap();
+		}
+	}
+
+	#[test]
+	fn rejects_malformed_tbs() {
+		let key = test_keys::ed25519();
+		for tbs in [
+			&[][..],
+			&[0x30, 0x03, 0x02, 0x01],
+			&[0x02, 0x01, 0x00, 0x00],
+		] {
+			assert_eq!(
+				signed_sequence(&key, tbs).unwrap_err(),
+				Error::CouldNotParseTbs
+			);
+		}
+	}
+}
@@ -1,5 +1,6 @@
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 
 #[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
 use aws_lc_rs::unstable::signature::{
@@ -8,6 +9,7 @@ use aws_lc_rs::unstable::signature::{
 use yasna::models::ObjectIdentifier;
 use yasna::{DERWriter, Tag};
 
+use crate::oid::{MGF1, RSASSA_PSS};
 #[cfg(feature = "crypto")]
 use crate::ring_like::signature::{self, EcdsaSigningAlgorithm, EdDSAParameters, RsaEncoding};
 use crate::Error;
@@ -19,7 +21,11 @@ pub(crate) enum SignAlgo {
 	EdDsa(&'static EdDSAParameters),
 	#[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
 	PqDsa(&'static PqdsaSigningAlgorithm),
+	#[cfg(feature = "ed448")]
+	Ed448,
 	Rsa(&'static dyn RsaEncoding),
+	/// The crypto backend can't sign with this algorithm, only a custom `SigningKey` can
+	Unavailable,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -38,52 +44,60 @@ pub(crate) enum SignatureAlgorithmParams {
 /// Signature algorithm type
 #[derive(Clone)]
 pub struct SignatureAlgorithm {
+	name: &'static str,
+	display_name: &'static str,
+	digest: Option<DigestAlgorithm>,
 	oids_sign_alg: &'static [&'static [u64]],
 	#[cfg(feature = "crypto")]
 	pub(crate) sign_alg: SignAlgo,
-	oid_components: &'static [u64],
+	pub(crate) oid_components: &'static [u64],
 	params: SignatureAlgorithmParams,
 }
 
 impl fmt::Debug for SignatureAlgorithm {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		use algo::*;
-		if self == &PKCS_RSA_SHA256 {
-			write!(f, "PKCS_RSA_SHA256")
-		} else if self == &PKCS_RSA_SHA384 {
-			write!(f, "PKCS_RSA_SHA384")
-		} else if self == &PKCS_RSA_SHA512 {
-			write!(f, "PKCS_RSA_SHA512")
-		} else if self == &PKCS_RSA_PSS_SHA256 {
-			write!(f, "PKCS_RSA_PSS_SHA256")
-		} else if self == &PKCS_ECDSA_P256_SHA256 {
-			write!(f, "PKCS_ECDSA_P256_SHA256")
-		} else if self == &PKCS_ECDSA_P384_SHA384 {
-			write!(f, "PKCS_ECDSA_P384_SHA384")
-		} else if self == &PKCS_ED25519 {
-			write!(f, "PKCS_ED25519")
-		} else {
-			#[cfg(feature = "aws_lc_rs")]
-			if self == &PKCS_ECDSA_P521_SHA256 {
-				return write!(f, "PKCS_ECDSA_P521_SHA256");
-			}
-			#[cfg(feature = "aws_lc_rs")]
-			if self == &PKCS_ECDSA_P521_SHA384 {
-				return write!(f, "PKCS_ECDSA_P521_SHA384");
-			}
-			#[cfg(feature = "aws_lc_rs")]
-			if self == &PKCS_ECDSA_P521_SHA512 {
-				return write!(f, "PKCS_ECDSA_P521_SHA512");
-			}
+		write!(f, "PKCS_{}", self.name)
+	}
+}
+
+/// Writes a human readable name that is kept stable across releases,
+/// e.g. `ECDSA P-256 with SHA-256` or `RSA PKCS#1 SHA-384`
+impl fmt::Display for SignatureAlgorithm {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.display_name)
+	}
+}
 
-			write!(f, "Unknown")
+/// Looks up an algorithm by its [`name`](SignatureAlgorithm::name), with or without the
+/// `PKCS_` prefix, by its [`Display`](fmt::Display) name, or by its OID in dotted notation
+///
+/// Names are known for all algorithm constants enabled by the crate features. OIDs are
+/// resolved like [`SignatureAlgorithm::from_oid`] does, so an OID shared by several
+/// algorithms resolves to the first one of them.
+impl FromStr for &'static SignatureAlgorithm {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let name = s.strip_prefix("PKCS_").unwrap_or(s);
+		if let Some(alg) = SignatureAlgorithm::all()
+			.iter()
+			.find(|alg| alg.name == name || alg.display_name == s)
+		{
+			return Ok(alg);
 		}
+		let oid = s
+			.split('.')
+			.map(|c| c.parse().ok())
+			.collect::<Option<Vec<u64>>>()
+			.ok_or(Error::UnsupportedSignatureAlgorithm)?;
+		SignatureAlgorithm::from_oid(&oid)
 	}
 }
 
 impl PartialEq for SignatureAlgorithm {
 	fn eq(&self, other: &Self) -> bool {
-		(self.oids_sign_alg, self.oid_components) == (other.oids_sign_alg, other.oid_components)
+		(self.oids_sign_alg, self.oid_components, self.params)
+			== (other.oids_sign_alg, other.oid_components, other.params)
 	}
 }
 
@@ -103,21 +117,237 @@ impl SignatureAlgorithm {
 			&PKCS_RSA_SHA256,
 			&PKCS_RSA_SHA384,
 			&PKCS_RSA_SHA512,
-			//&PKCS_RSA_PSS_SHA256,
+			&PKCS_RSA_PSS_SHA256,
+			&PKCS_RSA_PSS_SHA384,
+			&PKCS_RSA_PSS_SHA512,
 			&PKCS_ECDSA_P256_SHA256,
 			&PKCS_ECDSA_P384_SHA384,
-			#[cfg(feature = "aws_lc_rs")]
 			&PKCS_ECDSA_P521_SHA256,
-			#[cfg(feature = "aws_lc_rs")]
 			&PKCS_ECDSA_P521_SHA384,
-			#[cfg(feature = "aws_lc_rs")]
 			&PKCS_ECDSA_P521_SHA512,
+			&PKCS_ECDSA_K256_SHA256,
 			&PKCS_ED25519,
+			&PKCS_ED448,
 		];
 		ALGORITHMS.iter()
 	}
 
+	/// All public algorithm constants, including the ones [`iter()`](Self::iter) leaves out
+	/// because they share an OID with another algorithm or are behind a feature flag
+	fn all() -> &'static [&'static SignatureAlgorithm] {
+		use algo::*;
+		static ALGORITHMS: &[&SignatureAlgorithm] = &[
+			&PKCS_RSA_SHA256,
+			&PKCS_RSA_SHA384,
+			&PKCS_RSA_SHA512,
+			&PKCS_RSA_PSS_SHA256,
+			&PKCS_RSA_PSS_SHA384,
+			&PKCS_RSA_PSS_SHA512,
+			&PKCS_ECDSA_P256_SHA256,
+			&PKCS_ECDSA_P384_SHA384,
+			&PKCS_ECDSA_P521_SHA256,
+			&PKCS_ECDSA_P521_SHA384,
+			&PKCS_ECDSA_P521_SHA512,
+			&PKCS_ECDSA_K256_SHA256,
+			&PKCS_ED25519,
+			&PKCS_ED448,
+			&PKCS_ECDSA_P256_SHA384,
+			&PKCS_ECDSA_P384_SHA256,
+			#[cfg(feature = "insecure_legacy_algorithms")]
+			&PKCS_RSA_SHA1,
+			#[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
+			&PKCS_ML_DSA_44,
+			#[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
+			&PKCS_ML_DSA_65,
+			#[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
+			&PKCS_ML_DSA_87,
+			#[cfg(all(feature = "composite_signatures_unstable", not(feature = "fips")))]
+			&PKCS_MLDSA44_ED25519,
+			#[cfg(all(feature = "composite_signatures_unstable", not(feature = "fips")))]
+			&PKCS_MLDSA44_ECDSA_P256_SHA256,
+			#[cfg(all(feature = "composite_signatures_unstable", not(feature = "fips")))]
+			&PKCS_MLDSA65_ED25519,
+		];
+		ALGORITHMS
+	}
+
+	/// The name of the algorithm, e.g. `ECDSA_P256_SHA256`
+	///
+	/// This matches the name of the corresponding constant without its `PKCS_` prefix.
+	/// For a human readable name, use the [`Display`](fmt::Display) implementation.
+	pub fn name(&self) -> &'static str {
+		self.name
+	}
+
+	/// The OID of the signature algorithm in dotted notation, e.g. `1.2.840.10045.4.3.2`
+	pub fn oid_string(&self) -> String {
+		dotted(self.oid_components)
+	}
+
+	/// The OID of the subject public key's algorithm in dotted notation,
+	/// e.g. `1.2.840.10045.2.1` for id-ecPublicKey
+	pub fn key_oid_string(&self) -> String {
+		dotted(self.key_alg_oid())
+	}
+
+	/// The digest the message is hashed with before signing
+	///
+	/// Returns `None` for algorithms that don't use a separate digest step, like Ed25519.
+	pub fn digest(&self) -> Option<DigestAlgorithm> {
+		self.digest
+	}
+
+	/// Whether the crypto backend rcgen was built with can sign with this algorithm
+	///
+	/// This is `false` if the `crypto` feature is disabled, in which case signing is
+	/// left to a custom [`SigningKey`](crate::SigningKey).
+	pub fn is_supported(&self) -> bool {
+		#[cfg(feature = "crypto")]
+		return !matches!(self.sign_alg, SignAlgo::Unavailable);
+		#[cfg(not(feature = "crypto"))]
+		false
+	}
+
+	/// All algorithms the crypto backend rcgen was built with can sign with
+	///
+	/// These are the [supported](Self::is_supported) ones, like the P-521 algorithms only with
+	/// the `aws_lc_rs` feature. Whether key pairs can be generated or loaded for an algorithm
+	/// depends on the backend too, see [`can_generate()`](Self::can_generate) and
+	/// [`can_load()`](Self::can_load).
+	pub fn supported() -> impl Iterator<Item = &'static SignatureAlgorithm> {
+		#[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
+		static UNSTABLE_ALGORITHMS: &[&SignatureAlgorithm] = &[
+			&algo::PKCS_ML_DSA_44,
+			&algo::PKCS_ML_DSA_65,
+			&algo::PKCS_ML_DSA_87,
+		];
+		#[cfg(not(all(feature = "aws_lc_rs_unstable", not(feature = "fips"))))]
+		static UNSTABLE_ALGORITHMS: &[&SignatureAlgorithm] = &[];
+
+		Self::iter()
+			.chain(UNSTABLE_ALGORITHMS)
+			.copied()
+			.filter(|alg| alg.is_supported())
+	}
+
+	/// Whether [`KeyPair::generate_for()`](crate::KeyPair::generate_for) can generate key pairs
+	/// for this algorithm
+	///
+	/// This is `false` for unsupported algorithms, and for RSA unless the `aws_lc_rs` feature
+	/// is enabled, as `ring` can't generate RSA keys.
+	pub fn can_generate(&self) -> bool {
+		#[cfg(feature = "crypto")]
+		return match self.sign_alg {
+			SignAlgo::Rsa(_) => cfg!(feature = "aws_lc_rs"),
+			_ => self.is_supported(),
+		};
+		#[cfg(not(feature = "crypto"))]
+		false
+	}
+
+	/// Whether existing private keys can be loaded for this algorithm, e.g. with
+	/// [`KeyPair::from_pkcs8_der_and_sign_algo()`](crate::KeyPair::from_pkcs8_der_and_sign_algo)
+	///
+	/// This is `false` for unsupported algorithms, and for ML-DSA, whose keys can only be
+	/// generated.
+	pub fn can_load(&self) -> bool {
+		#[cfg(feature = "crypto")]
+		return match self.sign_alg {
+			#[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
+			SignAlgo::PqDsa(_) => false,
+			SignAlgo::Unavailable => false,
+			_ => true,
+		};
+		#[cfg(not(feature = "crypto"))]
+		false
+	}
+
+	/// The algorithm that verifies signatures as the crypto backend makes them for this algorithm
+	///
+	/// Returns `None` if the backend can't verify them, like for Ed448, which isn't implemented
+	/// by either backend.
+	#[cfg(feature = "crypto")]
+	pub(crate) fn verification_alg(&self) -> Option<&'static dyn signature::VerificationAlgorithm> {
+		use algo::*;
+
+		Some(if self == &PKCS_ECDSA_P256_SHA256 {
+			&signature::ECDSA_P256_SHA256_ASN1
+		} else if self == &PKCS_ECDSA_P256_SHA384 {
+			&signature::ECDSA_P256_SHA384_ASN1
+		} else if self == &PKCS_ECDSA_P384_SHA256 {
+			&signature::ECDSA_P384_SHA256_ASN1
+		} else if self == &PKCS_ECDSA_P384_SHA384 {
+			&signature::ECDSA_P384_SHA384_ASN1
+		} else if self == &PKCS_ED25519 {
+			&signature::ED25519
+		} else if self == &PKCS_RSA_SHA256 {
+			&signature::RSA_PKCS1_2048_8192_SHA256
+		} else if self == &PKCS_RSA_SHA384 {
+			&signature::RSA_PKCS1_2048_8192_SHA384
+		} else if self == &PKCS_RSA_SHA512 {
+			&signature::RSA_PKCS1_2048_8192_SHA512
+		} else if self == &PKCS_RSA_PSS_SHA256 {
+			&signature::RSA_PSS_2048_8192_SHA256
+		} else if self == &PKCS_RSA_PSS_SHA384 {
+			&signature::RSA_PSS_2048_8192_SHA384
+		} else if self == &PKCS_RSA_PSS_SHA512 {
+			&signature::RSA_PSS_2048_8192_SHA512
+		} else {
+			return self.backend_verification_alg();
+		})
+	}
+
+	/// The part of [`verification_alg()`](Self::verification_alg) specific to `aws-lc-rs`
+	#[cfg(feature = "crypto")]
+	fn backend_verification_alg(&self) -> Option<&'static dyn signature::VerificationAlgorithm> {
+		#[cfg(feature = "aws_lc_rs")]
+		{
+			use algo::*;
+
+			Some(if self == &PKCS_ECDSA_P521_SHA256 {
+				&signature::ECDSA_P521_SHA256_ASN1
+			} else if self == &PKCS_ECDSA_P521_SHA384 {
+				&signature::ECDSA_P521_SHA384_ASN1
+			} else if self == &PKCS_ECDSA_P521_SHA512 {
+				&signature::ECDSA_P521_SHA512_ASN1
+			} else if self == &PKCS_ECDSA_K256_SHA256 {
+				&signature::ECDSA_P256K1_SHA256_ASN1
+			} else {
+				#[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
+				if self == &PKCS_ML_DSA_44 {
+					return Some(&aws_lc_rs::unstable::signature::ML_DSA_44);
+				} else if self == &PKCS_ML_DSA_65 {
+					return Some(&aws_lc_rs::unstable::signature::ML_DSA_65);
+				} else if self == &PKCS_ML_DSA_87 {
+					return Some(&aws_lc_rs::unstable::signature::ML_DSA_87);
+				}
+				return None;
+			})
+		}
+		#[cfg(not(feature = "aws_lc_rs"))]
+		None
+	}
+
+	/// The OID identifying the subject public key's algorithm, e.g. rsaEncryption or id-ecPublicKey
+	pub(crate) fn key_alg_oid(&self) -> &'static [u64] {
+		self.oids_sign_alg[0]
+	}
+
+	/// The OID of the named curve for EC keys, if any
+	pub(crate) fn curve_oid(&self) -> Option<&'static [u64]> {
+		match self.key_alg_oid() {
+			crate::oid::EC_PUBLIC_KEY => self.oids_sign_alg.get(1).copied(),
+			_ => None,
+		}
+	}
+
 	/// Retrieve the SignatureAlgorithm for the provided OID
+	///
+	/// Some algorithms share their OID, like ECDSA with SHA-256 on the P-256 and P-521 curves,
+	/// or the RSASSA-PSS variants, which are told apart by their parameters. The first
+	/// of them is returned, which is the most common one, e.g. [`PKCS_ECDSA_P256_SHA256`].
+	///
+	/// [`PKCS_ECDSA_P256_SHA256`]: crate::PKCS_ECDSA_P256_SHA256
 	pub fn from_oid(oid: &[u64]) -> Result<&'static SignatureAlgorithm, Error> {
 		for algo in Self::iter() {
 			if algo.oid_components == oid {
@@ -126,6 +356,116 @@ impl SignatureAlgorithm {
 		}
 		Err(Error::UnsupportedSignatureAlgorithm)
 	}
+
+	/// Retrieves the RSASSA-PSS algorithm for a DER-encoded id-RSASSA-PSS AlgorithmIdentifier
+	///
+	/// Returns `None` if the parameters are absent, which is how keys that may be used with
+	/// any hash are identified. Only the parameters the crypto backends can handle are
+	/// supported: the same hash for the message and MGF1, a salt as long as the hash, and the
+	/// default trailer field.
+	pub(crate) fn from_rsa_pss_alg_ident(
+		der: &[u8],
+	) -> Result<Option<&'static SignatureAlgorithm>, Error> {
+		fn read_hash(reader: yasna::BERReader) -> yasna::ASN1Result<ObjectIdentifier> {
+			reader.read_sequence(|reader| {
+				let oid = reader.next().read_oid()?;
+				reader.read_optional(|reader| reader.read_null())?;
+				Ok(oid)
+			})
+		}
+
+		let params = crate::asn1::parse_der(der, |reader| {
+			reader.read_sequence(|reader| {
+				if reader.next().read_oid()?.components() != RSASSA_PSS {
+					return Err(yasna::ASN1Error::new(yasna::ASN1ErrorKind::Invalid));
+				}
+				reader.read_optional(|reader| {
+					reader.read_sequence(|reader| {
+						let hash = reader.read_optional(|reader| {
+							reader.read_tagged(Tag::context(0), read_hash)
+						})?;
+						let mgf = reader.read_optional(|reader| {
+							reader.read_tagged(Tag::context(1), |reader| {
+								reader.read_sequence(|reader| {
+									let oid = reader.next().read_oid()?;
+									Ok((oid, read_hash(reader.next())?))
+								})
+							})
+						})?;
+						let salt_length = reader.read_optional(|reader| {
+							reader.read_tagged(Tag::context(2), |reader| reader.read_u64())
+						})?;
+						let trailer_field = reader.read_optional(|reader| {
+							reader.read_tagged(Tag::context(3), |reader| reader.read_u64())
+						})?;
+						Ok((hash, mgf, salt_length, trailer_field))
+					})
+				})
+			})
+		})
+		.map_err(|_| Error::UnsupportedSignatureAlgorithm)?;
+
+		// Absent fields default to SHA-1, which none of the algorithms use
+		let Some((Some(hash), Some((mgf, mgf_hash)), salt_length, None | Some(1))) = params else {
+			return match params {
+				None => Ok(None),
+				Some(_) => Err(Error::UnsupportedSignatureAlgorithm),
+			};
+		};
+
+		Self::iter()
+			.find(|alg| {
+				let SignatureAlgorithmParams::RsaPss {
+					hash_algorithm,
+					salt_length: expected_salt_length,
+				} = alg.params
+				else {
+					return false;
+				};
+				hash.components() == hash_algorithm
+					&& mgf.components() == MGF1
+					&& mgf_hash == hash
+					&& salt_length == Some(expected_salt_length)
+			})
+			.map(|alg| Some(*alg))
+			.ok_or(Error::UnsupportedSignatureAlgorithm)
+	}
+}
+
+/// A message digest algorithm used as part of a [`SignatureAlgorithm`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DigestAlgorithm {
+	/// SHA-1 as per [FIPS 180-4](https://csrc.nist.gov/pubs/fips/180-4/upd1/final)
+	///
+	/// SHA-1 is vulnerable to collision attacks and must not be used for new certificates.
+	#[cfg(feature = "insecure_legacy_algorithms")]
+	Sha1,
+	/// SHA-256 as per [FIPS 180-4](https://csrc.nist.gov/pubs/fips/180-4/upd1/final)
+	Sha256,
+	/// SHA-384 as per [FIPS 180-4](https://csrc.nist.gov/pubs/fips/180-4/upd1/final)
+	Sha384,
+	/// SHA-512 as per [FIPS 180-4](https://csrc.nist.gov/pubs/fips/180-4/upd1/final)
+	Sha512,
+}
+
+impl fmt::Display for DigestAlgorithm {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(match self {
+			#[cfg(feature = "insecure_legacy_algorithms")]
+			Self::Sha1 => "SHA-1",
+			Self::Sha256 => "SHA-256",
+			Self::Sha384 => "SHA-384",
+			Self::Sha512 => "SHA-512",
+		})
+	}
+}
+
+fn dotted(oid: &[u64]) -> String {
+	oid.iter()
+		.map(|component| component.to_string())
+		.collect::<Vec<_>>()
+		.join(".")
 }
 
 /// The list of supported signature algorithms
@@ -133,8 +473,33 @@ pub(crate) mod algo {
 	use super::*;
 	use crate::oid::*;
 
+	/// RSA signing with PKCS#1 1.5 padding and SHA-1 hashing as per [RFC 3279](https://tools.ietf.org/html/rfc3279#section-2.2.1)
+	///
+	/// **This algorithm is insecure.** SHA-1 collisions are practical, so certificates signed with
+	/// it can be forged. It only exists to produce certificates and CRLs for legacy systems that
+	/// accept nothing else, and requires the `insecure_legacy_algorithms` feature.
+	///
+	/// None of the crypto backends can sign with SHA-1, so it requires a custom
+	/// [`SigningKey`](crate::SigningKey) implementation. It is never picked when
+	/// detecting the algorithm of a key.
+	#[cfg(feature = "insecure_legacy_algorithms")]
+	pub static PKCS_RSA_SHA1: SignatureAlgorithm = SignatureAlgorithm {
+		name: "RSA_SHA1",
+		display_name: "RSA PKCS#1 SHA-1",
+		digest: Some(DigestAlgorithm::Sha1),
+		oids_sign_alg: &[RSA_ENCRYPTION],
+		#[cfg(feature = "crypto")]
+		sign_alg: SignAlgo::Unavailable,
+		// sha1WithRSAEncryption in RFC 3279
+		oid_components: &[1, 2, 840, 113549, 1, 1, 5],
+		params: SignatureAlgorithmParams::Null,
+	};
+
 	/// RSA signing with PKCS#1 1.5 padding and SHA-256 hashing as per [RFC 4055](https://tools.ietf.org/html/rfc4055)
 	pub static PKCS_RSA_SHA256: SignatureAlgorithm = SignatureAlgorithm {
+		name: "RSA_SHA256",
+		display_name: "RSA PKCS#1 SHA-256",
+		digest: Some(DigestAlgorithm::Sha256),
 		oids_sign_alg: &[RSA_ENCRYPTION],
 		#[cfg(feature = "crypto")]
 		sign_alg: SignAlgo::Rsa(&signature::RSA_PKCS1_SHA256),
@@ -145,6 +510,9 @@ pub(crate) mod algo {
 
 	/// RSA signing with PKCS#1 1.5 padding and SHA-384 hashing as per [RFC 4055](https://tools.ietf.org/html/rfc4055)
 	pub static PKCS_RSA_SHA384: SignatureAlgorithm = SignatureAlgorithm {
+		name: "RSA_SHA384",
+		display_name: "RSA PKCS#1 SHA-384",
+		digest: Some(DigestAlgorithm::Sha384),
 		oids_sign_alg: &[RSA_ENCRYPTION],
 		#[cfg(feature = "crypto")]
 		sign_alg: SignAlgo::Rsa(&signature::RSA_PKCS1_SHA384),
@@ -155,6 +523,9 @@ pub(crate) mod algo {
 
 	/// RSA signing with PKCS#1 1.5 padding and SHA-512 hashing as per [RFC 4055](https://tools.ietf.org/html/rfc4055)
 	pub static PKCS_RSA_SHA512: SignatureAlgorithm = SignatureAlgorithm {
+		name: "RSA_SHA512",
+		display_name: "RSA PKCS#1 SHA-512",
+		digest: Some(DigestAlgorithm::Sha512),
 		oids_sign_alg: &[RSA_ENCRYPTION],
 		#[cfg(feature = "crypto")]
 		sign_alg: SignAlgo::Rsa(&signature::RSA_PKCS1_SHA512),
@@ -163,29 +534,66 @@ pub(crate) mod algo {
 		params: SignatureAlgorithmParams::Null,
 	};
 
-	// TODO: not really sure whether the certs we generate actually work.
-	// Both openssl and webpki reject them. It *might* be possible that openssl
-	// accepts the certificate if the key is a proper RSA-PSS key, but ring doesn't
-	// support those: https://github.com/briansmith/ring/issues/1353
-	//
 	/// RSA signing with PKCS#1 2.1 RSASSA-PSS padding and SHA-256 hashing as per [RFC 4055](https://tools.ietf.org/html/rfc4055)
-	pub(crate) static PKCS_RSA_PSS_SHA256: SignatureAlgorithm = SignatureAlgorithm {
+	///
+	/// The subject public key is identified by id-RSASSA-PSS with parameters that restrict it
+	/// to this algorithm, rather than by rsaEncryption. Some validators, among them webpki,
+	/// don't accept such keys.
+	pub static PKCS_RSA_PSS_SHA256: SignatureAlgorithm = SignatureAlgorithm {
+		name: "RSA_PSS_SHA256",
+		display_name: "RSA-PSS SHA-256",
+		digest: Some(DigestAlgorithm::Sha256),
 		// We could also use RSA_ENCRYPTION here, but it's recommended
 		// to use ID-RSASSA-PSS if possible.
 		oids_sign_alg: &[RSASSA_PSS],
 		#[cfg(feature = "crypto")]
 		sign_alg: SignAlgo::Rsa(&signature::RSA_PSS_SHA256),
-		oid_components: RSASSA_PSS, //&[1, 2, 840, 113549, 1, 1, 13],
-		// rSASSA-PSS-SHA256-Params in RFC 4055
+		oid_components: RSASSA_PSS,
 		params: SignatureAlgorithmParams::RsaPss {
-			// id-sha256 in https://datatracker.ietf.org/doc/html/rfc4055#section-2.1
-			hash_algorithm: &[2, 16, 840, 1, 101, 3, 4, 2, 1],
-			salt_length: 20,
+			hash_algorithm: SHA256,
+			salt_length: 32,
+		},
+	};
+
+	/// RSA signing with PKCS#1 2.1 RSASSA-PSS padding and SHA-384 hashing as per [RFC 4055](https://tools.ietf.org/html/rfc4055)
+	///
+	/// See [`PKCS_RSA_PSS_SHA256`] for how the subject public key is identified.
+	pub static PKCS_RSA_PSS_SHA384: SignatureAlgorithm = SignatureAlgorithm {
+		name: "RSA_PSS_SHA384",
+		display_name: "RSA-PSS SHA-384",
+		digest: Some(DigestAlgorithm::Sha384),
+		oids_sign_alg: &[RSASSA_PSS],
+		#[cfg(feature = "crypto")]
+		sign_alg: SignAlgo::Rsa(&signature::RSA_PSS_SHA384),
+		oid_components: RSASSA_PSS,
+		params: SignatureAlgorithmParams::RsaPss {
+			hash_algorithm: SHA384,
+			salt_length: 48,
+		},
+	};
+
+	/// RSA signing with PKCS#1 2.1 RSASSA-PSS padding and SHA-512 hashing as per [RFC 4055](https://tools.ietf.org/html/rfc4055)
+	///
+	/// See [`PKCS_RSA_PSS_SHA256`] for how the subject public key is identified.
+	pub static PKCS_RSA_PSS_SHA512: SignatureAlgorithm = SignatureAlgorithm {
+		name: "RSA_PSS_SHA512",
+		display_name: "RSA-PSS SHA-512",
+		digest: Some(DigestAlgorithm::Sha512),
+		oids_sign_alg: &[RSASSA_PSS],
+		#[cfg(feature = "crypto")]
+		sign_alg: SignAlgo::Rsa(&signature::RSA_PSS_SHA512),
+		oid_components: RSASSA_PSS,
+		params: SignatureAlgorithmParams::RsaPss {
+			hash_algorithm: SHA512,
+			salt_length: 64,
 		},
 	};
 
 	/// ECDSA signing using the P-256 curves and SHA-256 hashing as per [RFC 5758](https://tools.ietf.org/html/rfc5758#section-3.2)
 	pub static PKCS_ECDSA_P256_SHA256: SignatureAlgorithm = SignatureAlgorithm {
+		name: "ECDSA_P256_SHA256",
+		display_name: "ECDSA P-256 with SHA-256",
+		digest: Some(DigestAlgorithm::Sha256),
 		oids_sign_alg: &[EC_PUBLIC_KEY, EC_SECP_256_R1],
 		#[cfg(feature = "crypto")]
 		sign_alg: SignAlgo::EcDsa(&signature::ECDSA_P256_SHA256_ASN1_SIGNING),
@@ -196,6 +604,9 @@ pub(crate) mod algo {
 
 	/// ECDSA signing using the P-384 curves and SHA-384 hashing as per [RFC 5758](https://tools.ietf.org/html/rfc5758#section-3.2)
 	pub static PKCS_ECDSA_P384_SHA384: SignatureAlgorithm = SignatureAlgorithm {
+		name: "ECDSA_P384_SHA384",
+		display_name: "ECDSA P-384 with SHA-384",
+		digest: Some(DigestAlgorithm::Sha384),
 		oids_sign_alg: &[EC_PUBLIC_KEY, EC_SECP_384_R1],
 		#[cfg(feature = "crypto")]
 		sign_alg: SignAlgo::EcDsa(&signature::ECDSA_P384_SHA384_ASN1_SIGNING),
@@ -204,16 +615,53 @@ pub(crate) mod algo {
 		params: SignatureAlgorithmParams::None,
 	};
 
+	/// ECDSA signing using the P-256 curves and SHA-384 hashing as per [RFC 5758](https://tools.ietf.org/html/rfc5758#section-3.2)
+	///
+	/// None of the crypto backends can sign with this combination, so it requires a custom
+	/// [`SigningKey`](crate::SigningKey) implementation.
+	pub static PKCS_ECDSA_P256_SHA384: SignatureAlgorithm = SignatureAlgorithm {
+		name: "ECDSA_P256_SHA384",
+		display_name: "ECDSA P-256 with SHA-384",
+		digest: Some(DigestAlgorithm::Sha384),
+		oids_sign_alg: &[EC_PUBLIC_KEY, EC_SECP_256_R1],
+		#[cfg(feature = "crypto")]
+		sign_alg: SignAlgo::Unavailable,
+		// ecdsa-with-SHA384 in RFC 5758
+		oid_components: &[1, 2, 840, 10045, 4, 3, 3],
+		params: SignatureAlgorithmParams::None,
+	};
+
+	/// ECDSA signing using the P-384 curves and SHA-256 hashing as per [RFC 5758](https://tools.ietf.org/html/rfc5758#section-3.2)
+	///
+	/// None of the crypto backends can sign with this combination, so it requires a custom
+	/// [`SigningKey`](crate::SigningKey) implementation.
+	pub static PKCS_ECDSA_P384_SHA256: SignatureAlgorithm = SignatureAlgorithm {
+		name: "ECDSA_P384_SHA256",
+		display_name: "ECDSA P-384 with SHA-256",
+		digest: Some(DigestAlgorithm::Sha256),
+		oids_sign_alg: &[EC_PUBLIC_KEY, EC_SECP_384_R1],
+		#[cfg(feature = "crypto")]
+		sign_alg: SignAlgo::Unavailable,
+		// ecdsa-with-SHA256 in RFC 5758
+		oid_components: &[1, 2, 840, 10045, 4, 3, 2],
+		params: SignatureAlgorithmParams::None,
+	};
+
 	/// ECDSA signing using the P-521 curves and SHA-256 hashing as per [RFC 5758](https://tools.ietf.org/html/rfc5758#section-3.2)
 	///
 	/// Note that this algorithm is not widely supported, and is not supported in TLS 1.3.
 	///
-	/// Only supported with the `aws_lc_rs` backend.
-	#[cfg(feature = "aws_lc_rs")]
+	/// Only supported with the `aws_lc_rs` backend. With other backends, generating or loading
+	/// keys for it returns an error, see [`SignatureAlgorithm::is_supported()`].
 	pub static PKCS_ECDSA_P521_SHA256: SignatureAlgorithm = SignatureAlgorithm {
+		name: "ECDSA_P521_SHA256",
+		display_name: "ECDSA P-521 with SHA-256",
+		digest: Some(DigestAlgorithm::Sha256),
 		oids_sign_alg: &[EC_PUBLIC_KEY, EC_SECP_521_R1],
-		#[cfg(feature = "crypto")]
+		#[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
 		sign_alg: SignAlgo::EcDsa(&signature::ECDSA_P521_SHA256_ASN1_SIGNING),
+		#[cfg(all(feature = "crypto", not(feature = "aws_lc_rs")))]
+		sign_alg: SignAlgo::Unavailable,
 		// ecdsa-with-SHA256 in RFC 5758
 		oid_components: &[1, 2, 840, 10045, 4, 3, 2],
 		params: SignatureAlgorithmParams::None,
@@ -223,12 +671,17 @@ pub(crate) mod algo {
 	///
 	/// Note that this algorithm is not widely supported, and is not supported in TLS 1.3.
 	///
-	/// Only supported with the `aws_lc_rs` backend.
-	#[cfg(feature = "aws_lc_rs")]
+	/// Only supported with the `aws_lc_rs` backend. With other backends, generating or loading
+	/// keys for it returns an error, see [`SignatureAlgorithm::is_supported()`].
 	pub static PKCS_ECDSA_P521_SHA384: SignatureAlgorithm = SignatureAlgorithm {
+		name: "ECDSA_P521_SHA384",
+		display_name: "ECDSA P-521 with SHA-384",
+		digest: Some(DigestAlgorithm::Sha384),
 		oids_sign_alg: &[EC_PUBLIC_KEY, EC_SECP_521_R1],
-		#[cfg(feature = "crypto")]
+		#[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
 		sign_alg: SignAlgo::EcDsa(&signature::ECDSA_P521_SHA384_ASN1_SIGNING),
+		#[cfg(all(feature = "crypto", not(feature = "aws_lc_rs")))]
+		sign_alg: SignAlgo::Unavailable,
 		// ecdsa-with-SHA384 in RFC 5758
 		oid_components: &[1, 2, 840, 10045, 4, 3, 3],
 		params: SignatureAlgorithmParams::None,
@@ -236,21 +689,49 @@ pub(crate) mod algo {
 
 	/// ECDSA signing using the P-521 curves and SHA-512 hashing as per [RFC 5758](https://tools.ietf.org/html/rfc5758#section-3.2)
 	///
-	/// Only supported with the `aws_lc_rs` backend.
-	#[cfg(feature = "aws_lc_rs")]
+	/// Only supported with the `aws_lc_rs` backend. With other backends, generating or loading
+	/// keys for it returns an error, see [`SignatureAlgorithm::is_supported()`].
 	pub static PKCS_ECDSA_P521_SHA512: SignatureAlgorithm = SignatureAlgorithm {
+		name: "ECDSA_P521_SHA512",
+		display_name: "ECDSA P-521 with SHA-512",
+		digest: Some(DigestAlgorithm::Sha512),
 		oids_sign_alg: &[EC_PUBLIC_KEY, EC_SECP_521_R1],
-		#[cfg(feature = "crypto")]
+		#[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
 		sign_alg: SignAlgo::EcDsa(&signature::ECDSA_P521_SHA512_ASN1_SIGNING),
+		#[cfg(all(feature = "crypto", not(feature = "aws_lc_rs")))]
+		sign_alg: SignAlgo::Unavailable,
 		// ecdsa-with-SHA512 in RFC 5758
 		oid_components: &[1, 2, 840, 10045, 4, 3, 4],
 		params: SignatureAlgorithmParams::None,
 	};
 
+	/// ECDSA signing using the secp256k1 curve and SHA-256 hashing as per [RFC 5758](https://tools.ietf.org/html/rfc5758#section-3.2)
+	///
+	/// This curve is used by Bitcoin and Ethereum. It's not supported in TLS 1.3, nor by the
+	/// Web PKI.
+	///
+	/// Only supported with the `aws_lc_rs` backend. With other backends, generating or loading
+	/// keys for it returns an error, see [`SignatureAlgorithm::is_supported()`].
+	pub static PKCS_ECDSA_K256_SHA256: SignatureAlgorithm = SignatureAlgorithm {
+		name: "ECDSA_K256_SHA256",
+		display_name: "ECDSA secp256k1 with SHA-256",
+		digest: Some(DigestAlgorithm::Sha256),
+		oids_sign_alg: &[EC_PUBLIC_KEY, EC_SECP_256_K1],
+		#[cfg(all(feature = "crypto", feature = "aws_lc_rs"))]
+		sign_alg: SignAlgo::EcDsa(&signature::ECDSA_P256K1_SHA256_ASN1_SIGNING),
+		#[cfg(all(feature = "crypto", not(feature = "aws_lc_rs")))]
+		sign_alg: SignAlgo::Unavailable,
+		// ecdsa-with-SHA256 in RFC 5758
+		oid_components: &[1, 2, 840, 10045, 4, 3, 2],
+		params: SignatureAlgorithmParams::None,
+	};
+
 	/// ED25519 curve signing as per [RFC 8410](https://tools.ietf.org/html/rfc8410)
 	pub static PKCS_ED25519: SignatureAlgorithm = SignatureAlgorithm {
-		// id-Ed25519 in RFC 8410
-		oids_sign_alg: &[&[1, 3, 101, 112]],
+		name: "ED25519",
+		display_name: "Ed25519",
+		digest: None,
+		oids_sign_alg: &[ED25519],
 		#[cfg(feature = "crypto")]
 		sign_alg: SignAlgo::EdDsa(&signature::ED25519),
 		// id-Ed25519 in RFC 8410
@@ -258,9 +739,31 @@ pub(crate) mod algo {
 		params: SignatureAlgorithmParams::None,
 	};
 
+	/// Ed448 signing as per [RFC 8410](https://tools.ietf.org/html/rfc8410)
+	///
+	/// Neither `ring` nor `aws-lc-rs` implement Ed448, so it's only supported with the `ed448`
+	/// feature, which uses OpenSSL. Without it, generating or loading keys for it returns an
+	/// error, see [`SignatureAlgorithm::is_supported()`].
+	pub static PKCS_ED448: SignatureAlgorithm = SignatureAlgorithm {
+		name: "ED448",
+		display_name: "Ed448",
+		digest: None,
+		oids_sign_alg: &[ED448],
+		#[cfg(all(feature = "crypto", feature = "ed448"))]
+		sign_alg: SignAlgo::Ed448,
+		#[cfg(all(feature = "crypto", not(feature = "ed448")))]
+		sign_alg: SignAlgo::Unavailable,
+		// id-Ed448 in RFC 8410
+		oid_components: ED448,
+		params: SignatureAlgorithmParams::None,
+	};
+
 	/// ML-DSA-44 signing as per <https://www.ietf.org/archive/id/draft-ietf-lamps-dilithium-certificates-12.html#name-identifiers>.
 	#[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
 	pub static PKCS_ML_DSA_44: SignatureAlgorithm = SignatureAlgorithm {
+		name: "ML_DSA_44",
+		display_name: "ML-DSA-44",
+		digest: None,
 		oids_sign_alg: &[ML_DSA_44],
 		#[cfg(all(feature = "crypto", feature = "aws_lc_rs_unstable"))]
 		sign_alg: SignAlgo::PqDsa(&ML_DSA_44_SIGNING),
@@ -271,6 +774,9 @@ pub(crate) mod algo {
 	/// ML-DSA-65 signing as per <https://www.ietf.org/archive/id/draft-ietf-lamps-dilithium-certificates-12.html#name-identifiers>.
 	#[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
 	pub static PKCS_ML_DSA_65: SignatureAlgorithm = SignatureAlgorithm {
+		name: "ML_DSA_65",
+		display_name: "ML-DSA-65",
+		digest: None,
 		oids_sign_alg: &[ML_DSA_65],
 		#[cfg(all(feature = "crypto", feature = "aws_lc_rs_unstable"))]
 		sign_alg: SignAlgo::PqDsa(&ML_DSA_65_SIGNING),
@@ -281,12 +787,63 @@ pub(crate) mod algo {
 	/// ML-DSA-87 signing as per <https://www.ietf.org/archive/id/draft-ietf-lamps-dilithium-certificates-12.html#name-identifiers>.
 	#[cfg(all(feature = "aws_lc_rs_unstable", not(feature = "fips")))]
 	pub static PKCS_ML_DSA_87: SignatureAlgorithm = SignatureAlgorithm {
+		name: "ML_DSA_87",
+		display_name: "ML-DSA-87",
+		digest: None,
 		oids_sign_alg: &[ML_DSA_87],
 		#[cfg(all(feature = "crypto", feature = "aws_lc_rs_unstable"))]
 		sign_alg: SignAlgo::PqDsa(&ML_DSA_87_SIGNING),
 		oid_components: ML_DSA_87,
 		params: SignatureAlgorithmParams::None,
 	};
+
+	/// Composite ML-DSA-44 and Ed25519 signing as per [draft-ietf-lamps-pq-composite-sigs-02](https://www.ietf.org/archive/id/draft-ietf-lamps-pq-composite-sigs-02.html)
+	///
+	/// Experimental and subject to change with the draft. Signing requires a
+	/// [`CompositeKeyPair`](crate::CompositeKeyPair).
+	#[cfg(all(feature = "composite_signatures_unstable", not(feature = "fips")))]
+	pub static PKCS_MLDSA44_ED25519: SignatureAlgorithm = SignatureAlgorithm {
+		name: "MLDSA44_ED25519",
+		display_name: "Composite ML-DSA-44 and Ed25519",
+		digest: None,
+		oids_sign_alg: &[MLDSA44_ED25519_SHA512],
+		#[cfg(feature = "crypto")]
+		sign_alg: SignAlgo::Unavailable,
+		oid_components: MLDSA44_ED25519_SHA512,
+		params: SignatureAlgorithmParams::None,
+	};
+
+	/// Composite ML-DSA-44 and ECDSA P-256 with SHA-256 signing as per [draft-ietf-lamps-pq-composite-sigs-02](https://www.ietf.org/archive/id/draft-ietf-lamps-pq-composite-sigs-02.html)
+	///
+	/// Experimental and subject to change with the draft. Signing requires a
+	/// [`CompositeKeyPair`](crate::CompositeKeyPair).
+	#[cfg(all(feature = "composite_signatures_unstable", not(feature = "fips")))]
+	pub static PKCS_MLDSA44_ECDSA_P256_SHA256: SignatureAlgorithm = SignatureAlgorithm {
+		name: "MLDSA44_ECDSA_P256_SHA256",
+		display_name: "Composite ML-DSA-44 and ECDSA P-256 with SHA-256",
+		digest: None,
+		oids_sign_alg: &[MLDSA44_ECDSA_P256_SHA256],
+		#[cfg(feature = "crypto")]
+		sign_alg: SignAlgo::Unavailable,
+		oid_components: MLDSA44_ECDSA_P256_SHA256,
+		params: SignatureAlgorithmParams::None,
+	};
+
+	/// Composite ML-DSA-65 and Ed25519 signing as per [draft-ietf-lamps-pq-composite-sigs-02](https://www.ietf.org/archive/id/draft-ietf-lamps-pq-composite-sigs-02.html)
+	///
+	/// Experimental and subject to change with the draft. Signing requires a
+	/// [`CompositeKeyPair`](crate::CompositeKeyPair).
+	#[cfg(all(feature = "composite_signatures_unstable", not(feature = "fips")))]
+	pub static PKCS_MLDSA65_ED25519: SignatureAlgorithm = SignatureAlgorithm {
+		name: "MLDSA65_ED25519",
+		display_name: "Composite ML-DSA-65 and Ed25519",
+		digest: None,
+		oids_sign_alg: &[MLDSA65_ED25519_SHA512],
+		#[cfg(feature = "crypto")]
+		sign_alg: SignAlgo::Unavailable,
+		oid_components: MLDSA65_ED25519_SHA512,
+		params: SignatureAlgorithmParams::None,
+	};
 }
 // Signature algorithm IDs as per https://tools.ietf.org/html/rfc4055
 impl SignatureAlgorithm {
@@ -311,14 +868,13 @@ impl SignatureAlgorithm {
 					writer.next().write_tagged(Tag::context(0), |writer| {
 						writer.write_sequence(|writer| {
 							writer.next().write_oid(&oid);
+							writer.next().write_null();
 						});
 					});
 					// maskGenAlgorithm
 					writer.next().write_tagged(Tag::context(1), |writer| {
 						writer.write_sequence(|writer| {
-							// id-mgf1 in RFC 4055
-							const ID_MGF1: &[u64] = &[1, 2, 840, 113549, 1, 1, 8];
-							let oid = ObjectIdentifier::from_slice(ID_MGF1);
+							let oid = ObjectIdentifier::from_slice(MGF1);
 							writer.next().write_oid(&oid);
 							writer.next().write_sequence(|writer| {
 								let oid = ObjectIdentifier::from_slice(hash_algorithm);
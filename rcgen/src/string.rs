@@ -258,6 +258,131 @@ impl PartialEq<&String> for Ia5String {
 	}
 }
 
+/// A DNS name for subject alternative names, a subset of [`Ia5String`]
+///
+/// Converting a string with [`DnsName::try_from`] normalizes it to the form that certificate
+/// verifiers like webpki match against: a single trailing dot, as in names copied from DNS
+/// zone files, is stripped, and the name is lowercased. Names with empty labels, like
+/// `.example.com` or `example..com`, are rejected.
+///
+/// With the `idna` feature, internationalized names are converted to their ASCII form with
+/// the [UTS #46](https://www.unicode.org/reports/tr46/) processing that browsers use, so
+/// `bücher.example` becomes `xn--bcher-kva.example`. Names containing code points it
+/// disallows are rejected, as are names that aren't valid DNS names after the conversion.
+/// Without the feature, names with characters outside of ASCII are rejected.
+///
+/// # Examples
+///
+/// ```
+/// use rcgen::string::DnsName;
+/// let name = DnsName::try_from("Example.COM.").unwrap();
+/// assert_eq!(name, "example.com");
+/// assert!(DnsName::try_from("example..com").is_err());
+/// ```
+///
+/// Use [`DnsName::raw()`] to keep a name exactly as given.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct DnsName(Ia5String);
+
+impl DnsName {
+	/// Uses `name` as it is, without normalizing or validating it
+	///
+	/// This is how names are kept when parsing certificates and CSRs, and the way to pass
+	/// names that are already in their ASCII form, e.g. with A-labels like `xn--bcher-kva`.
+	pub fn raw(name: Ia5String) -> Self {
+		Self(name)
+	}
+
+	/// Extracts a string slice containing the entire `DnsName`.
+	pub fn as_str(&self) -> &str {
+		self.0.as_str()
+	}
+}
+
+impl TryFrom<&str> for DnsName {
+	type Error = Error;
+
+	/// Converts a `&str` to a normalized [`DnsName`]
+	///
+	/// See [`DnsName`] for the normalization and the names that are rejected.
+	fn try_from(input: &str) -> Result<Self, Error> {
+		let name = input.strip_suffix('.').unwrap_or(input);
+		if name.is_empty() || name.split('.').any(str::is_empty) {
+			return Err(Error::InvalidAsn1String(InvalidAsn1String::DnsName(
+				input.to_owned(),
+			)));
+		}
+		#[cfg(feature = "idna")]
+		if !name.is_ascii() {
+			// The wildcard label isn't a valid label for IDNA
+			let (wildcard, domain) = match name.strip_prefix("*.") {
+				Some(domain) => ("*.", domain),
+				None => ("", name),
+			};
+			let ascii = idna::domain_to_ascii_strict(domain).map_err(|_| {
+				Error::InvalidAsn1String(InvalidAsn1String::Ia5String(input.to_owned()))
+			})?;
+			return Ok(Self(format!("{wildcard}{ascii}").try_into()?));
+		}
+		Ok(Self(name.to_ascii_lowercase().try_into()?))
+	}
+}
+
+impl TryFrom<String> for DnsName {
+	type Error = Error;
+
+	/// Converts a [`String`][`std::string::String`] into a normalized [`DnsName`]
+	///
+	/// See [`DnsName`] for the normalization and the names that are rejected.
+	fn try_from(input: String) -> Result<Self, Error> {
+		input.as_str().try_into()
+	}
+}
+
+impl FromStr for DnsName {
+	type Err = Error;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		s.try_into()
+	}
+}
+
+impl AsRef<str> for DnsName {
+	fn as_ref(&self) -> &str {
+		self.as_str()
+	}
+}
+
+impl fmt::Display for DnsName {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		fmt::Display::fmt(self.as_str(), f)
+	}
+}
+
+impl PartialEq<str> for DnsName {
+	fn eq(&self, other: &str) -> bool {
+		self.as_str() == other
+	}
+}
+
+impl PartialEq<String> for DnsName {
+	fn eq(&self, other: &String) -> bool {
+		self.as_str() == other.as_str()
+	}
+}
+
+impl PartialEq<&str> for DnsName {
+	fn eq(&self, other: &&str) -> bool {
+		self.as_str() == *other
+	}
+}
+
+impl PartialEq<&String> for DnsName {
+	fn eq(&self, other: &&String) -> bool {
+		self.as_str() == other.as_str()
+	}
+}
+
 /// ASN.1 `TeletexString` type.
 ///
 /// # Examples
@@ -606,6 +731,7 @@ impl TryFrom<String> for UniversalString {
 #[allow(clippy::unwrap_used)]
 mod tests {
 
+	use crate::string::DnsName;
 	use crate::{BmpString, Ia5String, PrintableString, TeletexString, UniversalString};
 
 	#[test]
@@ -626,6 +752,72 @@ mod tests {
 		assert!(Ia5String::try_from(String::from('\u{8F}')).is_err());
 	}
 
+	#[test]
+	fn dns_name() {
+		let name = DnsName::try_from("Crabs.Example.").unwrap();
+		assert_eq!(name, "crabs.example");
+		assert_eq!(
+			DnsName::try_from("*.crabs.example").unwrap(),
+			"*.crabs.example"
+		);
+		for invalid in [
+			"",
+			".",
+			"..",
+			".crabs.example",
+			"crabs..example",
+			"crabs.example..",
+		] {
+			assert!(DnsName::try_from(invalid).is_err(), "{invalid:?}");
+		}
+		#[cfg(not(feature = "idna"))]
+		assert!(DnsName::try_from("crabs.exämple").is_err());
+
+		let raw = DnsName::raw(Ia5String::try_from("Crabs.Example.").unwrap());
+		assert_eq!(raw, "Crabs.Example.");
+	}
+
+	#[cfg(feature = "idna")]
+	#[test]
+	fn dns_name_idna() {
+		use crate::error::InvalidAsn1String;
+		use crate::Error;
+
+		let name = DnsName::try_from("www.Bücher.example.").unwrap();
+		assert_eq!(name, "www.xn--bcher-kva.example");
+		assert_eq!(
+			DnsName::try_from("*.münchen.bücher.example").unwrap(),
+			"*.xn--mnchen-3ya.xn--bcher-kva.example"
+		);
+		assert_eq!(
+			DnsName::try_from("bü cher.example"),
+			Err(Error::InvalidAsn1String(InvalidAsn1String::Ia5String(
+				"bü cher.example".to_owned()
+			)))
+		);
+		assert!(DnsName::try_from("bücher..example").is_err());
+		// Mapped like browsers do, to the same A-labels
+		assert_eq!(
+			DnsName::try_from("ｂüｃｈｅｒ.example").unwrap(),
+			"xn--bcher-kva.example"
+		);
+		assert_eq!(
+			DnsName::try_from("bu\u{308}cher.example").unwrap(),
+			"xn--bcher-kva.example"
+		);
+		assert_eq!(
+			DnsName::try_from("ⅻ.bücher.example").unwrap(),
+			"xii.xn--bcher-kva.example"
+		);
+		for disallowed in [
+			"⒈.bücher.example",
+			"bü\u{e000}cher.example",
+			"-bücher.example",
+		] {
+			assert!(DnsName::try_from(disallowed).is_err(), "{disallowed:?}");
+		}
+	}
+
 	#[test]
 	fn teletext_string() {
 		const EXAMPLE_UTF8: &str = "CertificateTemplate";
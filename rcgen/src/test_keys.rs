@@ -0,0 +1,71 @@
+//! Pre-generated key pairs for tests
+//!
+//! Generating keys, RSA keys in particular, is slow enough to dominate the runtime of test
+//! suites that create many certificates. The functions of this module instead load keys that
+//! are embedded into the binary in PKCS#8 format, which is a lot faster. Each call returns
+//! the same key.
+//!
+//! **The private keys are publicly known.** Anyone can sign with them, so they must never be
+//! used outside of tests. This module requires the `test_keys` feature, which should only
+//! ever be enabled for dev-dependencies.
+
+use pki_types::PrivatePkcs8KeyDer;
+
+use crate::{
+	KeyPair, SignatureAlgorithm, PKCS_ECDSA_P256_SHA256, PKCS_ECDSA_P384_SHA384, PKCS_ED25519,
+	PKCS_RSA_SHA256,
+};
+
+/// An ECDSA key pair on the P-256 curve, for use with [`PKCS_ECDSA_P256_SHA256`]
+pub fn ecdsa_p256() -> KeyPair {
+	load(
+		include_bytes!("test_keys/ecdsa_p256.der"),
+		&PKCS_ECDSA_P256_SHA256,
+	)
+}
+
+/// An ECDSA key pair on the P-384 curve, for use with [`PKCS_ECDSA_P384_SHA384`]
+pub fn ecdsa_p384() -> KeyPair {
+	load(
+		include_bytes!("test_keys/ecdsa_p384.der"),
+		&PKCS_ECDSA_P384_SHA384,
+	)
+}
+
+/// An Ed25519 key pair
+pub fn ed25519() -> KeyPair {
+	load(include_bytes!("test_keys/ed25519.der"), &PKCS_ED25519)
+}
+
+/// A 2048 bit RSA key pair, for use with [`PKCS_RSA_SHA256`]
+///
+/// Unlike [`KeyPair::generate_for()`], this also works with the `ring` backend.
+pub fn rsa_2048() -> KeyPair {
+	load(include_bytes!("test_keys/rsa_2048.der"), &PKCS_RSA_SHA256)
+}
+
+#[allow(clippy::expect_used)]
+fn load(pkcs8: &'static [u8], alg: &'static SignatureAlgorithm) -> KeyPair {
+	KeyPair::from_pkcs8_der_and_sign_algo(&PrivatePkcs8KeyDer::from(pkcs8), alg)
+		.expect("embedded test key is valid")
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{CertificateParams, PublicKeyData};
+
+	#[test]
+	fn keys_are_usable() {
+		for (key, alg) in [
+			(ecdsa_p256(), &PKCS_ECDSA_P256_SHA256),
+			(ecdsa_p384(), &PKCS_ECDSA_P384_SHA384),
+			(ed25519(), &PKCS_ED25519),
+			(rsa_2048(), &PKCS_RSA_SHA256),
+		] {
+			assert_eq!(key.algorithm(), alg);
+			CertificateParams::default().self_signed(&key).unwrap();
+		}
+		assert_eq!(ed25519().der_bytes(), ed25519().der_bytes());
+	}
+}
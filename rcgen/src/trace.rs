@@ -0,0 +1,266 @@
+//! Spans and events emitted with the `tracing` feature
+//!
+//! Every issuance operation runs in an `INFO` span named after it, carrying what is being
+//! signed, and ends with an event carrying the size of the signed DER and how long the
+//! operation took, or the error. Nothing derived from private keys is ever recorded.
+
+use std::fmt::Write;
+use std::time::Instant;
+
+use tracing::Span;
+
+#[cfg(feature = "crypto")]
+use crate::certificate::derive_serial_number;
+use crate::{
+	CertificateParams, CertificateRevocationListParams, DistinguishedName, DnType, DnValue, Error,
+	Issuer, PublicKeyData, SerialNumber, SigningKey,
+};
+
+/// Runs `sign`, which signs a certificate for `public_key` with `params`
+pub(crate) fn certificate(
+	params: &CertificateParams,
+	public_key: &(impl PublicKeyData + ?Sized),
+	issuer: &Issuer<'_, impl SigningKey>,
+	sign: impl FnOnce() -> Result<Vec<u8>, Error>,
+) -> Result<Vec<u8>, Error> {
+	let span = tracing::info_span!(
+		"rcgen::certificate",
+		subject = %dn_string(&params.distinguished_name),
+		san_count = params.subject_alt_names.len(),
+		serial = %serial(params, public_key),
+		issuer = %dn_string(&issuer.distinguished_name),
+		issuer_key_id = %hex(&issuer.authority_key_identifier(&issuer.key_identifier_method)),
+		algorithm = %issuer.signing_key.algorithm(),
+	);
+	record(span, sign)
+}
+
+/// Runs `sign`, which signs a certificate signing request for `params` with `subject_key`
+pub(crate) fn certificate_request(
+	params: &CertificateParams,
+	subject_key: &(impl SigningKey + ?Sized),
+	sign: impl FnOnce() -> Result<Vec<u8>, Error>,
+) -> Result<Vec<u8>, Error> {
+	let span = tracing::info_span!(
+		"rcgen::certificate_request",
+		subject = %dn_string(&params.distinguished_name),
+		san_count = params.subject_alt_names.len(),
+		algorithm = %subject_key.algorithm(),
+	);
+	record(span, sign)
+}
+
+/// Runs `sign`, which signs a certificate revocation list with `params`
+pub(crate) fn crl(
+	params: &CertificateRevocationListParams,
+	issuer: &Issuer<'_, impl SigningKey>,
+	sign: impl FnOnce() -> Result<Vec<u8>, Error>,
+) -> Result<Vec<u8>, Error> {
+	let span = tracing::info_span!(
+		"rcgen::crl",
+		crl_number = %params.crl_number,
+		revoked_count = params.revoked_certs.len(),
+		issuer = %dn_string(&issuer.distinguished_name),
+		issuer_key_id = %hex(&issuer.authority_key_identifier(&params.key_identifier_method)),
+		algorithm = %issuer.signing_key.algorithm(),
+	);
+	record(span, sign)
+}
+
+/// The serial number of the certificate, which is derived from the public key if unspecified
+#[cfg_attr(not(feature = "crypto"), allow(unused_variables))]
+fn serial(params: &CertificateParams, public_key: &(impl PublicKeyData + ?Sized)) -> String {
+	match &params.serial_number {
+		Some(serial) => serial.to_string(),
+		#[cfg(feature = "crypto")]
+		None => SerialNumber::from(derive_serial_number(public_key)).to_string(),
+		#[cfg(not(feature = "crypto"))]
+		None => String::new(),
+	}
+}
+
+fn record(span: Span, sign: impl FnOnce() -> Result<Vec<u8>, Error>) -> Result<Vec<u8>, Error> {
+	let _entered = span.enter();
+	let start = Instant::now();
+	let result = sign();
+	let elapsed_us = start.elapsed().as_micros() as u64;
+	match &result {
+		Ok(der) => tracing::info!(size = der.len(), elapsed_us, "signed"),
+		Err(e) => tracing::warn!(error = %e, elapsed_us, "signing failed"),
+	}
+	result
+}
+
+/// Formats a distinguished name in the style of RFC 4514, in the order of insertion
+fn dn_string(dn: &DistinguishedName) -> String {
+	let mut out = String::new();
+	for (i, (ty, value)) in dn.iter().enumerate() {
+		if i > 0 {
+			out.push_str(", ");
+		}
+		let _ = match ty {
+			DnType::CountryName => write!(out, "C="),
+			DnType::LocalityName => write!(out, "L="),
+			DnType::StateOrProvinceName => write!(out, "ST="),
+			DnType::OrganizationName => write!(out, "O="),
+			DnType::OrganizationalUnitName => write!(out, "OU="),
+			DnType::CommonName => write!(out, "CN="),
+			DnType::BusinessCategory => write!(out, "businessCategory="),
+			DnType::OrganizationIdentifier => write!(out, "organizationIdentifier="),
+			DnType::JurisdictionLocalityName => write!(out, "jurisdictionL="),
+			DnType::JurisdictionStateOrProvinceName => write!(out, "jurisdictionST="),
+			DnType::JurisdictionCountryName => write!(out, "jurisdictionC="),
+			DnType::CustomDnType(oid) => {
+				let oid = oid.iter().map(u64::to_string).collect::<Vec<_>>();
+				write!(out, "{}=", oid.join("."))
+			},
+		};
+		match value {
+			DnValue::BmpString(s) => out.extend(
+				char::decode_utf16(
+					s.as_bytes()
+						.chunks_exact(2)
+						.map(|c| u16::from_be_bytes([c[0], c[1]])),
+				)
+				.map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER)),
+			),
+			DnValue::UniversalString(s) => out.extend(s.as_bytes().chunks_exact(4).map(|c| {
+				char::from_u32(u32::from_be_bytes([c[0], c[1], c[2], c[3]]))
+					.unwrap_or(char::REPLACEMENT_CHARACTER)
+			})),
+			DnValue::Ia5String(s) => out.push_str(s.as_str()),
+			DnValue::PrintableString(s) => out.push_str(s.as_str()),
+			DnValue::TeletexString(s) => out.push_str(s.as_str()),
+			DnValue::Utf8String(s) => out.push_str(s),
+		}
+	}
+	out
+}
+
+fn hex(bytes: &[u8]) -> String {
+	SerialNumber::from_slice(bytes).to_string()
+}
+
+#[cfg(all(test, feature = "crypto"))]
+mod tests {
+	use std::collections::HashMap;
+	use std::sync::{Arc, Mutex};
+
+	use time::OffsetDateTime;
+	use tracing::field::{Field, Visit};
+	use tracing::span::{Attributes, Id, Record};
+	use tracing::{Event, Metadata, Subscriber};
+
+	use super::*;
+	use crate::{BasicConstraints, IsCa, KeyIdMethod, KeyPair, KeyUsagePurpose, RevokedCertParams};
+
+	/// The fields recorded in spans and events, keyed by the span name or event message
+	#[derive(Clone, Default)]
+	struct Recorder(Arc<Mutex<HashMap<String, HashMap<String, String>>>>);
+
+	struct Fields(HashMap<String, String>);
+
+	impl Visit for Fields {
+		fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+			self.0.insert(field.name().to_owned(), format!("{value:?}"));
+		}
+
+		fn record_str(&mut self, field: &Field, value: &str) {
+			self.0.insert(field.name().to_owned(), value.to_owned());
+		}
+	}
+
+	impl Subscriber for Recorder {
+		fn enabled(&self, _: &Metadata<'_>) -> bool {
+			true
+		}
+
+		fn new_span(&self, span: &Attributes<'_>) -> Id {
+			let mut fields = Fields(HashMap::new());
+			span.record(&mut fields);
+			let mut spans = self.0.lock().unwrap();
+			spans.insert(span.metadata().name().to_owned(), fields.0);
+			Id::from_u64(spans.len() as u64)
+		}
+
+		fn record(&self, _: &Id, _: &Record<'_>) {}
+
+		fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+		fn event(&self, event: &Event<'_>) {
+			let mut fields = Fields(HashMap::new());
+			event.record(&mut fields);
+			let message = fields.0["message"].clone();
+			self.0.lock().unwrap().insert(message, fields.0);
+		}
+
+		fn enter(&self, _: &Id) {}
+
+		fn exit(&self, _: &Id) {}
+	}
+
+	#[test]
+	fn issuance_fields() {
+		let recorder = Recorder::default();
+		let _guard = tracing::subscriber::set_default(recorder.clone());
+
+		let mut ca_params = CertificateParams::new(Vec::new()).unwrap();
+		ca_params
+			.distinguished_name
+			.push(DnType::OrganizationName, "Crab widgits SE");
+		ca_params
+			.distinguished_name
+			.push(DnType::CommonName, "Master CA");
+		ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+		ca_params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+		let ca_key = KeyPair::generate().unwrap();
+		let issuer = Issuer::new(ca_params, &ca_key);
+		let issuer_key_id = hex(&KeyIdMethod::Sha256.derive(&ca_key));
+
+		let mut params = CertificateParams::new(vec!["crabs.crabs".to_owned()]).unwrap();
+		params.serial_number = Some(SerialNumber::from(vec![0x12, 0x34]));
+		let key = KeyPair::generate().unwrap();
+		let cert = params.signed_by(&key, &issuer).unwrap();
+
+		let spans = recorder.0.lock().unwrap().clone();
+		let span = &spans["rcgen::certificate"];
+		assert_eq!(span["subject"], "CN=rcgen self signed cert");
+		assert_eq!(span["san_count"], "1");
+		assert_eq!(span["serial"], "12:34");
+		assert_eq!(span["issuer"], "CN=Master CA, O=Crab widgits SE");
+		assert_eq!(span["issuer_key_id"], issuer_key_id);
+		assert_eq!(span["algorithm"], "ECDSA P-256 with SHA-256");
+		assert_eq!(spans["signed"]["size"], cert.der().len().to_string());
+		assert!(spans["signed"].contains_key("elapsed_us"));
+
+		params.serial_number = None;
+		let csr = params.serialize_request(&key).unwrap();
+		let spans = recorder.0.lock().unwrap().clone();
+		let span = &spans["rcgen::certificate_request"];
+		assert_eq!(span["subject"], "CN=rcgen self signed cert");
+		assert_eq!(span["san_count"], "1");
+		assert_eq!(spans["signed"]["size"], csr.der().len().to_string());
+
+		let now = OffsetDateTime::now_utc();
+		let crl_params = CertificateRevocationListParams {
+			this_update: now,
+			next_update: now + time::Duration::weeks(1),
+			crl_number: SerialNumber::from(7),
+			issuing_distribution_point: None,
+			revoked_certs: vec![RevokedCertParams {
+				serial_number: SerialNumber::from(vec![0x12, 0x34]),
+				revocation_time: now,
+				reason_code: None,
+				invalidity_date: None,
+			}],
+			key_identifier_method: KeyIdMethod::Sha256,
+		};
+		let crl = crl_params.signed_by(&issuer).unwrap();
+		let spans = recorder.0.lock().unwrap().clone();
+		let span = &spans["rcgen::crl"];
+		assert_eq!(span["crl_number"], "00:00:00:00:00:00:00:07");
+		assert_eq!(span["revoked_count"], "1");
+		assert_eq!(span["issuer_key_id"], issuer_key_id);
+		assert_eq!(spans["signed"]["size"], crl.der().len().to_string());
+	}
+}
@@ -6,14 +6,15 @@ use std::rc::Rc;
 
 use openssl::asn1::{Asn1Integer, Asn1Time};
 use openssl::bn::BigNum;
+use openssl::ec::EcKey;
 use openssl::pkey::PKey;
 use openssl::ssl::{HandshakeError, SslAcceptor, SslConnector, SslMethod};
 use openssl::stack::Stack;
 use openssl::x509::store::{X509Store, X509StoreBuilder};
 use openssl::x509::{CrlStatus, X509Crl, X509Req, X509StoreContext, X509};
 use rcgen::{
-	BasicConstraints, Certificate, CertificateParams, DistinguishedName, DnType, DnValue,
-	GeneralSubtree, IsCa, Issuer, KeyPair, NameConstraints,
+	BasicConstraints, Certificate, CertificateParams, CertificateVersion, DistinguishedName,
+	DnType, DnValue, GeneralSubtree, IsCa, Issuer, KeyPair, NameConstraints,
 };
 use verify_tests as util;
 
@@ -187,6 +188,20 @@ fn test_request() {
 	verify_csr(&params, &key_pair);
 }
 
+#[test]
+fn test_openssl_v1() {
+	let (mut params, key_pair) = util::default_params();
+	params.subject_alt_names.clear();
+	params.version = CertificateVersion::V1;
+	let cert = params.self_signed(&key_pair).unwrap();
+
+	// openssl reports the zero-based version number
+	let x509 = X509::from_der(cert.der()).unwrap();
+	assert_eq!(x509.version(), 0);
+	// Without subject alternative names, the TLS handshake of `verify_cert` would fail
+	verify_cert_basic(&cert);
+}
+
 #[test]
 fn test_openssl_256() {
 	let (params, _) = util::default_params();
@@ -221,6 +236,39 @@ fn test_openssl_521() {
 	verify_csr(&params, &key_pair);
 }
 
+#[test]
+#[cfg(feature = "aws_lc_rs")]
+fn test_openssl_k256() {
+	use openssl::ec::EcGroup;
+	use openssl::nid::Nid;
+	use pki_types::PrivateKeyDer;
+
+	let (params, _) = util::default_params();
+	let key_pair = KeyPair::generate_for(&rcgen::PKCS_ECDSA_K256_SHA256).unwrap();
+	let cert = params.self_signed(&key_pair).unwrap();
+
+	// TLS doesn't support secp256k1, so only the certificate itself is verified
+	verify_cert_basic(&cert);
+	let x509 = X509::from_der(cert.der()).unwrap();
+	let public_key = x509.public_key().unwrap();
+	assert_eq!(
+		public_key.ec_key().unwrap().group().curve_name(),
+		Some(Nid::SECP256K1)
+	);
+	verify_csr(&params, &key_pair);
+
+	// Keys generated by openssl are detected as secp256k1 keys
+	let group = EcGroup::from_curve_name(Nid::SECP256K1).unwrap();
+	let ec_key = PKey::from_ec_key(EcKey::generate(&group).unwrap()).unwrap();
+	let der = PrivateKeyDer::try_from(ec_key.private_key_to_pkcs8().unwrap()).unwrap();
+	let key_pair = KeyPair::try_from(&der).unwrap();
+	assert_eq!(key_pair.algorithm(), &rcgen::PKCS_ECDSA_K256_SHA256);
+
+	let csr = params.serialize_request(&key_pair).unwrap();
+	let csr = rcgen::CertificateSigningRequestParams::from_der(csr.der()).unwrap();
+	assert_eq!(csr.public_key.algorithm(), &rcgen::PKCS_ECDSA_K256_SHA256);
+}
+
 #[test]
 fn test_openssl_25519() {
 	let (params, _) = util::default_params();
@@ -267,6 +315,17 @@ fn test_openssl_25519_v2_given() {
 	//verify_csr(&cert);
 }
 
+#[test]
+#[cfg(feature = "ed448")]
+fn test_openssl_ed448() {
+	let (params, _) = util::default_params();
+	let key_pair = KeyPair::generate_for(&rcgen::PKCS_ED448).unwrap();
+	let cert = params.self_signed(&key_pair).unwrap();
+
+	verify_cert(&cert, &key_pair);
+	verify_csr(&params, &key_pair);
+}
+
 #[test]
 fn test_openssl_rsa_given() {
 	let (params, _) = util::default_params();
@@ -284,26 +343,46 @@ fn test_openssl_rsa_combinations_given() {
 		&rcgen::PKCS_RSA_SHA256,
 		&rcgen::PKCS_RSA_SHA384,
 		&rcgen::PKCS_RSA_SHA512,
-		//&rcgen::PKCS_RSA_PSS_SHA256,
+		&rcgen::PKCS_RSA_PSS_SHA256,
+		&rcgen::PKCS_RSA_PSS_SHA384,
+		&rcgen::PKCS_RSA_PSS_SHA512,
 	];
-	for (i, alg) in alg_list.iter().enumerate() {
+	for alg in alg_list {
 		let (params, _) = util::default_params();
 		let key_pair =
 			KeyPair::from_pkcs8_pem_and_sign_algo(util::RSA_TEST_KEY_PAIR_PEM, alg).unwrap();
 		let cert = params.self_signed(&key_pair).unwrap();
 
-		// Now verify the certificate.
-		if i >= 4 {
-			verify_cert(&cert, &key_pair);
-			verify_csr(&params, &key_pair);
-		} else {
-			// The PSS key types are not fully supported.
-			// An attempt to use them gives a handshake error.
+		// Now verify the certificate. The key of RSASSA-PSS certificates is restricted to
+		// RSASSA-PSS, for which the TLS handshake of `verify_cert` finds no cipher suite.
+		if alg.key_oid_string() == "1.2.840.113549.1.1.10" {
 			verify_cert_basic(&cert);
+		} else {
+			verify_cert(&cert, &key_pair);
 		}
+		verify_csr(&params, &key_pair);
 	}
 }
 
+#[test]
+fn test_openssl_rsa_rebind() {
+	use openssl::nid::Nid;
+
+	let (params, _) = util::default_params();
+	let mut key_pair = KeyPair::from_pem(util::RSA_TEST_KEY_PAIR_PEM).unwrap();
+	assert_eq!(key_pair.algorithm(), &rcgen::PKCS_RSA_SHA256);
+	key_pair.rebind(&rcgen::PKCS_RSA_SHA512).unwrap();
+	let cert = params.self_signed(&key_pair).unwrap();
+
+	let x509 = X509::from_der(cert.der()).unwrap();
+	assert_eq!(
+		x509.signature_algorithm().object().nid(),
+		Nid::SHA512WITHRSAENCRYPTION
+	);
+	verify_cert(&cert, &key_pair);
+	verify_csr(&params, &key_pair);
+}
+
 #[test]
 fn test_openssl_separate_ca() {
 	let (mut ca_params, ca_key) = util::default_params();
@@ -437,6 +516,299 @@ fn test_openssl_separate_ca_name_constraints_directory_name() {
 	verify_cert_ca(&cert.pem(), &key, &ca_cert.pem());
 }
 
+#[test]
+fn test_openssl_separate_ca_name_constraints_ip_and_email() {
+	use openssl::x509::X509VerifyResult;
+	use rcgen::SanType;
+
+	let (mut ca_params, ca_key) = util::default_params();
+	ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+	ca_params.name_constraints = Some(NameConstraints {
+		permitted_subtrees: vec![
+			GeneralSubtree::IpAddress("10.0.0.0/8".parse().unwrap()),
+			GeneralSubtree::DnsName("corp.example".to_string()),
+			GeneralSubtree::Rfc822Name("corp.example".to_string()),
+		],
+		excluded_subtrees: vec![GeneralSubtree::Rfc822Name("ceo@corp.example".to_string())],
+	});
+	let ca_cert = ca_params.self_signed(&ca_key).unwrap();
+	let ca_x509 = X509::from_der(ca_cert.der()).unwrap();
+	let ca = Issuer::new(ca_params, ca_key);
+
+	let verify = |san: SanType| {
+		let mut params = CertificateParams::new(vec!["host.corp.example".to_string()]).unwrap();
+		params.subject_alt_names.push(san);
+		let cert_key = KeyPair::generate().unwrap();
+		let cert = params.signed_by(&cert_key, &ca).unwrap();
+		let x509 = X509::from_der(cert.der()).unwrap();
+
+		let mut builder = X509StoreBuilder::new().unwrap();
+		builder.add_cert(ca_x509.clone()).unwrap();
+		let store = builder.build();
+		let mut ctx = X509StoreContext::new().unwrap();
+		let chain = Stack::new().unwrap();
+		ctx.init(&store, &x509, &chain, |ctx| {
+			ctx.verify_cert()?;
+			Ok(ctx.error())
+		})
+		.unwrap()
+	};
+
+	assert_eq!(
+		verify(SanType::IpAddress("10.1.2.3".parse().unwrap())),
+		X509VerifyResult::OK
+	);
+	assert_eq!(
+		verify(SanType::Rfc822Name("ops@corp.example".try_into().unwrap())),
+		X509VerifyResult::OK
+	);
+	// X509_V_ERR_PERMITTED_VIOLATION and X509_V_ERR_EXCLUDED_VIOLATION
+	assert_eq!(
+		verify(SanType::IpAddress("192.0.2.1".parse().unwrap())).as_raw(),
+		47
+	);
+	assert_eq!(
+		verify(SanType::Rfc822Name("ops@example.com".try_into().unwrap())).as_raw(),
+		47
+	);
+	assert_eq!(
+		verify(SanType::Rfc822Name("ceo@corp.example".try_into().unwrap())).as_raw(),
+		48
+	);
+}
+
+#[test]
+#[cfg(feature = "aws_lc_rs")]
+fn test_openssl_pkcs12() {
+	use openssl::pkcs12::Pkcs12;
+
+	let (mut ca_params, ca_key) = util::default_params();
+	ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+	let ca_cert = ca_params.self_signed(&ca_key).unwrap();
+	let ca = Issuer::new(ca_params, ca_key);
+
+	let mut params = CertificateParams::new(vec!["crabs.crabs".to_string()]).unwrap();
+	params
+		.distinguished_name
+		.push(DnType::CommonName, "Dev domain");
+	let signing_key = KeyPair::generate().unwrap();
+	let cert = params.signed_by(&signing_key, &ca).unwrap();
+	let chain = rcgen::CertifiedChain::new(
+		rcgen::CertifiedKey { cert, signing_key },
+		vec![ca_cert.clone()],
+	);
+	let certified = chain.leaf();
+
+	let der = chain
+		.serialize_pkcs12("crab password", Some("crabs"))
+		.unwrap();
+	let pkcs12 = Pkcs12::from_der(&der).unwrap();
+	// Fails if the MAC doesn't match
+	assert!(pkcs12.parse2("wrong password").is_err());
+	let parsed = pkcs12.parse2("crab password").unwrap();
+
+	let key = parsed.pkey.unwrap();
+	let expected_key = PKey::private_key_from_der(&certified.signing_key.serialize_der()).unwrap();
+	assert!(key.public_eq(&expected_key));
+	let cert = parsed.cert.unwrap();
+	assert_eq!(cert.to_der().unwrap(), certified.cert.der().as_ref());
+	let alias = cert
+		.alias()
+		.map(|alias| String::from_utf8(alias.to_vec()).unwrap());
+	assert_eq!(alias.as_deref(), Some("crabs"));
+	let ca = parsed.ca.unwrap();
+	assert_eq!(ca.len(), 1);
+	assert_eq!(ca[0].to_der().unwrap(), ca_cert.der().as_ref());
+
+	// Without a chain or friendly name
+	let der = certified.serialize_pkcs12("", None).unwrap();
+	let parsed = Pkcs12::from_der(&der).unwrap().parse2("").unwrap();
+	assert!(parsed.pkey.unwrap().public_eq(&expected_key));
+	assert_eq!(parsed.cert.unwrap().alias(), None);
+	assert!(parsed.ca.is_none_or(|chain| chain.is_empty()));
+}
+
+#[test]
+#[cfg(feature = "aws_lc_rs")]
+fn test_openssl_encrypted_pkcs8() {
+	let key_pair = KeyPair::generate().unwrap();
+	let expected = PKey::private_key_from_der(key_pair.serialized_der()).unwrap();
+
+	let der = key_pair.serialize_der_encrypted("crab password").unwrap();
+	let key = PKey::private_key_from_pkcs8_passphrase(&der, b"crab password").unwrap();
+	assert!(key.public_eq(&expected));
+	assert!(PKey::private_key_from_pkcs8_passphrase(&der, b"wrong password").is_err());
+
+	let pem = key_pair
+		.serialize_pem_encrypted_with_iterations("crab password", 2048.try_into().unwrap())
+		.unwrap();
+	let key = PKey::private_key_from_pem_passphrase(pem.as_bytes(), b"crab password").unwrap();
+	// The decrypted key is the plain PKCS#8 key
+	assert_eq!(
+		key.private_key_to_pkcs8().unwrap(),
+		key_pair.serialized_der()
+	);
+}
+
+#[test]
+fn test_openssl_pin_sha256() {
+	use openssl::base64;
+	use openssl::sha::sha256;
+	use rcgen::PublicKeyData;
+
+	let key_pairs = vec![
+		KeyPair::generate_for(&rcgen::PKCS_ECDSA_P256_SHA256).unwrap(),
+		KeyPair::generate_for(&rcgen::PKCS_ECDSA_P384_SHA384).unwrap(),
+		KeyPair::generate_for(&rcgen::PKCS_ED25519).unwrap(),
+		KeyPair::from_pem(util::RSA_TEST_KEY_PAIR_PEM).unwrap(),
+		KeyPair::from_pkcs8_pem_and_sign_algo(
+			util::RSA_TEST_KEY_PAIR_PEM,
+			&rcgen::PKCS_RSA_PSS_SHA256,
+		)
+		.unwrap(),
+		#[cfg(feature = "aws_lc_rs")]
+		KeyPair::generate_for(&rcgen::PKCS_ECDSA_P521_SHA512).unwrap(),
+	];
+
+	for key_pair in key_pairs {
+		let cert = CertificateParams::default().self_signed(&key_pair).unwrap();
+		// Like `openssl x509 -pubkey | openssl pkey -pubin -outform der | openssl dgst -sha256`
+		let x509 = X509::from_der(cert.der()).unwrap();
+		let spki = x509.public_key().unwrap().public_key_to_der().unwrap();
+		assert_eq!(key_pair.spki_sha256(), sha256(&spki));
+		assert_eq!(
+			key_pair.pin_sha256_base64(),
+			base64::encode_block(&sha256(&spki))
+		);
+	}
+}
+
+#[test]
+#[cfg(feature = "x509-parser")]
+fn test_openssl_spki_der() {
+	use openssl::ec::EcGroup;
+	use openssl::nid::Nid;
+	use openssl::rsa::Rsa;
+	use pki_types::SubjectPublicKeyInfoDer;
+	use rcgen::SubjectPublicKeyInfo;
+
+	let p256 = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+	let p384 = EcGroup::from_curve_name(Nid::SECP384R1).unwrap();
+	for key in [
+		PKey::from_ec_key(EcKey::generate(&p256).unwrap()).unwrap(),
+		PKey::from_ec_key(EcKey::generate(&p384).unwrap()).unwrap(),
+		PKey::generate_ed25519().unwrap(),
+		PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap(),
+	] {
+		let openssl_spki = SubjectPublicKeyInfoDer::from(key.public_key_to_der().unwrap());
+		let spki = SubjectPublicKeyInfo::try_from(&openssl_spki).unwrap();
+		assert_eq!(SubjectPublicKeyInfoDer::from(&spki), openssl_spki);
+
+		let key_pair = KeyPair::try_from(key.private_key_to_pkcs8().unwrap()).unwrap();
+		assert_eq!(key_pair.subject_public_key_info_der(), openssl_spki);
+	}
+}
+
+#[test]
+fn test_openssl_key_identifier() {
+	use openssl::hash::MessageDigest;
+	use openssl::x509::extension::SubjectKeyIdentifier;
+	use openssl::x509::X509Builder;
+	use rcgen::{KeyIdMethod, PublicKeyData, SubjectPublicKeyInfo};
+
+	let key_pair = KeyPair::generate().unwrap();
+	let spki = SubjectPublicKeyInfo::from_der(&key_pair.subject_public_key_info()).unwrap();
+
+	// The SHA-1 method is the one of `subjectKeyIdentifier = hash`
+	let pkey = PKey::private_key_from_pkcs8(&key_pair.serialize_der()).unwrap();
+	let mut builder = X509Builder::new().unwrap();
+	builder.set_version(2).unwrap();
+	builder.set_pubkey(&pkey).unwrap();
+	let ski = SubjectKeyIdentifier::new()
+		.build(&builder.x509v3_context(None, None))
+		.unwrap();
+	builder.append_extension(ski).unwrap();
+	builder.sign(&pkey, MessageDigest::sha256()).unwrap();
+	let openssl_ski = builder
+		.build()
+		.subject_key_id()
+		.unwrap()
+		.as_slice()
+		.to_vec();
+	assert_eq!(key_pair.key_identifier(&KeyIdMethod::Sha1), openssl_ski);
+
+	for method in [
+		KeyIdMethod::Sha1,
+		KeyIdMethod::Sha256,
+		KeyIdMethod::Sha384,
+		KeyIdMethod::Sha512,
+		KeyIdMethod::PreSpecified(vec![1, 2, 3]),
+	] {
+		let mut params = CertificateParams::default();
+		params.is_ca = IsCa::ExplicitNoCa;
+		params.key_identifier_method = method.clone();
+		let cert = X509::from_der(params.self_signed(&key_pair).unwrap().der()).unwrap();
+		let cert_ski = cert.subject_key_id().unwrap().as_slice();
+		assert_eq!(key_pair.key_identifier(&method), cert_ski);
+		assert_eq!(spki.key_identifier(&method), cert_ski);
+	}
+}
+
+#[test]
+fn test_openssl_raw_ed25519_seed() {
+	use openssl::pkey::Id;
+
+	let seed = [0x2a; 32];
+	// The public key derived by OpenSSL
+	let openssl_key = PKey::private_key_from_raw_bytes(&seed, Id::ED25519).unwrap();
+	let public_key = openssl_key.raw_public_key().unwrap();
+
+	let key_pair = KeyPair::from_raw_ed25519_seed_and_public_key(
+		&seed,
+		public_key.as_slice().try_into().unwrap(),
+	)
+	.unwrap();
+	let cert = CertificateParams::default().self_signed(&key_pair).unwrap();
+
+	let x509 = X509::from_der(cert.der()).unwrap();
+	let public_key = PKey::public_key_from_raw_bytes(&public_key, Id::ED25519).unwrap();
+	assert!(x509.verify(&public_key).unwrap());
+}
+
+#[test]
+fn test_openssl_sec1() {
+	let key_pair = KeyPair::generate().unwrap();
+	let expected = PKey::private_key_from_der(key_pair.serialized_der()).unwrap();
+
+	let key =
+		EcKey::private_key_from_pem(key_pair.serialize_sec1_pem().unwrap().as_bytes()).unwrap();
+	key.check_key().unwrap();
+	assert!(PKey::from_ec_key(key.clone()).unwrap().public_eq(&expected));
+	// OpenSSL writes the same SEC1 encoding
+	assert_eq!(
+		key.private_key_to_der().unwrap(),
+		key_pair.serialize_sec1_der().unwrap()
+	);
+}
+
+#[test]
+fn test_openssl_pkcs1() {
+	use openssl::rsa::Rsa;
+
+	let key_pair = KeyPair::from_pem(util::RSA_TEST_KEY_PAIR_PEM).unwrap();
+	let expected = PKey::private_key_from_der(key_pair.serialized_der()).unwrap();
+
+	let key =
+		Rsa::private_key_from_pem(key_pair.serialize_pkcs1_pem().unwrap().as_bytes()).unwrap();
+	assert!(key.check_key().unwrap());
+	assert!(PKey::from_rsa(key.clone()).unwrap().public_eq(&expected));
+	// OpenSSL writes the same PKCS#1 encoding
+	assert_eq!(
+		key.private_key_to_der().unwrap(),
+		key_pair.serialize_pkcs1_der().unwrap()
+	);
+}
+
 #[test]
 fn test_openssl_crl_parse() {
 	// Create a CRL with one revoked cert, and an issuer to sign the CRL.
@@ -561,3 +933,301 @@ fn test_openssl_pkcs1_and_sec1_keys() {
 	let pkcs8_ec_key_der = PrivateKeyDer::try_from(ec_key.private_key_to_pkcs8().unwrap()).unwrap();
 	KeyPair::try_from(&pkcs8_ec_key_der).unwrap();
 }
+
+/// A key held by openssl, which can sign with any digest
+struct OpensslKey {
+	key: PKey<openssl::pkey::Private>,
+	public_key: Vec<u8>,
+	alg: &'static rcgen::SignatureAlgorithm,
+}
+
+impl OpensslKey {
+	fn generate_ec(curve: openssl::nid::Nid, alg: &'static rcgen::SignatureAlgorithm) -> Self {
+		use openssl::bn::BigNumContext;
+		use openssl::ec::{EcGroup, EcKey, PointConversionForm};
+
+		let group = EcGroup::from_curve_name(curve).unwrap();
+		let ec_key = EcKey::generate(&group).unwrap();
+		let mut ctx = BigNumContext::new().unwrap();
+		let public_key = ec_key
+			.public_key()
+			.to_bytes(&group, PointConversionForm::UNCOMPRESSED, &mut ctx)
+			.unwrap();
+		Self {
+			key: PKey::from_ec_key(ec_key).unwrap(),
+			public_key,
+			alg,
+		}
+	}
+
+	fn generate_rsa(alg: &'static rcgen::SignatureAlgorithm) -> Self {
+		let rsa = openssl::rsa::Rsa::generate(2048).unwrap();
+		Self {
+			public_key: rsa.public_key_to_der_pkcs1().unwrap(),
+			key: PKey::from_rsa(rsa).unwrap(),
+			alg,
+		}
+	}
+}
+
+impl rcgen::SigningKey for OpensslKey {
+	fn sign(&self, msg: &[u8]) -> Result<Vec<u8>, rcgen::Error> {
+		use openssl::hash::MessageDigest;
+		use openssl::sign::Signer;
+
+		let digest = match self.alg.digest() {
+			Some(rcgen::DigestAlgorithm::Sha1) => MessageDigest::sha1(),
+			Some(rcgen::DigestAlgorithm::Sha256) => MessageDigest::sha256(),
+			Some(rcgen::DigestAlgorithm::Sha384) => MessageDigest::sha384(),
+			_ => MessageDigest::sha512(),
+		};
+		let mut signer = Signer::new(digest, &self.key).unwrap();
+		signer.update(msg).unwrap();
+		Ok(signer.sign_to_vec().unwrap())
+	}
+}
+
+impl rcgen::PublicKeyData for OpensslKey {
+	fn der_bytes(&self) -> &[u8] {
+		&self.public_key
+	}
+
+	fn algorithm(&self) -> &'static rcgen::SignatureAlgorithm {
+		self.alg
+	}
+}
+
+#[test]
+fn test_openssl_mixed_curve_and_hash() {
+	use openssl::nid::Nid;
+
+	for (curve, alg, sig_nid) in [
+		(
+			Nid::X9_62_PRIME256V1,
+			&rcgen::PKCS_ECDSA_P256_SHA384,
+			Nid::ECDSA_WITH_SHA384,
+		),
+		(
+			Nid::SECP384R1,
+			&rcgen::PKCS_ECDSA_P384_SHA256,
+			Nid::ECDSA_WITH_SHA256,
+		),
+	] {
+		assert!(!alg.is_supported());
+		assert!(matches!(
+			KeyPair::generate_for(alg),
+			Err(rcgen::Error::KeyGenerationUnavailable)
+		));
+
+		let (params, _) = util::default_params();
+		let key = OpensslKey::generate_ec(curve, alg);
+		let cert = params.self_signed(&key).unwrap();
+		verify_cert_basic(&cert);
+
+		// The SPKI names the key's curve, while the signature uses the other hash
+		let x509 = X509::from_der(cert.der()).unwrap();
+		assert_eq!(x509.signature_algorithm().object().nid(), sig_nid);
+		let public_key = x509.public_key().unwrap().ec_key().unwrap();
+		assert_eq!(public_key.group().curve_name(), Some(curve));
+
+		let csr = params.serialize_request(&key).unwrap();
+		let req = X509Req::from_der(csr.der()).unwrap();
+		req.verify(&key.key).unwrap();
+	}
+}
+
+#[test]
+fn test_openssl_rsa_sha1() {
+	use openssl::nid::Nid;
+
+	let alg = &rcgen::PKCS_RSA_SHA1;
+	assert!(!alg.is_supported());
+	assert!(rcgen::SignatureAlgorithm::from_oid(&[1, 2, 840, 113549, 1, 1, 5]).is_err());
+
+	let (mut params, _) = util::default_params();
+	params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+	let key = OpensslKey::generate_rsa(alg);
+	let cert = params.self_signed(&key).unwrap();
+
+	let x509 = X509::from_der(cert.der()).unwrap();
+	assert_eq!(
+		x509.signature_algorithm().object().nid(),
+		Nid::SHA1WITHRSAENCRYPTION
+	);
+	assert!(x509.verify(&key.key).unwrap());
+
+	let now = time::OffsetDateTime::now_utc();
+	let crl_params = rcgen::CertificateRevocationListParams {
+		this_update: now,
+		next_update: now + time::Duration::weeks(1),
+		crl_number: rcgen::SerialNumber::from(1),
+		issuing_distribution_point: None,
+		revoked_certs: Vec::new(),
+		key_identifier_method: rcgen::KeyIdMethod::Sha256,
+	};
+	let crl = crl_params.signed_by(&Issuer::new(params, &key)).unwrap();
+	// openssl picks the digest from the CRL's signatureAlgorithm
+	let openssl_crl = X509Crl::from_der(crl.der()).unwrap();
+	assert!(openssl_crl.verify(&key.key).unwrap());
+}
+
+#[test]
+fn test_openssl_compressed_ec_point() {
+	use openssl::bn::BigNumContext;
+	use openssl::ec::PointConversionForm;
+
+	for alg in [
+		&rcgen::PKCS_ECDSA_P256_SHA256,
+		&rcgen::PKCS_ECDSA_P384_SHA384,
+	] {
+		let key_pair = KeyPair::generate_for(alg).unwrap();
+		let key = rcgen::CompressedEcKey::new(&key_pair).unwrap();
+		let (mut params, _) = util::default_params();
+		params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+		params.use_authority_key_identifier_extension = true;
+		let cert = params.self_signed(&key).unwrap();
+		verify_cert_basic(&cert);
+
+		let x509 = X509::from_der(cert.der()).unwrap();
+		let public_key = x509.public_key().unwrap();
+		assert!(x509.verify(&public_key).unwrap());
+
+		// openssl decompresses the point to the key we started with
+		let ec_key = public_key.ec_key().unwrap();
+		let mut ctx = BigNumContext::new().unwrap();
+		let uncompressed = ec_key
+			.public_key()
+			.to_bytes(ec_key.group(), PointConversionForm::UNCOMPRESSED, &mut ctx)
+			.unwrap();
+		assert_eq!(uncompressed, key_pair.public_key_raw());
+		let compressed = ec_key
+			.public_key()
+			.to_bytes(ec_key.group(), PointConversionForm::COMPRESSED, &mut ctx)
+			.unwrap();
+		assert_eq!(compressed, rcgen::PublicKeyData::der_bytes(&key));
+
+		// The key identifiers are computed over the compressed SPKI
+		let key_id = params.key_identifier(&key);
+		assert_eq!(x509.subject_key_id().unwrap().as_slice(), key_id);
+		assert_eq!(x509.authority_key_id().unwrap().as_slice(), key_id);
+	}
+
+	assert_eq!(
+		rcgen::CompressedEcKey::new(KeyPair::generate_for(&rcgen::PKCS_ED25519).unwrap())
+			.unwrap_err(),
+		rcgen::Error::UnsupportedSignatureAlgorithm
+	);
+}
+
+#[test]
+fn test_openssl_user_principal_name() {
+	let (mut params, key_pair) = util::default_params();
+	params.subject_alt_names = vec![rcgen::SanType::user_principal_name("user@corp.example")];
+	let cert = params.self_signed(&key_pair).unwrap();
+	verify_cert_basic(&cert);
+
+	// openssl recognizes the name as a UPN, like `certutil -dump` does, which prints it as
+	// "Principal Name=user@corp.example"
+	let x509 = X509::from_der(cert.der()).unwrap();
+	let text = String::from_utf8(x509.to_text().unwrap()).unwrap();
+	assert!(text.contains("othername: UPN::user@corp.example"), "{text}");
+}
+
+#[test]
+fn test_openssl_tls_feature() {
+	let (mut params, key_pair) = util::default_params();
+	params.tls_features = vec![rcgen::TlsFeature::StatusRequest];
+	let cert = params.self_signed(&key_pair).unwrap();
+	verify_cert_basic(&cert);
+
+	let x509 = X509::from_der(cert.der()).unwrap();
+	let text = String::from_utf8(x509.to_text().unwrap()).unwrap();
+	let feature = text
+		.lines()
+		.skip_while(|line| line.trim() != "TLS Feature:")
+		.nth(1)
+		.map(str::trim);
+	assert_eq!(feature, Some("status_request"), "{text}");
+}
+
+#[test]
+fn test_openssl_netscape_extensions() {
+	let (mut params, key_pair) = util::default_params();
+	params.netscape_cert_type =
+		Some(rcgen::NetscapeCertType::SSL_SERVER | rcgen::NetscapeCertType::OBJECT_SIGNING_CA);
+	params.netscape_comment = Some("Ticket 1234".to_string());
+	let cert = params.self_signed(&key_pair).unwrap();
+	verify_cert_basic(&cert);
+
+	let x509 = X509::from_der(cert.der()).unwrap();
+	let text = String::from_utf8(x509.to_text().unwrap()).unwrap();
+	let value = |name| {
+		text.lines()
+			.skip_while(|line| line.trim() != name)
+			.nth(1)
+			.map(str::trim)
+	};
+	assert_eq!(
+		value("Netscape Cert Type:"),
+		Some("SSL Server, Object Signing CA"),
+		"{text}"
+	);
+	assert_eq!(value("Netscape Comment:"), Some("Ticket 1234"), "{text}");
+}
+
+/// The freshest CRL extension, which the openssl crate has no accessor for
+enum FreshestCrl {}
+
+// SAFETY: OpenSSL decodes freshestCRL with the CRLDistributionPoints template, as a
+// STACK_OF(DIST_POINT).
+unsafe impl openssl::x509::ExtensionType for FreshestCrl {
+	const NID: openssl::nid::Nid = openssl::nid::Nid::FRESHEST_CRL;
+	type Output = Stack<openssl::x509::DistPoint>;
+}
+
+#[test]
+fn test_openssl_freshest_crl() {
+	let freshest_crl = vec![rcgen::CrlDistributionPoint {
+		uris: vec!["http://crl.example.com/delta.crl".to_string()],
+	}];
+	let (mut params, key_pair) = util::default_params();
+	params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+	params.freshest_crl = freshest_crl.clone();
+	let cert = params.self_signed(&key_pair).unwrap();
+	verify_cert_basic(&cert);
+
+	let x509 = X509::from_der(cert.der()).unwrap();
+	let text = String::from_utf8(x509.to_text().unwrap()).unwrap();
+	let uri = text
+		.lines()
+		.skip_while(|line| line.trim() != "X509v3 Freshest CRL:")
+		.nth(2)
+		.map(str::trim);
+	assert_eq!(uri, Some("URI:http://crl.example.com/delta.crl"), "{text}");
+
+	// Only the base CRL points to the delta CRLs
+	let now = time::OffsetDateTime::now_utc();
+	let mut extensions = rcgen::CrlExtensions::default();
+	extensions.freshest_crl = freshest_crl;
+	let crl_params = rcgen::CertificateRevocationListParams {
+		this_update: now,
+		next_update: now + time::Duration::weeks(1),
+		crl_number: rcgen::SerialNumber::from(1),
+		issuing_distribution_point: None,
+		revoked_certs: Vec::new(),
+		key_identifier_method: rcgen::KeyIdMethod::Sha256,
+	};
+	let crl = crl_params
+		.with_extensions(&extensions)
+		.signed_by(&Issuer::new(params, &key_pair))
+		.unwrap();
+	let openssl_crl = X509Crl::from_der(crl.der()).unwrap();
+	let (critical, points) = openssl_crl.extension::<FreshestCrl>().unwrap().unwrap();
+	assert!(!critical);
+	let uris = points
+		.iter()
+		.flat_map(|dp| dp.distpoint().unwrap().fullname().unwrap().iter())
+		.map(|name| name.uri().unwrap().to_owned())
+		.collect::<Vec<_>>();
+	assert_eq!(uris, ["http://crl.example.com/delta.crl"]);
+}
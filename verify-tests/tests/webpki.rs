@@ -4,11 +4,15 @@ use std::time::Duration as StdDuration;
 use aws_lc_rs::unstable::signature::{
 	PqdsaKeyPair, PqdsaSigningAlgorithm, ML_DSA_44_SIGNING, ML_DSA_65_SIGNING, ML_DSA_87_SIGNING,
 };
-use pki_types::{CertificateDer, ServerName, SignatureVerificationAlgorithm, UnixTime};
+use pki_types::pem::PemObject;
+use pki_types::{
+	CertificateDer, ServerName, SignatureVerificationAlgorithm, SubjectPublicKeyInfoDer, UnixTime,
+};
+use rcgen::string::DnsName;
 use rcgen::{
 	BasicConstraints, Certificate, CertificateParams, CertificateRevocationListParams, DnType,
 	Error, ExtendedKeyUsagePurpose, IsCa, Issuer, KeyPair, KeyUsagePurpose, PublicKeyData,
-	RevocationReason, RevokedCertParams, SerialNumber, SigningKey,
+	RevocationReason, RevokedCertParams, SanType, SerialNumber, SigningKey,
 };
 #[cfg(feature = "x509-parser")]
 use rcgen::{CertificateSigningRequestParams, DnValue};
@@ -20,7 +24,7 @@ use time::{Duration, OffsetDateTime};
 use verify_tests as util;
 use webpki::{
 	anchor_from_trusted_cert, BorrowedCertRevocationList, CertRevocationList, EndEntityCert,
-	KeyUsage, RevocationOptionsBuilder,
+	KeyUsage, RawPublicKeyEntity, RevocationOptionsBuilder,
 };
 
 fn sign_msg_ecdsa(key_pair: &KeyPair, msg: &[u8], alg: &'static EcdsaSigningAlgorithm) -> Vec<u8> {
@@ -306,6 +310,44 @@ fn test_webpki_rsa_combinations_given() {
 	}
 }
 
+/// Checks that the public keys are accepted as TLS raw public keys of RFC 7250, which rustls
+/// verifies with webpki's `RawPublicKeyEntity`
+#[test]
+fn test_webpki_raw_public_key() {
+	let keys: [(KeyPair, &dyn SignatureVerificationAlgorithm); 4] = [
+		(
+			KeyPair::generate_for(&rcgen::PKCS_ECDSA_P256_SHA256).unwrap(),
+			webpki::ring::ECDSA_P256_SHA256,
+		),
+		(
+			KeyPair::generate_for(&rcgen::PKCS_ECDSA_P384_SHA384).unwrap(),
+			webpki::ring::ECDSA_P384_SHA384,
+		),
+		(
+			KeyPair::generate_for(&rcgen::PKCS_ED25519).unwrap(),
+			webpki::ring::ED25519,
+		),
+		(
+			KeyPair::from_pem(util::RSA_TEST_KEY_PAIR_PEM).unwrap(),
+			webpki::ring::RSA_PKCS1_2048_8192_SHA256,
+		),
+	];
+
+	let msg = b"raw public key";
+	for (key_pair, verification_alg) in keys {
+		let signature = key_pair.sign(msg).unwrap();
+
+		let spki = SubjectPublicKeyInfoDer::from(&key_pair);
+		let rpk = RawPublicKeyEntity::try_from(&spki).unwrap();
+		rpk.verify_signature(verification_alg, msg, &signature)
+			.unwrap();
+
+		let pem = key_pair.public_key_pem();
+		let from_pem = SubjectPublicKeyInfoDer::from_pem_slice(pem.as_bytes()).unwrap();
+		assert_eq!(from_pem, spki);
+	}
+}
+
 #[test]
 fn test_webpki_separate_ca() {
 	let (mut ca_params, ca_key) = util::default_params();
@@ -598,6 +640,31 @@ fn test_certificate_from_csr() {
 	);
 }
 
+#[test]
+fn test_webpki_trailing_dot_dns_name() {
+	let key_pair = KeyPair::generate().unwrap();
+	let dns_name = ServerName::try_from("example.com").unwrap();
+
+	// Names copied from zone files are normalized, so they match
+	let params = CertificateParams::new(vec!["Example.com.".to_owned()]).unwrap();
+	let cert = params.self_signed(&key_pair).unwrap();
+	let end_entity_cert = EndEntityCert::try_from(cert.der()).unwrap();
+	end_entity_cert
+		.verify_is_valid_for_subject_name(&dns_name)
+		.expect("valid for DNS name");
+
+	// Raw names with the trailing dot don't
+	let mut params = CertificateParams::new(Vec::new()).unwrap();
+	params.subject_alt_names = vec![SanType::DnsName(DnsName::raw(
+		"example.com.".try_into().unwrap(),
+	))];
+	let cert = params.self_signed(&key_pair).unwrap();
+	let end_entity_cert = EndEntityCert::try_from(cert.der()).unwrap();
+	assert!(end_entity_cert
+		.verify_is_valid_for_subject_name(&dns_name)
+		.is_err());
+}
+
 #[test]
 fn test_webpki_serial_number() {
 	let (mut params, key_pair) = util::default_params();
@@ -731,3 +798,72 @@ fn test_webpki_cert_crl_dps() {
 	// Webpki doesn't expose the parsed CRL distribution extension, so we can't interrogate that
 	// it matches the expected form. See `openssl.rs` for more extensive coverage.
 }
+
+#[test]
+fn test_webpki_ip_name_constraints() {
+	use rcgen::{CidrSubnet, GeneralSubtree, NameConstraints};
+
+	let alg = &rcgen::PKCS_ECDSA_P256_SHA256;
+	let (mut root, _) = util::default_params();
+	root.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+	let root_key = KeyPair::generate_for(alg).unwrap();
+	let root_cert = root.self_signed(&root_key).unwrap();
+	let root = Issuer::new(root, root_key);
+
+	// An intermediate CA constrained to 10.0.0.0/8 and corp.example, minus 10.99.0.0/16
+	let (mut intermediate, _) = util::default_params();
+	intermediate.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+	intermediate
+		.distinguished_name
+		.push(DnType::CommonName, "Corp intermediate CA");
+	intermediate.name_constraints = Some(NameConstraints {
+		permitted_subtrees: vec![
+			GeneralSubtree::IpAddress("10.0.0.0/8".parse().unwrap()),
+			GeneralSubtree::DnsName("corp.example".to_string()),
+		],
+		excluded_subtrees: vec![GeneralSubtree::IpAddress(CidrSubnet::from_v4_prefix(
+			[10, 99, 0, 0],
+			16,
+		))],
+	});
+	let intermediate_key = KeyPair::generate_for(alg).unwrap();
+	let intermediate_cert = intermediate.signed_by(&intermediate_key, &root).unwrap();
+	let intermediate = Issuer::new(intermediate, intermediate_key);
+
+	let trust_anchor = anchor_from_trusted_cert(root_cert.der()).unwrap();
+	let time = UnixTime::since_unix_epoch(StdDuration::from_secs(0x40_00_00_00));
+	let verify = |san: SanType| {
+		let (mut params, _) = util::default_params();
+		params.distinguished_name = rcgen::DistinguishedName::new();
+		params.subject_alt_names = vec![
+			SanType::DnsName("host.corp.example".try_into().unwrap()),
+			san,
+		];
+		let key = KeyPair::generate_for(alg).unwrap();
+		let cert = params.signed_by(&key, &intermediate).unwrap();
+		EndEntityCert::try_from(cert.der())
+			.unwrap()
+			.verify_for_usage(
+				&[webpki::ring::ECDSA_P256_SHA256],
+				std::slice::from_ref(&trust_anchor),
+				&[intermediate_cert.der().clone()],
+				time,
+				KeyUsage::server_auth(),
+				None,
+				None,
+			)
+			.map(|_| ())
+	};
+
+	verify(SanType::IpAddress("10.1.2.3".parse().unwrap())).unwrap();
+	for outside in ["192.0.2.1", "10.99.0.1"] {
+		assert!(matches!(
+			verify(SanType::IpAddress(outside.parse().unwrap())),
+			Err(webpki::Error::NameConstraintViolation)
+		));
+	}
+	assert!(matches!(
+		verify(SanType::DnsName("host.example.com".try_into().unwrap())),
+		Err(webpki::Error::NameConstraintViolation)
+	));
+}